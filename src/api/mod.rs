@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::auth::AuthToken;
+use crate::metrics::metrics;
 use crate::{Error, Result};
 
 // Request timeout configuration
@@ -17,6 +18,17 @@ const LIBRARY_API_URL: &str =
     "https://library-service.live.use1a.on.epicgames.com/library/api/public";
 const LAUNCHER_API_URL: &str =
     "https://launcher-public-service-prod.ol.epicgames.com/launcher/api/public";
+const CATALOG_API_URL: &str =
+    "https://catalog-public-service-prod06.ol.epicgames.com/catalog/api/shared";
+const ENTITLEMENT_API_URL: &str =
+    "https://entitlement-public-service-prod08.ol.epicgames.com/entitlement/api";
+const DATASTORAGE_API_URL: &str =
+    "https://datastorage-public-service-liveegs.live.use1a.on.epicgames.com/api/v1/access/egstore/savesync";
+
+/// Whether cloud saves can be uploaded. Not until uploads write the
+/// manifest and chunk format Epic's cloud storage and launcher read back;
+/// anything else at a save's path would replace the account's real save.
+pub const SAVE_UPLOADS_ENABLED: bool = false;
 
 // Epic Games launcher client credentials (publicly available)
 const CLIENT_ID: &str = "34a02cf8f4414e29b15921876da36f9a";
@@ -56,11 +68,19 @@ struct LibraryItem {
     #[serde(rename = "appName")]
     app_name: String,
     #[serde(rename = "namespace")]
-    _namespace: String,
+    namespace: String,
     #[serde(rename = "catalogItemId")]
     catalog_item_id: String,
 }
 
+/// Where a game sits in the store catalog, which the Epic Games Launcher
+/// needs to recognise an install
+#[derive(Debug, Clone, Default)]
+pub struct CatalogIds {
+    pub namespace: String,
+    pub catalog_item_id: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct AssetResponse {
     id: String,
@@ -76,6 +96,89 @@ struct AssetMetadata {
     application_id: String,
 }
 
+/// Catalog entry returned by the namespace bulk items endpoint
+#[derive(Debug, Deserialize)]
+struct CatalogBulkItem {
+    id: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(rename = "mainGameItem")]
+    main_game_item: Option<CatalogItemRef>,
+    #[serde(rename = "releaseInfo", default)]
+    release_info: Vec<ReleaseInfo>,
+    #[serde(rename = "keyImages", default)]
+    key_images: Vec<KeyImage>,
+    #[serde(rename = "customAttributes", default)]
+    custom_attributes: std::collections::HashMap<String, CustomAttribute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomAttribute {
+    value: String,
+}
+
+/// Store artwork of a catalog item, e.g. its box art or logo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyImage {
+    /// Epic's name for the kind of image, such as `DieselGameBoxTall`
+    #[serde(rename = "type")]
+    pub image_type: String,
+    pub url: String,
+}
+
+impl KeyImage {
+    /// File extension of the image, from its URL
+    pub fn extension(&self) -> String {
+        std::path::Path::new(self.url.split('?').next().unwrap_or_default())
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png")
+            .to_ascii_lowercase()
+    }
+}
+
+/// What the store says about a game
+#[derive(Debug, Clone, Default)]
+pub struct StoreListing {
+    pub description: String,
+    pub key_images: Vec<KeyImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogItemRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseInfo {
+    #[serde(rename = "appId")]
+    app_id: Option<String>,
+}
+
+/// Ownership record for a catalog item on the user's account
+#[derive(Debug, Deserialize)]
+struct Entitlement {
+    namespace: String,
+    #[serde(rename = "catalogItemId")]
+    catalog_item_id: String,
+    #[serde(default = "default_entitlement_active")]
+    active: bool,
+}
+
+fn default_entitlement_active() -> bool {
+    true
+}
+
+/// Add-on content owned by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dlc {
+    pub app_name: String,
+    pub app_title: String,
+    /// App name of the base game, or its catalog id if the base game isn't owned
+    pub base_game: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct CatalogItem {
@@ -86,7 +189,7 @@ struct CatalogItem {
 }
 
 // Manifest structures for Epic Games manifest format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameManifest {
     #[serde(rename = "ManifestFileVersion")]
     pub manifest_file_version: String,
@@ -110,9 +213,37 @@ pub struct GameManifest {
     pub chunk_sha_list: std::collections::HashMap<String, Vec<u8>>,
     #[serde(rename = "DataGroupList")]
     pub data_group_list: std::collections::HashMap<String, Vec<String>>,
+    #[serde(rename = "ChunkFilesizeList", default)]
+    pub chunk_filesize_list: std::collections::HashMap<String, u64>,
+    /// Redistributables (Visual C++, DirectX) to run once after install
+    #[serde(rename = "PrereqIds", default)]
+    pub prereq_ids: Vec<String>,
+    #[serde(rename = "PrereqName", default)]
+    pub prereq_name: String,
+    /// Installer inside the game directory; empty when there is none
+    #[serde(rename = "PrereqPath", default)]
+    pub prereq_path: String,
+    #[serde(rename = "PrereqArgs", default)]
+    pub prereq_args: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl GameManifest {
+    /// Bytes on disk once every file is installed
+    pub fn install_size(&self) -> u64 {
+        self.file_list.iter().map(FileManifest::size).sum()
+    }
+
+    /// Bytes to download, if the manifest lists chunk file sizes
+    pub fn download_size(&self) -> Option<u64> {
+        if self.chunk_filesize_list.is_empty() {
+            None
+        } else {
+            Some(self.chunk_filesize_list.values().sum())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileManifest {
     #[serde(rename = "Filename")]
     pub filename: String,
@@ -120,6 +251,63 @@ pub struct FileManifest {
     pub file_hash: Vec<u8>,
     #[serde(rename = "FileChunkParts")]
     pub file_chunk_parts: Vec<ChunkPart>,
+    /// Attribute flag; `None` when the manifest doesn't say
+    #[serde(rename = "bIsReadOnly", default)]
+    pub is_read_only: Option<bool>,
+    #[serde(rename = "bIsCompressed", default)]
+    pub is_compressed: bool,
+    /// Attribute flag; `None` when the manifest doesn't say
+    #[serde(rename = "bIsUnixExecutable", default)]
+    pub is_unix_executable: Option<bool>,
+    /// Optional content this file belongs to, such as a language pack
+    #[serde(rename = "InstallTags", default)]
+    pub install_tags: Vec<String>,
+    /// Where the entry points when it is a symlink rather than a file
+    #[serde(rename = "SymlinkTarget", default)]
+    pub symlink_target: Option<String>,
+}
+
+impl FileManifest {
+    /// Target of a symlink entry; empty targets mean a regular file
+    pub fn symlink_target(&self) -> Option<&str> {
+        self.symlink_target
+            .as_deref()
+            .filter(|target| !target.is_empty())
+    }
+
+    /// Size of the file once reassembled from its chunk parts
+    pub fn size(&self) -> u64 {
+        self.file_chunk_parts.iter().map(|part| part.size).sum()
+    }
+
+    /// Unix permission bits for the installed file, taken from the manifest's
+    /// attribute flags. Without an executable flag, the file extension decides.
+    pub fn unix_mode(&self) -> u32 {
+        let executable = self
+            .is_unix_executable
+            .unwrap_or_else(|| self.looks_executable());
+        let mode = if executable { 0o755 } else { 0o644 };
+
+        if self.is_read_only.unwrap_or(false) {
+            mode & !0o222
+        } else {
+            mode
+        }
+    }
+
+    /// Shell scripts and Linux or macOS binaries, by their usual extensions
+    fn looks_executable(&self) -> bool {
+        let extension = std::path::Path::new(&self.filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        matches!(
+            extension.as_str(),
+            "sh" | "x86" | "x86_64" | "run" | "appimage" | "command"
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,6 +331,9 @@ pub struct DownloadProgress {
 
 pub struct EpicClient {
     client: Client,
+    /// Mirror serving `<app>/manifest.json` and `chunks/<guid>`, used instead
+    /// of Epic's CDN when set
+    cdn_base_url: Option<String>,
 }
 
 impl EpicClient {
@@ -152,7 +343,33 @@ impl EpicClient {
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
             .build()?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            cdn_base_url: None,
+        })
+    }
+
+    /// Fetch manifests and chunks from a mirror instead of Epic's CDN
+    pub fn with_cdn_base_url(mut self, url: Option<String>) -> Self {
+        self.cdn_base_url = url.map(|url| url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// GET a file from the configured mirror
+    async fn cdn_get(&self, base: &str, path: &str) -> Result<reqwest::Response> {
+        let url = format!("{}/{}", base, path);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            metrics().record_api_error();
+            return Err(Error::Api(format!(
+                "Failed to download {}: {}",
+                url,
+                response.status()
+            )));
+        }
+
+        Ok(response)
     }
 
     /// Request device authorization (Step 1 of OAuth device flow)
@@ -168,6 +385,7 @@ impl EpicClient {
             .await?;
 
         if !device_auth_response.status().is_success() {
+            metrics().record_api_error();
             let status = device_auth_response.status();
             let error_text = device_auth_response.text().await.unwrap_or_default();
             return Err(Error::Auth(format!(
@@ -222,6 +440,7 @@ impl EpicClient {
         }
 
         // Any other error should be reported
+        metrics().record_api_error();
         let error_text = response.text().await.unwrap_or_default();
         Err(Error::Auth(format!(
             "Authentication failed: {} - {}",
@@ -281,12 +500,16 @@ impl EpicClient {
             .await?;
 
         if !response.status().is_success() {
+            metrics().record_api_error();
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::Auth(format!(
-                "Failed to refresh token: {} - {}",
-                status, error_text
-            )));
+            let message = format!("Failed to refresh token: {} - {}", status, error_text);
+            // Only a refused refresh token means signing in again
+            return Err(if status.is_server_error() {
+                Error::Api(message)
+            } else {
+                Error::Auth(message)
+            });
         }
 
         let oauth_response: OAuthTokenResponse = response.json().await?;
@@ -305,6 +528,262 @@ impl EpicClient {
     pub async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
         log::info!("Fetching game library from Epic Games");
 
+        let records = self.get_library_items(token).await?;
+
+        // Convert library items to games
+        // Note: We need to fetch additional details for each game
+        let mut games = Vec::new();
+
+        for item in records {
+            // For now, we'll create basic game entries
+            // In a full implementation, we'd fetch catalog details for each
+            games.push(Game {
+                app_name: item.app_name.clone(),
+                app_title: item.app_name.clone(), // Will be replaced with catalog lookup
+                app_version: "unknown".to_string(), // Will be replaced with catalog lookup
+                install_path: None,
+            });
+        }
+
+        log::info!("Successfully fetched {} games from library", games.len());
+
+        Ok(games)
+    }
+
+    /// Catalog namespace and item of every game in the user's library, by
+    /// app name
+    pub async fn get_catalog_ids(
+        &self,
+        token: &AuthToken,
+    ) -> Result<std::collections::HashMap<String, CatalogIds>> {
+        Ok(self
+            .get_library_items(token)
+            .await?
+            .into_iter()
+            .map(|record| {
+                let ids = CatalogIds {
+                    namespace: record.namespace,
+                    catalog_item_id: record.catalog_item_id,
+                };
+                (record.app_name, ids)
+            })
+            .collect())
+    }
+
+    /// Get owned add-ons, each linked to its base game
+    pub async fn get_dlcs(&self, token: &AuthToken) -> Result<Vec<Dlc>> {
+        log::info!("Fetching DLC details from Epic Games catalog");
+
+        let records = self.get_library_items(token).await?;
+
+        let catalog_items = self.get_catalog_items(token, &records).await?;
+
+        let dlcs = classify_dlcs(&records, catalog_items);
+        log::info!("Found {} DLC item(s) in library", dlcs.len());
+
+        Ok(dlcs)
+    }
+
+    /// Store artwork of every game in the user's library, by app name
+    pub async fn get_library_key_images(
+        &self,
+        token: &AuthToken,
+    ) -> Result<std::collections::HashMap<String, Vec<KeyImage>>> {
+        let records = self.get_library_items(token).await?;
+        let mut items: std::collections::HashMap<String, CatalogBulkItem> = self
+            .get_catalog_items(token, &records)
+            .await?
+            .into_iter()
+            .map(|item| (item.id.clone(), item))
+            .collect();
+
+        Ok(records
+            .iter()
+            .filter_map(|record| {
+                let item = items.remove(&record.catalog_item_id)?;
+                Some((record.app_name.clone(), item.key_images))
+            })
+            .collect())
+    }
+
+    /// Catalog entries of `records`, fetched a namespace at a time
+    async fn get_catalog_items(
+        &self,
+        token: &AuthToken,
+        records: &[LibraryItem],
+    ) -> Result<Vec<CatalogBulkItem>> {
+        let mut namespaces: Vec<&str> = records.iter().map(|r| r.namespace.as_str()).collect();
+        namespaces.sort_unstable();
+        namespaces.dedup();
+
+        let mut catalog_items = Vec::new();
+
+        for namespace in namespaces {
+            let ids: Vec<&str> = records
+                .iter()
+                .filter(|r| r.namespace == namespace)
+                .map(|r| r.catalog_item_id.as_str())
+                .collect();
+
+            let url = format!(
+                "{}/namespace/{}/bulk/items?id={}&includeDLCDetails=true&includeMainGameDetails=true&country=US&locale=en-US",
+                CATALOG_API_URL,
+                namespace,
+                ids.join(",")
+            );
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token.access_token))
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                metrics().record_api_error();
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(Error::Api(format!(
+                    "Failed to fetch catalog items for {}: {} - {}",
+                    namespace, status, error_text
+                )));
+            }
+
+            let items: std::collections::HashMap<String, CatalogBulkItem> = response.json().await?;
+            catalog_items.extend(items.into_values());
+        }
+
+        Ok(catalog_items)
+    }
+
+    /// Store artwork of a game in the user's library
+    pub async fn get_key_images(&self, token: &AuthToken, app_name: &str) -> Result<Vec<KeyImage>> {
+        Ok(self
+            .get_catalog_item(token, app_name)
+            .await?
+            .map(|item| item.key_images)
+            .unwrap_or_default())
+    }
+
+    /// Store description and artwork of a game in the user's library
+    pub async fn get_store_listing(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<StoreListing> {
+        Ok(self
+            .get_catalog_item(token, app_name)
+            .await?
+            .map(|item| StoreListing {
+                description: item.description,
+                key_images: item.key_images,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Where the game keeps its saves, as a Windows path template such as
+    /// `{AppData}/Game/Saved`; `None` if it has no cloud saves
+    pub async fn get_save_path_template(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<Option<String>> {
+        Ok(self
+            .get_catalog_item(token, app_name)
+            .await?
+            .and_then(|mut item| item.custom_attributes.remove("CloudSaveFolder"))
+            .map(|attribute| attribute.value)
+            .filter(|template| !template.is_empty()))
+    }
+
+    async fn get_catalog_item(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<Option<CatalogBulkItem>> {
+        let records = self.get_library_items(token).await?;
+        let Some(item) = records.iter().find(|r| r.app_name == app_name) else {
+            return Err(Error::GameNotFound(app_name.to_string()));
+        };
+
+        let url = format!(
+            "{}/namespace/{}/bulk/items?id={}&country=US&locale=en-US",
+            CATALOG_API_URL, item.namespace, item.catalog_item_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            metrics().record_api_error();
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::Api(format!(
+                "Failed to fetch catalog item for {}: {} - {}",
+                app_name, status, error_text
+            )));
+        }
+
+        let mut items: std::collections::HashMap<String, CatalogBulkItem> = response.json().await?;
+        Ok(items.remove(&item.catalog_item_id))
+    }
+
+    /// Download an image from the store's CDN
+    pub async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.client.get(url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Error::Api(format!(
+                "Failed to download image {}: {}",
+                url,
+                response.status()
+            )));
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Check that the account still holds an active entitlement for a game
+    pub async fn has_entitlement(&self, token: &AuthToken, app_name: &str) -> Result<bool> {
+        log::info!("Verifying entitlement for game: {}", app_name);
+
+        let records = self.get_library_items(token).await?;
+        let Some(item) = records.iter().find(|r| r.app_name == app_name) else {
+            return Ok(false);
+        };
+
+        let url = format!(
+            "{}/account/{}/entitlements?start=0&count=5000",
+            ENTITLEMENT_API_URL, token.account_id
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            metrics().record_api_error();
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::Api(format!(
+                "Failed to fetch entitlements: {} - {}",
+                status, error_text
+            )));
+        }
+
+        let entitlements: Vec<Entitlement> = response.json().await?;
+
+        Ok(owns_item(&entitlements, item))
+    }
+
+    async fn get_library_items(&self, token: &AuthToken) -> Result<Vec<LibraryItem>> {
         let library_url = format!("{}/users/{}/items", LIBRARY_API_URL, token.account_id);
 
         let response = self
@@ -315,6 +794,7 @@ impl EpicClient {
             .await?;
 
         if !response.status().is_success() {
+            metrics().record_api_error();
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             return Err(Error::Api(format!(
@@ -327,24 +807,7 @@ impl EpicClient {
 
         log::debug!("Found {} items in library", library_response.records.len());
 
-        // Convert library items to games
-        // Note: We need to fetch additional details for each game
-        let mut games = Vec::new();
-
-        for item in library_response.records {
-            // For now, we'll create basic game entries
-            // In a full implementation, we'd fetch catalog details for each
-            games.push(Game {
-                app_name: item.app_name.clone(),
-                app_title: item.app_name.clone(), // Will be replaced with catalog lookup
-                app_version: "unknown".to_string(), // Will be replaced with catalog lookup
-                install_path: None,
-            });
-        }
-
-        log::info!("Successfully fetched {} games from library", games.len());
-
-        Ok(games)
+        Ok(library_response.records)
     }
 
     /// Get game manifest URL for download
@@ -362,6 +825,7 @@ impl EpicClient {
             .await?;
 
         if !response.status().is_success() {
+            metrics().record_api_error();
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             return Err(Error::Api(format!(
@@ -391,6 +855,22 @@ impl EpicClient {
         token: &AuthToken,
         app_name: &str,
     ) -> Result<GameManifest> {
+        let (manifest, _) = self
+            .download_manifest_if_changed(token, app_name, None)
+            .await?
+            .ok_or_else(|| Error::Api(format!("No manifest returned for {}", app_name)))?;
+        Ok(manifest)
+    }
+
+    /// Download a manifest unless it still has the ETag `etag`, returning
+    /// it with the ETag it has now, if the server sent one. `None` means
+    /// the copy with `etag` is current.
+    pub async fn download_manifest_if_changed(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<(GameManifest, Option<String>)>> {
         // TODO: Implement real CDN manifest download
         // TODO: Parse manifest URL from asset metadata (build_info or manifest_location fields)
         // TODO: Handle gzip decompression for manifest files
@@ -400,6 +880,34 @@ impl EpicClient {
 
         log::info!("Downloading manifest for game: {}", app_name);
 
+        if let Some(base) = &self.cdn_base_url {
+            let url = format!("{}/{}/manifest.json", base, app_name);
+            let mut request = self.client.get(&url);
+            if let Some(etag) = etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            let response = request.send().await?;
+
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED && etag.is_some() {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                metrics().record_api_error();
+                return Err(Error::Api(format!(
+                    "Failed to download {}: {}",
+                    url,
+                    response.status()
+                )));
+            }
+
+            let etag = response
+                .headers()
+                .get(reqwest::header::ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            return Ok(Some((response.json().await?, etag)));
+        }
+
         // Get asset ID first
         let _asset_id = self.get_game_manifest(token, app_name).await?;
 
@@ -413,19 +921,27 @@ impl EpicClient {
         // This allows the installation process to proceed
         log::warn!("Using mock manifest data - real CDN download not implemented");
 
-        Ok(GameManifest {
-            manifest_file_version: "21".to_string(),
-            is_file_data: true,
-            app_name: app_name.to_string(),
-            app_version: "1.0.0".to_string(),
-            launch_exe: format!("{}.exe", app_name),
-            launch_command: String::new(),
-            build_size: 0,
-            file_list: Vec::new(),
-            chunk_hash_list: std::collections::HashMap::new(),
-            chunk_sha_list: std::collections::HashMap::new(),
-            data_group_list: std::collections::HashMap::new(),
-        })
+        Ok(Some((
+            GameManifest {
+                manifest_file_version: "21".to_string(),
+                is_file_data: true,
+                app_name: app_name.to_string(),
+                app_version: "1.0.0".to_string(),
+                launch_exe: format!("{}.exe", app_name),
+                launch_command: String::new(),
+                build_size: 0,
+                file_list: Vec::new(),
+                chunk_hash_list: std::collections::HashMap::new(),
+                chunk_sha_list: std::collections::HashMap::new(),
+                data_group_list: std::collections::HashMap::new(),
+                chunk_filesize_list: std::collections::HashMap::new(),
+                prereq_ids: Vec::new(),
+                prereq_name: String::new(),
+                prereq_path: String::new(),
+                prereq_args: String::new(),
+            },
+            None,
+        )))
     }
 
     /// Download a game chunk
@@ -442,6 +958,11 @@ impl EpicClient {
 
         log::debug!("Downloading chunk: {}", chunk_guid);
 
+        if let Some(base) = &self.cdn_base_url {
+            let path = format!("chunks/{}", chunk_guid);
+            return Ok(self.cdn_get(base, &path).await?.bytes().await?.to_vec());
+        }
+
         // In a real implementation:
         // 1. Construct CDN URL for the chunk
         // 2. Download the chunk data
@@ -477,6 +998,30 @@ impl EpicClient {
         }
     }
 
+    /// How much of the account's cloud save storage is used, in total and
+    /// by each game
+    pub async fn get_save_quota(&self, token: &AuthToken) -> Result<SaveQuota> {
+        let url = format!("{}/{}/quota", DATASTORAGE_API_URL, token.account_id);
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            metrics().record_api_error();
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::Api(format!(
+                "Failed to fetch cloud save quota: {} - {}",
+                status, error_text
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
     /// Get cloud saves for a game
     pub async fn get_cloud_saves(
         &self,
@@ -519,34 +1064,80 @@ impl EpicClient {
         Ok(Vec::new())
     }
 
-    /// Upload a cloud save file
+    /// Upload a cloud save file. Refused for now, see
+    /// [`SAVE_UPLOADS_ENABLED`]
     pub async fn upload_cloud_save(
         &self,
         _token: &AuthToken,
         app_name: &str,
-        save_data: &[u8],
+        filename: &str,
+        _save_data: &[u8],
     ) -> Result<()> {
-        // TODO: Implement cloud save upload
-        // TODO: Request upload URL from Epic API
         // TODO: Encrypt saves if required by game
-        // TODO: Handle upload conflicts with existing saves
-        // TODO: Implement save metadata (timestamp, game version)
-        // TODO: Add upload progress reporting for large saves
-
-        log::info!(
-            "Uploading cloud save for {} ({} bytes)",
-            app_name,
-            save_data.len()
-        );
+        // TODO: Upload in Epic's manifest and chunk format, in parts with
+        // per-part retry, resuming cut-off uploads
 
-        // In a real implementation:
-        // 1. Get upload URL from API
-        // 2. Upload save data
-        // 3. Verify upload success
+        log::warn!("Not uploading cloud save {} for {}", filename, app_name);
+        Err(Error::SaveUploadsUnsupported)
+    }
+}
+
+/// Cloud save storage of an account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveQuota {
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+    /// Bytes used by each game, by app name
+    #[serde(default)]
+    pub apps: std::collections::HashMap<String, u64>,
+}
 
-        log::warn!("Cloud save upload not implemented");
-        Ok(())
+impl SaveQuota {
+    /// Bytes used by one game's saves
+    pub fn used_by(&self, app_name: &str) -> u64 {
+        self.apps.get(app_name).copied().unwrap_or(0)
     }
+
+    /// Bytes still free
+    pub fn available(&self) -> u64 {
+        self.quota_bytes.saturating_sub(self.used_bytes)
+    }
+}
+
+/// Catalog items with a main game are add-ons; resolve both sides to app names
+fn classify_dlcs(records: &[LibraryItem], items: Vec<CatalogBulkItem>) -> Vec<Dlc> {
+    let app_name_for = |catalog_id: &str| {
+        records
+            .iter()
+            .find(|r| r.catalog_item_id == catalog_id)
+            .map(|r| r.app_name.clone())
+    };
+
+    let mut dlcs: Vec<Dlc> = items
+        .into_iter()
+        .filter_map(|item| {
+            let main_game = item.main_game_item?;
+            let app_name = app_name_for(&item.id)
+                .or_else(|| item.release_info.into_iter().find_map(|r| r.app_id))
+                .unwrap_or(item.id);
+
+            Some(Dlc {
+                app_name,
+                app_title: item.title,
+                base_game: app_name_for(&main_game.id).unwrap_or(main_game.id),
+            })
+        })
+        .collect();
+
+    dlcs.sort_by(|a, b| a.app_title.cmp(&b.app_title));
+    dlcs
+}
+
+fn owns_item(entitlements: &[Entitlement], item: &LibraryItem) -> bool {
+    entitlements.iter().any(|e| {
+        e.active && e.namespace == item.namespace && e.catalog_item_id == item.catalog_item_id
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -574,6 +1165,22 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_unix_mode_prefers_manifest_flags() {
+        let file =
+            |filename: &str, executable: Option<bool>, read_only: Option<bool>| FileManifest {
+                filename: filename.to_string(),
+                is_unix_executable: executable,
+                is_read_only: read_only,
+                ..Default::default()
+            };
+
+        assert_eq!(file("start.sh", None, None).unix_mode(), 0o755);
+        assert_eq!(file("data.pak", None, None).unix_mode(), 0o644);
+        assert_eq!(file("start.sh", Some(false), None).unix_mode(), 0o644);
+        assert_eq!(file("Game", Some(true), Some(true)).unix_mode(), 0o555);
+    }
+
     #[test]
     fn test_game_serialization() {
         let game = Game {
@@ -600,6 +1207,19 @@ mod tests {
         assert_eq!(response.expires_in, 3600);
     }
 
+    #[test]
+    fn test_save_quota_deserialization() {
+        let json = r#"{
+            "usedBytes": 400,
+            "quotaBytes": 1000,
+            "apps": { "Fortnite": 300, "Hades": 100 }
+        }"#;
+        let quota: SaveQuota = serde_json::from_str(json).unwrap();
+        assert_eq!(quota.used_by("Fortnite"), 300);
+        assert_eq!(quota.used_by("Celeste"), 0);
+        assert_eq!(quota.available(), 600);
+    }
+
     #[test]
     fn test_library_response_deserialization() {
         let json = r#"{
@@ -615,4 +1235,99 @@ mod tests {
         assert_eq!(response.records.len(), 1);
         assert_eq!(response.records[0].app_name, "Fortnite");
     }
+
+    #[test]
+    fn test_classify_dlcs_links_base_game() {
+        let records: Vec<LibraryItem> = serde_json::from_str(
+            r#"[
+                {"appName": "BaseGame", "namespace": "ns", "catalogItemId": "base"},
+                {"appName": "Expansion", "namespace": "ns", "catalogItemId": "dlc"}
+            ]"#,
+        )
+        .unwrap();
+        let items: std::collections::HashMap<String, CatalogBulkItem> = serde_json::from_str(
+            r#"{
+                "base": {"id": "base", "title": "Base Game"},
+                "dlc": {"id": "dlc", "title": "Expansion Pack", "mainGameItem": {"id": "base"}}
+            }"#,
+        )
+        .unwrap();
+
+        let dlcs = classify_dlcs(&records, items.into_values().collect());
+        assert_eq!(dlcs.len(), 1);
+        assert_eq!(dlcs[0].app_name, "Expansion");
+        assert_eq!(dlcs[0].app_title, "Expansion Pack");
+        assert_eq!(dlcs[0].base_game, "BaseGame");
+    }
+
+    #[tokio::test]
+    async fn test_save_uploads_write_nothing_while_disabled() {
+        let client = EpicClient::new().unwrap();
+        let token = AuthToken {
+            access_token: "token".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_at: chrono::Utc::now(),
+            account_id: "account".to_string(),
+        };
+
+        let upload = client
+            .upload_cloud_save(&token, "Fortnite", "slot1.sav", b"save")
+            .await;
+
+        assert!(matches!(upload, Err(Error::SaveUploadsUnsupported)));
+    }
+
+    #[test]
+    fn test_catalog_item_save_folder() {
+        let item: CatalogBulkItem = serde_json::from_str(
+            r#"{
+                "id": "base",
+                "title": "Base Game",
+                "customAttributes": {
+                    "CloudSaveFolder": {"type": "STRING", "value": "{AppData}/Game/Saved"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            item.custom_attributes["CloudSaveFolder"].value,
+            "{AppData}/Game/Saved"
+        );
+    }
+
+    #[test]
+    fn test_owns_item_requires_active_entitlement() {
+        let item = LibraryItem {
+            app_name: "Fortnite".to_string(),
+            namespace: "fn".to_string(),
+            catalog_item_id: "4fe75bbc".to_string(),
+        };
+        let json = r#"[
+            {"namespace": "fn", "catalogItemId": "4fe75bbc", "active": false},
+            {"namespace": "other", "catalogItemId": "4fe75bbc"}
+        ]"#;
+        let entitlements: Vec<Entitlement> = serde_json::from_str(json).unwrap();
+        assert!(!owns_item(&entitlements, &item));
+
+        let json = r#"[{"namespace": "fn", "catalogItemId": "4fe75bbc"}]"#;
+        let entitlements: Vec<Entitlement> = serde_json::from_str(json).unwrap();
+        assert!(owns_item(&entitlements, &item));
+    }
+
+    #[test]
+    fn test_file_manifest_attributes() {
+        let json = r#"{
+            "Filename": "Game/run.sh",
+            "FileHash": [],
+            "FileChunkParts": [],
+            "bIsUnixExecutable": true,
+            "bIsReadOnly": true
+        }"#;
+        let file: FileManifest = serde_json::from_str(json).unwrap();
+        assert_eq!(file.unix_mode(), 0o555);
+
+        let plain = FileManifest::default();
+        assert_eq!(plain.unix_mode(), 0o644);
+    }
 }
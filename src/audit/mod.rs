@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::{storage, Config};
+use crate::Result;
+
+/// Which front-end triggered an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Initiator {
+    Cli,
+    Gui,
+    Daemon,
+}
+
+impl std::fmt::Display for Initiator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Initiator::Cli => write!(f, "cli"),
+            Initiator::Gui => write!(f, "gui"),
+            Initiator::Daemon => write!(f, "daemon"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Uninstall,
+    FileDeletion,
+    SaveOverwrite,
+    Rollback,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::Uninstall => write!(f, "uninstall"),
+            AuditAction::FileDeletion => write!(f, "file_deletion"),
+            AuditAction::SaveOverwrite => write!(f, "save_overwrite"),
+            AuditAction::Rollback => write!(f, "rollback"),
+        }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub initiator: Initiator,
+    pub action: AuditAction,
+    pub app_name: Option<String>,
+    pub paths: Vec<PathBuf>,
+}
+
+impl AuditEntry {
+    pub fn new(initiator: Initiator, action: AuditAction, app_name: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            initiator,
+            action,
+            app_name: Some(app_name.to_string()),
+            paths: Vec::new(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+}
+
+/// Append an entry to the audit log. Failures are logged rather than
+/// propagated so auditing never blocks the operation being recorded.
+pub fn record(entry: AuditEntry) {
+    let result = audit_log_path().and_then(|path| append_entry(&path, &entry));
+
+    if let Err(e) = result {
+        log::warn!("Failed to write audit log entry: {}", e);
+    }
+}
+
+/// Read all recorded entries, oldest first. Unparseable lines are skipped.
+pub fn read_entries() -> Result<Vec<AuditEntry>> {
+    read_entries_from(&audit_log_path()?)
+}
+
+pub fn audit_log_path() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("audit.log"))
+}
+
+fn append_entry(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        storage::create_private_dir(parent)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, storage::PRIVATE_FILE_MODE);
+
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+    Ok(())
+}
+
+fn read_entries_from(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_audit_log_appends_entries() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit.log");
+
+        let first = AuditEntry::new(Initiator::Cli, AuditAction::Uninstall, "game_a")
+            .with_path("/games/game_a");
+        let second = AuditEntry::new(Initiator::Gui, AuditAction::SaveOverwrite, "game_b");
+
+        append_entry(&path, &first).unwrap();
+        append_entry(&path, &second).unwrap();
+
+        let entries = read_entries_from(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, AuditAction::Uninstall);
+        assert_eq!(entries[0].paths, vec![PathBuf::from("/games/game_a")]);
+        assert_eq!(entries[1].initiator, Initiator::Gui);
+    }
+
+    #[test]
+    fn test_audit_log_skips_corrupt_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit.log");
+
+        fs::write(&path, "not json\n").unwrap();
+        append_entry(
+            &path,
+            &AuditEntry::new(Initiator::Daemon, AuditAction::Rollback, "game"),
+        )
+        .unwrap();
+
+        let entries = read_entries_from(&path).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+}
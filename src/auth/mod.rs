@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-use crate::config::Config;
+use crate::config::{storage, Config};
 use crate::{Error, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,21 +25,8 @@ impl AuthToken {
 
         let auth_path = Self::auth_path()?;
 
-        if let Some(parent) = auth_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
         let contents = serde_json::to_string_pretty(self)?;
-        fs::write(&auth_path, &contents)?;
-
-        // Set restrictive file permissions (0600) on Unix systems
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&auth_path)?.permissions();
-            perms.set_mode(0o600);
-            fs::set_permissions(&auth_path, perms)?;
-        }
+        storage::write_private(&auth_path, contents)?;
 
         Ok(())
     }
@@ -102,6 +89,12 @@ impl AuthManager {
         }
     }
 
+    /// Account of the stored session, even once it has expired. Offline
+    /// launches identify the player by it without needing a valid token.
+    pub fn account_id(&self) -> Option<&str> {
+        self.token.as_ref().map(|t| t.account_id.as_str())
+    }
+
     /// Check if token will expire soon (within 5 minutes)
     pub fn token_needs_refresh(&self) -> bool {
         if let Some(token) = &self.token {
@@ -164,4 +157,18 @@ mod tests {
         };
         assert!(!valid_token.is_expired());
     }
+
+    #[test]
+    fn test_expired_session_keeps_account_id() {
+        let manager = AuthManager {
+            token: Some(AuthToken {
+                access_token: "test".to_string(),
+                refresh_token: "test".to_string(),
+                expires_at: Utc::now() - chrono::Duration::hours(1),
+                account_id: "account".to_string(),
+            }),
+        };
+        assert!(manager.get_token().is_err());
+        assert_eq!(manager.account_id(), Some("account"));
+    }
 }
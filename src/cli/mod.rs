@@ -1,4 +1,10 @@
-use clap::{Parser, Subcommand};
+pub mod prompt;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::games::Gpu;
+use crate::i18n;
 
 #[derive(Parser)]
 #[command(name = "rauncher")]
@@ -7,9 +13,119 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Enable verbose logging
-    #[arg(short, long, global = true)]
-    pub verbose: bool,
+    /// Increase log verbosity (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Per-module log filter, e.g. `api=trace,games=debug`
+    #[arg(long, value_name = "FILTER", global = true)]
+    pub log_filter: Option<String>,
+
+    /// Never prompt; use configured defaults for every decision
+    #[arg(
+        short = 'y',
+        long = "yes",
+        visible_alias = "non-interactive",
+        global = true
+    )]
+    pub non_interactive: bool,
+
+    /// Keep config, data and cache in this directory (also RAUNCHER_DATA_DIR)
+    #[arg(long, value_name = "DIR", global = true)]
+    pub data_dir: Option<PathBuf>,
+
+    /// Report a failure as JSON with its error code, hint and help URL
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+
+    /// Scale the GUI's text and controls, e.g. 2 for a TV; overrides `ui_scale`
+    #[arg(long, value_name = "FACTOR", value_parser = parse_scale, global = true)]
+    pub scale: Option<f32>,
+}
+
+/// Top-level modules of this crate, which `--log-filter` accepts without the
+/// `rauncher::` prefix
+const CRATE_MODULES: &[&str] = &[
+    "api", "audit", "auth", "cli", "config", "egl", "games", "gui", "i18n", "metrics",
+];
+
+impl Cli {
+    /// Build an env_logger filter from the verbosity flags, or `None` when
+    /// neither was given so `RUST_LOG` keeps working as before
+    pub fn log_filter(&self) -> Option<String> {
+        if self.verbose == 0 && self.log_filter.is_none() {
+            return None;
+        }
+
+        let level = match self.verbose {
+            0 => "warn",
+            1 => "info",
+            2 => "debug",
+            _ => "trace",
+        };
+
+        let mut directives = vec![level.to_string()];
+        if let Some(filter) = &self.log_filter {
+            directives.extend(
+                filter
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                    .map(qualify_directive),
+            );
+        }
+
+        Some(directives.join(","))
+    }
+}
+
+fn parse_scale(value: &str) -> Result<f32, String> {
+    let scale: f32 = value
+        .parse()
+        .map_err(|_| format!("'{}' isn't a number", value))?;
+    if !crate::config::UI_SCALE_RANGE.contains(&scale) {
+        return Err(format!(
+            "must be between {} and {}",
+            crate::config::UI_SCALE_RANGE.start(),
+            crate::config::UI_SCALE_RANGE.end()
+        ));
+    }
+    Ok(scale)
+}
+
+fn qualify_directive(directive: &str) -> String {
+    let module = directive.split('=').next().unwrap_or(directive);
+
+    if CRATE_MODULES.contains(&module) {
+        format!("rauncher::{}", directive)
+    } else {
+        directive.to_string()
+    }
+}
+
+/// The command line with its help in the user's language. The English help
+/// is the doc comments here; a catalog translates it with `cli-<command>`
+/// messages for commands and `cli-<command>--<arg>` for their arguments,
+/// e.g. `cli-config-set` and `cli--verbose`.
+pub fn localized_command() -> clap::Command {
+    localize(Cli::command(), "cli")
+}
+
+fn localize(mut command: clap::Command, id: &str) -> clap::Command {
+    if let Some(about) = i18n::translation(id) {
+        command = command.about(about);
+    }
+    command
+        .mut_args(
+            |arg| match i18n::translation(&format!("{}--{}", id, arg.get_id())) {
+                Some(help) => arg.help(help),
+                None => arg,
+            },
+        )
+        .mut_subcommands(|sub| {
+            let sub_id = format!("{}-{}", id, sub.get_name());
+            localize(sub, &sub_id)
+        })
 }
 
 #[derive(Subcommand)]
@@ -26,43 +142,211 @@ pub enum Commands {
         /// Show installed games only
         #[arg(short, long)]
         installed: bool,
+
+        /// Show owned DLC nested under its base game
+        #[arg(long, conflicts_with = "installed")]
+        include_dlc: bool,
+
+        /// Show only DLC
+        #[arg(long, conflicts_with_all = ["installed", "include_dlc"])]
+        dlc_only: bool,
     },
 
-    /// Install a game
+    /// Search the library by title or app name
+    Search {
+        /// Text to look for; letters may be spread out, e.g. "rktlg"
+        query: String,
+
+        /// Only show installed games
+        #[arg(long, conflicts_with = "not_installed")]
+        installed: bool,
+
+        /// Only show games that aren't installed
+        #[arg(long)]
+        not_installed: bool,
+
+        /// Fetch the library from Epic instead of using the cached copy
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Install one or more games
     Install {
-        /// App name of the game to install
+        /// App names of the games to install
+        #[arg(required_unless_present = "file")]
+        app_names: Vec<String>,
+
+        /// Read app names from a file, one per line
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+
+        /// Start each game briefly after installing to check it doesn't crash
+        #[arg(long)]
+        verify_launch: bool,
+
+        /// Download now, outside the configured download windows
+        #[arg(long)]
+        now: bool,
+    },
+
+    /// Register an existing installation of a game
+    Import {
+        /// App name of the game to import
         app_name: String,
+
+        /// Directory containing the game files
+        path: PathBuf,
+
+        /// Re-download files that don't match the manifest instead of failing
+        #[arg(short, long)]
+        repair: bool,
     },
 
     /// Launch a game
     Launch {
         /// App name of the game to launch
         app_name: String,
+
+        /// Confirm the game is still owned before launching
+        #[arg(long, conflicts_with = "offline")]
+        verify_entitlement: bool,
+
+        /// Launch without network access, skipping account and cloud-save steps
+        #[arg(long)]
+        offline: bool,
+
+        /// Run through this Wine binary for this launch
+        #[arg(long, value_name = "PATH", group = "runner")]
+        wine: Option<PathBuf>,
+
+        /// Run through this Proton build (e.g. GE-Proton9-20) for this launch
+        #[arg(long, value_name = "VERSION", group = "runner")]
+        proton: Option<String>,
+
+        /// Run the executable directly for this launch
+        #[arg(long, group = "runner")]
+        no_wine: bool,
+
+        /// Render on this GPU for this launch
+        #[arg(long, value_enum)]
+        gpu: Option<Gpu>,
+    },
+
+    /// Set or remove the parental PIN that guards the daily play time limit
+    ParentalPin {
+        /// Remove the PIN and the daily limit it protects
+        #[arg(long)]
+        clear: bool,
     },
 
     /// Uninstall a game
     Uninstall {
         /// App name of the game to uninstall
         app_name: String,
+
+        /// Move the game files to the trash instead of deleting them
+        #[arg(short, long)]
+        trash: bool,
+    },
+
+    /// Move an installed game to another directory
+    Move {
+        /// App name of the game to move
+        app_name: String,
+
+        /// Directory to move the game into
+        destination: PathBuf,
+    },
+
+    /// Compress a rarely played game into a disk image to save space
+    Archive {
+        /// App name of the game to archive
+        app_name: String,
+
+        /// Only estimate how much space archiving would save
+        #[arg(short, long, conflicts_with = "restore")]
+        estimate: bool,
+
+        /// Extract an archived game back into place. Launching one extracts it
+        /// only until the session ends; this keeps it extracted.
+        #[arg(short, long)]
+        restore: bool,
+    },
+
+    /// Verify installed game files against the manifest
+    Verify {
+        /// App name of the game to verify
+        app_name: String,
+
+        /// Only re-hash files whose size or modification time changed since
+        /// the last verify
+        #[arg(long)]
+        incremental: bool,
+    },
+
+    /// Re-download files that fail verification
+    Repair {
+        /// App name of the game to repair
+        app_name: String,
     },
 
     /// Show information about a game
     Info {
         /// App name of the game
         app_name: String,
+
+        /// Fetch the latest manifest to show sizes and version, even if not installed
+        #[arg(long)]
+        remote: bool,
     },
 
     /// Show status and configuration
     Status,
 
+    /// Check whether a newer rauncher release is available
+    CheckUpdate {
+        /// Include release candidates, whatever the configured channel
+        #[arg(long)]
+        beta: bool,
+    },
+
+    /// Read or change settings without editing config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Check for game updates
     Update {
         /// App name of the game to check/update
-        app_name: String,
+        #[arg(required_unless_present = "all")]
+        app_name: Option<String>,
+
+        /// Update every installed game whose updates aren't on hold
+        #[arg(long, conflicts_with_all = ["app_name", "hold", "unhold"])]
+        all: bool,
+
+        /// Keep this game on its installed version
+        #[arg(long, conflicts_with_all = ["check_only", "unhold"])]
+        hold: bool,
+
+        /// Let this game be updated again
+        #[arg(long, conflicts_with = "check_only")]
+        unhold: bool,
 
         /// Only check for updates, don't install them
         #[arg(short, long)]
         check_only: bool,
+
+        /// Download now, outside the configured download windows
+        #[arg(long)]
+        now: bool,
+    },
+
+    /// Go back to the version a game had before its last update
+    Rollback {
+        /// App name of the game
+        app_name: String,
     },
 
     /// Manage cloud saves
@@ -77,8 +361,316 @@ pub enum Commands {
         /// Upload local saves to cloud
         #[arg(short, long)]
         upload: bool,
+
+        /// Only show which files --download/--upload would transfer, skip or
+        /// flag as conflicts
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Turn cloud-save sync on for this game
+        #[arg(long, conflicts_with_all = ["download", "upload", "dry_run", "disable_sync"])]
+        enable_sync: bool,
+
+        /// Turn cloud-save sync off for this game
+        #[arg(long, conflicts_with_all = ["download", "upload", "dry_run"])]
+        disable_sync: bool,
+    },
+
+    /// Show what a game printed during its last session
+    Logs {
+        /// App name of the game
+        app_name: String,
+
+        /// List the kept logs of earlier sessions instead
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Add a game to the desktop's application menu
+    Shortcut {
+        /// App name of the game
+        app_name: String,
+
+        /// Remove the game's menu entry instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Add installed games to Steam as non-Steam games, with their artwork
+    SteamExport {
+        /// App names of the games; every installed game when none are given
+        app_names: Vec<String>,
+    },
+
+    /// Write Lutris installers for installed games, to import with `lutris -i`
+    LutrisExport {
+        /// App names of the games; every installed game when none are given
+        app_names: Vec<String>,
+
+        /// Directory for the installers (default: lutris/ in the data directory)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Inspect cloud and local saves
+    Saves {
+        #[command(subcommand)]
+        action: SavesAction,
+    },
+
+    /// Manage a game's Wine or Proton prefix
+    Prefix {
+        #[command(subcommand)]
+        action: PrefixAction,
     },
 
+    /// Wine and Proton builds that can run Windows games
+    Runner {
+        #[command(subcommand)]
+        action: RunnerAction,
+    },
+
+    /// Sync installed games with the Epic Games Launcher in a Wine prefix
+    EglSync {
+        /// Path to the Wine prefix containing the Epic Games Launcher
+        #[arg(short, long)]
+        prefix: PathBuf,
+
+        /// Only import games installed by the Epic Games Launcher
+        #[arg(long, conflicts_with = "export_only")]
+        import_only: bool,
+
+        /// Only export games installed by rauncher
+        #[arg(long)]
+        export_only: bool,
+    },
+
+    /// Remove caches, orphaned manifests, update backups and partial downloads
+    Clean {
+        /// Only show what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show how much the download and shader caches hold
+    Cache {
+        /// Drop expired entries and evict old ones until under the size limit
+        #[arg(long)]
+        prune: bool,
+
+        /// Delete the DXVK/VKD3D shader caches of these games, or of every
+        /// game when none are given
+        #[arg(long, num_args = 0.., value_name = "APP_NAME")]
+        clear_shaders: Option<Vec<String>>,
+    },
+
+    /// Check launcher metadata for problems
+    Doctor {
+        /// Rebuild unreadable installed-game records
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Finish installs, updates and uninstalls interrupted by a crash
+    Resume {
+        /// Undo the interrupted operations instead of finishing them
+        #[arg(long)]
+        rollback: bool,
+    },
+
+    /// Show past launcher operations
+    History {
+        /// Show the audit log of destructive operations
+        #[arg(long)]
+        audit: bool,
+
+        /// Maximum number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Keep running and check installed games for updates every
+    /// `auto_update_interval_hours`
+    Daemon,
+
     /// Launch the GUI
     Gui,
 }
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Show every setting and its value
+    List,
+
+    /// Show one setting, e.g. `time_limits.daily_limit_minutes`
+    Get { key: String },
+
+    /// Change a setting; the value is checked before it is saved
+    Set { key: String, value: String },
+
+    /// Put a setting back to its default
+    Unset { key: String },
+}
+
+#[derive(Subcommand)]
+pub enum SavesAction {
+    /// Show cloud saves and the local files `cloud-save --upload` would send
+    List {
+        /// App name of the game
+        app_name: String,
+    },
+
+    /// Show the earlier versions of save files that syncs replaced
+    History {
+        /// App name of the game
+        app_name: String,
+    },
+
+    /// Pack the game's save folder into a timestamped .tar.zst, for offline backups
+    Backup {
+        /// App name of the game
+        app_name: String,
+
+        /// Directory for the archive (default: save-backups/ in the data directory)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Put an earlier version of a save file back, or restore a backup
+    Restore {
+        /// App name of the game
+        app_name: String,
+
+        /// Version id from `saves history`, or enough of its start to tell it apart
+        #[arg(required_unless_present = "from")]
+        version: Option<String>,
+
+        /// Restore every file of a backup made by `saves backup` instead
+        #[arg(long, value_name = "ARCHIVE", conflicts_with = "version")]
+        from: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PrefixAction {
+    /// Run winetricks on the game's prefix, e.g. to install dxvk or vcrun2019
+    Winetricks {
+        /// App name of the game
+        app_name: String,
+
+        /// Winetricks verbs; opens the winetricks GUI when omitted
+        verbs: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RunnerAction {
+    /// Show the runners found on this machine
+    List {
+        /// List Proton-GE builds that can be installed instead
+        #[arg(long)]
+        available: bool,
+
+        /// With --available, list Wine-GE builds
+        #[arg(long, requires = "available")]
+        wine: bool,
+    },
+
+    /// Download a Proton-GE build into the launcher's runners directory
+    Install {
+        /// Release tag, e.g. GE-Proton9-20; the newest release when omitted
+        tag: Option<String>,
+
+        /// Install a Wine-GE build instead
+        #[arg(long)]
+        wine: bool,
+    },
+
+    /// Delete a build added by `runner install`
+    Remove {
+        /// Name shown by `runner list`
+        name: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_levels() {
+        let cli = Cli::parse_from(["rauncher", "-vv", "list"]);
+        assert_eq!(cli.log_filter().as_deref(), Some("debug"));
+
+        let cli = Cli::parse_from(["rauncher", "list"]);
+        assert_eq!(cli.log_filter(), None);
+    }
+
+    #[test]
+    fn test_log_filter_qualifies_crate_modules() {
+        let cli = Cli::parse_from([
+            "rauncher",
+            "--log-filter",
+            "api=trace,games=debug,reqwest=info",
+            "list",
+        ]);
+        assert_eq!(
+            cli.log_filter().as_deref(),
+            Some("warn,rauncher::api=trace,rauncher::games=debug,reqwest=info")
+        );
+    }
+
+    /// Every message id `localize` could look up, for `command` named `id`
+    fn help_ids(command: &clap::Command, id: &str, ids: &mut Vec<String>) {
+        ids.push(id.to_string());
+        ids.extend(
+            command
+                .get_arguments()
+                .map(|arg| format!("{}--{}", id, arg.get_id())),
+        );
+        for sub in command.get_subcommands() {
+            help_ids(sub, &format!("{}-{}", id, sub.get_name()), ids);
+        }
+    }
+
+    #[test]
+    fn test_help_translations_name_real_commands() {
+        let mut ids = Vec::new();
+        help_ids(&Cli::command(), "cli", &mut ids);
+
+        for language in i18n::languages() {
+            let catalog = i18n::catalog(language).unwrap();
+            for id in catalog.ids().filter(|id| id.starts_with("cli")) {
+                assert!(
+                    ids.iter().any(|known| known == id),
+                    "{}: no {}",
+                    language,
+                    id
+                );
+            }
+        }
+        assert!(localized_command()
+            .try_get_matches_from(["rauncher", "list"])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_scale_is_checked() {
+        let cli = Cli::parse_from(["rauncher", "gui", "--scale", "1.5"]);
+        assert_eq!(cli.scale, Some(1.5));
+
+        assert!(Cli::try_parse_from(["rauncher", "--scale", "10"]).is_err());
+        assert!(Cli::try_parse_from(["rauncher", "--scale", "big"]).is_err());
+    }
+
+    #[test]
+    fn test_update_needs_a_game_unless_all() {
+        assert!(Cli::try_parse_from(["rauncher", "update"]).is_err());
+        assert!(Cli::try_parse_from(["rauncher", "update", "--all"]).is_ok());
+        assert!(Cli::try_parse_from(["rauncher", "update", "Fortnite", "--hold"]).is_ok());
+        assert!(Cli::try_parse_from(["rauncher", "update", "--all", "--hold"]).is_err());
+        assert!(
+            Cli::try_parse_from(["rauncher", "update", "Fortnite", "--hold", "--unhold"]).is_err()
+        );
+    }
+}
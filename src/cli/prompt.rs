@@ -0,0 +1,87 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Disable all prompts; callers fall back to their default answers
+pub fn set_non_interactive(non_interactive: bool) {
+    NON_INTERACTIVE.store(non_interactive, Ordering::Relaxed);
+}
+
+/// Prompts are shown only when not disabled and stdin is a terminal
+pub fn is_interactive() -> bool {
+    !NON_INTERACTIVE.load(Ordering::Relaxed) && io::stdin().is_terminal()
+}
+
+/// Ask a yes/no question, returning `default` when prompts are disabled
+pub fn confirm(question: &str, default: bool) -> bool {
+    if !is_interactive() {
+        return default;
+    }
+
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+
+    match read_answer(&format!("{} {} ", question, hint)) {
+        Some(answer) if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") => {
+            true
+        }
+        Some(answer) if answer.eq_ignore_ascii_case("n") || answer.eq_ignore_ascii_case("no") => {
+            false
+        }
+        _ => default,
+    }
+}
+
+/// Ask the user to pick one of `options`, returning the chosen index.
+/// Returns `default` when prompts are disabled or the answer is invalid.
+pub fn choose(question: &str, options: &[&str], default: usize) -> usize {
+    if !is_interactive() {
+        return default;
+    }
+
+    println!("{}", question);
+    for (idx, option) in options.iter().enumerate() {
+        let marker = if idx == default { " (default)" } else { "" };
+        println!("  [{}] {}{}", idx + 1, option, marker);
+    }
+
+    read_answer("Choice: ")
+        .and_then(|answer| answer.parse::<usize>().ok())
+        .filter(|choice| (1..=options.len()).contains(choice))
+        .map(|choice| choice - 1)
+        .unwrap_or(default)
+}
+
+/// Ask for free-form input, or `None` when prompts are disabled
+pub fn ask(question: &str) -> Option<String> {
+    if !is_interactive() {
+        return None;
+    }
+
+    read_answer(&format!("{} ", question))
+}
+
+fn read_answer(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().ok()?;
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).ok()?;
+
+    Some(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_interactive_returns_defaults() {
+        set_non_interactive(true);
+
+        assert!(confirm("Continue?", true));
+        assert!(!confirm("Continue?", false));
+        assert_eq!(choose("Pick one", &["first", "second"], 1), 1);
+        assert_eq!(ask("Name?"), None);
+    }
+}
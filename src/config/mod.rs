@@ -1,32 +1,380 @@
+mod pin;
+pub mod storage;
+mod watch;
+
+pub use watch::ConfigWatcher;
+
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
+use crate::games::{self, DownloadWindow, Gpu, Runner, SaveConflictPolicy, SaveFilter};
+use crate::update::UpdateChannel;
 use crate::{Error, Result};
 
 // TODO: Add more configuration options:
-// - download_threads: Number of concurrent downloads
-// - bandwidth_limit: Optional download speed limit
 // - cdn_region: Preferred CDN region
-// - auto_update: Auto-update games in background
 // - proxy_settings: HTTP/SOCKS proxy configuration
-// - cache_size: Maximum cache size for manifests/metadata
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub install_dir: PathBuf,
     pub log_level: String,
+    /// Address to serve Prometheus metrics on (e.g. "127.0.0.1:9897"), disabled when unset
+    #[serde(default)]
+    pub metrics_address: Option<String>,
+    /// Move uninstalled games to the trash instead of deleting them
+    #[serde(default)]
+    pub uninstall_to_trash: bool,
+    /// Days to keep games in the launcher's own trash area before purging
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Confirm ownership with Epic before launching a game
+    #[serde(default)]
+    pub verify_entitlement_on_launch: bool,
+    /// Games directory is shared between users; launcher metadata stays per-user
+    #[serde(default)]
+    pub shared_install_dir: bool,
+    /// Runner for every game; detected from the executable when unset
+    #[serde(default)]
+    pub runner: Option<Runner>,
+    /// Per-game `runner` overrides, keyed by app name
+    #[serde(default)]
+    pub game_runners: HashMap<String, Runner>,
+    /// GPU each game renders on, keyed by app name; games not listed use
+    /// the system default
+    #[serde(default)]
+    pub game_gpus: HashMap<String, Gpu>,
+    /// Per-game launch templates such as `gamemoderun %command% -dx11`,
+    /// keyed by app name
+    #[serde(default)]
+    pub game_wrappers: HashMap<String, String>,
+    /// Briefly start every game after installing it to catch instant crashes
+    #[serde(default)]
+    pub verify_launch_after_install: bool,
+    /// Playtime reminders and the PIN-protected daily limit
+    #[serde(default)]
+    pub time_limits: TimeLimits,
+    /// Restore window size and library filters when the GUI starts
+    #[serde(default = "default_remember_gui_state")]
+    pub remember_gui_state: bool,
+    /// Size of the GUI's text and controls, e.g. 1.5 for a TV across the
+    /// room; multiplies the display's own scale
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    /// Where GUI state is kept; `gui-state.ron` in the data directory when unset
+    #[serde(default)]
+    pub gui_state_file: Option<PathBuf>,
+    /// Launcher releases to offer: "stable", or "beta" to include release candidates
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+    /// Mirror to download manifests and chunks from instead of Epic's CDN
+    #[serde(default)]
+    pub cdn_base_url: Option<String>,
+    /// Hours a cache entry is kept without being used
+    #[serde(default = "default_cache_ttl_hours")]
+    pub cache_ttl_hours: u64,
+    /// Largest the cache may grow before old entries are evicted; 0 disables it
+    #[serde(default = "default_cache_size_mb")]
+    pub cache_size_mb: u64,
+    /// Also cache downloaded chunks, so chunks shared by several files are
+    /// fetched once. Each cached chunk is a second copy of game data on disk.
+    #[serde(default)]
+    pub cache_chunks: bool,
+    /// Download speed limit in KiB/s, shared by all downloads; unlimited when unset
+    #[serde(default)]
+    pub bandwidth_limit: Option<u64>,
+    /// Times of day downloads may run, e.g. ["01:00-07:00"]; any time when empty
+    #[serde(default)]
+    pub download_windows: Vec<DownloadWindow>,
+    /// Games a queued install downloads at the same time
+    #[serde(default = "default_max_concurrent_games")]
+    pub max_concurrent_games: usize,
+    /// Files of one game downloaded in parallel
+    #[serde(default = "default_workers_per_download")]
+    pub workers_per_download: usize,
+    /// Earlier builds of each game kept for `rauncher rollback`; 0 keeps none
+    #[serde(default = "default_rollback_versions")]
+    pub rollback_versions: usize,
+    /// Preferred game language, e.g. "de" or "pt-BR"; packs for other
+    /// languages are skipped. Games use their default when unset.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Per-game `language` overrides, keyed by app name
+    #[serde(default)]
+    pub game_languages: HashMap<String, String>,
+    /// Download cloud saves before a game starts and upload them after it exits
+    #[serde(default)]
+    pub auto_sync_saves: bool,
+    /// Per-game `auto_sync_saves` overrides, keyed by app name
+    #[serde(default)]
+    pub game_auto_sync_saves: HashMap<String, bool>,
+    /// Which version wins when a cloud save differs from the local one
+    #[serde(default)]
+    pub save_conflict_policy: SaveConflictPolicy,
+    /// Earlier versions of each save file kept when a sync replaces it, for
+    /// `rauncher saves restore`; 0 keeps none
+    #[serde(default = "default_save_history_versions")]
+    pub save_history_versions: usize,
+    /// Per-game include and exclude patterns of save files to sync, keyed
+    /// by app name
+    #[serde(default)]
+    pub game_save_filters: HashMap<String, SaveFilter>,
+    /// Run games inside a bubblewrap sandbox that only lets them write to
+    /// their install and prefix
+    #[serde(default)]
+    pub sandbox: bool,
+    /// Per-game `sandbox` overrides, keyed by app name
+    #[serde(default)]
+    pub game_sandbox: HashMap<String, bool>,
+    /// Keep checking installed games for updates in the background (GUI
+    /// and `rauncher daemon`); the GUI always checks once at startup
+    #[serde(default)]
+    pub auto_update: bool,
+    /// Hours between background update checks
+    #[serde(default = "default_auto_update_interval_hours")]
+    pub auto_update_interval_hours: u64,
+    /// Install updates the background check finds instead of only reporting them
+    #[serde(default)]
+    pub auto_apply_updates: bool,
+    /// Sync the saves of games with `auto_sync_saves` in the background
+    /// (GUI and `rauncher daemon`), leaving conflicts for the user
+    #[serde(default)]
+    pub background_save_sync: bool,
+    /// Minutes between background save syncs
+    #[serde(default = "default_save_sync_interval_minutes")]
+    pub save_sync_interval_minutes: u64,
+    /// Desktop notifications when downloads finish or fail, updates are
+    /// found and saves conflict (GUI and `rauncher daemon`)
+    #[serde(default = "default_desktop_notifications")]
+    pub desktop_notifications: bool,
+    /// Show the game being played on the user's Discord profile
+    #[serde(default)]
+    pub discord_presence: bool,
+    /// Id of the Discord application the presence is reported as
+    #[serde(default)]
+    pub discord_client_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeLimits {
+    /// Remind the player after this many minutes in one session
+    #[serde(default)]
+    pub reminder_minutes: Option<u32>,
+    /// Per-game reminder overrides, keyed by app name
+    #[serde(default)]
+    pub game_reminder_minutes: HashMap<String, u32>,
+    /// Minutes of play allowed per day across all games
+    #[serde(default)]
+    pub daily_limit_minutes: Option<u32>,
+    /// Salted hash of the parental PIN, which unlocks play past the daily
+    /// limit. Earlier versions stored an unsalted SHA-1 under the old name.
+    #[serde(default, alias = "parental_pin_sha1")]
+    pub parental_pin: Option<String>,
+}
+
+impl TimeLimits {
+    /// Reminder interval for a game, falling back to the global setting
+    pub fn reminder_for(&self, app_name: &str) -> Option<u32> {
+        self.game_reminder_minutes
+            .get(app_name)
+            .copied()
+            .or(self.reminder_minutes)
+    }
+
+    pub fn set_pin(&mut self, pin: &str) -> Result<()> {
+        self.parental_pin = Some(pin::hash(pin)?);
+        Ok(())
+    }
+
+    pub fn pin_matches(&self, pin: &str) -> bool {
+        self.parental_pin
+            .as_deref()
+            .is_some_and(|hash| pin::verify(hash, pin))
+    }
+
+    /// Whether changing the limits needs the PIN. A daily limit without a
+    /// PIN counts too: nothing unlocks it, rather than anyone.
+    pub fn locked(&self) -> bool {
+        self.parental_pin.is_some() || self.daily_limit_minutes.is_some()
+    }
+}
+
+/// Settings `rauncher config` can read and change, as dotted TOML keys
+pub const SETTINGS: &[&str] = &[
+    "install_dir",
+    "log_level",
+    "metrics_address",
+    "uninstall_to_trash",
+    "trash_retention_days",
+    "verify_entitlement_on_launch",
+    "shared_install_dir",
+    "runner",
+    "verify_launch_after_install",
+    "remember_gui_state",
+    "ui_scale",
+    "gui_state_file",
+    "update_channel",
+    "cdn_base_url",
+    "cache_ttl_hours",
+    "cache_size_mb",
+    "cache_chunks",
+    "bandwidth_limit",
+    "download_windows",
+    "max_concurrent_games",
+    "workers_per_download",
+    "rollback_versions",
+    "language",
+    "auto_sync_saves",
+    "save_conflict_policy",
+    "save_history_versions",
+    "sandbox",
+    "auto_update",
+    "auto_update_interval_hours",
+    "auto_apply_updates",
+    "background_save_sync",
+    "save_sync_interval_minutes",
+    "desktop_notifications",
+    "discord_presence",
+    "discord_client_id",
+    "time_limits.reminder_minutes",
+    "time_limits.daily_limit_minutes",
+];
+
+fn default_trash_retention_days() -> u32 {
+    7
+}
+
+fn default_remember_gui_state() -> bool {
+    true
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+/// Scales `ui_scale` and `--scale` accept; beyond them the GUI doesn't fit
+/// the window or can't be read
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+fn default_cache_ttl_hours() -> u64 {
+    24
+}
+
+fn default_cache_size_mb() -> u64 {
+    512
+}
+
+fn default_max_concurrent_games() -> usize {
+    1
+}
+
+fn default_workers_per_download() -> usize {
+    4
+}
+
+fn default_rollback_versions() -> usize {
+    1
+}
+
+fn default_save_history_versions() -> usize {
+    5
+}
+
+fn default_auto_update_interval_hours() -> u64 {
+    6
+}
+
+fn default_save_sync_interval_minutes() -> u64 {
+    30
+}
+
+fn default_desktop_notifications() -> bool {
+    true
+}
+
+/// Environment variable that relocates all launcher state, like `--data-dir`
+pub const DATA_DIR_ENV: &str = "RAUNCHER_DATA_DIR";
+
+static PORTABLE_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keep config, data and cache under `root` instead of the per-user system
+/// directories, e.g. to run from a USB stick. Takes precedence over
+/// `RAUNCHER_DATA_DIR`; only the first call has an effect.
+pub fn set_portable_root(root: PathBuf) {
+    let root = std::path::absolute(&root).unwrap_or(root);
+    if PORTABLE_ROOT.set(root).is_err() {
+        log::warn!("Data directory override was already set");
+    }
+}
+
+fn portable_root() -> Option<PathBuf> {
+    PORTABLE_ROOT.get().cloned().or_else(|| {
+        std::env::var_os(DATA_DIR_ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| {
+                let dir = PathBuf::from(dir);
+                std::path::absolute(&dir).unwrap_or(dir)
+            })
+    })
+}
+
+fn project_dirs() -> Result<ProjectDirs> {
+    ProjectDirs::from("", "", "rauncher")
+        .ok_or_else(|| Error::Config("Failed to determine project directories".to_string()))
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let project_dirs = ProjectDirs::from("", "", "rauncher")
-            .expect("Failed to determine project directories");
+        let data_dir = Self::data_dir().expect("Failed to determine project directories");
 
         Self {
-            install_dir: project_dirs.data_dir().join("games"),
+            install_dir: data_dir.join("games"),
             log_level: "info".to_string(),
+            metrics_address: None,
+            uninstall_to_trash: false,
+            trash_retention_days: default_trash_retention_days(),
+            verify_entitlement_on_launch: false,
+            shared_install_dir: false,
+            runner: None,
+            game_runners: HashMap::new(),
+            game_wrappers: HashMap::new(),
+            game_gpus: HashMap::new(),
+            verify_launch_after_install: false,
+            time_limits: TimeLimits::default(),
+            remember_gui_state: default_remember_gui_state(),
+            ui_scale: default_ui_scale(),
+            gui_state_file: None,
+            update_channel: UpdateChannel::default(),
+            cdn_base_url: None,
+            cache_ttl_hours: default_cache_ttl_hours(),
+            cache_size_mb: default_cache_size_mb(),
+            cache_chunks: false,
+            bandwidth_limit: None,
+            download_windows: Vec::new(),
+            max_concurrent_games: default_max_concurrent_games(),
+            workers_per_download: default_workers_per_download(),
+            rollback_versions: default_rollback_versions(),
+            language: None,
+            game_languages: HashMap::new(),
+            auto_sync_saves: false,
+            game_auto_sync_saves: HashMap::new(),
+            save_conflict_policy: SaveConflictPolicy::default(),
+            save_history_versions: default_save_history_versions(),
+            game_save_filters: HashMap::new(),
+            sandbox: false,
+            game_sandbox: HashMap::new(),
+            auto_update: false,
+            auto_update_interval_hours: default_auto_update_interval_hours(),
+            auto_apply_updates: false,
+            background_save_sync: false,
+            save_sync_interval_minutes: default_save_sync_interval_minutes(),
+            desktop_notifications: default_desktop_notifications(),
+            discord_presence: false,
+            discord_client_id: None,
         }
     }
 }
@@ -35,15 +383,11 @@ impl Config {
     pub fn load() -> Result<Self> {
         // TODO: Handle config migration for version changes
         // TODO: Merge user config with defaults for missing values
-        // TODO: Add config file watching for hot-reload
 
         let config_path = Self::config_path()?;
 
         if config_path.exists() {
-            let contents = fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&contents)?;
-            config.validate()?;
-            Ok(config)
+            Self::read(&config_path)
         } else {
             let config = Self::default();
             config.save()?;
@@ -51,6 +395,13 @@ impl Config {
         }
     }
 
+    fn read(path: &std::path::Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let config: Config = toml::from_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
     /// Validate configuration values
     fn validate(&self) -> Result<()> {
         // Validate log level
@@ -73,34 +424,266 @@ impl Config {
             }
         }
 
+        // Validate metrics address
+        if let Some(address) = &self.metrics_address {
+            address.parse::<std::net::SocketAddr>().map_err(|_| {
+                Error::Config(format!(
+                    "Invalid metrics address: '{}'. Expected host:port, e.g. 127.0.0.1:9897",
+                    address
+                ))
+            })?;
+        }
+
+        // Validate CDN mirror
+        if let Some(url) = &self.cdn_base_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(Error::Config(format!(
+                    "Invalid CDN base URL: '{}'. Expected an http:// or https:// URL",
+                    url
+                )));
+            }
+        }
+
+        // Validate save filters
+        for (app_name, filter) in &self.game_save_filters {
+            for pattern in filter.include.iter().chain(&filter.exclude) {
+                if pattern.is_empty() || pattern.starts_with('/') {
+                    return Err(Error::Config(format!(
+                        "Invalid save filter pattern for {}: '{}'. Patterns are relative to the save folder",
+                        app_name, pattern
+                    )));
+                }
+            }
+        }
+
+        // Validate languages
+        for language in self.language.iter().chain(self.game_languages.values()) {
+            if !games::is_language_tag(language) {
+                return Err(Error::Config(format!(
+                    "Invalid language: '{}'. Expected a code such as 'de' or 'pt-BR'",
+                    language
+                )));
+            }
+        }
+
+        // Validate launch templates
+        for template in self.game_wrappers.values() {
+            games::LaunchTemplate::parse(template)?;
+        }
+
+        if !UI_SCALE_RANGE.contains(&self.ui_scale) {
+            return Err(Error::Config(format!(
+                "Invalid ui_scale: {}. Must be between {} and {}",
+                self.ui_scale,
+                UI_SCALE_RANGE.start(),
+                UI_SCALE_RANGE.end()
+            )));
+        }
+
+        // Validate download speed limit
+        if self.bandwidth_limit == Some(0) {
+            return Err(Error::Config(
+                "bandwidth_limit must be at least 1 KiB/s; unset it to remove the limit"
+                    .to_string(),
+            ));
+        }
+
+        // Validate download parallelism
+        if self.max_concurrent_games == 0 || self.workers_per_download == 0 {
+            return Err(Error::Config(
+                "max_concurrent_games and workers_per_download must be at least 1".to_string(),
+            ));
+        }
+
+        if self.discord_presence && self.discord_client_id.is_none() {
+            return Err(Error::Config(
+                "discord_presence needs discord_client_id, the id of a Discord application"
+                    .to_string(),
+            ));
+        }
+
+        if self.auto_update_interval_hours == 0 {
+            return Err(Error::Config(
+                "auto_update_interval_hours must be at least 1".to_string(),
+            ));
+        }
+
+        if self.save_sync_interval_minutes == 0 {
+            return Err(Error::Config(
+                "save_sync_interval_minutes must be at least 1".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
-    pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
+    /// Current value of a setting, `None` when it is unset
+    pub fn get(&self, key: &str) -> Result<Option<toml::Value>> {
+        check_setting(key)?;
 
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
+        let mut value = toml::Value::try_from(self).map_err(|e| Error::Config(e.to_string()))?;
+        for part in key.split('.') {
+            match value.get(part) {
+                Some(next) => value = next.clone(),
+                None => return Ok(None),
+            }
         }
 
+        Ok(Some(value))
+    }
+
+    /// Change a setting. `value` is read as a TOML value (numbers, booleans,
+    /// inline tables) and otherwise taken as a plain string. The changed
+    /// configuration must still pass validation.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let parsed = format!("value = {}", value)
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+        self.replace(key, Some(parsed))
+    }
+
+    /// Put a setting back to its default
+    pub fn unset(&mut self, key: &str) -> Result<()> {
+        let default = Self::default().get(key)?;
+        self.replace(key, default)
+    }
+
+    fn replace(&mut self, key: &str, value: Option<toml::Value>) -> Result<()> {
+        check_setting(key)?;
+
+        let mut root = toml::Table::try_from(&*self).map_err(|e| Error::Config(e.to_string()))?;
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let name = parts.pop().unwrap_or(key);
+
+        let mut table = &mut root;
+        for part in parts {
+            table = table
+                .entry(part)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| Error::Config(format!("'{}' is not a table", part)))?;
+        }
+
+        match value {
+            Some(value) => table.insert(name.to_string(), value),
+            None => table.remove(name),
+        };
+
+        let config: Config = root
+            .try_into()
+            .map_err(|e| Error::Config(format!("Invalid value for {}: {}", key, e)))?;
+        config.validate()?;
+
+        *self = config;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let config_path = Self::config_path()?;
+
         let contents = toml::to_string_pretty(self).map_err(|e| Error::Config(e.to_string()))?;
-        fs::write(&config_path, contents)?;
+        storage::write_private(&config_path, contents)?;
 
         Ok(())
     }
 
     pub fn config_path() -> Result<PathBuf> {
-        let project_dirs = ProjectDirs::from("", "", "rauncher")
-            .ok_or_else(|| Error::Config("Failed to determine project directories".to_string()))?;
-
-        Ok(project_dirs.config_dir().join("config.toml"))
+        match portable_root() {
+            Some(root) => Ok(root.join("config.toml")),
+            None => Ok(project_dirs()?.config_dir().join("config.toml")),
+        }
     }
 
     pub fn data_dir() -> Result<PathBuf> {
-        let project_dirs = ProjectDirs::from("", "", "rauncher")
-            .ok_or_else(|| Error::Config("Failed to determine project directories".to_string()))?;
+        match portable_root() {
+            Some(root) => Ok(root.join("data")),
+            None => Ok(project_dirs()?.data_dir().to_path_buf()),
+        }
+    }
+
+    /// File holding GUI window and view state
+    pub fn gui_state_path(&self) -> Result<PathBuf> {
+        match &self.gui_state_file {
+            Some(path) => Ok(path.clone()),
+            None => Ok(Self::data_dir()?.join("gui-state.ron")),
+        }
+    }
+
+    /// Language to install and launch a game in, falling back to the global
+    /// setting
+    pub fn language_for(&self, app_name: &str) -> Option<&str> {
+        self.game_languages
+            .get(app_name)
+            .or(self.language.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Runner configured for a game, falling back to the global setting
+    pub fn runner_for(&self, app_name: &str) -> Option<&Runner> {
+        self.game_runners.get(app_name).or(self.runner.as_ref())
+    }
+
+    /// Download speed limit in bytes per second
+    pub fn bandwidth_limit_bytes(&self) -> Option<u64> {
+        self.bandwidth_limit.map(|kib| kib * 1024)
+    }
+
+    /// Whether launching a game syncs its cloud saves, falling back to the
+    /// global setting
+    pub fn auto_sync_saves_for(&self, app_name: &str) -> bool {
+        self.game_auto_sync_saves
+            .get(app_name)
+            .copied()
+            .unwrap_or(self.auto_sync_saves)
+    }
+
+    /// Which save files of a game cloud sync touches
+    pub fn save_filter_for(&self, app_name: &str) -> SaveFilter {
+        self.game_save_filters
+            .get(app_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether a game runs sandboxed, falling back to the global setting
+    pub fn sandbox_for(&self, app_name: &str) -> bool {
+        self.game_sandbox
+            .get(app_name)
+            .copied()
+            .unwrap_or(self.sandbox)
+    }
+
+    /// Time between background update checks
+    pub fn auto_update_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.auto_update_interval_hours * 60 * 60)
+    }
 
-        Ok(project_dirs.data_dir().to_path_buf())
+    /// Time between background save syncs
+    pub fn save_sync_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.save_sync_interval_minutes * 60)
+    }
+
+    /// Disposable data such as downloaded manifests and chunks
+    pub fn cache_dir() -> Result<PathBuf> {
+        match portable_root() {
+            Some(root) => Ok(root.join("cache")),
+            None => Ok(project_dirs()?.cache_dir().to_path_buf()),
+        }
+    }
+}
+
+fn check_setting(key: &str) -> Result<()> {
+    if SETTINGS.contains(&key) {
+        Ok(())
+    } else {
+        Err(Error::Config(format!(
+            "Unknown setting: '{}'. Known settings: {}",
+            key,
+            SETTINGS.join(", ")
+        )))
     }
 }
 
@@ -113,6 +696,16 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.log_level, "info");
         assert!(config.install_dir.to_string_lossy().contains("games"));
+        assert!(config.remember_gui_state);
+
+        let config = Config {
+            gui_state_file: Some(PathBuf::from("/tmp/state.ron")),
+            ..config
+        };
+        assert_eq!(
+            config.gui_state_path().unwrap(),
+            PathBuf::from("/tmp/state.ron")
+        );
     }
 
     #[test]
@@ -122,4 +715,154 @@ mod tests {
         let deserialized: Config = toml::from_str(&serialized).unwrap();
         assert_eq!(config.log_level, deserialized.log_level);
     }
+
+    #[test]
+    fn test_time_limits_pin_and_reminders() {
+        let mut limits = TimeLimits {
+            reminder_minutes: Some(60),
+            ..TimeLimits::default()
+        };
+        limits
+            .game_reminder_minutes
+            .insert("Fortnite".to_string(), 30);
+        assert!(!limits.locked());
+        limits.set_pin("1234").unwrap();
+
+        assert!(limits.locked());
+        assert!(limits.pin_matches("1234"));
+        assert!(!limits.pin_matches("0000"));
+        assert_eq!(limits.reminder_for("Fortnite"), Some(30));
+        assert_eq!(limits.reminder_for("Hades"), Some(60));
+
+        let config = Config {
+            runner: Some(Runner::Wine(PathBuf::from("/usr/bin/wine"))),
+            time_limits: limits,
+            ..Config::default()
+        };
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert!(deserialized.time_limits.pin_matches("1234"));
+
+        // A limit nobody set a PIN for stays shut
+        let limits = TimeLimits {
+            daily_limit_minutes: Some(60),
+            ..TimeLimits::default()
+        };
+        assert!(limits.locked());
+        assert!(!limits.pin_matches(""));
+    }
+
+    #[test]
+    fn test_auto_sync_saves_override() {
+        let mut config = Config {
+            auto_sync_saves: true,
+            ..Config::default()
+        };
+        config
+            .game_auto_sync_saves
+            .insert("Fortnite".to_string(), false);
+
+        assert!(!config.auto_sync_saves_for("Fortnite"));
+        assert!(config.auto_sync_saves_for("Hades"));
+
+        config.auto_sync_saves = false;
+        config
+            .game_auto_sync_saves
+            .insert("Hades".to_string(), true);
+        assert!(config.auto_sync_saves_for("Hades"));
+    }
+
+    #[test]
+    fn test_get_set_unset() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            install_dir: dir.path().join("games"),
+            ..Config::default()
+        };
+
+        config.set("trash_retention_days", "30").unwrap();
+        config.set("log_level", "debug").unwrap();
+        config.set("time_limits.daily_limit_minutes", "90").unwrap();
+        config
+            .set("runner", "{ proton = \"GE-Proton9-1\" }")
+            .unwrap();
+
+        assert_eq!(config.trash_retention_days, 30);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.time_limits.daily_limit_minutes, Some(90));
+        assert_eq!(
+            config.runner,
+            Some(Runner::Proton("GE-Proton9-1".to_string()))
+        );
+        assert_eq!(
+            config.get("trash_retention_days").unwrap(),
+            Some(toml::Value::Integer(30))
+        );
+
+        config.unset("time_limits.daily_limit_minutes").unwrap();
+        assert_eq!(config.time_limits.daily_limit_minutes, None);
+        assert_eq!(config.get("metrics_address").unwrap(), None);
+    }
+
+    #[test]
+    fn test_set_rejects_bad_values() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            install_dir: dir.path().join("games"),
+            ..Config::default()
+        };
+
+        assert!(config.set("trash_retention_days", "soon").is_err());
+        assert!(config.set("log_level", "loud").is_err());
+        assert!(config.set("no_such_setting", "1").is_err());
+        assert!(config.set("workers_per_download", "0").is_err());
+        assert!(config.set("auto_update_interval_hours", "0").is_err());
+        assert!(config.set("save_sync_interval_minutes", "0").is_err());
+        assert!(config.set("language", "German").is_err());
+        assert_eq!(config.trash_retention_days, default_trash_retention_days());
+        assert_eq!(config.log_level, "info");
+    }
+
+    #[test]
+    fn test_config_rejects_invalid_metrics_address() {
+        let config = Config {
+            metrics_address: Some("not-an-address".to_string()),
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_malformed_launch_template() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            install_dir: dir.path().join("games"),
+            ..Config::default()
+        };
+        config
+            .game_wrappers
+            .insert("Game".to_string(), "gamescope -f --".to_string());
+        assert!(config.validate().is_err());
+
+        config
+            .game_wrappers
+            .insert("Game".to_string(), "gamescope -f -- %command%".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_rejects_unreadable_ui_scale() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            install_dir: dir.path().join("games"),
+            ui_scale: 1.5,
+            ..Config::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.ui_scale = 0.0;
+        assert!(config.validate().is_err());
+        config.ui_scale = f32::NAN;
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,110 @@
+//! Hashing of the parental PIN. A PIN has few digits, so a plain hash in
+//! `config.toml` is reversed by trying them all; each PIN is instead
+//! stretched with PBKDF2 and its own random salt. Hashes are stored as
+//! `pbkdf2-sha256$<iterations>$<salt>$<hash>` with hex fields.
+
+use ring::{pbkdf2, rand::SecureRandom};
+use sha1::{Digest, Sha1};
+use std::num::NonZeroU32;
+
+use crate::{Error, Result};
+
+const SCHEME: &str = "pbkdf2-sha256";
+const ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+/// Salt and stretch `pin`
+pub fn hash(pin: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| Error::Other("No randomness available to salt the PIN".to_string()))?;
+
+    let iterations = NonZeroU32::new(ITERATIONS).expect("iterations are not zero");
+    let mut hash = [0u8; HASH_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        pin.trim().as_bytes(),
+        &mut hash,
+    );
+
+    Ok(format!(
+        "{}${}${}${}",
+        SCHEME,
+        ITERATIONS,
+        to_hex(&salt),
+        to_hex(&hash)
+    ))
+}
+
+/// Whether `pin` is the one `stored` was made from. Unsalted SHA-1 hashes
+/// written by earlier versions still verify, until the PIN is set again.
+pub fn verify(stored: &str, pin: &str) -> bool {
+    let fields: Vec<&str> = stored.split('$').collect();
+    let [SCHEME, iterations, salt, hash] = fields[..] else {
+        return stored.len() == 40 && legacy_sha1(pin).eq_ignore_ascii_case(stored);
+    };
+    let (Some(iterations), Some(salt), Some(hash)) = (
+        iterations.parse().ok().and_then(NonZeroU32::new),
+        from_hex(salt),
+        from_hex(hash),
+    ) else {
+        return false;
+    };
+
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        pin.trim().as_bytes(),
+        &hash,
+    )
+    .is_ok()
+}
+
+fn legacy_sha1(pin: &str) -> String {
+    to_hex(&Sha1::digest(pin.trim().as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_are_salted_and_verify() {
+        let first = hash("1234").unwrap();
+        let second = hash("1234").unwrap();
+        assert_ne!(first, second);
+        assert!(first.starts_with("pbkdf2-sha256$600000$"));
+
+        assert!(verify(&first, "1234"));
+        assert!(verify(&second, " 1234\n"));
+        assert!(!verify(&first, "0000"));
+        assert!(!verify("pbkdf2-sha256$0$00$00", "1234"));
+        assert!(!verify("garbage", "1234"));
+    }
+
+    #[test]
+    fn test_legacy_sha1_hashes_verify() {
+        let legacy = "7110eda4d09e062aa5e4a390b0a572ac0d2c0220";
+        assert!(verify(legacy, "1234"));
+        assert!(!verify(legacy, "0000"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Ownership rules for files the launcher creates on shared machines.
+//!
+//! Metadata (config, auth token, installed-game records, manifests, audit log)
+//! always lives under the invoking user's XDG directories and is private to
+//! them: directories are `0700` and files `0600`. Game files may live in a
+//! directory shared between users; with `shared_install_dir` enabled they are
+//! created group-writable with the setgid bit so every member of that
+//! directory's group can update them.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+pub const PRIVATE_DIR_MODE: u32 = 0o700;
+pub const PRIVATE_FILE_MODE: u32 = 0o600;
+pub const SHARED_DIR_MODE: u32 = 0o2775;
+
+/// Create a metadata directory readable only by the current user
+pub fn create_private_dir(path: &Path) -> Result<()> {
+    fs::create_dir_all(path)?;
+    set_mode(path, PRIVATE_DIR_MODE)
+}
+
+/// Write a metadata file readable only by the current user
+pub fn write_private(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        create_private_dir(parent)?;
+    }
+
+    open_private(path)?.write_all(contents.as_ref())?;
+    // A file that already existed keeps its mode when opened
+    set_mode(path, PRIVATE_FILE_MODE)
+}
+
+/// Create a game install directory, group-writable when games are shared
+pub fn create_install_dir(path: &Path, shared: bool) -> Result<()> {
+    fs::create_dir_all(path)?;
+
+    if shared {
+        set_mode(path, SHARED_DIR_MODE)?;
+    }
+
+    Ok(())
+}
+
+/// A metadata path that breaks per-user isolation
+#[derive(Debug, Clone)]
+pub struct IsolationIssue {
+    pub path: PathBuf,
+    pub problem: String,
+}
+
+/// Walk a metadata directory and report entries owned by another user or
+/// accessible to group/others, e.g. after running the launcher with sudo.
+pub fn audit_private_dir(dir: &Path) -> Result<Vec<IsolationIssue>> {
+    let mut issues = Vec::new();
+
+    if dir.exists() {
+        audit_entry(dir, &mut issues)?;
+    }
+
+    Ok(issues)
+}
+
+#[cfg(unix)]
+fn audit_entry(path: &Path, issues: &mut Vec<IsolationIssue>) -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs::symlink_metadata(path)?;
+    let uid = unsafe { libc::geteuid() };
+
+    if metadata.uid() != uid {
+        issues.push(IsolationIssue {
+            path: path.to_path_buf(),
+            problem: format!("owned by uid {} instead of {}", metadata.uid(), uid),
+        });
+    }
+
+    if metadata.mode() & 0o077 != 0 && !metadata.file_type().is_symlink() {
+        issues.push(IsolationIssue {
+            path: path.to_path_buf(),
+            problem: format!(
+                "mode {:o} is accessible to other users",
+                metadata.mode() & 0o777
+            ),
+        });
+    }
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            audit_entry(&entry?.path(), issues)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn audit_entry(_path: &Path, _issues: &mut Vec<IsolationIssue>) -> Result<()> {
+    Ok(())
+}
+
+/// Open a file for writing, creating it private from the start rather than
+/// with the umask's mode
+#[cfg(unix)]
+fn open_private(path: &Path) -> Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    Ok(fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(PRIVATE_FILE_MODE)
+        .open(path)?)
+}
+
+#[cfg(not(unix))]
+fn open_private(path: &Path) -> Result<fs::File> {
+    Ok(fs::File::create(path)?)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_private_restricts_mode() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("installed/Fortnite.json");
+
+        write_private(&path, "{}").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, PRIVATE_FILE_MODE);
+        assert!(audit_private_dir(&dir.path().join("installed"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_audit_flags_open_modes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("auth.json");
+        fs::write(&path, "{}").unwrap();
+        set_mode(&path, 0o644).unwrap();
+        set_mode(dir.path(), PRIVATE_DIR_MODE).unwrap();
+
+        let issues = audit_private_dir(dir.path()).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, path);
+    }
+}
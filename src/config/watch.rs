@@ -0,0 +1,112 @@
+//! Notices when `config.toml` changes on disk so long-running frontends can
+//! pick up new settings without a restart. Polls the file's modification
+//! time; cheap enough to call every frame.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::Config;
+use crate::Result;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_poll: Instant,
+}
+
+impl ConfigWatcher {
+    /// Watch the launcher's config file
+    pub fn new() -> Result<Self> {
+        Ok(Self::for_path(Config::config_path()?))
+    }
+
+    pub fn for_path(path: PathBuf) -> Self {
+        let modified = modified(&path);
+
+        Self {
+            path,
+            modified,
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// The reloaded config if the file changed since the last call. An edit
+    /// that doesn't parse or validate is returned as an error once; the
+    /// previous config stays in effect until the file is fixed.
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return None;
+        }
+        self.check()
+    }
+
+    fn check(&mut self) -> Option<Result<Config>> {
+        self.last_poll = Instant::now();
+
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+
+        // A deleted file is recreated with defaults on the next load
+        modified?;
+        log::info!("{} changed, reloading", self.path.display());
+        Some(Config::read(&self.path))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path, contents: &str, age: Duration) {
+        fs::write(path, contents).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_reloads_changed_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let install_dir = dir.path().join("games");
+        let config = format!("install_dir = {:?}\nlog_level = \"info\"\n", install_dir);
+        touch(&path, &config, Duration::from_secs(60));
+
+        let mut watcher = ConfigWatcher::for_path(path.clone());
+        assert!(watcher.check().is_none());
+
+        touch(&path, &config.replace("info", "debug"), Duration::ZERO);
+        let reloaded = watcher.check().unwrap().unwrap();
+        assert_eq!(reloaded.log_level, "debug");
+        assert_eq!(reloaded.install_dir, install_dir);
+        assert!(watcher.check().is_none());
+    }
+
+    #[test]
+    fn test_invalid_edit_is_reported_once() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let config = format!("install_dir = {:?}\nlog_level = \"info\"\n", dir.path());
+        touch(&path, &config, Duration::from_secs(60));
+
+        let mut watcher = ConfigWatcher::for_path(path.clone());
+        touch(&path, &config.replace("info", "loud"), Duration::ZERO);
+
+        assert!(watcher.check().unwrap().is_err());
+        assert!(watcher.check().is_none());
+    }
+}
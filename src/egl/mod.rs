@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{Error, Result};
+
+const LAUNCHER_INSTALLED: &str =
+    "drive_c/ProgramData/Epic/UnrealEngineLauncher/LauncherInstalled.dat";
+const MANIFESTS_DIR: &str = "drive_c/ProgramData/Epic/EpicGamesLauncher/Data/Manifests";
+
+/// Entry of EGL's `LauncherInstalled.dat`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EglInstallation {
+    pub install_location: String,
+    #[serde(default)]
+    pub namespace_id: String,
+    #[serde(default)]
+    pub item_id: String,
+    #[serde(default)]
+    pub artifact_id: String,
+    pub app_version: String,
+    pub app_name: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LauncherInstalled {
+    installation_list: Vec<EglInstallation>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Per-game `.item` manifest EGL keeps in its Manifests directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EglManifestItem {
+    pub format_version: u32,
+    #[serde(rename = "bIsIncompleteInstall")]
+    pub is_incomplete_install: bool,
+    pub launch_command: String,
+    pub launch_executable: String,
+    pub manifest_location: String,
+    pub install_location: String,
+    pub install_size: u64,
+    pub app_name: String,
+    pub app_version_string: String,
+    pub display_name: String,
+    pub installation_guid: String,
+    #[serde(default)]
+    pub catalog_namespace: String,
+    #[serde(default)]
+    pub catalog_item_id: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// `.egstore/<guid>.mancpn` file linking an install directory to its catalog entry
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ManCpn {
+    format_version: u32,
+    app_name: String,
+    catalog_namespace: String,
+    catalog_item_id: String,
+}
+
+/// View of the Epic Games Launcher state inside a Wine prefix
+pub struct EglPrefix {
+    prefix: PathBuf,
+}
+
+impl EglPrefix {
+    pub fn new(prefix: &Path) -> Result<Self> {
+        if !prefix.join("drive_c").is_dir() {
+            return Err(Error::Config(format!(
+                "Not a Wine prefix (no drive_c): {:?}",
+                prefix
+            )));
+        }
+
+        Ok(Self {
+            prefix: prefix.to_path_buf(),
+        })
+    }
+
+    pub fn installations(&self) -> Result<Vec<EglInstallation>> {
+        Ok(self.read_launcher_installed()?.installation_list)
+    }
+
+    /// Read every `.item` manifest, skipping files EGL left half-written
+    pub fn manifest_items(&self) -> Result<Vec<EglManifestItem>> {
+        let dir = self.prefix.join(MANIFESTS_DIR);
+
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut items = Vec::new();
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+
+            if path.extension().and_then(|s| s.to_str()) == Some("item") {
+                match fs::read_to_string(&path)
+                    .map_err(Error::from)
+                    .and_then(|c| serde_json::from_str(&c).map_err(Error::from))
+                {
+                    Ok(item) => items.push(item),
+                    Err(e) => log::warn!("Skipping unreadable EGL manifest {:?}: {}", path, e),
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Register a game with EGL: LauncherInstalled.dat, the `.item` manifest
+    /// and the `.egstore` catalog link inside the install directory.
+    pub fn add_installation(&self, item: EglManifestItem) -> Result<()> {
+        let mut installed = self.read_launcher_installed()?;
+        installed
+            .installation_list
+            .retain(|i| i.app_name != item.app_name);
+        installed.installation_list.push(EglInstallation {
+            install_location: item.install_location.clone(),
+            namespace_id: item.catalog_namespace.clone(),
+            item_id: item.catalog_item_id.clone(),
+            artifact_id: item.app_name.clone(),
+            app_version: item.app_version_string.clone(),
+            app_name: item.app_name.clone(),
+            extra: serde_json::Map::new(),
+        });
+        self.write_launcher_installed(&installed)?;
+
+        let manifests_dir = self.prefix.join(MANIFESTS_DIR);
+        fs::create_dir_all(&manifests_dir)?;
+        fs::write(
+            manifests_dir.join(format!("{}.item", item.installation_guid)),
+            serde_json::to_string_pretty(&item)?,
+        )?;
+
+        if let Some(install_path) = self.to_unix_path(&item.install_location) {
+            let egstore = install_path.join(".egstore");
+            fs::create_dir_all(&egstore)?;
+            let mancpn = ManCpn {
+                format_version: 0,
+                app_name: item.app_name.clone(),
+                catalog_namespace: item.catalog_namespace.clone(),
+                catalog_item_id: item.catalog_item_id.clone(),
+            };
+            fs::write(
+                egstore.join(format!("{}.mancpn", item.installation_guid)),
+                serde_json::to_string_pretty(&mancpn)?,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Map a Windows path as seen by EGL to a path on the host
+    pub fn to_unix_path(&self, windows_path: &str) -> Option<PathBuf> {
+        let normalized = windows_path.replace('\\', "/");
+        let (drive, rest) = normalized.split_once(":/")?;
+        let rest = rest.trim_end_matches('/');
+
+        match drive.to_ascii_lowercase().as_str() {
+            "c" => Some(self.prefix.join("drive_c").join(rest)),
+            "z" => Some(PathBuf::from("/").join(rest)),
+            _ => None,
+        }
+    }
+
+    /// Map a host path to the Windows path EGL expects
+    pub fn to_windows_path(&self, path: &Path) -> String {
+        let drive_c = self.prefix.join("drive_c");
+
+        let (drive, relative) = match path.strip_prefix(&drive_c) {
+            Ok(rest) => ("C:", rest),
+            Err(_) => ("Z:", path.strip_prefix("/").unwrap_or(path)),
+        };
+
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+
+        format!("{}\\{}", drive, parts.join("\\"))
+    }
+
+    fn read_launcher_installed(&self) -> Result<LauncherInstalled> {
+        let path = self.prefix.join(LAUNCHER_INSTALLED);
+
+        if !path.exists() {
+            return Ok(LauncherInstalled::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn write_launcher_installed(&self, installed: &LauncherInstalled) -> Result<()> {
+        let path = self.prefix.join(LAUNCHER_INSTALLED);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(installed)?)?;
+        Ok(())
+    }
+}
+
+/// App name recorded in an install directory's `.egstore` catalog link, if any
+pub fn egstore_app_name(install_path: &Path) -> Option<String> {
+    fs::read_dir(install_path.join(".egstore"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mancpn"))
+        .find_map(|path| {
+            let contents = fs::read_to_string(path).ok()?;
+            serde_json::from_str::<ManCpn>(&contents).ok()
+        })
+        .map(|mancpn| mancpn.app_name)
+}
+
+/// EGL identifies installs by a 32-character upper-case hex GUID; derive a
+/// stable one so repeated syncs don't create duplicate manifests.
+pub fn installation_guid(app_name: &str) -> String {
+    let digest = Sha1::digest(format!("rauncher:{}", app_name).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn prefix() -> (TempDir, EglPrefix) {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("drive_c")).unwrap();
+        let egl = EglPrefix::new(dir.path()).unwrap();
+        (dir, egl)
+    }
+
+    #[test]
+    fn test_path_mapping_round_trip() {
+        let (dir, egl) = prefix();
+
+        let inside = dir.path().join("drive_c/Program Files/Epic Games/Game");
+        let windows = egl.to_windows_path(&inside);
+        assert_eq!(windows, "C:\\Program Files\\Epic Games\\Game");
+        assert_eq!(egl.to_unix_path(&windows).unwrap(), inside);
+
+        let outside = Path::new("/home/user/Games/Other");
+        let windows = egl.to_windows_path(outside);
+        assert_eq!(windows, "Z:\\home\\user\\Games\\Other");
+        assert_eq!(egl.to_unix_path(&windows).unwrap(), outside);
+    }
+
+    #[test]
+    fn test_add_installation_is_readable() {
+        let (dir, egl) = prefix();
+        let install = dir.path().join("drive_c/Games/Test");
+        fs::create_dir_all(&install).unwrap();
+
+        let guid = installation_guid("Test");
+        egl.add_installation(EglManifestItem {
+            format_version: 0,
+            is_incomplete_install: false,
+            launch_command: String::new(),
+            launch_executable: "Test.exe".to_string(),
+            manifest_location: String::new(),
+            install_location: egl.to_windows_path(&install),
+            install_size: 0,
+            app_name: "Test".to_string(),
+            app_version_string: "1.0".to_string(),
+            display_name: "Test Game".to_string(),
+            installation_guid: guid.clone(),
+            catalog_namespace: "test-namespace".to_string(),
+            catalog_item_id: "0123abcd".to_string(),
+            extra: serde_json::Map::new(),
+        })
+        .unwrap();
+
+        let installations = egl.installations().unwrap();
+        assert_eq!(installations.len(), 1);
+        assert_eq!(installations[0].app_name, "Test");
+        assert_eq!(installations[0].item_id, "0123abcd");
+
+        let items = egl.manifest_items().unwrap();
+        assert_eq!(items[0].display_name, "Test Game");
+
+        let mancpn = fs::read_to_string(install.join(format!(".egstore/{}.mancpn", guid))).unwrap();
+        let mancpn: ManCpn = serde_json::from_str(&mancpn).unwrap();
+        assert_eq!(mancpn.catalog_namespace, "test-namespace");
+        assert_eq!(mancpn.catalog_item_id, "0123abcd");
+        assert_eq!(egstore_app_name(&install).as_deref(), Some("Test"));
+    }
+}
@@ -1,36 +1,168 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
+use crate::i18n::{tr, tr_args};
+
+/// Where each error code is explained, anchored by the lower-cased code
+const HELP_BASE_URL: &str = "https://github.com/kairosci/rauncher/blob/main/docs/errors.md";
+
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("API error: {0}")]
+    #[error("{}", detail("error-api", .0))]
     Api(String),
 
-    #[error("Authentication error: {0}")]
+    #[error("{}", detail("error-auth", .0))]
     Auth(String),
 
-    #[error("Configuration error: {0}")]
+    #[error("{}", detail("error-config", .0))]
     Config(String),
 
-    #[error("IO error: {0}")]
+    #[error("{}", detail("error-io", .0))]
     Io(#[from] std::io::Error),
 
-    #[error("HTTP error: {0}")]
+    #[error("{}", detail("error-http", .0))]
     Http(#[from] reqwest::Error),
 
-    #[error("JSON error: {0}")]
+    #[error("{}", detail("error-json", .0))]
     Json(#[from] serde_json::Error),
 
-    #[error("TOML error: {0}")]
+    #[error("{}", detail("error-toml", .0))]
     Toml(#[from] toml::de::Error),
 
-    #[error("Not authenticated")]
+    #[error("{}", tr("error-not-authenticated"))]
     NotAuthenticated,
 
-    #[error("Game not found: {0}")]
+    #[error("{}", tr_args("error-game-not-found", &[("game", .0)]))]
     GameNotFound(String),
 
+    #[error("{}", tr("error-cancelled"))]
+    Cancelled,
+
+    #[error("{}", tr("error-save-uploads-unsupported"))]
+    SaveUploadsUnsupported,
+
     #[error("{0}")]
     Other(String),
 }
 
+impl Error {
+    /// Stable identifier for scripts and support requests. Codes never change
+    /// meaning once released.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Api(_) => "EGS-500",
+            Error::Auth(_) => "EGS-401",
+            Error::NotAuthenticated => "AUTH-001",
+            Error::Http(_) => "NET-001",
+            Error::Config(_) => "CFG-001",
+            Error::Toml(_) => "CFG-002",
+            Error::Json(_) => "DATA-001",
+            Error::Io(_) => "IO-001",
+            Error::GameNotFound(_) => "GAME-404",
+            Error::Cancelled => "GEN-002",
+            Error::SaveUploadsUnsupported => "SAVE-501",
+            Error::Other(_) => "GEN-001",
+        }
+    }
+
+    /// What the user can do about it, when there is something to do
+    pub fn hint(&self) -> Option<String> {
+        let id = match self {
+            Error::Auth(_) | Error::NotAuthenticated => "error-hint-login",
+            Error::Api(_) | Error::Http(_) => "error-hint-network",
+            Error::Config(_) | Error::Toml(_) => "error-hint-config",
+            Error::Json(_) => "error-hint-data",
+            Error::GameNotFound(_) => "error-hint-game",
+            Error::Cancelled => "error-hint-cancelled",
+            Error::SaveUploadsUnsupported => "error-hint-save-uploads",
+            Error::Io(_) | Error::Other(_) => return None,
+        };
+        Some(tr(id))
+    }
+
+    pub fn help_url(&self) -> String {
+        format!("{}#{}", HELP_BASE_URL, self.code().to_lowercase())
+    }
+
+    /// Process exit status, following the BSD `sysexits.h` conventions
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Auth(_) | Error::NotAuthenticated => 77,
+            Error::Api(_) | Error::Http(_) => 69,
+            Error::Config(_) | Error::Toml(_) => 78,
+            Error::Json(_) => 65,
+            Error::GameNotFound(_) => 66,
+            Error::Io(_) => 74,
+            Error::Cancelled => 130,
+            Error::SaveUploadsUnsupported => 69,
+            Error::Other(_) => 1,
+        }
+    }
+
+    /// Signing in again would fix this
+    pub fn requires_login(&self) -> bool {
+        matches!(self, Error::Auth(_) | Error::NotAuthenticated)
+    }
+
+    /// The network or Epic's services couldn't be reached, so local data is
+    /// the best there is
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, Error::Api(_) | Error::Http(_))
+    }
+
+    /// One-line form for notifications and logs, e.g. `EGS-401: ... — sign in again ...`
+    pub fn summary(&self) -> String {
+        match self.hint() {
+            Some(hint) => format!("{}: {} — {}", self.code(), self, hint),
+            None => format!("{}: {}", self.code(), self),
+        }
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Error", 4)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("hint", &self.hint())?;
+        state.serialize_field("help_url", &self.help_url())?;
+        state.end()
+    }
+}
+
+/// A translated message whose `{ $detail }` is the underlying error
+fn detail(id: &str, detail: &dyn std::fmt::Display) -> String {
+    tr_args(id, &[("detail", detail)])
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_metadata() {
+        let error = Error::Auth("token expired".to_string());
+
+        assert_eq!(error.code(), "EGS-401");
+        assert_eq!(error.exit_code(), 77);
+        assert!(error.requires_login());
+        assert!(error
+            .summary()
+            .starts_with("EGS-401: Authentication error: token expired — "));
+        assert!(error.help_url().ends_with("errors.md#egs-401"));
+        assert!(!error.is_unreachable());
+        assert!(Error::Api("503 Service Unavailable".to_string()).is_unreachable());
+    }
+
+    #[test]
+    fn test_error_serializes_code() {
+        let json = serde_json::to_value(Error::GameNotFound("Hades".to_string())).unwrap();
+
+        assert_eq!(json["code"], "GAME-404");
+        assert_eq!(json["message"], "Game not found: Hades");
+        assert!(json["hint"].is_string());
+    }
+}
@@ -0,0 +1,153 @@
+//! Human-readable sizes, speeds, durations and dates, shared by the CLI and
+//! the GUI so a value always reads the same way wherever it is shown.
+
+use chrono::{DateTime, Local, Locale, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::time::Duration;
+
+const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// `1536` → `"1.5 KiB"`
+pub fn size(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Transfer rate, e.g. `"12.3 MiB/s"`
+pub fn speed(bytes_per_second: f64) -> String {
+    format!("{}/s", size(bytes_per_second.max(0.0) as u64))
+}
+
+/// Two most significant units, e.g. `"2 h 5 m"`, `"3 m 10 s"`, `"45 s"`
+pub fn duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{} h {} m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{} m {} s", minutes, seconds)
+    } else {
+        format!("{} s", seconds)
+    }
+}
+
+/// Date and time in the local time zone, written the way the user's locale
+/// (`LC_ALL`, `LC_TIME` or `LANG`) writes them
+pub fn date(timestamp: DateTime<Utc>) -> String {
+    date_in(timestamp, system_locale())
+}
+
+fn date_in(timestamp: DateTime<Utc>, locale: Locale) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format_localized("%x %X", locale)
+        .to_string()
+}
+
+fn system_locale() -> Locale {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            // "de_DE.UTF-8@euro" → "de_DE"
+            let name = value.split(['.', '@']).next().unwrap_or_default();
+            Locale::try_from(name).ok()
+        })
+        .unwrap_or(Locale::POSIX)
+}
+
+/// Byte count that displays in human units and serializes as both the raw
+/// value and the formatted string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&size(self.0))
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Bytes", 2)?;
+        state.serialize_field("bytes", &self.0)?;
+        state.serialize_field("formatted", &size(self.0))?;
+        state.end()
+    }
+}
+
+/// Length of time that displays as `"2 h 5 m"` and serializes as both whole
+/// seconds and the formatted string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&duration(self.0))
+    }
+}
+
+impl Serialize for Elapsed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Elapsed", 2)?;
+        state.serialize_field("seconds", &self.0.as_secs())?;
+        state.serialize_field("formatted", &duration(self.0))?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_size_and_duration() {
+        assert_eq!(size(512), "512 B");
+        assert_eq!(size(1536), "1.5 KiB");
+        assert_eq!(size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(speed(2.5 * 1024.0 * 1024.0), "2.5 MiB/s");
+
+        assert_eq!(duration(Duration::from_secs(45)), "45 s");
+        assert_eq!(duration(Duration::from_secs(190)), "3 m 10 s");
+        assert_eq!(duration(Duration::from_secs(7500)), "2 h 5 m");
+    }
+
+    #[test]
+    fn test_serializes_raw_and_formatted() {
+        let json = serde_json::to_value(Bytes(2048)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "bytes": 2048, "formatted": "2.0 KiB" })
+        );
+
+        let json = serde_json::to_value(Elapsed(Duration::from_secs(60))).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "seconds": 60, "formatted": "1 m 0 s" })
+        );
+    }
+
+    #[test]
+    fn test_date_follows_locale() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+
+        assert!(date_in(timestamp, Locale::de_DE).contains("09.03.2024"));
+        assert!(date_in(timestamp, Locale::en_US).contains("03/09/2024"));
+    }
+}
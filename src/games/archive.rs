@@ -0,0 +1,166 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{Error, Result};
+
+/// Bytes sampled from each file when estimating the compression ratio
+const SAMPLE_PER_FILE: u64 = 1024 * 1024;
+/// Upper bound on the total amount of data compressed for an estimate
+const SAMPLE_TOTAL: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArchiveEstimate {
+    pub installed_bytes: u64,
+    pub estimated_bytes: u64,
+}
+
+impl ArchiveEstimate {
+    pub fn savings(&self) -> u64 {
+        self.installed_bytes.saturating_sub(self.estimated_bytes)
+    }
+}
+
+/// Squash an install directory into a zstd-compressed squashfs image and
+/// remove the original files once the image has been written.
+pub fn archive_dir(install_path: &Path, archive_path: &Path) -> Result<u64> {
+    let status = Command::new("mksquashfs")
+        .arg(install_path)
+        .arg(archive_path)
+        .args(["-comp", "zstd", "-noappend", "-quiet"])
+        .status()
+        .map_err(|e| tool_error("mksquashfs", e))?;
+
+    if !status.success() || !archive_path.is_file() {
+        let _ = fs::remove_file(archive_path);
+        return Err(Error::Other(format!(
+            "mksquashfs failed for {:?} ({})",
+            install_path, status
+        )));
+    }
+
+    fs::remove_dir_all(install_path)?;
+    Ok(fs::metadata(archive_path)?.len())
+}
+
+/// Extract a squashfs image back into place and delete the image
+pub fn extract_archive(archive_path: &Path, install_path: &Path) -> Result<()> {
+    if install_path.exists() {
+        return Err(Error::Other(format!(
+            "Cannot extract archive, {:?} already exists",
+            install_path
+        )));
+    }
+
+    let status = Command::new("unsquashfs")
+        .args(["-quiet", "-no-progress", "-d"])
+        .arg(install_path)
+        .arg(archive_path)
+        .status()
+        .map_err(|e| tool_error("unsquashfs", e))?;
+
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "unsquashfs failed for {:?} ({})",
+            archive_path, status
+        )));
+    }
+
+    fs::remove_file(archive_path)?;
+    Ok(())
+}
+
+/// Default location of the image for an install directory
+pub fn archive_path_for(install_path: &Path) -> PathBuf {
+    let mut path = install_path.as_os_str().to_owned();
+    path.push(".squashfs");
+    PathBuf::from(path)
+}
+
+/// Estimate the archived size by compressing a sample of every file with zstd
+pub fn estimate_archive(install_path: &Path) -> Result<ArchiveEstimate> {
+    let mut files = Vec::new();
+    collect_files(install_path, &mut files)?;
+
+    let mut estimate = ArchiveEstimate::default();
+    let mut sampled_raw = 0u64;
+    let mut sampled_compressed = 0u64;
+
+    for (path, size) in &files {
+        estimate.installed_bytes += size;
+
+        if sampled_raw >= SAMPLE_TOTAL {
+            continue;
+        }
+
+        let mut sample = Vec::new();
+        File::open(path)?
+            .take(SAMPLE_PER_FILE)
+            .read_to_end(&mut sample)?;
+
+        if sample.is_empty() {
+            continue;
+        }
+
+        let compressed = zstd::bulk::compress(&sample, 3)?;
+        sampled_raw += sample.len() as u64;
+        sampled_compressed += compressed.len() as u64;
+    }
+
+    estimate.estimated_bytes = if sampled_raw == 0 {
+        estimate.installed_bytes
+    } else {
+        (estimate.installed_bytes as f64 * sampled_compressed as f64 / sampled_raw as f64) as u64
+    };
+
+    Ok(estimate)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push((entry.path(), entry.metadata()?.len()));
+        }
+    }
+
+    Ok(())
+}
+
+fn tool_error(tool: &str, e: std::io::Error) -> Error {
+    Error::Other(format!(
+        "Failed to run {} ({}). Install squashfs-tools to archive games.",
+        tool, e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_estimate_compressible_data() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("data")).unwrap();
+        fs::write(dir.path().join("data/zeros.pak"), vec![0u8; 256 * 1024]).unwrap();
+
+        let estimate = estimate_archive(dir.path()).unwrap();
+        assert_eq!(estimate.installed_bytes, 256 * 1024);
+        assert!(estimate.estimated_bytes < estimate.installed_bytes / 10);
+        assert!(estimate.savings() > 0);
+    }
+
+    #[test]
+    fn test_archive_path_for() {
+        assert_eq!(
+            archive_path_for(Path::new("/games/Fortnite")),
+            PathBuf::from("/games/Fortnite.squashfs")
+        );
+    }
+}
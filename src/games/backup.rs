@@ -0,0 +1,243 @@
+//! Offline save backups, independent of Epic's cloud: a game's save folder
+//! packed into a zstd-compressed tar, `<app_name>-<timestamp>.tar.zst`.
+//! The archives open with ordinary tools (`tar --zstd -xf`), so they stay
+//! useful without the launcher.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use chrono::Utc;
+
+use super::saves::{self, LocalSave};
+use crate::config::Config;
+use crate::{Error, Result};
+
+const BLOCK: usize = 512;
+/// Longest name that fits a tar header; longer ones get a GNU long name
+/// entry in front
+const NAME_LEN: usize = 100;
+const LONG_NAME: &str = "././@LongLink";
+/// Largest file read back from a backup. No save comes near it, so a bigger
+/// size in a header means the archive is damaged or not a backup.
+const MAX_FILE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// A file read back from a backup
+#[derive(Debug, Clone)]
+pub struct BackupEntry {
+    /// Path relative to the save folder, with `/` separators
+    pub filename: String,
+    pub data: Vec<u8>,
+}
+
+/// Where backups go unless another directory is given
+pub fn default_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("save-backups"))
+}
+
+/// File name of a backup of `app_name` taken now
+pub fn file_name(app_name: &str) -> String {
+    format!(
+        "{}-{}.tar.zst",
+        app_name,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Write `files` to a new backup at `path`
+pub fn write(path: &Path, files: &[LocalSave]) -> Result<()> {
+    let partial = path.with_extension("zst.partial");
+    let mut encoder = zstd::Encoder::new(File::create(&partial)?, 0)?;
+
+    for file in files {
+        let mut data = Vec::new();
+        File::open(&file.path)?.read_to_end(&mut data)?;
+        let mtime = file
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or_default();
+
+        if file.filename.len() > NAME_LEN {
+            let mut name = file.filename.clone().into_bytes();
+            name.push(0);
+            encoder.write_all(&header(LONG_NAME, name.len() as u64, 0, b'L'))?;
+            write_padded(&mut encoder, &name)?;
+        }
+        encoder.write_all(&header(&file.filename, data.len() as u64, mtime, b'0'))?;
+        write_padded(&mut encoder, &data)?;
+    }
+    encoder.write_all(&[0u8; BLOCK * 2])?;
+    encoder.finish()?.sync_all()?;
+
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// Read the files of the backup at `path`
+pub fn read(path: &Path) -> Result<Vec<BackupEntry>> {
+    let mut decoder = zstd::Decoder::new(File::open(path)?)?;
+    let damaged = |reason: &str| Error::Other(format!("Backup {:?} is damaged: {}", path, reason));
+
+    let mut entries = Vec::new();
+    let mut long_name = None;
+    loop {
+        let mut block = [0u8; BLOCK];
+        decoder
+            .read_exact(&mut block)
+            .map_err(|_| damaged("it ends early"))?;
+        if block.iter().all(|&b| b == 0) {
+            return Ok(entries);
+        }
+        if octal(&block[148..156]) != Some(checksum(&block)) {
+            return Err(damaged("a header checksum doesn't match"));
+        }
+
+        let size = octal(&block[124..136]).ok_or_else(|| damaged("a file size is unreadable"))?;
+        if size > MAX_FILE_BYTES {
+            return Err(damaged("a file is too large to be a save"));
+        }
+        // Grown as it is read, so a truncated archive allocates only what it has
+        let mut data = Vec::new();
+        match (&mut decoder).take(size).read_to_end(&mut data) {
+            Ok(read) if read as u64 == size => {}
+            _ => return Err(damaged("it ends early")),
+        }
+        let padding = (BLOCK - size as usize % BLOCK) % BLOCK;
+        decoder
+            .read_exact(&mut vec![0u8; padding])
+            .map_err(|_| damaged("it ends early"))?;
+
+        match block[156] {
+            b'L' => {
+                let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                long_name = Some(String::from_utf8_lossy(&data[..end]).into_owned());
+            }
+            b'0' | 0 => {
+                let filename = long_name.take().unwrap_or_else(|| header_name(&block));
+                // Checked here so a bad archive can't reach outside the
+                // save folder on restore
+                saves::save_file_path(Path::new(""), &filename)?;
+                entries.push(BackupEntry { filename, data });
+            }
+            // Directories and anything else a hand-made archive might hold
+            _ => long_name = None,
+        }
+    }
+}
+
+fn header(name: &str, size: u64, mtime: u64, kind: u8) -> [u8; BLOCK] {
+    let mut block = [0u8; BLOCK];
+    let name = name.as_bytes();
+    let name = &name[..name.len().min(NAME_LEN)];
+    block[..name.len()].copy_from_slice(name);
+    put_octal(&mut block[100..108], 0o644);
+    put_octal(&mut block[108..116], 0);
+    put_octal(&mut block[116..124], 0);
+    put_octal(&mut block[124..136], size);
+    put_octal(&mut block[136..148], mtime);
+    block[156] = kind;
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+
+    let sum = checksum(&block);
+    put_octal(&mut block[148..155], sum);
+    block[155] = b' ';
+    block
+}
+
+/// Sum of the header's bytes, counting the checksum field as spaces
+fn checksum(block: &[u8; BLOCK]) -> u64 {
+    block
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b } as u64)
+        .sum()
+}
+
+/// Name of a ustar header, joined to its prefix field if it has one
+fn header_name(block: &[u8; BLOCK]) -> String {
+    let field = |bytes: &[u8]| {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).into_owned()
+    };
+    let name = field(&block[..NAME_LEN]);
+    let prefix = field(&block[345..500]);
+    // GNU tar's own format keeps other fields where ustar has the prefix
+    if &block[257..263] == b"ustar\0" && !prefix.is_empty() {
+        format!("{}/{}", prefix, name)
+    } else {
+        name
+    }
+}
+
+/// Zero-padded octal digits followed by a NUL, filling `field`
+fn put_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    field[digits.len()] = 0;
+}
+
+fn octal(field: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(field).ok()?;
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    u64::from_str_radix(text, 8).ok()
+}
+
+fn write_padded(out: &mut impl Write, data: &[u8]) -> Result<()> {
+    out.write_all(data)?;
+    out.write_all(&vec![0u8; (BLOCK - data.len() % BLOCK) % BLOCK])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let long = format!("{}/slot.sav", "Deeply/Nested".repeat(10));
+        std::fs::create_dir_all(dir.path().join("saves").join(&long).parent().unwrap()).unwrap();
+        std::fs::write(dir.path().join("saves/slot1.sav"), b"one").unwrap();
+        std::fs::write(dir.path().join("saves").join(&long), vec![7u8; 1000]).unwrap();
+
+        let files = saves::local_saves(&dir.path().join("saves")).unwrap();
+        let archive = dir.path().join("Game.tar.zst");
+        write(&archive, &files).unwrap();
+
+        let mut entries = read(&archive).unwrap();
+        entries.sort_by(|a, b| a.filename.cmp(&b.filename));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].filename, long);
+        assert_eq!(entries[0].data, vec![7u8; 1000]);
+        assert_eq!(entries[1].filename, "slot1.sav");
+        assert_eq!(entries[1].data, b"one");
+    }
+
+    #[test]
+    fn test_unsafe_names_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("evil.tar.zst");
+        let mut tar = header("../outside", 1, 0, b'0').to_vec();
+        tar.push(b'x');
+        tar.resize(BLOCK * 4, 0);
+        std::fs::write(&archive, zstd::bulk::compress(&tar, 0).unwrap()).unwrap();
+
+        assert!(read(&archive).is_err());
+    }
+
+    #[test]
+    fn test_oversized_entries_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("huge.tar.zst");
+        let mut tar = header("slot.sav", 1 << 31, 0, b'0').to_vec();
+        tar.resize(BLOCK * 4, 0);
+        std::fs::write(&archive, zstd::bulk::compress(&tar, 0).unwrap()).unwrap();
+
+        let error = read(&archive).unwrap_err();
+        assert!(error.to_string().contains("too large"), "{}", error);
+    }
+}
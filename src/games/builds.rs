@@ -0,0 +1,418 @@
+//! Proton-GE and Wine-GE builds, downloaded from GitHub into the launcher's
+//! runners directory. They work without Steam or a distribution package and
+//! can be picked per game like any other runner. Each download is checked
+//! against the SHA-512 sum published with the release before it is unpacked.
+
+use serde::Deserialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use super::Runner;
+use crate::config::Config;
+use crate::{format, Error, Result};
+
+const PROTON_GE_RELEASES: &str =
+    "https://api.github.com/repos/GloriousEggroll/proton-ge-custom/releases";
+const WINE_GE_RELEASES: &str =
+    "https://api.github.com/repos/GloriousEggroll/wine-ge-custom/releases";
+/// The most GitHub returns per page
+const RELEASES_PER_PAGE: usize = 100;
+
+/// Which family of GE builds to fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildKind {
+    Proton,
+    Wine,
+}
+
+impl BuildKind {
+    fn releases_url(self) -> &'static str {
+        match self {
+            BuildKind::Proton => PROTON_GE_RELEASES,
+            BuildKind::Wine => WINE_GE_RELEASES,
+        }
+    }
+
+    /// Directory a release is installed to. Both projects tag releases
+    /// `GE-ProtonX-Y`, so Wine builds get a prefix to tell them apart.
+    pub fn build_name(self, tag: &str) -> String {
+        match self {
+            BuildKind::Proton => tag.to_string(),
+            BuildKind::Wine => format!("wine-{}", tag),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildRelease {
+    pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub assets: Vec<BuildAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl BuildRelease {
+    /// The tarball holding the build, as opposed to checksums
+    pub fn archive(&self) -> Option<&BuildAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name.ends_with(".tar.gz") || asset.name.ends_with(".tar.xz"))
+    }
+
+    /// The SHA-512 sum of the archive
+    pub fn checksum(&self) -> Option<&BuildAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name.ends_with(".sha512sum"))
+    }
+}
+
+/// A build in the runners directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledBuild {
+    pub name: String,
+    pub runner: Runner,
+}
+
+/// Where managed builds are kept, one directory per build
+pub fn runners_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("runners"))
+}
+
+/// Published releases of `kind`, newest first
+pub async fn available(kind: BuildKind) -> Result<Vec<BuildRelease>> {
+    let client = client()?;
+    let mut releases = Vec::new();
+    for page in 1.. {
+        let batch: Vec<BuildRelease> = client
+            .get(kind.releases_url())
+            .query(&[("per_page", RELEASES_PER_PAGE), ("page", page)])
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Api(format!("Failed to list runner builds: {}", e)))?
+            .json()
+            .await?;
+        let last = batch.len() < RELEASES_PER_PAGE;
+        releases.extend(batch);
+        if last {
+            break;
+        }
+    }
+
+    Ok(releases
+        .into_iter()
+        .filter(|release| !release.draft && release.archive().is_some())
+        .collect())
+}
+
+/// Download and unpack release `tag` of `kind`, or the newest stable one
+/// when no tag is given
+pub async fn install(kind: BuildKind, tag: Option<&str>) -> Result<InstalledBuild> {
+    let releases = available(kind).await?;
+    let release = match tag {
+        Some(tag) => releases.iter().find(|release| release.tag_name == tag),
+        None => releases.iter().find(|release| !release.prerelease),
+    }
+    .ok_or_else(|| {
+        Error::Other(format!(
+            "No build {} found. Run 'rauncher runner list --available' to see the builds.",
+            tag.unwrap_or("release")
+        ))
+    })?;
+    let name = kind.build_name(&release.tag_name);
+    check_name(&name)?;
+
+    let dir = runners_dir()?;
+    let target = dir.join(&name);
+    if target.exists() {
+        return Err(Error::Other(format!("{} is already installed", name)));
+    }
+    fs::create_dir_all(&dir)?;
+
+    // Checked by `available`
+    let asset = release.archive().unwrap();
+    let checksum = release.checksum().ok_or_else(|| {
+        Error::Other(format!(
+            "{} has no .sha512sum to check the download against",
+            release.tag_name
+        ))
+    })?;
+    let expected = client()?
+        .get(&checksum.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Api(format!("Failed to download the checksum: {}", e)))?
+        .text()
+        .await?;
+
+    println!(
+        "Downloading {} ({})...",
+        asset.name,
+        format::Bytes(asset.size)
+    );
+    let archive = dir.join(format!("{}.download", name));
+    let sha512 = download(&asset.browser_download_url, &archive).await?;
+    if let Err(e) = check_sha512(&expected, &sha512) {
+        fs::remove_file(&archive)?;
+        return Err(e);
+    }
+
+    let unpacked = unpack(&archive, &dir, &name);
+    fs::remove_file(&archive)?;
+    unpacked?;
+
+    match runner_in(&target) {
+        Some(runner) => Ok(InstalledBuild { name, runner }),
+        None => Err(Error::Other(format!(
+            "{} holds neither a proton script nor bin/wine",
+            name
+        ))),
+    }
+}
+
+/// Builds in the runners directory, by name
+pub fn installed() -> Result<Vec<InstalledBuild>> {
+    installed_in(&runners_dir()?)
+}
+
+fn installed_in(dir: &Path) -> Result<Vec<InstalledBuild>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut builds = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // Unfinished installs carry a `.partial` suffix
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if name.ends_with(".partial") {
+            continue;
+        }
+        if let Some(runner) = runner_in(&entry.path()) {
+            builds.push(InstalledBuild { name, runner });
+        }
+    }
+    builds.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(builds)
+}
+
+/// Delete the build called `name`
+pub fn remove(name: &str) -> Result<()> {
+    check_name(name)?;
+
+    let path = runners_dir()?.join(name);
+    if !path.is_dir() {
+        return Err(Error::Other(format!("{} is not installed", name)));
+    }
+
+    fs::remove_dir_all(&path)?;
+    Ok(())
+}
+
+/// How to run games with the build at `path`. Proton builds carry a
+/// `proton` script; Wine builds a `bin/wine` binary.
+fn runner_in(path: &Path) -> Option<Runner> {
+    let name = path.file_name()?.to_str()?;
+    if path.join("proton").is_file() {
+        Some(Runner::Proton(name.to_string()))
+    } else if path.join("bin/wine").is_file() {
+        Some(Runner::Wine(path.join("bin/wine")))
+    } else {
+        None
+    }
+}
+
+/// Names become directories, so they must be a single plain component
+fn check_name(name: &str) -> Result<()> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(()),
+        _ => Err(Error::Other(format!("Invalid runner name: {}", name))),
+    }
+}
+
+fn client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent(concat!("rauncher/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// Download `url` to `path`, returning the SHA-512 of what was written
+async fn download(url: &str, path: &Path) -> Result<String> {
+    let mut response = client()?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Api(format!("Failed to download runner: {}", e)))?;
+
+    let mut file = File::create(path)?;
+    let mut digest = ring::digest::Context::new(&ring::digest::SHA512);
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        digest.update(&chunk);
+    }
+    file.sync_all()?;
+
+    Ok(digest
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Compare a download's SHA-512 with a `.sha512sum` file, which holds the
+/// sum followed by the archive's name
+fn check_sha512(sum_file: &str, sha512: &str) -> Result<()> {
+    let expected = sum_file.split_whitespace().next().unwrap_or_default();
+    if expected.len() != 128 {
+        return Err(Error::Other(format!(
+            "Unreadable checksum file: {:?}",
+            sum_file.lines().next().unwrap_or_default()
+        )));
+    }
+    if !expected.eq_ignore_ascii_case(sha512) {
+        return Err(Error::Other(
+            "The runner download doesn't match its published checksum".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Extract `archive` with the system `tar` and move the directory it holds
+/// to `dir/name`. Extraction happens in `name.partial`, so an interrupted
+/// install never looks like a build.
+fn unpack(archive: &Path, dir: &Path, name: &str) -> Result<()> {
+    let partial = dir.join(format!("{}.partial", name));
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    fs::create_dir_all(&partial)?;
+
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(&partial)
+        .status()
+        .map_err(|e| Error::Other(format!("Failed to run tar: {}", e)))?;
+    if !status.success() {
+        fs::remove_dir_all(&partial)?;
+        return Err(Error::Other(format!(
+            "tar exited with {} while unpacking {}",
+            status, name
+        )));
+    }
+
+    // Archives hold one top-level directory, named differently from the tag
+    // for Wine-GE (e.g. `lutris-GE-Proton8-26-x86_64`)
+    let mut entries: Vec<PathBuf> = fs::read_dir(&partial)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    let root = match entries.as_slice() {
+        [single] if single.is_dir() => entries.remove(0),
+        _ => partial.clone(),
+    };
+
+    fs::rename(&root, dir.join(name))?;
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn asset(name: &str) -> BuildAsset {
+        BuildAsset {
+            name: name.to_string(),
+            browser_download_url: String::new(),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_release_archive_skips_checksums() {
+        let release = BuildRelease {
+            tag_name: "GE-Proton9-20".to_string(),
+            prerelease: false,
+            draft: false,
+            assets: vec![
+                asset("GE-Proton9-20.sha512sum"),
+                asset("GE-Proton9-20.tar.gz"),
+            ],
+        };
+
+        assert_eq!(release.archive().unwrap().name, "GE-Proton9-20.tar.gz");
+        assert_eq!(release.checksum().unwrap().name, "GE-Proton9-20.sha512sum");
+        assert!(check_name("GE-Proton9-20").is_ok());
+        assert!(check_name("../outside").is_err());
+    }
+
+    #[test]
+    fn test_check_sha512() {
+        let sha512 = "ab".repeat(64);
+        let sum_file = format!("{}  GE-Proton9-20.tar.gz\n", sha512.to_uppercase());
+
+        assert!(check_sha512(&sum_file, &sha512).is_ok());
+        assert!(check_sha512(&sum_file, &"cd".repeat(64)).is_err());
+        assert!(check_sha512("Not Found", &sha512).is_err());
+        assert!(check_sha512("", &sha512).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unpack_renames_the_top_directory() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("lutris-GE-Proton8-26-x86_64");
+        fs::create_dir_all(source.join("bin")).unwrap();
+        fs::write(source.join("bin/wine"), "").unwrap();
+
+        let archive = dir.path().join("build.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir.path())
+            .arg("lutris-GE-Proton8-26-x86_64")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let runners = dir.path().join("runners");
+        fs::create_dir_all(&runners).unwrap();
+        let name = BuildKind::Wine.build_name("GE-Proton8-26");
+        unpack(&archive, &runners, &name).unwrap();
+
+        assert_eq!(
+            installed_in(&runners).unwrap(),
+            vec![InstalledBuild {
+                name: "wine-GE-Proton8-26".to_string(),
+                runner: Runner::Wine(runners.join("wine-GE-Proton8-26/bin/wine")),
+            }]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::config::storage;
+use crate::Result;
+
+/// Files under the launcher's cache directory, expired after a time-to-live
+/// and evicted least-recently-used first once they outgrow a size limit.
+/// Reading an entry refreshes its modification time, which is what the
+/// eviction order is based on. The directory is scanned once for its size,
+/// which is then kept up to date as entries are added and removed, so storing
+/// an entry only walks the cache again when it goes over the limit.
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Duration,
+    max_bytes: u64,
+    /// Bytes held, once counted. Other processes' changes show up at the
+    /// next prune.
+    used: Mutex<Option<u64>>,
+}
+
+/// What the cache currently holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+struct Entry {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+}
+
+impl DiskCache {
+    pub fn new(dir: PathBuf, ttl: Duration, max_bytes: u64) -> Self {
+        Self {
+            dir,
+            ttl,
+            max_bytes,
+            used: Mutex::new(None),
+        }
+    }
+
+    /// Contents of `key` (a relative path such as `manifests/Fortnite.json`)
+    /// if it exists and hasn't expired. A disabled cache never has entries.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        if self.max_bytes == 0 {
+            return None;
+        }
+
+        let path = self.dir.join(key);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+
+        if self.is_expired(modified) {
+            log::debug!("Cache entry {} expired", key);
+            let _ = self.remove(key);
+            return None;
+        }
+
+        let data = fs::read(&path).ok()?;
+        if let Err(e) = File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            log::debug!("Failed to mark cache entry {} as used: {}", key, e);
+        }
+
+        Some(data)
+    }
+
+    /// Store `data` under `key`, then evict old entries if the cache is over
+    /// its size limit. A limit of zero disables the cache.
+    pub fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        if self.max_bytes == 0 {
+            return Ok(());
+        }
+
+        // Counted before the first write, so the new entry isn't seen twice
+        self.used_bytes()?;
+        let path = self.dir.join(key);
+        let replaced = entry_size(&path);
+        storage::write_private(&path, data)?;
+
+        if self.resize(data.len() as u64, replaced) > self.max_bytes {
+            self.prune()?;
+        }
+        Ok(())
+    }
+
+    /// Drop `key` if it is cached
+    pub fn remove(&self, key: &str) -> Result<()> {
+        let path = self.dir.join(key);
+        let size = entry_size(&path);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            Err(_) => Ok(()),
+            Ok(()) => {
+                self.resize(0, size);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn usage(&self) -> Result<CacheUsage> {
+        let entries = self.entries()?;
+
+        Ok(CacheUsage {
+            entries: entries.len(),
+            bytes: entries.iter().map(|entry| entry.bytes).sum(),
+        })
+    }
+
+    /// Remove expired entries, then the least recently used ones until the
+    /// cache fits its size limit. Returns the number of bytes freed.
+    pub fn prune(&self) -> Result<u64> {
+        let mut used = self.used.lock().unwrap();
+        let mut entries = self.entries()?;
+        let mut freed = 0;
+
+        entries.sort_by_key(|entry| entry.modified);
+        let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+
+        for entry in entries {
+            if !self.is_expired(entry.modified) && total <= self.max_bytes {
+                continue;
+            }
+
+            fs::remove_file(&entry.path)?;
+            total -= entry.bytes;
+            freed += entry.bytes;
+        }
+
+        *used = Some(total);
+        Ok(freed)
+    }
+
+    /// Bytes held, counting them if this is the first time they are needed
+    fn used_bytes(&self) -> Result<u64> {
+        let mut used = self.used.lock().unwrap();
+        if let Some(bytes) = *used {
+            return Ok(bytes);
+        }
+        let bytes = self.usage()?.bytes;
+        *used = Some(bytes);
+        Ok(bytes)
+    }
+
+    /// Account for `added` bytes written and `removed` bytes deleted.
+    /// Returns the new total, or zero if the cache wasn't counted yet.
+    fn resize(&self, added: u64, removed: u64) -> u64 {
+        let mut used = self.used.lock().unwrap();
+        let Some(bytes) = used.as_mut() else {
+            return 0;
+        };
+        *bytes = (*bytes + added).saturating_sub(removed);
+        *bytes
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        modified.elapsed().is_ok_and(|age| age > self.ttl)
+    }
+
+    fn entries(&self) -> Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        collect_entries(&self.dir, &mut entries)?;
+        Ok(entries)
+    }
+}
+
+fn entry_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn collect_entries(dir: &Path, entries: &mut Vec<Entry>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            collect_entries(&entry.path(), entries)?;
+        } else {
+            entries.push(Entry {
+                path: entry.path(),
+                bytes: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn age(cache: &DiskCache, key: &str, by: Duration) {
+        File::options()
+            .write(true)
+            .open(cache.dir.join(key))
+            .unwrap()
+            .set_modified(SystemTime::now() - by)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path().to_path_buf(), HOUR, 1024);
+
+        cache.put("manifests/Fortnite.json", b"{}").unwrap();
+        assert_eq!(cache.get("manifests/Fortnite.json").unwrap(), b"{}");
+
+        age(&cache, "manifests/Fortnite.json", 2 * HOUR);
+        assert!(cache.get("manifests/Fortnite.json").is_none());
+        assert_eq!(cache.usage().unwrap(), CacheUsage::default());
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path().to_path_buf(), HOUR, 20);
+
+        cache.put("a", &[0u8; 8]).unwrap();
+        age(&cache, "a", Duration::from_secs(60));
+        cache.put("b", &[0u8; 8]).unwrap();
+        age(&cache, "b", Duration::from_secs(30));
+
+        // Reading `a` makes `b` the least recently used entry
+        assert!(cache.get("a").is_some());
+        cache.put("c", &[0u8; 8]).unwrap();
+
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert_eq!(
+            cache.usage().unwrap(),
+            CacheUsage {
+                entries: 2,
+                bytes: 16
+            }
+        );
+    }
+
+    #[test]
+    fn test_size_is_tracked_without_rescanning() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path().to_path_buf(), HOUR, 20);
+        let used = |cache: &DiskCache| cache.used.lock().unwrap().unwrap();
+
+        cache.put("a", &[0u8; 8]).unwrap();
+        cache.put("b", &[0u8; 4]).unwrap();
+        // Replacing an entry only counts the difference
+        cache.put("a", &[0u8; 6]).unwrap();
+        assert_eq!(used(&cache), 10);
+        cache.remove("b").unwrap();
+        cache.remove("b").unwrap();
+        assert_eq!(used(&cache), 6);
+
+        // Expired, but the cache is under its limit so nothing walks it
+        age(&cache, "a", 2 * HOUR);
+        cache.put("c", &[0u8; 4]).unwrap();
+        assert_eq!(cache.usage().unwrap().entries, 2);
+
+        // Going over the limit prunes, and the count follows
+        cache.put("d", &[0u8; 12]).unwrap();
+        assert_eq!(cache.usage().unwrap().bytes, 16);
+        assert_eq!(used(&cache), 16);
+    }
+}
@@ -0,0 +1,104 @@
+//! Lets a frontend pause or cancel a download from another task or thread.
+//! Downloads check the token between chunks, so a pause takes effect after
+//! the chunk in flight and a cancelled install keeps every finished file
+//! for the next attempt to resume from.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+use crate::{Error, Result};
+
+/// Shared handle; clones control the same downloads
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+    changed: Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop at the next chunk. Also ends a pause.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+    }
+
+    pub fn pause(&self) {
+        self.inner.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.inner.paused.store(false, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::SeqCst)
+    }
+
+    /// Wait out a pause, then fail with `Error::Cancelled` if cancelled
+    pub async fn checkpoint(&self) -> Result<()> {
+        loop {
+            // Registered before the checks so a resume in between isn't missed
+            let changed = self.inner.changed.notified();
+
+            if self.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+            if !self.is_paused() {
+                return Ok(());
+            }
+            changed.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_pause_holds_until_resumed() {
+        let token = CancellationToken::new();
+        token.pause();
+
+        let waiting = tokio::spawn({
+            let token = token.clone();
+            async move { token.checkpoint().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        token.resume();
+        assert!(waiting.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ends_a_pause() {
+        let token = CancellationToken::new();
+        token.pause();
+
+        let waiting = tokio::spawn({
+            let token = token.clone();
+            async move { token.checkpoint().await }
+        });
+        token.cancel();
+
+        assert!(matches!(waiting.await.unwrap(), Err(Error::Cancelled)));
+    }
+}
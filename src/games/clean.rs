@@ -0,0 +1,218 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+/// Why a path is safe to remove
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// Anything in the launcher's cache directory (manifests, chunks)
+    Cache,
+    /// Stored manifest of a game that is no longer installed
+    OrphanedManifest,
+    /// `<game>.backup` directory left behind by an update
+    UpdateBackup,
+    /// `.part` file from an interrupted download
+    PartialDownload,
+    /// `<game>.staging` directory of an install that never finished
+    StagedInstall,
+}
+
+impl std::fmt::Display for LeftoverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeftoverKind::Cache => write!(f, "cache"),
+            LeftoverKind::OrphanedManifest => write!(f, "orphaned manifest"),
+            LeftoverKind::UpdateBackup => write!(f, "update backup"),
+            LeftoverKind::PartialDownload => write!(f, "partial download"),
+            LeftoverKind::StagedInstall => write!(f, "unfinished install"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Leftover {
+    pub path: PathBuf,
+    pub kind: LeftoverKind,
+    pub bytes: u64,
+}
+
+/// Collect every leftover that `clean` would remove. Files of the `busy`
+/// games, which have an install, update or uninstall running, are still in
+/// use and left alone, as is the cache those operations read from.
+pub fn find_leftovers(
+    install_dir: &Path,
+    cache_dir: &Path,
+    manifests_dir: &Path,
+    installed: &[String],
+    busy: &[String],
+) -> Result<Vec<Leftover>> {
+    let mut leftovers = Vec::new();
+    let is_one_of = |entry: &Path, apps: &[String]| {
+        entry
+            .file_stem()
+            .is_some_and(|stem| apps.iter().any(|app| stem == app.as_str()))
+    };
+
+    if busy.is_empty() {
+        for entry in read_dir_if_exists(cache_dir)? {
+            leftovers.push(leftover(entry, LeftoverKind::Cache)?);
+        }
+    }
+
+    for entry in read_dir_if_exists(manifests_dir)? {
+        if !is_one_of(&entry, installed) && !is_one_of(&entry, busy) {
+            leftovers.push(leftover(entry, LeftoverKind::OrphanedManifest)?);
+        }
+    }
+
+    for entry in read_dir_if_exists(install_dir)? {
+        // `<game>`, `<game>.backup` and `<game>.staging` alike
+        if is_one_of(&entry, busy) {
+            continue;
+        }
+        if entry.is_dir() && entry.extension().is_some_and(|ext| ext == "backup") {
+            leftovers.push(leftover(entry, LeftoverKind::UpdateBackup)?);
+        } else if entry.is_dir() && entry.extension().is_some_and(|ext| ext == "staging") {
+            leftovers.push(leftover(entry, LeftoverKind::StagedInstall)?);
+        } else if entry.is_dir() {
+            find_partial_downloads(&entry, &mut leftovers)?;
+        }
+    }
+
+    Ok(leftovers)
+}
+
+/// Delete the given leftovers, returning the number of bytes reclaimed
+pub fn remove_leftovers(leftovers: &[Leftover]) -> Result<u64> {
+    let mut reclaimed = 0;
+
+    for item in leftovers {
+        if item.path.is_dir() {
+            fs::remove_dir_all(&item.path)?;
+        } else {
+            fs::remove_file(&item.path)?;
+        }
+        reclaimed += item.bytes;
+    }
+
+    Ok(reclaimed)
+}
+
+fn find_partial_downloads(dir: &Path, leftovers: &mut Vec<Leftover>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            find_partial_downloads(&path, leftovers)?;
+        } else if file_type.is_file() && path.extension().is_some_and(|ext| ext == "part") {
+            leftovers.push(leftover(path, LeftoverKind::PartialDownload)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_dir_if_exists(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        entries.push(entry?.path());
+    }
+    entries.sort();
+
+    Ok(entries)
+}
+
+fn leftover(path: PathBuf, kind: LeftoverKind) -> Result<Leftover> {
+    let bytes = size_of(&path)?;
+    Ok(Leftover { path, kind, bytes })
+}
+
+fn size_of(path: &Path) -> Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += size_of(&entry?.path())?;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_leftovers() {
+        let dir = TempDir::new().unwrap();
+        let install = dir.path().join("games");
+        let cache = dir.path().join("cache");
+        let manifests = dir.path().join("manifests");
+
+        fs::create_dir_all(install.join("Fortnite/Content")).unwrap();
+        fs::write(install.join("Fortnite/Content/pak0.pak"), b"done").unwrap();
+        fs::write(install.join("Fortnite/Content/pak1.pak.part"), b"half").unwrap();
+        fs::create_dir_all(install.join("Fortnite.backup")).unwrap();
+        fs::create_dir_all(install.join("Hades.staging")).unwrap();
+        fs::write(install.join("Hades.staging/Hades.exe.part"), b"half").unwrap();
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(cache.join("chunk.bin"), b"chunk").unwrap();
+        fs::create_dir_all(&manifests).unwrap();
+        fs::write(manifests.join("Fortnite.json"), b"{}").unwrap();
+        fs::write(manifests.join("Hades.json"), b"{}").unwrap();
+
+        let leftovers =
+            find_leftovers(&install, &cache, &manifests, &["Fortnite".to_string()], &[]).unwrap();
+        let kinds: Vec<_> = leftovers.iter().map(|l| l.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LeftoverKind::Cache,
+                LeftoverKind::OrphanedManifest,
+                LeftoverKind::PartialDownload,
+                LeftoverKind::UpdateBackup,
+                LeftoverKind::StagedInstall,
+            ]
+        );
+        assert_eq!(leftovers[1].path, manifests.join("Hades.json"));
+
+        // Hades is being installed, and Fortnite updated
+        let busy = ["Hades".to_string(), "Fortnite".to_string()];
+        let leftovers = find_leftovers(
+            &install,
+            &cache,
+            &manifests,
+            &["Fortnite".to_string()],
+            &busy,
+        )
+        .unwrap();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    }
+
+    #[test]
+    fn test_remove_leftovers_reports_bytes() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Game.backup")).unwrap();
+        fs::write(dir.path().join("Game.backup/data"), vec![0u8; 100]).unwrap();
+
+        let missing = dir.path().join("missing");
+        let leftovers = find_leftovers(dir.path(), &missing, &missing, &[], &[]).unwrap();
+        assert_eq!(leftovers.len(), 1);
+
+        assert_eq!(remove_leftovers(&leftovers).unwrap(), 100);
+        assert!(!dir.path().join("Game.backup").exists());
+    }
+}
@@ -0,0 +1,210 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use crate::Result;
+
+/// How a file's contents ended up at its destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyStrategy {
+    /// Copy-on-write clone sharing extents with the source (btrfs, XFS)
+    Reflink,
+    /// In-kernel copy without round-tripping through userspace
+    CopyFileRange,
+    /// Plain read/write loop
+    Buffered,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CopyStats {
+    pub files: usize,
+    pub bytes: u64,
+    pub reflinked: usize,
+    pub kernel_copied: usize,
+    pub buffered: usize,
+}
+
+impl CopyStats {
+    fn record(&mut self, strategy: CopyStrategy, bytes: u64) {
+        self.files += 1;
+        self.bytes += bytes;
+        match strategy {
+            CopyStrategy::Reflink => self.reflinked += 1,
+            CopyStrategy::CopyFileRange => self.kernel_copied += 1,
+            CopyStrategy::Buffered => self.buffered += 1,
+        }
+    }
+}
+
+/// Recursively copy a directory tree using the cheapest strategy available per file
+pub fn copy_tree(src: &Path, dst: &Path) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
+    copy_dir(src, dst, &mut stats)?;
+    Ok(stats)
+}
+
+fn copy_dir(src: &Path, dst: &Path, stats: &mut CopyStats) -> Result<()> {
+    fs::create_dir_all(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = dst.join(entry.file_name());
+
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target, stats)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &target)?;
+        } else {
+            let bytes = entry.metadata()?.len();
+            let strategy = copy_file(&entry.path(), &target)?;
+            stats.record(strategy, bytes);
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy a single file, preferring reflink, then copy_file_range, then a buffered copy
+pub fn copy_file(src: &Path, dst: &Path) -> Result<CopyStrategy> {
+    let mut input = File::open(src)?;
+    let metadata = input.metadata()?;
+    let mut output = File::create(dst)?;
+
+    let strategy = if try_reflink(&input, &output) {
+        CopyStrategy::Reflink
+    } else if try_copy_file_range(&input, &output, metadata.len())? {
+        CopyStrategy::CopyFileRange
+    } else {
+        io::copy(&mut input, &mut output)?;
+        CopyStrategy::Buffered
+    };
+
+    fs::set_permissions(dst, metadata.permissions())?;
+
+    Ok(strategy)
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> Result<()> {
+    let target = fs::read_link(src)?;
+    std::os::unix::fs::symlink(target, dst)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dst: &Path) -> Result<()> {
+    fs::copy(src, dst)?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn try_reflink(input: &File, output: &File) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    // _IOW(0x94, 9, int) from linux/fs.h
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+
+    // SAFETY: both descriptors are valid open files for the duration of the call
+    unsafe { libc::ioctl(output.as_raw_fd(), FICLONE as _, input.as_raw_fd()) == 0 }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn try_reflink(_input: &File, _output: &File) -> bool {
+    false
+}
+
+/// Returns Ok(false) when the kernel or filesystem pair can't do an in-kernel copy
+#[cfg(target_os = "linux")]
+fn try_copy_file_range(input: &File, output: &File, len: u64) -> io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut remaining = len;
+    let mut copied_any = false;
+
+    while remaining > 0 {
+        let chunk = remaining.min(1 << 30) as usize;
+
+        // SAFETY: null offsets make the kernel use and advance the file positions
+        let copied = unsafe {
+            libc::copy_file_range(
+                input.as_raw_fd(),
+                std::ptr::null_mut(),
+                output.as_raw_fd(),
+                std::ptr::null_mut(),
+                chunk,
+                0,
+            )
+        };
+
+        if copied < 0 {
+            let err = io::Error::last_os_error();
+            let unsupported = matches!(
+                err.raw_os_error(),
+                Some(libc::EXDEV | libc::ENOSYS | libc::EINVAL | libc::EOPNOTSUPP)
+            );
+
+            if unsupported && !copied_any {
+                return Ok(false);
+            }
+            return Err(err);
+        }
+
+        if copied == 0 {
+            break;
+        }
+
+        copied_any = true;
+        remaining -= copied as u64;
+    }
+
+    Ok(true)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn try_copy_file_range(_input: &File, _output: &File, _len: u64) -> io::Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_copy_tree_preserves_contents() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("a.txt"), b"alpha").unwrap();
+        fs::write(src.join("nested/b.bin"), vec![7u8; 4096]).unwrap();
+
+        let dst = dir.path().join("dst");
+        let stats = copy_tree(&src, &dst).unwrap();
+
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.bytes, 5 + 4096);
+        assert_eq!(fs::read(dst.join("a.txt")).unwrap(), b"alpha");
+        assert_eq!(fs::read(dst.join("nested/b.bin")).unwrap(), vec![7u8; 4096]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_tree_recreates_symlinks() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("real"), b"data").unwrap();
+        std::os::unix::fs::symlink("real", src.join("link")).unwrap();
+
+        let dst = dir.path().join("dst");
+        copy_tree(&src, &dst).unwrap();
+
+        let link = dst.join("link");
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("real"));
+    }
+}
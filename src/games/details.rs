@@ -0,0 +1,84 @@
+//! What the GUI shows of a game beyond its library entry: the cover art on
+//! its card, and on its page the store description and artwork, how much
+//! space it takes and its add-ons.
+
+use crate::api::{Dlc, KeyImage};
+
+/// Artwork types wide enough for the top of a game's page, best first
+const BANNER_IMAGE_TYPES: [&str; 4] = [
+    "DieselStoreFrontWide",
+    "OfferImageWide",
+    "DieselGameBox",
+    "Featured",
+];
+
+/// Artwork types for a library card, which is wider than tall, best first
+const COVER_IMAGE_TYPES: [&str; 4] = [
+    "DieselGameBox",
+    "OfferImageWide",
+    "DieselStoreFrontWide",
+    "Thumbnail",
+];
+
+/// Cover art downloaded at once while the library fills in
+pub const COVER_DOWNLOADS: usize = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct GameDetails {
+    pub description: String,
+    /// Image data of the banner, if the store has one
+    pub artwork: Option<Vec<u8>>,
+    /// Bytes on disk once installed; from the installed build if there is
+    /// one, else from the latest
+    pub install_size: Option<u64>,
+    /// Add-ons owned for this game
+    pub dlcs: Vec<Dlc>,
+}
+
+/// The artwork to show as a game's banner
+pub fn banner_image(images: &[KeyImage]) -> Option<&KeyImage> {
+    preferred_image(images, &BANNER_IMAGE_TYPES)
+}
+
+/// The artwork to show on a game's library card
+pub fn cover_image(images: &[KeyImage]) -> Option<&KeyImage> {
+    preferred_image(images, &COVER_IMAGE_TYPES)
+}
+
+/// The first of `types` the store has
+fn preferred_image<'a>(images: &'a [KeyImage], types: &[&str]) -> Option<&'a KeyImage> {
+    types
+        .iter()
+        .find_map(|kind| images.iter().find(|image| image.image_type == *kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_images_go_by_type() {
+        let image = |kind: &str, extension: &str| KeyImage {
+            image_type: kind.to_string(),
+            url: format!("https://cdn.example/{}.{}?h=480", kind, extension),
+        };
+
+        let images = vec![image("Featured", "jpg"), image("OfferImageWide", "jpg")];
+        assert_eq!(banner_image(&images).unwrap().image_type, "OfferImageWide");
+
+        let images = vec![
+            image("OfferImageWide", "jpg"),
+            image("DieselGameBox", "png"),
+        ];
+        assert_eq!(banner_image(&images).unwrap().image_type, "OfferImageWide");
+        assert_eq!(cover_image(&images).unwrap().image_type, "DieselGameBox");
+
+        assert!(banner_image(&[image("Thumbnail", "png")]).is_none());
+        assert_eq!(
+            cover_image(&[image("Thumbnail", "png")])
+                .unwrap()
+                .image_type,
+            "Thumbnail"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Compares the manifest a game was installed from with a newer one, so an
+//! update only touches files whose contents actually changed.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::api::{FileManifest, GameManifest};
+
+pub struct ManifestDiff<'a> {
+    /// Files that are new or whose hash differs from the installed build
+    pub changed: Vec<&'a FileManifest>,
+    /// Files identical in both builds
+    pub unchanged: Vec<&'a FileManifest>,
+    /// Paths the new build no longer has
+    pub removed: Vec<String>,
+}
+
+impl ManifestDiff<'_> {
+    /// Bytes the changed files add up to
+    pub fn changed_size(&self) -> u64 {
+        self.changed.iter().map(|file| file.size()).sum()
+    }
+}
+
+pub fn diff_manifests<'a>(old: &GameManifest, new: &'a GameManifest) -> ManifestDiff<'a> {
+    // A symlink's contents are its target, which the hash doesn't cover
+    let old_contents: HashMap<&str, (&[u8], Option<&str>)> = old
+        .file_list
+        .iter()
+        .map(|file| {
+            (
+                file.filename.as_str(),
+                (file.file_hash.as_slice(), file.symlink_target()),
+            )
+        })
+        .collect();
+
+    let (unchanged, changed) = new.file_list.iter().partition(|file| {
+        old_contents.get(file.filename.as_str())
+            == Some(&(file.file_hash.as_slice(), file.symlink_target()))
+    });
+
+    let new_names: HashSet<&str> = new
+        .file_list
+        .iter()
+        .map(|file| file.filename.as_str())
+        .collect();
+    let removed = old
+        .file_list
+        .iter()
+        .filter(|file| !new_names.contains(file.filename.as_str()))
+        .map(|file| file.filename.clone())
+        .collect();
+
+    ManifestDiff {
+        changed,
+        unchanged,
+        removed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::paths::{test_file, test_manifest};
+
+    fn manifest(files: &[(&str, u8)]) -> GameManifest {
+        test_manifest(files.iter().map(|&(path, hash)| test_file(path, hash)))
+    }
+
+    #[test]
+    fn test_diff_finds_changed_and_removed_files() {
+        let old = manifest(&[("Game.exe", 1), ("pak0.pak", 2), ("readme.txt", 3)]);
+        let new = manifest(&[("Game.exe", 9), ("pak0.pak", 2), ("pak1.pak", 4)]);
+
+        let diff = diff_manifests(&old, &new);
+        let names = |files: &[&FileManifest]| -> Vec<String> {
+            files.iter().map(|f| f.filename.clone()).collect()
+        };
+
+        assert_eq!(names(&diff.changed), ["Game.exe", "pak1.pak"]);
+        assert_eq!(names(&diff.unchanged), ["pak0.pak"]);
+        assert_eq!(diff.removed, ["readme.txt"]);
+    }
+}
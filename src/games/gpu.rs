@@ -0,0 +1,82 @@
+//! GPU choice for laptops with hybrid graphics. Games start on the
+//! integrated GPU unless told otherwise; these settings move them to the
+//! discrete one through the driver's render offload environment or NVIDIA's
+//! `prime-run` wrapper.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+use super::LaunchTemplate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Gpu {
+    /// Leave the choice to the system, usually the integrated GPU
+    Integrated,
+    /// Offload rendering to the discrete GPU
+    Discrete,
+    /// Start the game through `prime-run`
+    PrimeRun,
+}
+
+impl Gpu {
+    /// `command` set up to render on this GPU
+    pub fn apply(self, mut command: Command) -> Command {
+        match self {
+            Gpu::Integrated => command,
+            Gpu::Discrete => {
+                command.envs(discrete_env(Path::new("/proc/driver/nvidia").exists()));
+                command
+            }
+            Gpu::PrimeRun => LaunchTemplate {
+                env: Vec::new(),
+                wrapper: vec!["prime-run".to_string()],
+                args: Vec::new(),
+            }
+            .apply(command),
+        }
+    }
+}
+
+impl std::fmt::Display for Gpu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gpu::Integrated => write!(f, "integrated"),
+            Gpu::Discrete => write!(f, "discrete"),
+            Gpu::PrimeRun => write!(f, "prime-run"),
+        }
+    }
+}
+
+/// Render offload variables. The NVIDIA ones break OpenGL on machines
+/// without its driver, so they are only set when it is loaded; Mesa
+/// drivers use `DRI_PRIME`.
+fn discrete_env(nvidia: bool) -> Vec<(&'static str, &'static str)> {
+    if nvidia {
+        vec![
+            ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+            ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+            ("__VK_LAYER_NV_optimus", "NVIDIA_only"),
+        ]
+    } else {
+        vec![("DRI_PRIME", "1")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_settings() {
+        assert_eq!(discrete_env(false), [("DRI_PRIME", "1")]);
+        assert!(discrete_env(true).contains(&("__NV_PRIME_RENDER_OFFLOAD", "1")));
+
+        let mut command = Command::new("wine");
+        command.arg("Game.exe");
+        let wrapped = Gpu::PrimeRun.apply(command);
+        assert_eq!(wrapped.get_program(), "prime-run");
+        assert_eq!(wrapped.get_args().collect::<Vec<_>>(), ["wine", "Game.exe"]);
+    }
+}
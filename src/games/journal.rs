@@ -0,0 +1,265 @@
+//! Write-ahead journal of operations that change installed games. An entry
+//! is written and synced to disk before an install, update, uninstall or
+//! rollback touches anything, and removed once it returns. An entry still present
+//! after its process has exited means the operation was cut short by a
+//! crash or power loss. Entries are written under a lock that `clean` also
+//! holds, so it sees every operation that started before it and none starts
+//! while it runs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{storage, Config};
+use crate::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+    Install,
+    Update,
+    Uninstall,
+    Rollback,
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Install => write!(f, "install"),
+            Operation::Update => write!(f, "update"),
+            Operation::Uninstall => write!(f, "uninstall"),
+            Operation::Rollback => write!(f, "rollback"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub operation: Operation,
+    pub app_name: String,
+    pub started_at: DateTime<Utc>,
+    /// Process running the operation
+    pub pid: u32,
+}
+
+pub struct Journal {
+    dir: PathBuf,
+}
+
+/// Removes its journal entry when the operation returns, successfully or not
+#[must_use]
+pub struct JournalGuard {
+    path: PathBuf,
+}
+
+/// Keeps new operations from starting until dropped
+#[must_use]
+pub struct JournalLock {
+    _file: fs::File,
+}
+
+impl Journal {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The journal in the launcher's data directory
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(Config::data_dir()?.join("journal")))
+    }
+
+    /// Record that `operation` is about to start on `app_name`
+    pub fn begin(&self, operation: Operation, app_name: &str) -> Result<JournalGuard> {
+        let _lock = self.lock()?;
+        let entry = JournalEntry {
+            operation,
+            app_name: app_name.to_string(),
+            started_at: Utc::now(),
+            pid: std::process::id(),
+        };
+        let path = self.entry_path(operation, app_name);
+
+        storage::write_private(&path, serde_json::to_vec_pretty(&entry)?)?;
+        fs::File::open(&path)?.sync_all()?;
+        fs::File::open(&self.dir)?.sync_all()?;
+
+        Ok(JournalGuard { path })
+    }
+
+    /// Wait until no operation is being begun, and hold off new ones
+    pub fn lock(&self) -> Result<JournalLock> {
+        storage::create_private_dir(&self.dir)?;
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.dir.join(".lock"))?;
+        lock_exclusive(&file)?;
+        Ok(JournalLock { _file: file })
+    }
+
+    /// Operations whose process is gone without finishing them, oldest first
+    pub fn incomplete(&self) -> Result<Vec<JournalEntry>> {
+        let mut entries = self.entries()?;
+        entries.retain(|entry| !process_is_running(entry.pid));
+        Ok(entries)
+    }
+
+    /// Operations still running, in this process or another, oldest first
+    pub fn running(&self) -> Result<Vec<JournalEntry>> {
+        let mut entries = self.entries()?;
+        entries.retain(|entry| process_is_running(entry.pid));
+        Ok(entries)
+    }
+
+    fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let mut entries = Vec::new();
+
+        if !self.dir.exists() {
+            return Ok(entries);
+        }
+
+        for file in fs::read_dir(&self.dir)? {
+            let path = file?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let entry: JournalEntry = match fs::read(&path)
+                .map_err(crate::Error::from)
+                .and_then(|data| Ok(serde_json::from_slice(&data)?))
+            {
+                Ok(entry) => entry,
+                // A crash while the entry itself was written
+                Err(e) => {
+                    log::warn!("Ignoring unreadable journal entry {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            entries.push(entry);
+        }
+
+        entries.sort_by_key(|entry| entry.started_at);
+        Ok(entries)
+    }
+
+    /// Forget an interrupted operation once it has been resumed or rolled back
+    pub fn clear(&self, entry: &JournalEntry) -> Result<()> {
+        remove_if_exists(&self.entry_path(entry.operation, &entry.app_name))
+    }
+
+    fn entry_path(&self, operation: Operation, app_name: &str) -> PathBuf {
+        self.dir.join(format!("{}-{}.json", operation, app_name))
+    }
+}
+
+impl Drop for JournalGuard {
+    fn drop(&mut self) {
+        if let Err(e) = remove_if_exists(&self.path) {
+            log::warn!("Failed to close journal entry {:?}: {}", self.path, e);
+        }
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn lock_exclusive(file: &fs::File) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // Released when the file is closed
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn lock_exclusive(_file: &fs::File) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(super) fn process_is_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks whether the process exists
+    pid > 0 && unsafe { libc::kill(pid, 0) } == 0
+}
+
+#[cfg(not(unix))]
+pub(super) fn process_is_running(pid: u32) -> bool {
+    pid == std::process::id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finished_operations_leave_no_entry() {
+        let dir = TempDir::new().unwrap();
+        let journal = Journal::new(dir.path().to_path_buf());
+
+        let guard = journal.begin(Operation::Install, "Fortnite").unwrap();
+        assert!(dir.path().join("install-Fortnite.json").is_file());
+        // Still running in this process
+        assert!(journal.incomplete().unwrap().is_empty());
+        assert_eq!(journal.running().unwrap()[0].app_name, "Fortnite");
+
+        drop(guard);
+        assert!(!dir.path().join("install-Fortnite.json").exists());
+    }
+
+    #[test]
+    fn test_entries_of_exited_processes_are_incomplete() {
+        let dir = TempDir::new().unwrap();
+        let journal = Journal::new(dir.path().to_path_buf());
+
+        std::mem::forget(journal.begin(Operation::Update, "Hades").unwrap());
+
+        // Rewrite the entry as if a process that has since exited wrote it
+        let path = dir.path().join("update-Hades.json");
+        let mut entry: JournalEntry = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        entry.pid = i32::MAX as u32;
+        fs::write(&path, serde_json::to_vec(&entry).unwrap()).unwrap();
+        fs::write(dir.path().join("install-Torn.json"), b"{\"operat").unwrap();
+
+        let incomplete = journal.incomplete().unwrap();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].operation, Operation::Update);
+
+        assert!(journal.running().unwrap().is_empty());
+
+        journal.clear(&incomplete[0]).unwrap();
+        assert!(journal.incomplete().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_operations_wait_for_the_lock() {
+        let dir = TempDir::new().unwrap();
+        let journal = Journal::new(dir.path().to_path_buf());
+
+        let lock = journal.lock().unwrap();
+        let begun = std::thread::spawn({
+            let journal = Journal::new(dir.path().to_path_buf());
+            move || {
+                let _guard = journal.begin(Operation::Install, "Hades").unwrap();
+            }
+        });
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!dir.path().join("install-Hades.json").exists());
+        assert!(!begun.is_finished());
+
+        drop(lock);
+        begun.join().unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+//! Epic manifests tag optional files with install tags. Language and voice
+//! packs carry tags such as `de-DE` or `fr`; untagged files are always
+//! needed. With a preferred language set, packs for every other language
+//! are left out of the install.
+
+use crate::api::GameManifest;
+
+/// ISO 639-1 language codes. Only these count as languages, as manifests
+/// also use short tags such as `all` or `hd` for other optional content.
+const LANGUAGE_CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// Drop files that only belong to other languages' packs. Files without
+/// tags, or with any tag that isn't a language, are kept. Returns the number
+/// of files removed.
+pub fn select_language(manifest: &mut GameManifest, language: &str) -> usize {
+    let before = manifest.file_list.len();
+
+    manifest.file_list.retain(|file| {
+        file.install_tags.is_empty()
+            || file
+                .install_tags
+                .iter()
+                .any(|tag| !is_language_tag(tag) || same_language(tag, language))
+    });
+
+    let removed = before - manifest.file_list.len();
+    if removed > 0 {
+        manifest.build_size = manifest.file_list.iter().map(|file| file.size()).sum();
+        log::info!(
+            "Skipping {} file(s) for languages other than {}",
+            removed,
+            language
+        );
+    }
+
+    removed
+}
+
+/// `en`, `pt-BR`, `es-419`, `zh-Hans`: an ISO 639-1 language code with an
+/// optional region (two letters or three digits) or script (four letters)
+pub fn is_language_tag(tag: &str) -> bool {
+    let mut parts = tag.split(['-', '_']);
+    let primary = parts.next().unwrap_or_default().to_ascii_lowercase();
+    let subtag = parts.next();
+
+    let region = |s: &str| {
+        (s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic()))
+            || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+    };
+    let script = |s: &str| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic());
+
+    LANGUAGE_CODES.contains(&primary.as_str())
+        && subtag.is_none_or(|s| region(s) || script(s))
+        && parts.next().is_none()
+}
+
+/// `de` matches `de-DE`; `pt-BR` matches `pt-BR` but not `pt-PT`
+fn same_language(tag: &str, language: &str) -> bool {
+    let normalize = |s: &str| s.replace('_', "-").to_ascii_lowercase();
+    let (tag, language) = (normalize(tag), normalize(language));
+    let primary = |s: &str| s.split('-').next().unwrap_or_default().to_string();
+
+    tag == language
+        || (primary(&tag) == primary(&language) && (!tag.contains('-') || !language.contains('-')))
+}
+
+/// Locale passed to games on launch, e.g. `-epiclocale=de-DE`
+pub fn epic_locale(language: Option<&str>) -> String {
+    language.map_or_else(|| "en".to_string(), |language| language.replace('_', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FileManifest;
+    use crate::games::paths::test_manifest;
+
+    fn file(name: &str, tags: &[&str]) -> FileManifest {
+        FileManifest {
+            filename: name.to_string(),
+            install_tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_other_languages_are_skipped() {
+        let mut manifest = test_manifest([
+            file("Game.exe", &[]),
+            file("Audio/de.pak", &["de-DE"]),
+            file("Audio/fr.pak", &["fr-FR"]),
+            file("Textures/High.pak", &["highres"]),
+            file("Audio/shared.pak", &["de-DE", "fr-FR"]),
+        ]);
+
+        assert_eq!(select_language(&mut manifest, "de"), 1);
+
+        let kept: Vec<_> = manifest
+            .file_list
+            .iter()
+            .map(|f| f.filename.as_str())
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                "Game.exe",
+                "Audio/de.pak",
+                "Textures/High.pak",
+                "Audio/shared.pak"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language_tags() {
+        assert!(is_language_tag("en"));
+        assert!(is_language_tag("pt-BR"));
+        assert!(is_language_tag("zh_Hans"));
+        assert!(is_language_tag("es-419"));
+        assert!(!is_language_tag("highres"));
+        assert!(!is_language_tag("ondemand"));
+        assert!(!is_language_tag("all"));
+        assert!(!is_language_tag("hd"));
+        assert!(!is_language_tag("en-chunk0"));
+
+        assert!(same_language("pt-BR", "pt_br"));
+        assert!(!same_language("pt-BR", "pt-PT"));
+        assert!(same_language("es", "es-MX"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Output of launched games. Each session writes stdout and stderr to
+//! `logs/<app_name>.log` in the data directory; earlier sessions are kept as
+//! `<app_name>.1.log` (the previous one), `<app_name>.2.log` and so on.
+//! A log that outgrows [`MAX_LOG_BYTES`] while the game runs is cut down to
+//! its newest half.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::Result;
+
+/// Sessions whose logs are kept, including the current one
+pub const KEPT_LOGS: usize = 5;
+
+/// Size a session's log may reach before its older output is dropped
+pub const MAX_LOG_BYTES: u64 = 16 * 1024 * 1024;
+
+const TRIMMED_NOTE: &[u8] = b"[earlier output trimmed]\n";
+
+/// Log of the latest session of `app_name`
+pub fn log_path(app_name: &str) -> Result<PathBuf> {
+    Ok(logs_dir()?.join(format!("{}.log", app_name)))
+}
+
+/// Start a new log for `app_name`, moving the earlier ones down. The game
+/// appends to it, so it keeps writing at the end after a trim.
+pub fn start_session(app_name: &str) -> Result<File> {
+    let dir = logs_dir()?;
+    fs::create_dir_all(&dir)?;
+    rotate(&dir, app_name, KEPT_LOGS)?;
+    let log = File::options()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.log", app_name)))?;
+    log.set_len(0)?;
+    Ok(log)
+}
+
+/// Keep the running session's log of `app_name` within [`MAX_LOG_BYTES`]
+pub fn cap_session(app_name: &str) -> Result<()> {
+    trim(&log_path(app_name)?, MAX_LOG_BYTES)
+}
+
+/// At most the last `max_bytes` of the log at `path`, from the start of a
+/// line
+pub fn tail(path: &Path, max_bytes: u64) -> Result<String> {
+    let mut file = File::open(path)?;
+    let skipped = file.metadata()?.len().saturating_sub(max_bytes);
+    // With the byte before, to tell whether the cut falls on a line start
+    file.seek(SeekFrom::Start(skipped.saturating_sub(1)))?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)?;
+
+    let start = match skipped {
+        0 => 0,
+        _ => data
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(data.len(), |i| i + 1),
+    };
+    Ok(String::from_utf8_lossy(&data[start..]).into_owned())
+}
+
+/// Cut the log at `path` down to its newest half once it is over
+/// `max_bytes`. Output written while that happens may be lost.
+fn trim(path: &Path, max_bytes: u64) -> Result<()> {
+    if fs::metadata(path).map_or(true, |m| m.len() <= max_bytes) {
+        return Ok(());
+    }
+
+    let kept = tail(path, max_bytes / 2)?;
+    let mut file = File::options().write(true).open(path)?;
+    file.set_len(0)?;
+    file.write_all(TRIMMED_NOTE)?;
+    file.write_all(kept.as_bytes())?;
+    Ok(())
+}
+
+/// Kept logs of `app_name`, latest first
+pub fn session_logs(app_name: &str) -> Result<Vec<PathBuf>> {
+    let dir = logs_dir()?;
+    Ok((0..KEPT_LOGS)
+        .map(|index| numbered(&dir, app_name, index))
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+fn logs_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("logs"))
+}
+
+fn numbered(dir: &Path, app_name: &str, index: usize) -> PathBuf {
+    match index {
+        0 => dir.join(format!("{}.log", app_name)),
+        _ => dir.join(format!("{}.{}.log", app_name, index)),
+    }
+}
+
+/// Shift every log one number up to make room for a new session, dropping
+/// the oldest so no more than `keep` remain afterwards
+fn rotate(dir: &Path, app_name: &str, keep: usize) -> Result<()> {
+    if keep == 0 {
+        return Ok(());
+    }
+
+    let oldest = numbered(dir, app_name, keep - 1);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for index in (0..keep - 1).rev() {
+        let path = numbered(dir, app_name, index);
+        if path.exists() {
+            fs::rename(&path, numbered(dir, app_name, index + 1))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rotate_keeps_the_newest_logs() {
+        let dir = TempDir::new().unwrap();
+
+        for session in 1..=4 {
+            rotate(dir.path(), "Game", 3).unwrap();
+            fs::write(numbered(dir.path(), "Game", 0), session.to_string()).unwrap();
+        }
+
+        let contents: Vec<String> = (0..4)
+            .map(|index| {
+                fs::read_to_string(numbered(dir.path(), "Game", index)).unwrap_or_default()
+            })
+            .collect();
+        assert_eq!(contents, ["4", "3", "2", ""]);
+    }
+
+    #[test]
+    fn test_trim_keeps_the_newest_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Game.log");
+        let lines: String = (0..100).map(|n| format!("line {:02}\n", n)).collect();
+        fs::write(&path, &lines).unwrap();
+
+        trim(&path, 2000).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), lines);
+
+        trim(&path, 200).unwrap();
+        let trimmed = fs::read_to_string(&path).unwrap();
+        assert!(
+            trimmed.starts_with("[earlier output trimmed]\nline 88\n"),
+            "{}",
+            trimmed
+        );
+        assert!(trimmed.ends_with("line 99\n"));
+        assert!(trimmed.len() as u64 <= 200);
+
+        assert_eq!(tail(&path, 16).unwrap(), "line 98\nline 99\n");
+        assert_eq!(tail(&path, 15).unwrap(), "line 99\n");
+    }
+}
@@ -0,0 +1,149 @@
+//! Lutris installer scripts for installed games. Installing one with
+//! `lutris -i <file>` adds the game to Lutris without downloading anything:
+//! the script points Lutris at the game's files, runner and prefix as this
+//! launcher set them up.
+
+use std::path::PathBuf;
+
+/// A game as Lutris should run it
+#[derive(Debug, Clone)]
+pub struct LutrisGame {
+    pub app_name: String,
+    pub title: String,
+    pub exe: PathBuf,
+    pub args: Vec<String>,
+    pub working_dir: PathBuf,
+    /// Wine binary and prefix; `None` for native games
+    pub wine: Option<(Option<PathBuf>, PathBuf)>,
+}
+
+impl LutrisGame {
+    /// Name of the installer file
+    pub fn file_name(&self) -> String {
+        format!("{}.yml", self.app_name)
+    }
+
+    /// The installer script, in YAML
+    pub fn installer(&self) -> String {
+        let slug = slug(&self.title);
+        let runner = if self.wine.is_some() { "wine" } else { "linux" };
+
+        let mut script = String::new();
+        field(&mut script, 0, "name", &self.title);
+        field(&mut script, 0, "game_slug", &slug);
+        field(&mut script, 0, "slug", &format!("{}-rauncher", slug));
+        field(&mut script, 0, "version", "rauncher");
+        field(&mut script, 0, "runner", runner);
+
+        script.push_str("script:\n  game:\n");
+        field(&mut script, 2, "exe", &self.exe.to_string_lossy());
+        field(&mut script, 2, "args", &shell_words(&self.args));
+        field(
+            &mut script,
+            2,
+            "working_dir",
+            &self.working_dir.to_string_lossy(),
+        );
+        if let Some((wine, prefix)) = &self.wine {
+            field(&mut script, 2, "prefix", &prefix.to_string_lossy());
+            // Without a binary, Lutris uses its default Wine
+            if let Some(wine) = wine {
+                script.push_str("  wine:\n");
+                field(&mut script, 2, "version", "custom");
+                field(&mut script, 2, "custom_wine_path", &wine.to_string_lossy());
+            }
+        }
+
+        script
+    }
+}
+
+fn field(script: &mut String, indent: usize, key: &str, value: &str) {
+    script.push_str(&format!(
+        "{}{}: {}\n",
+        "  ".repeat(indent),
+        key,
+        yaml(value)
+    ));
+}
+
+/// Lutris' identifier style: lowercase words joined by dashes
+fn slug(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A YAML scalar. JSON strings are valid YAML, and quoting every value
+/// keeps titles like `No: Way` from turning into something else.
+fn yaml(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+/// Arguments as one shell-style string, which is how Lutris takes them
+fn shell_words(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_=./:,@+".contains(c))
+            {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wine_installer() {
+        let game = LutrisGame {
+            app_name: "Fortnite".to_string(),
+            title: "Fortnite: Battle Royale".to_string(),
+            exe: PathBuf::from("/games/Fortnite/Game.exe"),
+            args: vec![
+                "-epicapp=Fortnite".to_string(),
+                "-name=Player One".to_string(),
+            ],
+            working_dir: PathBuf::from("/games/Fortnite"),
+            wine: Some((
+                Some(PathBuf::from("/usr/bin/wine")),
+                PathBuf::from("/prefixes/Fortnite"),
+            )),
+        };
+
+        let script = game.installer();
+        assert!(script.contains("name: \"Fortnite: Battle Royale\"\n"));
+        assert!(script.contains("game_slug: \"fortnite-battle-royale\"\n"));
+        assert!(script.contains("runner: \"wine\"\n"));
+        assert!(script.contains("    args: \"-epicapp=Fortnite '-name=Player One'\"\n"));
+        assert!(script.contains("    prefix: \"/prefixes/Fortnite\"\n"));
+        assert!(script.contains("  wine:\n    version: \"custom\"\n"));
+    }
+
+    #[test]
+    fn test_native_installer_has_no_prefix() {
+        let game = LutrisGame {
+            app_name: "Game".to_string(),
+            title: "Game".to_string(),
+            exe: PathBuf::from("/games/Game/game.x86_64"),
+            args: Vec::new(),
+            working_dir: PathBuf::from("/games/Game"),
+            wine: None,
+        };
+
+        let script = game.installer();
+        assert!(script.contains("runner: \"linux\"\n"));
+        assert!(!script.contains("prefix"));
+    }
+}
@@ -1,30 +1,147 @@
+mod archive;
+mod backup;
+pub mod builds;
+mod cache;
+mod cancel;
+mod clean;
+mod copy;
+mod details;
+mod diff;
+mod gpu;
+mod journal;
+mod language;
+pub mod logs;
+mod lutris;
+mod patch;
+mod paths;
+mod preflight;
+mod prereq;
+mod presence;
+mod progress;
+mod queue;
+mod recovery;
+mod rollback;
+mod runner;
+mod sandbox;
+mod saves;
+mod schedule;
+mod search;
+pub mod session;
+pub mod shaders;
+mod shortcut;
+mod smoke;
+mod sparse;
+mod steam;
+mod symlink;
+mod template;
+mod throttle;
+mod trash;
+mod verify;
+
+pub use archive::ArchiveEstimate;
+pub use cache::{CacheUsage, DiskCache};
+pub use cancel::CancellationToken;
+pub use clean::{Leftover, LeftoverKind};
+pub use copy::{copy_file, copy_tree, CopyStats, CopyStrategy};
+pub use details::GameDetails;
+pub use gpu::Gpu;
+pub use journal::{Journal, JournalEntry, Operation};
+pub use language::is_language_tag;
+pub use progress::{DownloadStatus, InstallProgress, ProgressSnapshot};
+pub use queue::{InstallQueue, QueueResult};
+pub use recovery::{CorruptRecord, RecoverySource};
+pub use runner::{installed_runners, Runner};
+pub use saves::{LocalSave, PlannedSync, SaveConflictPolicy, SaveFilter, SaveVersion, SyncAction};
+use saves::{SaveComparison, SaveHistory, SyncState};
+pub use schedule::DownloadWindow;
+pub use session::SessionLimits;
+pub use smoke::{SmokeReport, SMOKE_TEST_DURATION};
+pub use template::LaunchTemplate;
+pub use throttle::{throttle, Throttle};
+pub use verify::{
+    file_matches, hash_file, verify_file_integrity, verify_files, VerifyCache, VerifyReport,
+};
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
-
-use crate::api::{EpicClient, Game};
-use crate::auth::AuthManager;
-use crate::config::Config;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use crate::api::{self, CloudSave, Dlc, EpicClient, FileManifest, Game, GameManifest, SaveQuota};
+use crate::audit::{self, AuditAction, AuditEntry, Initiator};
+use crate::auth::{AuthManager, AuthToken};
+use crate::cli::prompt;
+use crate::config::{storage, Config};
+use crate::egl::{self, EglManifestItem, EglPrefix};
+use crate::format;
+use crate::metrics::metrics;
 use crate::{Error, Result};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct InstalledGame {
     pub app_name: String,
     pub app_title: String,
     pub app_version: String,
     pub install_path: PathBuf,
     pub executable: String,
+    /// Compressed image holding the game files while it is archived
+    #[serde(default)]
+    pub archive_path: Option<PathBuf>,
+    /// Extracted from its archive only to be played, and archived again
+    /// once the session ends
+    #[serde(default)]
+    pub archive_after_session: bool,
+    /// Per-game cloud sync setting; `None` leaves sync on
+    #[serde(default)]
+    pub cloud_sync_enabled: Option<bool>,
+    /// Manifest paths stored under a different spelling because they only
+    /// differed by case (manifest path → path on disk)
+    #[serde(default)]
+    pub case_remaps: BTreeMap<String, String>,
+    /// Skipped by `update --all`, for example to keep a modded install on
+    /// the version its mods were built for
+    #[serde(default)]
+    pub hold_updates: bool,
+    /// Ids of manifest prerequisites that have already been installed
+    #[serde(default)]
+    pub installed_prereqs: Vec<String>,
+    /// Time spent in the game across all sessions, in seconds
+    #[serde(default)]
+    pub playtime_secs: u64,
+    /// When the last session ended
+    #[serde(default)]
+    pub last_played: Option<DateTime<Utc>>,
+    /// Bytes the installed version takes on disk; 0 for records written
+    /// before it was kept
+    #[serde(default)]
+    pub install_size: u64,
+    /// When the installed version was installed, updated or rolled back to
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Epic's template of where the game saves, e.g. `{AppData}/Game/Saved`,
+    /// fetched on the first cloud-save sync
+    #[serde(default)]
+    pub save_path_template: Option<String>,
 }
 
 impl InstalledGame {
+    /// Whether any cloud-save sync may touch this game
+    pub fn cloud_sync_allowed(&self) -> bool {
+        self.cloud_sync_enabled != Some(false)
+    }
+
     pub fn save(&self, config: &Config) -> Result<()> {
         let games_dir = Self::installed_games_dir(config)?;
-        fs::create_dir_all(&games_dir)?;
 
         let game_file = games_dir.join(format!("{}.json", self.app_name));
         let contents = serde_json::to_string_pretty(self)?;
-        fs::write(&game_file, contents)?;
+        storage::write_private(&game_file, contents)?;
 
         Ok(())
     }
@@ -55,10 +172,16 @@ impl InstalledGame {
             let path = entry.path();
 
             if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                if let Ok(contents) = fs::read_to_string(&path) {
-                    if let Ok(game) = serde_json::from_str::<InstalledGame>(&contents) {
-                        games.push(game);
-                    }
+                match fs::read_to_string(&path)
+                    .map_err(Error::from)
+                    .and_then(|contents| Ok(serde_json::from_str::<InstalledGame>(&contents)?))
+                {
+                    Ok(game) => games.push(game),
+                    Err(e) => log::warn!(
+                        "Skipping unreadable record {:?}: {}. Run 'rauncher doctor'.",
+                        path,
+                        e
+                    ),
                 }
             }
         }
@@ -77,98 +200,353 @@ impl InstalledGame {
         Ok(())
     }
 
+    /// Persist the manifest this game was installed from, for later verification
+    pub fn save_manifest(&self, manifest: &GameManifest) -> Result<()> {
+        let manifests_dir = Self::manifests_dir()?;
+
+        let manifest_file = manifests_dir.join(format!("{}.json", self.app_name));
+        let contents = serde_json::to_string(manifest)?;
+        storage::write_private(&manifest_file, contents)?;
+
+        Ok(())
+    }
+
+    pub fn load_manifest(&self) -> Result<GameManifest> {
+        let manifest_file = Self::manifests_dir()?.join(format!("{}.json", self.app_name));
+
+        if !manifest_file.exists() {
+            return Err(Error::Other(format!(
+                "No stored manifest for {}. Reinstall the game to enable verification.",
+                self.app_name
+            )));
+        }
+
+        let contents = fs::read_to_string(&manifest_file)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn delete_manifest(&self) -> Result<()> {
+        let manifest_file = Self::manifests_dir()?.join(format!("{}.json", self.app_name));
+
+        if manifest_file.exists() {
+            fs::remove_file(&manifest_file)?;
+        }
+
+        Ok(())
+    }
+
     fn installed_games_dir(_config: &Config) -> Result<PathBuf> {
         let data_dir = Config::data_dir()?;
         Ok(data_dir.join("installed"))
     }
+
+    fn manifests_dir() -> Result<PathBuf> {
+        let data_dir = Config::data_dir()?;
+        Ok(data_dir.join("manifests"))
+    }
+}
+
+/// Games copied in each direction by [`GameManager::egl_sync`]
+#[derive(Debug, Clone, Default)]
+pub struct EglSyncReport {
+    pub imported: Vec<String>,
+    pub exported: Vec<String>,
+    /// Not exported because the library doesn't list them, so EGL couldn't
+    /// tell which catalog item they are
+    pub skipped: Vec<String>,
+}
+
+/// A newer build found by [`GameManager::check_all_for_updates`]
+#[derive(Debug, Clone)]
+pub struct GameUpdate {
+    pub app_name: String,
+    pub version: String,
+    /// The update was installed as part of the check
+    pub applied: bool,
+}
+
+/// What a background save sync did with one game, from
+/// [`GameManager::sync_all_saves`]
+#[derive(Debug, Clone)]
+pub struct SaveSyncReport {
+    pub app_name: String,
+    /// Files downloaded or uploaded
+    pub transferred: usize,
+    /// Files changed on both sides, left alone for the user to settle
+    pub conflicts: Vec<String>,
+}
+
+/// Overrides that apply to a single launch
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Skip everything that needs network access
+    pub offline: bool,
+    /// Use this runner instead of the configured or detected one
+    pub runner: Option<Runner>,
+    /// Use this GPU instead of the configured one
+    pub gpu: Option<Gpu>,
 }
 
 pub struct GameManager {
     config: Config,
     auth: AuthManager,
     client: EpicClient,
+    initiator: Initiator,
+    cancel: CancellationToken,
+    status: Option<DownloadStatus>,
+    cache: OnceLock<DiskCache>,
 }
 
 impl GameManager {
     pub fn new(config: Config, auth: AuthManager) -> Result<Self> {
-        let client = EpicClient::new()?;
+        let client = EpicClient::new()?.with_cdn_base_url(config.cdn_base_url.clone());
         Ok(Self {
             config,
             auth,
             client,
+            initiator: Initiator::Cli,
+            cancel: CancellationToken::new(),
+            status: None,
+            cache: OnceLock::new(),
         })
     }
 
+    /// Renew the session when it expires within minutes, saving the new
+    /// token. Long-running frontends call this before each round of work.
+    pub async fn refresh_session(&mut self) -> Result<()> {
+        if !self.auth.token_needs_refresh() {
+            return Ok(());
+        }
+        let refresh_token = self
+            .auth
+            .get_refresh_token()
+            .ok_or(Error::NotAuthenticated)?;
+        let token = self.client.refresh_token(&refresh_token).await?;
+        self.auth.set_token(token)
+    }
+
+    /// The session this manager works with, renewed by `refresh_session`
+    pub fn auth(&self) -> &AuthManager {
+        &self.auth
+    }
+
+    /// Set which front-end is driving this manager, for the audit log
+    pub fn with_initiator(mut self, initiator: Initiator) -> Self {
+        self.initiator = initiator;
+        self
+    }
+
+    /// Let `token` pause or cancel this manager's installs, updates and repairs
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Report the progress of this manager's downloads to `status`
+    pub fn with_progress(mut self, status: DownloadStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
     pub async fn list_library(&self) -> Result<Vec<Game>> {
         let token = self.auth.get_token()?;
-        self.client.get_games(token).await
+        let games = self.client.get_games(token).await?;
+
+        if let Err(e) = search::save_cached_library(&games) {
+            log::warn!("Failed to cache library: {}", e);
+        }
+
+        Ok(games)
+    }
+
+    /// Find library games matching `query`, using the cached library unless
+    /// there is none yet or `refresh` is set
+    pub async fn search_library(&self, query: &str, refresh: bool) -> Result<Vec<Game>> {
+        let games = match search::load_cached_library() {
+            Ok(Some(games)) if !refresh => games,
+            _ => self.list_library().await?,
+        };
+
+        Ok(search::search_games(&games, query)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    /// The installed games, for when Epic can't be reached. Titles come from
+    /// the last library fetched, as install records may only hold app names.
+    pub fn offline_library(&self) -> Result<Vec<Game>> {
+        let cached = search::load_cached_library()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        Ok(self
+            .list_installed()?
+            .into_iter()
+            .map(|installed| Game {
+                app_title: cached
+                    .iter()
+                    .find(|game| game.app_name == installed.app_name)
+                    .map_or(installed.app_title, |game| game.app_title.clone()),
+                app_version: installed.app_version,
+                install_path: Some(installed.install_path.display().to_string()),
+                app_name: installed.app_name,
+            })
+            .collect())
+    }
+
+    pub async fn list_dlcs(&self) -> Result<Vec<Dlc>> {
+        let token = self.auth.get_token()?;
+        self.client.get_dlcs(token).await
     }
 
     pub fn list_installed(&self) -> Result<Vec<InstalledGame>> {
         InstalledGame::list_installed(&self.config)
     }
 
+    /// Installed-game records that exist but can't be read
+    pub fn corrupt_records(&self) -> Result<Vec<CorruptRecord>> {
+        let dir = InstalledGame::installed_games_dir(&self.config)?;
+        Ok(recovery::find_corrupt_records(&dir))
+    }
+
+    /// Rebuild an unreadable record from the manifest stored at install time,
+    /// or with `allow_download`, from a fresh manifest checked against the
+    /// files on disk. The unreadable file is kept as `<app>.json.corrupt`.
+    pub async fn recover_record(
+        &self,
+        record: &CorruptRecord,
+        allow_download: bool,
+    ) -> Result<RecoverySource> {
+        let install_path = self.config.install_dir.join(&record.app_name);
+
+        if !install_path.is_dir() {
+            return Err(Error::Other(format!(
+                "No game files found at {:?}. Import the game from where it is installed.",
+                install_path
+            )));
+        }
+
+        // An EGL catalog link is more trustworthy than the record's file name
+        let app_name =
+            egl::egstore_app_name(&install_path).unwrap_or_else(|| record.app_name.clone());
+        let stub = InstalledGame {
+            app_name: app_name.clone(),
+            ..Default::default()
+        };
+
+        if let Ok(manifest) = stub.load_manifest() {
+            recovery::set_aside(record)?;
+            recovery::record_from_manifest(&app_name, &install_path, &manifest)
+                .save(&self.config)?;
+            return Ok(RecoverySource::StoredManifest);
+        }
+
+        if !allow_download {
+            return Err(Error::Other(format!(
+                "No stored manifest for {}. Sign in to rebuild it from a downloaded one.",
+                app_name
+            )));
+        }
+
+        recovery::set_aside(record)?;
+        self.import_game(&app_name, &install_path, false).await?;
+        Ok(RecoverySource::DownloadedManifest)
+    }
+
+    /// Install every queued game in order, up to `max_concurrent_games` at
+    /// a time. A failed install is reported and the queue moves on to the
+    /// next game.
+    pub async fn install_queue(&self, queue: &mut InstallQueue) -> Vec<QueueResult> {
+        let total = queue.len();
+        // Once cancelled, the rest of the queue is left for another run
+        let games =
+            std::iter::from_fn(|| queue.pop().filter(|_| !self.cancel.is_cancelled())).enumerate();
+
+        stream::iter(games)
+            .map(|(idx, app_name)| async move {
+                println!("\n==> [{}/{}] Installing {}", idx + 1, total, app_name);
+
+                let error = match self.install_game(&app_name).await {
+                    Ok(()) => {
+                        println!("==> {}: installed", app_name);
+                        None
+                    }
+                    Err(e) => {
+                        log::error!("Failed to install {}: {}", app_name, e);
+                        println!("==> {}: failed ({})", app_name, e);
+                        Some(e.to_string())
+                    }
+                };
+
+                QueueResult { app_name, error }
+            })
+            .buffered(self.config.max_concurrent_games.max(1))
+            .collect()
+            .await
+    }
+
     pub async fn install_game(&self, app_name: &str) -> Result<()> {
         // TODO: Check available disk space before installation
-        // TODO: Implement resume capability for interrupted installations
-        // TODO: Add progress tracking with download speed and ETA
-        // TODO: Verify file integrity after reconstruction
-        // TODO: Handle installation cancellation gracefully
-        // TODO: Support selective installation (choose components/languages)
 
         let token = self.auth.get_token()?;
+        self.wait_for_download_window().await?;
+        let _download = metrics().start_download();
+        let _journal = Journal::open()?.begin(Operation::Install, app_name)?;
 
         log::info!("Starting installation for game: {}", app_name);
 
         // Download and parse game manifest
         println!("Downloading game manifest...");
-        let manifest = self.client.download_manifest(token, app_name).await?;
+        let (manifest, case_remaps) = self.fetch_manifest(token, app_name, false).await?;
 
         log::info!("Manifest downloaded: version {}", manifest.app_version);
         println!("Manifest version: {}", manifest.app_version);
-        println!("Build size: {} bytes", manifest.build_size);
+        println!("Build size: {}", format::size(manifest.build_size));
         println!("Files to download: {}", manifest.file_list.len());
 
-        // Create install directory
+        // Files are downloaded next to the install directory and only moved
+        // into place once complete, so a failed install leaves nothing behind
+        // that looks like a game
         let install_path = self.config.install_dir.join(app_name);
-        fs::create_dir_all(&install_path)?;
+        if fs::read_dir(&install_path).is_ok_and(|mut entries| entries.next().is_some()) {
+            return Err(Error::Other(format!(
+                "{} already exists. Use 'rauncher import' to register an existing copy.",
+                install_path.display()
+            )));
+        }
 
-        log::info!("Created install directory: {:?}", install_path);
+        let staging_path = staging_dir(&install_path);
+        storage::create_install_dir(&staging_path, self.config.shared_install_dir)?;
+
+        log::info!("Created staging directory: {:?}", staging_path);
 
         // Download game files
         if !manifest.file_list.is_empty() {
-            // TODO: Implement parallel file downloads with thread pool
-            // TODO: Reconstruct files from downloaded chunks
-            // TODO: Verify file checksums against manifest
-            // TODO: Set proper file permissions (executable, read-only, etc.)
-            // TODO: Handle sparse files correctly
-            // TODO: Track and save download progress for resume capability
-
-            println!("\nDownloading game files...");
-
-            for (idx, file) in manifest.file_list.iter().enumerate() {
-                println!(
-                    "  [{}/{}] {}",
-                    idx + 1,
-                    manifest.file_list.len(),
-                    file.filename
-                );
-
-                // Download chunks for this file
-                for chunk in &file.file_chunk_parts {
-                    let _chunk_data = self.client.download_chunk(&chunk.guid, token).await?;
-                    // TODO: Reconstruct file from chunks
-                    // TODO: Write chunks to file at correct offsets
-                    // TODO: Verify chunk integrity before writing
-                }
+            // Files finished by an earlier, interrupted run are kept
+            let pending = pending_files(&staging_path, &manifest.file_list)?;
+            let complete = manifest.file_list.len() - pending.len();
+            if complete > 0 {
+                println!("\nResuming: {} file(s) already downloaded", complete);
             }
 
+            println!("\nDownloading game files...");
+            self.download_files(token, &staging_path, &manifest, &pending, None)
+                .await?;
             println!("✓ Game files downloaded");
         } else {
             println!("\nNote: Manifest parsing complete, but CDN download not fully implemented.");
             println!("Creating installation record with manifest data...");
         }
 
+        // Every file has now been checked against its manifest hash: resumed
+        // ones by `pending_files`, new ones as they were downloaded
+        if install_path.exists() {
+            fs::remove_dir(&install_path)?;
+        }
+        fs::rename(&staging_path, &install_path)?;
+
         // Create installed game entry with manifest data
         let installed_game = InstalledGame {
             app_name: app_name.to_string(),
@@ -176,9 +554,14 @@ impl GameManager {
             app_version: manifest.app_version.clone(),
             install_path: install_path.clone(),
             executable: manifest.launch_exe.clone(),
+            case_remaps,
+            install_size: manifest.install_size(),
+            updated_at: Some(Utc::now()),
+            ..Default::default()
         };
 
         installed_game.save(&self.config)?;
+        installed_game.save_manifest(&manifest)?;
 
         log::info!("Game installation completed for: {}", app_name);
         println!("\n✓ Installation complete!");
@@ -186,177 +569,2308 @@ impl GameManager {
         Ok(())
     }
 
-    pub fn launch_game(&self, app_name: &str) -> Result<()> {
-        let game = InstalledGame::load(&self.config, app_name)?;
+    /// Hold a download until one of the configured download windows opens.
+    /// Cancelling the manager ends the wait.
+    async fn wait_for_download_window(&self) -> Result<()> {
+        let windows = &self.config.download_windows;
+        let mut announced = false;
 
-        let executable_path = game.install_path.join(&game.executable);
+        while let Some(wait) = schedule::time_until_open(windows, chrono::Local::now().time()) {
+            if !announced {
+                println!(
+                    "Waiting {} for the next download window ({})",
+                    format::duration(wait),
+                    windows
+                        .iter()
+                        .map(|w| w.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                announced = true;
+            }
 
-        if !executable_path.exists() {
-            return Err(Error::Other(format!(
-                "Executable not found: {:?}",
-                executable_path
-            )));
+            self.cancel.checkpoint().await?;
+            // Wake up regularly to notice a cancel, or a clock change
+            tokio::time::sleep(wait.min(Duration::from_secs(60))).await;
         }
 
-        log::info!("Launching game: {} ({})", game.app_title, game.app_name);
-
-        Command::new(&executable_path)
-            .current_dir(&game.install_path)
-            .spawn()
-            .map_err(|e| Error::Other(format!("Failed to launch game: {}", e)))?;
-
         Ok(())
     }
 
-    pub fn uninstall_game(&self, app_name: &str) -> Result<()> {
-        let game = InstalledGame::load(&self.config, app_name)?;
+    /// Installs, updates, uninstalls and rollbacks cut short by a crash or
+    /// power loss
+    pub fn interrupted_operations(&self) -> Result<Vec<JournalEntry>> {
+        Journal::open()?.incomplete()
+    }
 
-        // Remove game files
-        if game.install_path.exists() {
-            fs::remove_dir_all(&game.install_path)?;
+    /// Finish an interrupted operation, or with `rollback` undo it: an
+    /// unfinished install is discarded, while a game left half updated, half
+    /// uninstalled or half rolled back is repaired back to its recorded
+    /// version.
+    pub async fn resume_operation(&self, entry: &JournalEntry, rollback: bool) -> Result<()> {
+        let app_name = entry.app_name.as_str();
+
+        match (entry.operation, rollback) {
+            (Operation::Install, false) => self.install_game(app_name).await?,
+            (Operation::Install, true) => {
+                let staging_path = staging_dir(&self.config.install_dir.join(app_name));
+                if staging_path.exists() {
+                    fs::remove_dir_all(&staging_path)?;
+                }
+            }
+            (Operation::Update, false) => self.update_game(app_name).await?,
+            (Operation::Uninstall, false) => match self.uninstall_game(app_name) {
+                // The record is removed last, so the uninstall had finished
+                Err(Error::GameNotFound(_)) => {}
+                result => result?,
+            },
+            (Operation::Rollback, false) => {
+                let game = InstalledGame::load(&self.config, app_name)?;
+                let snapshots = rollback::Snapshots::open(app_name)?;
+                match snapshots.list()?.pop() {
+                    // Restored and recorded, only the snapshot was left
+                    Some(snapshot) if snapshot.manifest()?.app_version == game.app_version => {
+                        snapshots.discard(&snapshot)?
+                    }
+                    _ => {
+                        self.rollback_game(app_name)?;
+                    }
+                }
+            }
+            (Operation::Update | Operation::Uninstall | Operation::Rollback, true) => {
+                self.repair_game(app_name).await?;
+            }
         }
 
-        // Remove metadata
-        game.delete(&self.config)?;
-
-        log::info!("Uninstalled game: {} ({})", game.app_title, game.app_name);
-
-        Ok(())
+        Journal::open()?.clear(entry)
     }
 
-    /// Check for game updates
-    pub async fn check_for_updates(&self, app_name: &str) -> Result<Option<String>> {
+    /// Everything the GUI's page for `app_name` shows that its library entry
+    /// doesn't have. Only the store listing has to be fetched; missing
+    /// artwork, sizes or add-ons are left out.
+    pub async fn game_details(&self, app_name: &str) -> Result<GameDetails> {
         let token = self.auth.get_token()?;
-        let game = InstalledGame::load(&self.config, app_name)?;
+        let listing = self.client.get_store_listing(token, app_name).await?;
+
+        let artwork = match details::banner_image(&listing.key_images) {
+            Some(image) => self
+                .client
+                .download_image(&image.url)
+                .await
+                .map_err(|e| log::debug!("No artwork for {}: {}", app_name, e))
+                .ok(),
+            None => None,
+        };
 
-        log::info!(
-            "Checking for updates for {} (current: {})",
-            app_name,
-            game.app_version
-        );
+        let install_size = match InstalledGame::load(&self.config, app_name)
+            .and_then(|game| game.load_manifest())
+        {
+            Ok(manifest) => Some(manifest.install_size()),
+            Err(_) => self
+                .remote_manifest(app_name)
+                .await
+                .map_err(|e| log::debug!("No manifest for {}: {}", app_name, e))
+                .ok()
+                .map(|manifest| manifest.install_size()),
+        };
 
-        self.client
-            .check_for_updates(token, app_name, &game.app_version)
+        let dlcs = self
+            .list_dlcs()
             .await
+            .map_err(|e| log::warn!("Failed to list DLC of {}: {}", app_name, e))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|dlc| dlc.base_game == app_name)
+            .collect();
+
+        Ok(GameDetails {
+            description: listing.description,
+            artwork,
+            install_size,
+            dlcs,
+        })
     }
 
-    /// Update a game to the latest version
-    pub async fn update_game(&self, app_name: &str) -> Result<()> {
-        // TODO: Implement differential updates (download only changed files)
-        // TODO: Compare old and new manifests to identify changes
-        // TODO: Support update rollback in case of failure
-        // TODO: Preserve user settings and save files during update
-        // TODO: Show update changelog to user
+    /// Cover art of each of `app_names` that has some, passed to `found` as
+    /// it arrives. Art fetched before comes from the cache, so only the rest
+    /// costs a catalog lookup and a download.
+    pub async fn cover_art(
+        &self,
+        app_names: &[String],
+        mut found: impl FnMut(&str, Vec<u8>),
+    ) -> Result<()> {
+        let cache = self.cache()?;
+        let key = |app_name: &str| format!("covers/{}", app_name);
+
+        let mut missing = Vec::new();
+        for app_name in app_names {
+            match cache.get(&key(app_name)) {
+                Some(data) => found(app_name, data),
+                None => missing.push(app_name.as_str()),
+            }
+        }
+        if missing.is_empty() {
+            return Ok(());
+        }
 
         let token = self.auth.get_token()?;
+        let images = self.client.get_library_key_images(token).await?;
+        // Collected first for the same reason as in `download_files`
+        let downloads: Vec<_> = missing
+            .into_iter()
+            .filter_map(|app_name| {
+                let image = details::cover_image(images.get(app_name)?)?;
+                Some(async move { (app_name, self.client.download_image(&image.url).await) })
+            })
+            .collect();
+        let mut downloads = stream::iter(downloads).buffer_unordered(details::COVER_DOWNLOADS);
+
+        while let Some((app_name, result)) = downloads.next().await {
+            match result {
+                Ok(data) => {
+                    if let Err(e) = cache.put(&key(app_name), &data) {
+                        log::warn!("Failed to cache the cover art of {}: {}", app_name, e);
+                    }
+                    found(app_name, data);
+                }
+                Err(e) => log::debug!("No cover art for {}: {}", app_name, e),
+            }
+        }
+        Ok(())
+    }
 
-        log::info!("Updating game: {}", app_name);
+    /// Fetch the latest manifest of a game, installed or not
+    pub async fn remote_manifest(&self, app_name: &str) -> Result<GameManifest> {
+        let token = self.auth.get_token()?;
+        let (manifest, _) = self.fetch_manifest(token, app_name, false).await?;
+        Ok(manifest)
+    }
 
-        // Check if update is available
-        match self.check_for_updates(app_name).await? {
-            Some(new_version) => {
-                println!("Update available: {}", new_version);
-                println!("Downloading update...");
+    /// Download a manifest with its paths folded for case-sensitive
+    /// filesystems, along with the paths that had to be respelled. Unless
+    /// `refresh` is set, a cached manifest is used if the server says it is
+    /// still current, going by the ETag it was served with.
+    async fn fetch_manifest(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        refresh: bool,
+    ) -> Result<(GameManifest, BTreeMap<String, String>)> {
+        let cache = self.cache()?;
+        let key = format!("manifests/{}.json", app_name);
+        let etag_key = format!("manifests/{}.etag", app_name);
+
+        let cached: Option<(GameManifest, String)> = (!refresh)
+            .then(|| {
+                let manifest = serde_json::from_slice(&cache.get(&key)?).ok()?;
+                let etag = String::from_utf8(cache.get(&etag_key)?).ok()?;
+                Some((manifest, etag))
+            })
+            .flatten();
+        let etag = cached.as_ref().map(|(_, etag)| etag.as_str());
+
+        let mut manifest = match self
+            .client
+            .download_manifest_if_changed(token, app_name, etag)
+            .await?
+        {
+            None => {
+                log::debug!("Using cached manifest for {}", app_name);
+                cached.map(|(manifest, _)| manifest).ok_or_else(|| {
+                    Error::Api(format!("Unrequested 304 for the manifest of {}", app_name))
+                })?
+            }
+            Some((manifest, etag)) => {
+                let stored = match &etag {
+                    Some(etag) => cache
+                        .put(&key, &serde_json::to_vec(&manifest)?)
+                        .and_then(|()| cache.put(&etag_key, etag.as_bytes())),
+                    // Nothing to check a cached copy against later
+                    None => cache.remove(&etag_key),
+                };
+                if let Err(e) = stored {
+                    log::warn!("Failed to cache manifest for {}: {}", app_name, e);
+                }
+                manifest
+            }
+        };
+        if let Some(language) = self.config.language_for(app_name) {
+            language::select_language(&mut manifest, language);
+        }
+        let case_remaps = paths::fold_case(&mut manifest);
 
-                // Download new manifest
-                let manifest = self.client.download_manifest(token, app_name).await?;
+        if !case_remaps.is_empty() {
+            println!(
+                "Merged {} path(s) that differ only by case, as Windows would",
+                case_remaps.len()
+            );
+        }
 
-                // Update game files (differential update would be more efficient)
-                println!("Updating game files...");
+        Ok((manifest, case_remaps))
+    }
 
-                // Update installation record
-                let mut game = InstalledGame::load(&self.config, app_name)?;
-                game.app_version = manifest.app_version.clone();
-                game.executable = manifest.launch_exe.clone();
-                game.save(&self.config)?;
+    /// Download `files` into `install_path`, up to `workers_per_download`
+    /// at a time. Stops at the first failure; finished files are kept.
+    async fn download_files(
+        &self,
+        token: &AuthToken,
+        install_path: &Path,
+        manifest: &GameManifest,
+        files: &[&FileManifest],
+        local: Option<&patch::LocalChunks>,
+    ) -> Result<()> {
+        let mut progress =
+            InstallProgress::new(files.iter().map(|file| file.size()).sum(), files.len());
+        if let Some(status) = &self.status {
+            progress = progress.with_status(status.clone());
+        }
+        let progress = &progress;
+
+        // The futures are collected up front: a stream mapped by a closure
+        // over borrowed files keeps the compiler from seeing that the whole
+        // install is `Send`, which spawning it on a runtime needs
+        let downloads: Vec<_> = files
+            .iter()
+            .enumerate()
+            .map(|(idx, file)| async move {
+                progress.start_file(idx, &file.filename, file.size());
+                self.download_file(token, install_path, manifest, file, local, progress)
+                    .await?;
+                progress.finish_file();
+                Ok::<_, Error>(())
+            })
+            .collect();
+        stream::iter(downloads)
+            .buffer_unordered(self.config.workers_per_download.max(1))
+            .try_collect::<()>()
+            .await?;
+
+        progress.finish();
+        Ok(())
+    }
 
-                println!("✓ Game updated to version {}", manifest.app_version);
-                Ok(())
+    /// Reconstruct a single file from its chunk parts, overwriting any
+    /// existing copy. Chunk data found in `local` is read from disk instead
+    /// of downloaded.
+    async fn download_file(
+        &self,
+        token: &AuthToken,
+        install_path: &Path,
+        manifest: &GameManifest,
+        file: &FileManifest,
+        mut local: Option<&patch::LocalChunks>,
+        progress: &InstallProgress,
+    ) -> Result<()> {
+        let path = install_path.join(&file.filename);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if let Some(target) = file.symlink_target() {
+            return symlink::create(install_path, file, target);
+        }
+
+        // Write to a `.part` file so an interrupted download never looks complete
+        let mut part_path = path.clone().into_os_string();
+        part_path.push(".part");
+        let part_path = PathBuf::from(part_path);
+
+        loop {
+            let reused = self
+                .write_part_file(token, manifest, file, &part_path, local, progress)
+                .await?;
+
+            if file_matches(&part_path, file)? {
+                break;
             }
-            None => {
-                println!("Game is already up to date");
-                Ok(())
+
+            fs::remove_file(&part_path)?;
+            if reused == 0 {
+                return Err(Error::Other(format!(
+                    "{} does not match its manifest hash after download",
+                    file.filename
+                )));
             }
-        }
-    }
 
-    /// Download cloud saves for a game
-    pub async fn download_cloud_saves(&self, app_name: &str) -> Result<()> {
-        // TODO: Implement conflict resolution for cloud vs local saves
-        // TODO: Compare timestamps to detect newer save
-        // TODO: Allow user to choose which save to keep
-        // TODO: Create backup of local saves before overwriting
-        // TODO: Support automatic sync on game launch/exit
+            log::warn!(
+                "{} did not match after reusing installed data, downloading it in full",
+                file.filename
+            );
+            local = None;
+        }
 
-        let token = self.auth.get_token()?;
-        let game = InstalledGame::load(&self.config, app_name)?;
+        fs::rename(&part_path, &path)?;
+        apply_mode(&path, file)
+    }
 
-        log::info!("Downloading cloud saves for {}", app_name);
-        println!("Fetching cloud saves...");
+    /// Write `file`'s contents to `part_path`, returning how many bytes came
+    /// from `local` rather than the network
+    async fn write_part_file(
+        &self,
+        token: &AuthToken,
+        manifest: &GameManifest,
+        file: &FileManifest,
+        part_path: &Path,
+        local: Option<&patch::LocalChunks>,
+        progress: &InstallProgress,
+    ) -> Result<u64> {
+        // Consecutive parts of a file usually come from the same chunk, so
+        // only the last one downloaded is kept
+        let mut current: Option<(&str, Vec<u8>)> = None;
+        let mut output = sparse::SparseWriter::new(fs::File::create(part_path)?);
+        let mut reused = 0;
+
+        for part in &file.file_chunk_parts {
+            if let Some(data) = local.and_then(|l| l.read(&part.guid, part.offset, part.size)) {
+                output.write_all(&data)?;
+                progress.add_bytes(part.size);
+                reused += part.size;
+                continue;
+            }
 
-        let saves = self.client.get_cloud_saves(token, app_name).await?;
+            let chunk_data = match current {
+                Some((guid, ref data)) if guid == part.guid => data,
+                _ => {
+                    self.cancel.checkpoint().await?;
+                    let data = self.download_chunk(token, manifest, &part.guid).await?;
+                    &current.insert((&part.guid, data)).1
+                }
+            };
+            let start = part.offset as usize;
+            let end = start + part.size as usize;
+
+            let data = chunk_data.get(start..end).ok_or_else(|| {
+                Error::Other(format!(
+                    "Chunk {} is too short for {} (needed {} bytes, got {})",
+                    part.guid,
+                    file.filename,
+                    end,
+                    chunk_data.len()
+                ))
+            })?;
+
+            output.write_all(data)?;
+            progress.add_bytes(data.len() as u64);
+        }
 
-        if saves.is_empty() {
-            println!("No cloud saves found");
-            return Ok(());
+        let holes = output.finish()?;
+        if holes > 0 {
+            log::debug!(
+                "Left {} of zeros in {} unallocated",
+                format::size(holes),
+                file.filename
+            );
         }
 
-        println!("Found {} cloud save(s)", saves.len());
+        Ok(reused)
+    }
 
-        // Create saves directory
-        let saves_dir = game.install_path.join("saves");
-        fs::create_dir_all(&saves_dir)?;
+    /// Download a chunk and check it against the manifest's SHA-1, retrying
+    /// once since a bad chunk is usually a transfer problem
+    async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        manifest: &GameManifest,
+        guid: &str,
+    ) -> Result<Vec<u8>> {
+        let expected = manifest.chunk_sha_list.get(guid);
+        let intact = |data: &[u8]| expected.is_none_or(|sha| Sha1::digest(data).as_slice() == sha);
+
+        // Builds reuse chunks across files, and a repair right after an
+        // install needs the same ones again
+        let cache = self.config.cache_chunks.then(|| self.cache()).transpose()?;
+        let key = format!("chunks/{}", guid);
+        match cache.as_ref().and_then(|cache| cache.get(&key)) {
+            Some(data) if intact(&data) => {
+                metrics().record_cache_hit();
+                return Ok(data);
+            }
+            Some(_) => log::debug!("Cached chunk {} is damaged, downloading it again", guid),
+            None if cache.is_some() => metrics().record_cache_miss(),
+            None => {}
+        }
 
-        for save in saves {
-            println!("  Downloading: {}", save.filename);
-            let save_data = self.client.download_cloud_save(token, &save.id).await?;
+        for attempt in 1..=2 {
+            let data = self.client.download_chunk(guid, token).await?;
+            metrics().add_downloaded_bytes(data.len() as u64);
+            throttle().consume(data.len() as u64).await;
 
-            let save_path = saves_dir.join(&save.filename);
-            fs::write(&save_path, &save_data)?;
+            if intact(&data) {
+                if let Some(Err(e)) = cache.as_ref().map(|cache| cache.put(&key, &data)) {
+                    log::warn!("Failed to cache chunk {}: {}", guid, e);
+                }
+                return Ok(data);
+            }
 
-            log::info!("Downloaded save: {:?}", save_path);
+            log::warn!(
+                "Chunk {} failed its integrity check (attempt {})",
+                guid,
+                attempt
+            );
         }
 
-        println!("✓ Cloud saves downloaded");
-        Ok(())
+        Err(Error::Other(format!(
+            "Chunk {} failed its integrity check twice; the download source may be damaged",
+            guid
+        )))
     }
 
-    /// Upload cloud saves for a game
-    pub async fn upload_cloud_saves(&self, app_name: &str) -> Result<()> {
+    /// Fail with an actionable error if the account no longer owns the game
+    pub async fn verify_entitlement(&self, app_name: &str) -> Result<()> {
         let token = self.auth.get_token()?;
-        let game = InstalledGame::load(&self.config, app_name)?;
-
-        log::info!("Uploading cloud saves for {}", app_name);
-        println!("Uploading cloud saves...");
 
-        let saves_dir = game.install_path.join("saves");
-
-        if !saves_dir.exists() {
-            println!("No local saves found");
+        if self.client.has_entitlement(token, app_name).await? {
             return Ok(());
         }
 
-        let mut uploaded = 0;
+        Err(Error::Auth(format!(
+            "Your Epic account has no active license for {}. \
+             If you own it, sign in with the right account ('rauncher auth'); \
+             otherwise the license was revoked or refunded.",
+            app_name
+        )))
+    }
 
-        for entry in fs::read_dir(&saves_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+    /// Launch a game. Offline launches never touch the account token, so they
+    /// work without network access or a valid session.
+    pub fn launch_game(&self, app_name: &str, options: &LaunchOptions) -> Result<Child> {
+        let mut command = self.launch_command(app_name, options)?;
 
-            if path.is_file() {
-                let save_data = fs::read(&path)?;
-                println!(
-                    "  Uploading: {}",
-                    path.file_name().unwrap().to_string_lossy()
+        let log = logs::start_session(app_name)?;
+        command.stdout(log.try_clone()?).stderr(log);
+
+        // Own process group, so the session lasts as long as anything the
+        // game or its launcher started
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+        command
+            .spawn()
+            .map_err(|e| Error::Other(format!("Failed to launch game: {}", e)))
+    }
+
+    /// Watch a launched game until it has exited, enforcing `limits`, then
+    /// add the session to the game's playtime
+    pub fn supervise_session(
+        &self,
+        child: Child,
+        app_name: &str,
+        limits: SessionLimits,
+    ) -> Result<Duration> {
+        let title = InstalledGame::load(&self.config, app_name)
+            .map(|game| game.app_title)
+            .unwrap_or_else(|_| app_name.to_string());
+        let mut presence = self.start_presence(&title);
+        let _session = session::ActiveSessions::open()?.begin(app_name, child.id())?;
+        let played = session::supervise(child, app_name, &title, limits);
+        if let Some(presence) = &mut presence {
+            if let Err(e) = presence.clear() {
+                log::debug!("Failed to clear Discord presence: {}", e);
+            }
+        }
+        let played = played?;
+
+        // Reloaded, as the record may have changed while the game ran
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        game.playtime_secs += played.as_secs();
+        game.last_played = Some(Utc::now());
+        game.save(&self.config)?;
+
+        if game.archive_after_session {
+            println!("Archiving the game files again...");
+            if let Err(e) = self.archive_game(app_name) {
+                log::warn!(
+                    "Failed to archive {} again; it stays extracted until 'rauncher archive {}': {}",
+                    app_name,
+                    app_name,
+                    e
                 );
+            }
+        }
 
-                self.client
-                    .upload_cloud_save(token, app_name, &save_data)
-                    .await?;
-                uploaded += 1;
+        Ok(played)
+    }
+
+    /// Show `title` on the user's Discord profile while it runs, when that
+    /// is turned on and Discord is running
+    fn start_presence(&self, title: &str) -> Option<presence::Presence> {
+        if !self.config.discord_presence {
+            return None;
+        }
+        let client_id = self.config.discord_client_id.as_deref()?;
+
+        match presence::Presence::connect(client_id).and_then(|mut presence| {
+            presence.set_game(title, SystemTime::now())?;
+            Ok(presence)
+        }) {
+            Ok(presence) => Some(presence),
+            Err(e) => {
+                log::info!("No Discord presence: {}", e);
+                None
             }
         }
+    }
+
+    /// Start a freshly installed game for `duration` to catch instant crashes,
+    /// e.g. from missing prerequisites, then close it again
+    pub fn smoke_launch(&self, app_name: &str, duration: Duration) -> Result<SmokeReport> {
+        let command = self.launch_command(app_name, &LaunchOptions::default())?;
+        let log_path = Config::data_dir()?
+            .join("logs")
+            .join(format!("{}-smoke.log", app_name));
+
+        smoke::run_smoke_test(command, &log_path, duration)
+    }
+
+    fn launch_command(&self, app_name: &str, options: &LaunchOptions) -> Result<Command> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+
+        if game.archive_path.is_some() {
+            println!("Extracting archived game files...");
+            self.restore_archived(&mut game)?;
+            game.archive_after_session = true;
+            game.save(&self.config)?;
+        }
 
-        println!("✓ Uploaded {} save file(s)", uploaded);
-        Ok(())
+        let executable_path = game.install_path.join(&game.executable);
+        let runner = options
+            .runner
+            .clone()
+            .unwrap_or_else(|| self.game_runner(&game));
+
+        preflight::check(&executable_path, &runner)?;
+
+        let prefix = Self::prefix_path(&game.app_name)?;
+        if runner.needs_prefix() {
+            fs::create_dir_all(&prefix)?;
+            runner.init_prefix(&prefix)?;
+        }
+
+        self.install_prerequisites(&mut game, &runner, &prefix)?;
+
+        log::info!(
+            "Launching game: {} ({}) with {}{}",
+            game.app_title,
+            game.app_name,
+            runner,
+            if options.offline {
+                " in offline mode"
+            } else {
+                ""
+            }
+        );
+
+        let mut command = runner.command(&executable_path, &prefix)?;
+        command
+            .args(self.launch_args(&game, options.offline))
+            .current_dir(&game.install_path);
+
+        let shader_cache = shaders::cache_dir(&game.app_name)?;
+        if runner.needs_prefix() {
+            fs::create_dir_all(&shader_cache)?;
+            command.envs(shaders::cache_env(&shader_cache));
+        }
+
+        if self.config.sandbox_for(&game.app_name) {
+            let mut sandbox = sandbox::Sandbox {
+                writable: vec![game.install_path.clone()],
+                readable: runner.directories(),
+            };
+            if runner.needs_prefix() {
+                sandbox.writable.push(prefix.clone());
+                sandbox.writable.push(shader_cache);
+            } else {
+                let dirs = saves::SaveDirs::native(&game.install_path, self.auth.account_id())?;
+                sandbox.allow_native_saves(&dirs, game.save_path_template.as_deref())?;
+            }
+            command = sandbox.apply(command)?;
+        }
+
+        if let Some(gpu) = options
+            .gpu
+            .or_else(|| self.config.game_gpus.get(&game.app_name).copied())
+        {
+            command = gpu.apply(command);
+        }
+
+        match self.config.game_wrappers.get(&game.app_name) {
+            Some(template) => Ok(LaunchTemplate::parse(template)?.apply(command)),
+            None => Ok(command),
+        }
+    }
+
+    /// Runner a game is set to use, detected from its executable when the
+    /// configuration doesn't name one
+    fn game_runner(&self, game: &InstalledGame) -> Runner {
+        self.config
+            .runner_for(&game.app_name)
+            .cloned()
+            .unwrap_or_else(|| Runner::detect(&game.install_path.join(&game.executable)))
+    }
+
+    /// Wine or Proton prefix of a game
+    pub fn prefix_path(app_name: &str) -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("prefixes").join(app_name))
+    }
+
+    /// Build a `winetricks` command that installs `verbs` into the game's
+    /// prefix, or opens the winetricks GUI when there are none
+    pub fn winetricks_command(&self, app_name: &str, verbs: &[String]) -> Result<Command> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        let runner = self.game_runner(&game);
+
+        let prefix = Self::prefix_path(&game.app_name)?;
+        fs::create_dir_all(&prefix)?;
+
+        let mut command = runner.winetricks(&prefix)?;
+        if !verbs.is_empty() {
+            command.arg("--unattended").args(verbs);
+        }
+        Ok(command)
+    }
+
+    /// Add `app_name` to the desktop's application menu. The icon comes from
+    /// the store; without a session or a connection the entry has none.
+    pub async fn create_shortcut(&self, app_name: &str) -> Result<PathBuf> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+
+        let icon = match self.shortcut_icon(app_name).await {
+            Ok(icon) => icon,
+            Err(e) => {
+                log::warn!("No icon for the {} shortcut: {}", app_name, e);
+                None
+            }
+        };
+        let icon = icon
+            .as_ref()
+            .map(|(data, extension)| (data.as_slice(), extension.as_str()));
+
+        shortcut::create(app_name, &game.app_title, icon)
+    }
+
+    /// Remove `app_name` from the application menu. Returns whether it had
+    /// an entry.
+    pub fn remove_shortcut(&self, app_name: &str) -> Result<bool> {
+        shortcut::remove(app_name)
+    }
+
+    async fn shortcut_icon(&self, app_name: &str) -> Result<Option<(Vec<u8>, String)>> {
+        let token = self.auth.get_token()?;
+        let images = self.client.get_key_images(token, app_name).await?;
+        let Some(image) = shortcut::icon_image(&images) else {
+            return Ok(None);
+        };
+
+        let data = self.client.download_image(&image.url).await?;
+        Ok(Some((data, image.extension())))
+    }
+
+    /// Add `app_name` to the library of every Steam account on this machine
+    /// as a non-Steam game, with its store artwork. Returns the number of
+    /// accounts. Steam reads shortcuts when it starts.
+    pub async fn export_to_steam(&self, app_name: &str) -> Result<usize> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        let config_dirs = steam::user_config_dirs();
+        if config_dirs.is_empty() {
+            return Err(Error::Other(
+                "No Steam account found. Sign in to Steam once, then try again.".to_string(),
+            ));
+        }
+
+        let artwork = match self.steam_artwork(app_name).await {
+            Ok(artwork) => artwork,
+            Err(e) => {
+                log::warn!("No artwork for the {} Steam shortcut: {}", app_name, e);
+                Vec::new()
+            }
+        };
+        let artwork: Vec<(&str, &[u8], &str)> = artwork
+            .iter()
+            .map(|(suffix, data, extension)| (*suffix, data.as_slice(), extension.as_str()))
+            .collect();
+
+        let shortcut = steam::SteamShortcut {
+            app_name: game.app_name.clone(),
+            title: game.app_title.clone(),
+            launcher: std::env::current_exe()?,
+        };
+        for config_dir in &config_dirs {
+            steam::add_shortcut(config_dir, &shortcut, &artwork)?;
+        }
+
+        Ok(config_dirs.len())
+    }
+
+    /// Write a Lutris installer for `app_name` into `dir`. It sets up the
+    /// game with the runner, prefix and arguments this launcher would use.
+    pub fn export_to_lutris(&self, app_name: &str, dir: &Path) -> Result<PathBuf> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        let runner = self.game_runner(&game);
+
+        let wine = if runner.needs_prefix() {
+            let env = runner.wine_env(&Self::prefix_path(app_name)?)?;
+            let var = |name: &str| {
+                env.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.clone())
+            };
+            // A bare name means Wine from PATH
+            let wine = var("WINE").and_then(|wine| {
+                if wine.components().count() > 1 {
+                    Some(wine)
+                } else {
+                    runner::find_in_path(&wine.to_string_lossy())
+                }
+            });
+            var("WINEPREFIX").map(|prefix| (wine, prefix))
+        } else {
+            None
+        };
+
+        let lutris_game = lutris::LutrisGame {
+            app_name: game.app_name.clone(),
+            title: game.app_title.clone(),
+            exe: game.install_path.join(&game.executable),
+            args: self.launch_args(&game, false),
+            working_dir: game.install_path.clone(),
+            wine,
+        };
+
+        fs::create_dir_all(dir)?;
+        let path = dir.join(lutris_game.file_name());
+        fs::write(&path, lutris_game.installer())?;
+        Ok(path)
+    }
+
+    async fn steam_artwork(&self, app_name: &str) -> Result<Vec<(&'static str, Vec<u8>, String)>> {
+        let token = self.auth.get_token()?;
+        let images = self.client.get_key_images(token, app_name).await?;
+
+        let mut downloaded: HashMap<&str, Vec<u8>> = HashMap::new();
+        let mut artwork = Vec::new();
+        for (suffix, image) in steam::grid_images(&images) {
+            if !downloaded.contains_key(image.url.as_str()) {
+                let data = self.client.download_image(&image.url).await?;
+                downloaded.insert(&image.url, data);
+            }
+            artwork.push((
+                suffix,
+                downloaded[image.url.as_str()].clone(),
+                image.extension(),
+            ));
+        }
+        Ok(artwork)
+    }
+
+    /// Run the manifest's prerequisite installer in the game's prefix the
+    /// first time the game starts. Native games only get a note, as their
+    /// dependencies come from the system's package manager.
+    fn install_prerequisites(
+        &self,
+        game: &mut InstalledGame,
+        runner: &Runner,
+        prefix: &Path,
+    ) -> Result<()> {
+        // Games imported without a stored manifest have nothing recorded
+        let Ok(manifest) = game.load_manifest() else {
+            return Ok(());
+        };
+        let Some(prereq) = prereq::Prerequisite::from_manifest(&manifest) else {
+            return Ok(());
+        };
+        if prereq.is_installed(&game.installed_prereqs) {
+            return Ok(());
+        }
+
+        if runner.needs_prefix() {
+            println!("Installing {} for {}...", prereq.name, game.app_title);
+            prereq.install(runner, &game.install_path, prefix)?;
+        } else {
+            println!(
+                "{} lists {} as a prerequisite. If the game fails to start, install it with your system's package manager.",
+                game.app_title, prereq.name
+            );
+        }
+
+        game.installed_prereqs.extend(prereq.ids);
+        game.save(&self.config)
+    }
+
+    /// Switch `options` to an offline launch when there is no valid session,
+    /// so an expired token never keeps an installed game from starting.
+    /// Returns whether it did.
+    pub fn fall_back_to_offline(&self, options: &mut LaunchOptions) -> bool {
+        if options.offline || self.auth.is_authenticated() {
+            return false;
+        }
+
+        options.offline = true;
+        true
+    }
+
+    /// Whether cloud saves should be synced around this launch: never offline,
+    /// then per `auto_sync_saves` as long as the game allows cloud sync at all
+    pub fn auto_sync_saves(&self, app_name: &str, options: &LaunchOptions) -> Result<bool> {
+        if options.offline || !self.config.auto_sync_saves_for(app_name) {
+            return Ok(false);
+        }
+
+        Ok(InstalledGame::load(&self.config, app_name)?.cloud_sync_allowed())
+    }
+
+    /// Whether today's play time is used up, so a new session needs the
+    /// parental PIN
+    pub fn daily_limit_reached(&self) -> Result<bool> {
+        let Some(daily) = self.config.time_limits.daily_limit_minutes else {
+            return Ok(false);
+        };
+        let daily = Duration::from_secs(u64::from(daily) * 60);
+        Ok(session::PlaytimeLog::load()?.played_today() >= daily)
+    }
+
+    /// Work out reminders and remaining daily play time for a new session.
+    /// Once the daily limit is used up, the parental PIN lifts it for one
+    /// session; without `pin` it is asked for on the terminal.
+    pub fn session_limits(&self, app_name: &str, pin: Option<&str>) -> Result<SessionLimits> {
+        let limits = &self.config.time_limits;
+        let mut session = SessionLimits {
+            reminder_after: limits
+                .reminder_for(app_name)
+                .map(|minutes| Duration::from_secs(u64::from(minutes) * 60)),
+            time_left: None,
+        };
+
+        if let Some(daily) = limits.daily_limit_minutes {
+            let daily = Duration::from_secs(u64::from(daily) * 60);
+            let played = session::PlaytimeLog::load()?.played_today();
+
+            if played < daily {
+                session.time_left = Some(daily - played);
+            } else {
+                let unlocked = match pin {
+                    Some(pin) => limits.pin_matches(pin),
+                    None => prompt::ask("Daily play time is used up. Parental PIN:")
+                        .is_some_and(|pin| limits.pin_matches(&pin)),
+                };
+
+                if !unlocked {
+                    return Err(Error::Other(
+                        "Daily play time limit reached. Enter the parental PIN to keep playing."
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(session)
+    }
+
+    /// Command-line arguments Epic titles expect from their launcher
+    fn launch_args(&self, game: &InstalledGame, offline: bool) -> Vec<String> {
+        launch_args(
+            &game.app_name,
+            &language::epic_locale(self.config.language_for(&game.app_name)),
+            self.auth.account_id().filter(|_| !offline),
+        )
+    }
+
+    pub fn uninstall_game(&self, app_name: &str) -> Result<()> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        let _journal = Journal::open()?.begin(Operation::Uninstall, app_name)?;
+
+        let mut entry = AuditEntry::new(self.initiator, AuditAction::Uninstall, app_name)
+            .with_path(&game.install_path);
+
+        if let Some(archive_path) = &game.archive_path {
+            if archive_path.exists() {
+                fs::remove_file(archive_path)?;
+            }
+            entry = entry.with_path(archive_path);
+        }
+
+        // Remove game files
+        if game.install_path.exists() {
+            if self.config.uninstall_to_trash {
+                let trash_dir = self.launcher_trash_dir();
+                let trashed = trash::move_to_trash(&game.install_path, &trash_dir)?;
+                println!("Moved game files to {:?}", trashed);
+                entry = entry.with_path(trashed);
+
+                for purged in trash::purge_expired(&trash_dir, self.config.trash_retention_days)? {
+                    log::info!("Purged expired trash entry {:?}", purged);
+                }
+            } else {
+                fs::remove_dir_all(&game.install_path)?;
+            }
+        }
+
+        audit::record(entry);
+
+        // Remove metadata
+        game.delete(&self.config)?;
+        game.delete_manifest()?;
+        rollback::Snapshots::open(app_name)?.remove_all()?;
+        shortcut::remove(app_name)?;
+        shaders::clear_cache(app_name)?;
+        let verify_cache = Self::verify_cache_path(app_name)?;
+        if verify_cache.exists() {
+            fs::remove_file(&verify_cache)?;
+        }
+
+        log::info!("Uninstalled game: {} ({})", game.app_title, game.app_name);
+
+        Ok(())
+    }
+
+    /// The game's local save folder, for `trash_local_saves` once the game
+    /// is uninstalled. Look it up before uninstalling: finding it needs the
+    /// install record. A template naming a folder other programs share is
+    /// refused, so the saves never take the rest of the user's files along.
+    pub async fn local_saves_to_delete(&self, app_name: &str) -> Result<PathBuf> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let dir = self.save_dir(&mut game).await?;
+        self.save_dirs(&game)?.game_folder(&dir)
+    }
+
+    /// Move the save folder found by `local_saves_to_delete` to the trash,
+    /// returning where it went, if it was there
+    pub fn trash_local_saves(&self, app_name: &str, dir: &Path) -> Result<Option<PathBuf>> {
+        if !dir.exists() {
+            return Ok(None);
+        }
+        let trashed = trash::move_to_trash(dir, &self.launcher_trash_dir())?;
+        audit::record(
+            AuditEntry::new(self.initiator, AuditAction::Uninstall, app_name).with_path(dir),
+        );
+        Ok(Some(trashed))
+    }
+
+    /// Delete the game's Wine or Proton prefix, if it has one
+    pub fn delete_prefix(&self, app_name: &str) -> Result<bool> {
+        let prefix = Self::prefix_path(app_name)?;
+        if !prefix.exists() {
+            return Ok(false);
+        }
+        fs::remove_dir_all(&prefix)?;
+        audit::record(
+            AuditEntry::new(self.initiator, AuditAction::Uninstall, app_name).with_path(&prefix),
+        );
+        Ok(true)
+    }
+
+    /// Fallback trash on the same filesystem as the games, so trashing is a rename
+    fn launcher_trash_dir(&self) -> PathBuf {
+        self.config.install_dir.join(".trash")
+    }
+
+    /// Move an installed game into another directory, keeping its folder name
+    pub fn move_game(&self, app_name: &str, destination: &Path) -> Result<PathBuf> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+
+        let folder_name = game.install_path.file_name().ok_or_else(|| {
+            Error::Other(format!("Invalid install path: {:?}", game.install_path))
+        })?;
+        let new_path = destination.join(folder_name);
+
+        if new_path.exists() {
+            return Err(Error::Other(format!(
+                "Destination already exists: {:?}",
+                new_path
+            )));
+        }
+
+        fs::create_dir_all(destination)?;
+
+        // A rename is instant on the same filesystem; only copy across devices
+        if let Err(e) = fs::rename(&game.install_path, &new_path) {
+            log::info!(
+                "Rename failed ({}), copying {:?} to {:?}",
+                e,
+                game.install_path,
+                new_path
+            );
+
+            let stats = copy_tree(&game.install_path, &new_path)?;
+            log::info!(
+                "Copied {} file(s), {} ({} reflinked, {} in-kernel, {} buffered)",
+                stats.files,
+                format::size(stats.bytes),
+                stats.reflinked,
+                stats.kernel_copied,
+                stats.buffered
+            );
+
+            audit::record(
+                AuditEntry::new(self.initiator, AuditAction::FileDeletion, app_name)
+                    .with_path(&game.install_path),
+            );
+            fs::remove_dir_all(&game.install_path)?;
+        }
+
+        game.install_path = new_path.clone();
+        game.save(&self.config)?;
+
+        log::info!("Moved {} to {:?}", app_name, new_path);
+
+        Ok(new_path)
+    }
+
+    /// Reconcile installs with the Epic Games Launcher in a Wine prefix so
+    /// games installed by either launcher show up in the other
+    pub async fn egl_sync(
+        &self,
+        prefix: &Path,
+        import: bool,
+        export: bool,
+    ) -> Result<EglSyncReport> {
+        let egl_prefix = EglPrefix::new(prefix)?;
+        let installed = self.list_installed()?;
+        let mut report = EglSyncReport::default();
+
+        if import {
+            for item in egl_prefix.manifest_items()? {
+                if item.is_incomplete_install
+                    || installed.iter().any(|g| g.app_name == item.app_name)
+                {
+                    continue;
+                }
+
+                let Some(install_path) = egl_prefix.to_unix_path(&item.install_location) else {
+                    log::warn!(
+                        "Skipping {}: unsupported install location {}",
+                        item.app_name,
+                        item.install_location
+                    );
+                    continue;
+                };
+
+                if !install_path.is_dir() {
+                    log::warn!(
+                        "Skipping {}: install directory {:?} is missing",
+                        item.app_name,
+                        install_path
+                    );
+                    continue;
+                }
+
+                let game = InstalledGame {
+                    app_name: item.app_name.clone(),
+                    app_title: item.display_name.clone(),
+                    app_version: item.app_version_string.clone(),
+                    install_path,
+                    executable: item.launch_executable.clone(),
+                    install_size: item.install_size,
+                    updated_at: Some(Utc::now()),
+                    ..Default::default()
+                };
+                game.save(&self.config)?;
+
+                log::info!("Imported {} from EGL", item.app_name);
+                report.imported.push(item.app_name);
+            }
+        }
+
+        if export {
+            let egl_games = egl_prefix.installations()?;
+            let missing: Vec<_> = installed
+                .iter()
+                .filter(|game| !egl_games.iter().any(|i| i.app_name == game.app_name))
+                .collect();
+            // Looked up only when there is something to export
+            let catalog_ids = if missing.is_empty() {
+                HashMap::new()
+            } else {
+                let token = self.auth.get_token()?;
+                self.client.get_catalog_ids(token).await?
+            };
+
+            for game in missing {
+                let Some(ids) = catalog_ids.get(&game.app_name) else {
+                    log::warn!("Not exporting {}: it isn't in the library", game.app_name);
+                    report.skipped.push(game.app_name.clone());
+                    continue;
+                };
+
+                let install_location = egl_prefix.to_windows_path(&game.install_path);
+                egl_prefix.add_installation(EglManifestItem {
+                    format_version: 0,
+                    is_incomplete_install: false,
+                    launch_command: String::new(),
+                    launch_executable: game.executable.clone(),
+                    manifest_location: format!("{}\\.egstore", install_location),
+                    install_location,
+                    install_size: game.install_size,
+                    app_name: game.app_name.clone(),
+                    app_version_string: game.app_version.clone(),
+                    display_name: game.app_title.clone(),
+                    installation_guid: egl::installation_guid(&game.app_name),
+                    catalog_namespace: ids.namespace.clone(),
+                    catalog_item_id: ids.catalog_item_id.clone(),
+                    extra: serde_json::Map::new(),
+                })?;
+
+                log::info!("Exported {} to EGL", game.app_name);
+                report.exported.push(game.app_name.clone());
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Compress a rarely played game into a squashfs image, returning the image size
+    pub fn archive_game(&self, app_name: &str) -> Result<u64> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+
+        let archive_path = archive::archive_path_for(&game.install_path);
+        log::info!("Archiving {} into {:?}", app_name, archive_path);
+
+        let archived_size = archive::archive_dir(&game.install_path, &archive_path)?;
+
+        game.archive_path = Some(archive_path);
+        game.archive_after_session = false;
+        game.save(&self.config)?;
+
+        Ok(archived_size)
+    }
+
+    /// Estimate how much disk space archiving a game would free
+    pub fn estimate_archive(&self, app_name: &str) -> Result<ArchiveEstimate> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+        archive::estimate_archive(&game.install_path)
+    }
+
+    /// Extract an archived game back into its install directory. A game
+    /// extracted for the session being played is kept extracted afterwards.
+    pub fn unarchive_game(&self, app_name: &str) -> Result<()> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+
+        if game.archive_after_session {
+            game.archive_after_session = false;
+            return game.save(&self.config);
+        }
+        if game.archive_path.is_none() {
+            return Err(Error::Other(format!("{} is not archived", app_name)));
+        }
+
+        self.restore_archived(&mut game)
+    }
+
+    fn restore_archived(&self, game: &mut InstalledGame) -> Result<()> {
+        if let Some(archive_path) = game.archive_path.take() {
+            log::info!("Extracting {:?} for {}", archive_path, game.app_name);
+            archive::extract_archive(&archive_path, &game.install_path)?;
+            game.save(&self.config)?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_not_archived(game: &InstalledGame) -> Result<()> {
+        if game.archive_path.is_some() {
+            return Err(Error::Other(format!(
+                "{} is archived. Run 'rauncher archive {} --restore' first.",
+                game.app_name, game.app_name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Verify installed files against the manifest stored at install time.
+    /// With `incremental`, files unchanged since the last verify are skipped.
+    pub fn verify_game(&self, app_name: &str, incremental: bool) -> Result<VerifyReport> {
+        let game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+        let manifest = game.load_manifest()?;
+
+        log::info!(
+            "Verifying {} file(s) for {}",
+            manifest.file_list.len(),
+            app_name
+        );
+
+        let cache_path = Self::verify_cache_path(app_name)?;
+        let mut cache = if incremental {
+            VerifyCache::load(cache_path)
+        } else {
+            VerifyCache::empty(cache_path)
+        };
+        let report = verify_files(&game.install_path, &manifest, Some(&mut cache))?;
+        cache.save()?;
+
+        log::info!(
+            "Verification finished for {}: {} missing, {} corrupt",
+            app_name,
+            report.missing_files.len(),
+            report.corrupt_files.len()
+        );
+
+        Ok(report)
+    }
+
+    fn verify_cache_path(app_name: &str) -> Result<PathBuf> {
+        Ok(Config::data_dir()?
+            .join("verify")
+            .join(format!("{}.json", app_name)))
+    }
+
+    /// Re-download only the files that fail verification
+    pub async fn repair_game(&self, app_name: &str) -> Result<Vec<String>> {
+        let token = self.auth.get_token()?;
+        let game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+        let manifest = game.load_manifest()?;
+
+        let report = verify_file_integrity(&game.install_path, &manifest)?;
+        if report.is_ok() {
+            log::info!("No damaged files found for {}", app_name);
+            return Ok(Vec::new());
+        }
+
+        let repaired = self
+            .repair_files(token, &game.install_path, &manifest, &report)
+            .await?;
+
+        log::info!("Repaired {} file(s) for {}", repaired.len(), app_name);
+
+        Ok(repaired)
+    }
+
+    /// Register an existing copy of a game found at `path` as installed.
+    /// Files are checked against the current manifest; with `repair` set,
+    /// mismatching files are re-downloaded instead of failing the import.
+    pub async fn import_game(&self, app_name: &str, path: &Path, repair: bool) -> Result<()> {
+        let token = self.auth.get_token()?;
+
+        if !path.is_dir() {
+            return Err(Error::Other(format!(
+                "Import path is not a directory: {:?}",
+                path
+            )));
+        }
+
+        if InstalledGame::load(&self.config, app_name).is_ok() {
+            return Err(Error::Other(format!("{} is already installed", app_name)));
+        }
+
+        let path = &path.canonicalize()?;
+
+        log::info!("Importing {} from {:?}", app_name, path);
+        println!("Downloading game manifest...");
+        let (manifest, case_remaps) = self.fetch_manifest(token, app_name, false).await?;
+
+        println!("Verifying {} file(s)...", manifest.file_list.len());
+        let report = verify_file_integrity(path, &manifest)?;
+
+        if !report.is_ok() {
+            if !repair {
+                return Err(Error::Other(format!(
+                    "{} missing and {} corrupt file(s) at {:?}. Re-run with --repair to fix them.",
+                    report.missing_files.len(),
+                    report.corrupt_files.len(),
+                    path
+                )));
+            }
+
+            self.repair_files(token, path, &manifest, &report).await?;
+        }
+
+        let installed_game = InstalledGame {
+            app_name: app_name.to_string(),
+            app_title: app_name.to_string(),
+            app_version: manifest.app_version.clone(),
+            install_path: path.to_path_buf(),
+            executable: manifest.launch_exe.clone(),
+            case_remaps,
+            install_size: manifest.install_size(),
+            updated_at: Some(Utc::now()),
+            ..Default::default()
+        };
+
+        installed_game.save(&self.config)?;
+        installed_game.save_manifest(&manifest)?;
+
+        log::info!("Imported {} (version {})", app_name, manifest.app_version);
+
+        Ok(())
+    }
+
+    /// Re-download every file that failed verification and confirm the result
+    async fn repair_files(
+        &self,
+        token: &AuthToken,
+        install_path: &Path,
+        manifest: &GameManifest,
+        report: &VerifyReport,
+    ) -> Result<Vec<String>> {
+        let damaged: Vec<&FileManifest> = manifest
+            .file_list
+            .iter()
+            .filter(|f| report.failed_files().any(|name| *name == f.filename))
+            .collect();
+
+        let _download = metrics().start_download();
+        println!("Repairing {} file(s)...", damaged.len());
+
+        self.download_files(token, install_path, manifest, &damaged, None)
+            .await?;
+
+        let mut repaired = Vec::new();
+        for file in damaged {
+            let path = install_path.join(&file.filename);
+            if !file_matches(&path, file)? {
+                return Err(Error::Other(format!(
+                    "File {} still fails verification after repair",
+                    file.filename
+                )));
+            }
+
+            repaired.push(file.filename.clone());
+        }
+
+        Ok(repaired)
+    }
+
+    /// Check every installed game whose updates aren't on hold, installing
+    /// what is found when `apply` is set. A game whose check or update fails
+    /// is logged and skipped so one broken game doesn't stop the rest.
+    pub async fn check_all_for_updates(&self, apply: bool) -> Result<Vec<GameUpdate>> {
+        self.auth.get_token()?;
+
+        let mut updates = Vec::new();
+        for game in self.list_installed()? {
+            if game.hold_updates || game.archive_path.is_some() {
+                continue;
+            }
+
+            let version = match self.check_for_updates(&game.app_name).await {
+                Ok(Some(version)) => version,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Update check for {} failed: {}", game.app_name, e);
+                    continue;
+                }
+            };
+
+            let applied = apply
+                && match self.update_game(&game.app_name).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::warn!("Automatic update of {} failed: {}", game.app_name, e);
+                        false
+                    }
+                };
+
+            updates.push(GameUpdate {
+                app_name: game.app_name,
+                version,
+                applied,
+            });
+        }
+
+        Ok(updates)
+    }
+
+    /// Sync the saves of every installed game that syncs automatically and
+    /// has drifted from its cloud saves. While `save_conflict_policy` is
+    /// `ask`, a game with conflicts is not synced at all and only reported.
+    /// Games being played are left alone, as their saves may be half written
+    /// and a download would replace them under the game.
+    pub async fn sync_all_saves(&self) -> Result<Vec<SaveSyncReport>> {
+        self.auth.get_token()?;
+
+        let sessions = session::ActiveSessions::open()?;
+        let mut reports = Vec::new();
+        for game in self.list_installed()? {
+            if game.archive_path.is_some()
+                || !game.cloud_sync_allowed()
+                || !self.config.auto_sync_saves_for(&game.app_name)
+            {
+                continue;
+            }
+            if sessions.is_active(&game.app_name) {
+                log::info!("Not syncing the saves of {} while it runs", game.app_name);
+                continue;
+            }
+            let app_name = game.app_name;
+
+            let upload = api::SAVE_UPLOADS_ENABLED;
+            let plan = match self.plan_cloud_sync(&app_name, true, upload).await {
+                Ok(plan) => plan,
+                Err(e) => {
+                    log::warn!("Save check for {} failed: {}", app_name, e);
+                    continue;
+                }
+            };
+
+            let conflicts: Vec<String> = plan
+                .files
+                .iter()
+                .filter(|file| file.action == SyncAction::Conflict)
+                .map(|file| file.filename.clone())
+                .collect();
+            if !conflicts.is_empty() && self.config.save_conflict_policy == SaveConflictPolicy::Ask
+            {
+                reports.push(SaveSyncReport {
+                    app_name,
+                    transferred: 0,
+                    conflicts,
+                });
+                continue;
+            }
+
+            let transferred = plan
+                .files
+                .iter()
+                .filter(|file| file.action != SyncAction::Skip)
+                .count();
+            if transferred == 0 {
+                continue;
+            }
+
+            let synced = async {
+                self.download_cloud_saves(&app_name).await?;
+                if upload {
+                    self.upload_cloud_saves(&app_name).await?;
+                }
+                Ok::<_, Error>(())
+            };
+            if let Err(e) = synced.await {
+                log::warn!("Background save sync of {} failed: {}", app_name, e);
+                continue;
+            }
+            reports.push(SaveSyncReport {
+                app_name,
+                transferred,
+                conflicts: Vec::new(),
+            });
+        }
+
+        Ok(reports)
+    }
+
+    /// Check for game updates
+    pub async fn check_for_updates(&self, app_name: &str) -> Result<Option<String>> {
+        let token = self.auth.get_token()?;
+        let game = InstalledGame::load(&self.config, app_name)?;
+
+        log::info!(
+            "Checking for updates for {} (current: {})",
+            app_name,
+            game.app_version
+        );
+
+        self.client
+            .check_for_updates(token, app_name, &game.app_version)
+            .await
+    }
+
+    /// Update a game to the latest version
+    pub async fn update_game(&self, app_name: &str) -> Result<()> {
+        // TODO: Preserve user settings and save files during update
+        // TODO: Show update changelog to user
+
+        let token = self.auth.get_token()?;
+
+        log::info!("Updating game: {}", app_name);
+
+        // Check if update is available
+        match self.check_for_updates(app_name).await? {
+            Some(new_version) => {
+                println!("Update available: {}", new_version);
+                self.wait_for_download_window().await?;
+                println!("Downloading update...");
+                let _journal = Journal::open()?.begin(Operation::Update, app_name)?;
+
+                // Download new manifest
+                let (manifest, case_remaps) = self.fetch_manifest(token, app_name, true).await?;
+
+                let mut game = InstalledGame::load(&self.config, app_name)?;
+                self.update_files(token, &game, &manifest).await?;
+
+                // Update installation record
+                game.app_version = manifest.app_version.clone();
+                game.executable = manifest.launch_exe.clone();
+                game.case_remaps = case_remaps;
+                game.install_size = manifest.install_size();
+                game.updated_at = Some(Utc::now());
+                game.save(&self.config)?;
+                game.save_manifest(&manifest)?;
+
+                println!("✓ Game updated to version {}", manifest.app_version);
+                Ok(())
+            }
+            None => {
+                println!("Game is already up to date");
+                Ok(())
+            }
+        }
+    }
+
+    /// Put back the build an update replaced, returning its version. Kept
+    /// builds are used newest first, so repeated rollbacks step further back.
+    pub fn rollback_game(&self, app_name: &str) -> Result<String> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        Self::ensure_not_archived(&game)?;
+        let current = game.load_manifest()?;
+
+        let snapshots = rollback::Snapshots::open(app_name)?;
+        let Some(snapshot) = snapshots.list()?.pop() else {
+            return Err(Error::Other(format!(
+                "No earlier version of {} is kept. Updates keep one while rollback_versions is above 0.",
+                app_name
+            )));
+        };
+
+        let _journal = Journal::open()?.begin(Operation::Rollback, app_name)?;
+        let old = snapshots.restore(&snapshot, &game.install_path, &current)?;
+
+        game.app_version = old.app_version.clone();
+        game.executable = old.launch_exe.clone();
+        game.install_size = old.install_size();
+        game.updated_at = Some(Utc::now());
+        game.save(&self.config)?;
+        game.save_manifest(&old)?;
+        // Only once the record is saved, so resuming can tell how far it got
+        snapshots.discard(&snapshot)?;
+
+        audit::record(
+            AuditEntry::new(self.initiator, AuditAction::Rollback, app_name)
+                .with_path(&game.install_path),
+        );
+
+        Ok(old.app_version)
+    }
+
+    /// Download the files that changed between the installed version and
+    /// `manifest`, and remove the ones it no longer lists
+    async fn update_files(
+        &self,
+        token: &AuthToken,
+        game: &InstalledGame,
+        manifest: &GameManifest,
+    ) -> Result<()> {
+        let install_path = &game.install_path;
+
+        let old = game.load_manifest();
+        let (changed, removed) = match &old {
+            Ok(old) => {
+                let diff = diff::diff_manifests(old, manifest);
+                println!(
+                    "{} file(s) changed ({}), {} removed, {} unchanged",
+                    diff.changed.len(),
+                    format::size(diff.changed_size()),
+                    diff.removed.len(),
+                    diff.unchanged.len()
+                );
+
+                // Changed files already in their new form are left over from
+                // an interrupted update; unchanged ones may have been deleted
+                let missing =
+                    diff.unchanged.iter().copied().filter(|file| {
+                        !verify::is_present(&install_path.join(&file.filename), file)
+                    });
+                let mut changed = pending_files(install_path, diff.changed)?;
+                changed.extend(missing);
+
+                (changed, diff.removed)
+            }
+            Err(e) => {
+                log::warn!(
+                    "No stored manifest for {}, checking every file: {}",
+                    game.app_name,
+                    e
+                );
+                (
+                    pending_files(install_path, &manifest.file_list)?,
+                    Vec::new(),
+                )
+            }
+        };
+
+        println!(
+            "Updating {} of {} file(s)...",
+            changed.len(),
+            manifest.file_list.len()
+        );
+
+        if let Ok(old) = &old {
+            rollback::Snapshots::open(&game.app_name)?.take(
+                install_path,
+                old,
+                manifest,
+                self.config.rollback_versions,
+            )?;
+        }
+
+        // Chunks the old build already had are copied out of the installed
+        // files. A file replaced before it was read from fails the hash check
+        // in `download_file` and falls back to a full download.
+        let local = old
+            .as_ref()
+            .ok()
+            .map(|old| patch::LocalChunks::from_manifest(install_path, old));
+        self.download_files(token, install_path, manifest, &changed, local.as_ref())
+            .await?;
+
+        if let Some(local) = &local {
+            println!(
+                "Reused {} of installed data",
+                format::size(local.reused_bytes())
+            );
+        }
+
+        for filename in removed {
+            let path = install_path.join(&filename);
+            if path
+                .symlink_metadata()
+                .is_ok_and(|metadata| !metadata.is_dir())
+            {
+                log::info!("Removing {} (no longer part of the game)", filename);
+                fs::remove_file(&path)?;
+            }
+        }
+
+        // A new build can change a file's flags without changing its contents
+        for file in &manifest.file_list {
+            let path = install_path.join(&file.filename);
+            if file.symlink_target().is_none() && path.is_file() {
+                apply_mode(&path, file)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Downloaded data kept under the cache directory, limited by the
+    /// configured TTL and size. Shared by everything this manager does, so
+    /// its size is only counted once.
+    pub fn cache(&self) -> Result<&DiskCache> {
+        if let Some(cache) = self.cache.get() {
+            return Ok(cache);
+        }
+        let cache = DiskCache::new(
+            Config::cache_dir()?,
+            Duration::from_secs(self.config.cache_ttl_hours * 3600),
+            self.config.cache_size_mb * 1024 * 1024,
+        );
+        Ok(self.cache.get_or_init(|| cache))
+    }
+
+    /// Find caches and leftovers from interrupted operations. Unless
+    /// `dry_run` is set they are deleted; the found items are returned.
+    /// Operations running meanwhile keep their files, and new ones wait.
+    pub fn clean(&self, dry_run: bool) -> Result<Vec<Leftover>> {
+        let journal = Journal::open()?;
+        let _lock = journal.lock()?;
+        let busy: Vec<String> = journal
+            .running()?
+            .into_iter()
+            .map(|entry| entry.app_name)
+            .collect();
+
+        let installed: Vec<String> = self
+            .list_installed()?
+            .into_iter()
+            .map(|game| game.app_name)
+            .collect();
+
+        let leftovers = clean::find_leftovers(
+            &self.config.install_dir,
+            &Config::cache_dir()?,
+            &InstalledGame::manifests_dir()?,
+            &installed,
+            &busy,
+        )?;
+
+        if !dry_run {
+            let reclaimed = clean::remove_leftovers(&leftovers)?;
+            log::info!(
+                "Cleaned {} item(s), {}",
+                leftovers.len(),
+                format::size(reclaimed)
+            );
+        }
+
+        Ok(leftovers)
+    }
+
+    /// Enable or disable cloud-save sync for one game, overriding the default
+    pub fn set_cloud_sync(&self, app_name: &str, enabled: bool) -> Result<()> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        game.cloud_sync_enabled = Some(enabled);
+        game.save(&self.config)
+    }
+
+    /// Hold or release updates for one game
+    pub fn set_hold_updates(&self, app_name: &str, hold: bool) -> Result<()> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        game.hold_updates = hold;
+        game.save(&self.config)
+    }
+
+    /// Download cloud saves for a game
+    pub async fn download_cloud_saves(&self, app_name: &str) -> Result<()> {
+        let token = self.auth.get_token()?;
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+
+        if !game.cloud_sync_allowed() {
+            println!("Cloud save sync is disabled for {}", app_name);
+            return Ok(());
+        }
+
+        log::info!("Downloading cloud saves for {}", app_name);
+        println!("Fetching cloud saves...");
+
+        let filter = self.config.save_filter_for(app_name);
+        let mut saves = self.client.get_cloud_saves(token, app_name).await?;
+        saves.retain(|save| filter.allows(&save.filename));
+
+        if saves.is_empty() {
+            println!("No cloud saves found");
+            return Ok(());
+        }
+
+        println!("Found {} cloud save(s)", saves.len());
+
+        let saves_dir = self.save_dir(&mut game).await?;
+        let mut sync = SyncState::load(app_name)?;
+        let mut history = SaveHistory::load(app_name)?;
+
+        for save in saves {
+            println!("  Downloading: {}", save.filename);
+            let save_path = saves::save_file_path(&saves_dir, &save.filename)?;
+            let save_data = self.client.download_cloud_save(token, &save.id).await?;
+            let cloud_hash = saves::sha1_hex(&save_data);
+
+            if save_path.exists() {
+                let local_data = fs::read(&save_path)?;
+                let local_hash = saves::sha1_hex(&local_data);
+                let use_cloud = match saves::compare(
+                    &local_hash,
+                    &cloud_hash,
+                    sync.synced_hash(&save.filename),
+                ) {
+                    SaveComparison::Same => {
+                        sync.record(&save.filename, cloud_hash);
+                        continue;
+                    }
+                    SaveComparison::CloudChanged => true,
+                    SaveComparison::LocalChanged => false,
+                    SaveComparison::Conflict => self.cloud_wins(&save, &save_path)?,
+                };
+                if !use_cloud {
+                    println!("  Keeping local: {}", save.filename);
+                    continue;
+                }
+
+                history.keep(
+                    &save.filename,
+                    &local_data,
+                    self.config.save_history_versions,
+                )?;
+                audit::record(
+                    AuditEntry::new(self.initiator, AuditAction::SaveOverwrite, app_name)
+                        .with_path(&save_path),
+                );
+            }
+            if let Some(parent) = save_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&save_path, &save_data)?;
+            sync.record(&save.filename, cloud_hash);
+
+            log::info!("Downloaded save: {:?}", save_path);
+        }
+        sync.save(app_name)?;
+
+        println!("✓ Cloud saves downloaded");
+        Ok(())
+    }
+
+    /// Settle a save changed both locally and in the cloud since the last
+    /// sync by `save_conflict_policy`. Returns whether the cloud version wins.
+    fn cloud_wins(&self, save: &CloudSave, save_path: &Path) -> Result<bool> {
+        let local_modified = fs::metadata(save_path)?.modified()?;
+        Ok(self
+            .config
+            .save_conflict_policy
+            .cloud_wins(local_modified, &save.uploaded_at, || {
+                let uploaded = save
+                    .uploaded_at
+                    .parse()
+                    .map(format::date)
+                    .unwrap_or_else(|_| save.uploaded_at.clone());
+                prompt::choose(
+                    &format!(
+                        "Local save {} (modified {}) and its cloud version \
+                         (uploaded {}) both changed since the last sync.",
+                        save.filename,
+                        format::date(local_modified.into()),
+                        uploaded
+                    ),
+                    &["Use cloud version", "Keep local version"],
+                    1,
+                ) == 0
+            }))
+    }
+
+    /// Upload cloud saves for a game
+    pub async fn upload_cloud_saves(&self, app_name: &str) -> Result<()> {
+        self.upload_cloud_saves_since(app_name, None).await
+    }
+
+    /// Upload the local saves written at or after `since`, e.g. during the
+    /// play session that just ended, or all of them when it is `None`
+    pub async fn upload_cloud_saves_since(
+        &self,
+        app_name: &str,
+        since: Option<SystemTime>,
+    ) -> Result<()> {
+        if !api::SAVE_UPLOADS_ENABLED {
+            return Err(Error::SaveUploadsUnsupported);
+        }
+        let token = self.auth.get_token()?;
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+
+        if !game.cloud_sync_allowed() {
+            println!("Cloud save sync is disabled for {}", app_name);
+            return Ok(());
+        }
+
+        log::info!("Uploading cloud saves for {}", app_name);
+        println!("Uploading cloud saves...");
+
+        let mut local = self.synced_local_saves(&self.save_dir(&mut game).await?, app_name)?;
+        if let Some(since) = since {
+            local.retain(|save| save.modified >= since);
+        }
+
+        if local.is_empty() {
+            match since {
+                Some(_) => println!("No saves changed"),
+                None => println!("No local saves found"),
+            }
+            return Ok(());
+        }
+
+        let mut sync = SyncState::load(app_name)?;
+        let mut changed = Vec::new();
+        for save in &local {
+            let sha1 = saves::sha1_hex(&fs::read(&save.path)?);
+            if sync.synced_hash(&save.filename) == Some(sha1.as_str()) {
+                log::debug!("{} is unchanged since the last sync", save.filename);
+            } else {
+                changed.push(save);
+            }
+        }
+
+        // A cloud save changed since the last sync too, e.g. from another
+        // machine, would be lost to the upload
+        let cloud = self.client.get_cloud_saves(token, app_name).await?;
+        let mut kept = Vec::new();
+        for save in std::mem::take(&mut changed) {
+            let Some(cloud_save) = cloud.iter().find(|cloud| cloud.filename == save.filename)
+            else {
+                changed.push(save);
+                continue;
+            };
+            let cloud_hash = saves::sha1_hex(
+                &self
+                    .client
+                    .download_cloud_save(token, &cloud_save.id)
+                    .await?,
+            );
+            let local_hash = saves::sha1_hex(&fs::read(&save.path)?);
+            match saves::compare(&local_hash, &cloud_hash, sync.synced_hash(&save.filename)) {
+                SaveComparison::Same => sync.record(&save.filename, cloud_hash),
+                SaveComparison::LocalChanged => changed.push(save),
+                SaveComparison::CloudChanged => kept.push(save),
+                SaveComparison::Conflict => {
+                    if self.cloud_wins(cloud_save, &save.path)? {
+                        kept.push(save);
+                    } else {
+                        changed.push(save);
+                    }
+                }
+            }
+        }
+        for save in kept {
+            println!("  Keeping the cloud version: {}", save.filename);
+        }
+
+        let upload_size: u64 = changed.iter().map(|save| save.size).sum();
+        if let Some(quota) = self.save_quota(token).await {
+            if upload_size > quota.available() {
+                println!(
+                    "Warning: uploading {} may exceed your cloud save quota ({} of {} used)",
+                    format::size(upload_size),
+                    format::size(quota.used_bytes),
+                    format::size(quota.quota_bytes)
+                );
+            }
+        }
+
+        let mut uploaded = 0;
+        for save in changed {
+            let save_data = fs::read(&save.path)?;
+            println!("  Uploading: {}", save.filename);
+
+            self.client
+                .upload_cloud_save(token, app_name, &save.filename, &save_data)
+                .await?;
+            sync.record(&save.filename, saves::sha1_hex(&save_data));
+            uploaded += 1;
+        }
+        sync.save(app_name)?;
+
+        println!("✓ Uploaded {} save file(s)", uploaded);
+        Ok(())
+    }
+
+    /// Saves stored in the cloud next to the local files an upload would send
+    pub async fn list_cloud_saves(&self, app_name: &str) -> Result<SaveListing> {
+        let token = self.auth.get_token()?;
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let dir = self.save_dir(&mut game).await?;
+
+        Ok(SaveListing {
+            cloud: self.client.get_cloud_saves(token, app_name).await?,
+            local: self.synced_local_saves(&dir, app_name)?,
+            quota: self.save_quota(token).await,
+            dir,
+            sync_allowed: game.cloud_sync_allowed(),
+        })
+    }
+
+    /// What `cloud-save --download` and/or `--upload` would do with each
+    /// file, without changing anything. Cloud saves are fetched to compare
+    /// their contents.
+    pub async fn plan_cloud_sync(
+        &self,
+        app_name: &str,
+        download: bool,
+        upload: bool,
+    ) -> Result<SyncPlan> {
+        let token = self.auth.get_token()?;
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let dir = self.save_dir(&mut game).await?;
+        let sync_allowed = game.cloud_sync_allowed();
+        if !sync_allowed {
+            return Ok(SyncPlan {
+                files: Vec::new(),
+                quota: None,
+                dir,
+                sync_allowed,
+            });
+        }
+
+        let mut cloud = Vec::new();
+        if download {
+            let filter = self.config.save_filter_for(app_name);
+            for save in self.client.get_cloud_saves(token, app_name).await? {
+                if !filter.allows(&save.filename) {
+                    continue;
+                }
+                let data = self.client.download_cloud_save(token, &save.id).await?;
+                cloud.push((save, saves::sha1_hex(&data)));
+            }
+        }
+        let local = self
+            .synced_local_saves(&dir, app_name)?
+            .into_iter()
+            .map(|save| {
+                let sha1 = saves::sha1_hex(&fs::read(&save.path)?);
+                Ok((save, sha1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SyncPlan {
+            files: saves::plan_sync(&cloud, &local, &SyncState::load(app_name)?, upload),
+            quota: self.save_quota(token).await,
+            dir,
+            sync_allowed,
+        })
+    }
+
+    /// The account's cloud save storage use, if the service tells
+    async fn save_quota(&self, token: &AuthToken) -> Option<SaveQuota> {
+        self.client
+            .get_save_quota(token)
+            .await
+            .map_err(|e| log::warn!("Failed to fetch the cloud save quota: {}", e))
+            .ok()
+    }
+
+    /// Local saves in `dir` that the game's save filter lets sync
+    fn synced_local_saves(&self, dir: &Path, app_name: &str) -> Result<Vec<LocalSave>> {
+        let filter = self.config.save_filter_for(app_name);
+        let mut local = saves::local_saves(dir)?;
+        local.retain(|save| filter.allows(&save.filename));
+        Ok(local)
+    }
+
+    /// Earlier versions of a game's save files, oldest first
+    pub fn save_history(&self, app_name: &str) -> Result<Vec<SaveVersion>> {
+        Ok(SaveHistory::load(app_name)?.versions().to_vec())
+    }
+
+    /// Put back the save file version whose id starts with `version`. The
+    /// file it replaces is kept in the history, so a restore can be undone.
+    pub async fn restore_save(&self, app_name: &str, version: &str) -> Result<SaveVersion> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let mut history = SaveHistory::load(app_name)?;
+        let version = history.find(version)?.clone();
+        let data = history.read(&version)?;
+
+        let save_path = saves::save_file_path(&self.save_dir(&mut game).await?, &version.filename)?;
+        if save_path.exists() {
+            // Keep at least the version being replaced even when the limit
+            // is 0, since restoring is asked for explicitly
+            let current = fs::read(&save_path)?;
+            history.keep(
+                &version.filename,
+                &current,
+                self.config.save_history_versions.max(1),
+            )?;
+            audit::record(
+                AuditEntry::new(self.initiator, AuditAction::SaveOverwrite, app_name)
+                    .with_path(&save_path),
+            );
+        } else if let Some(parent) = save_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&save_path, &data)?;
+
+        log::info!("Restored save {} to {:?}", version.id(), save_path);
+        Ok(version)
+    }
+
+    /// Pack the game's save folder into a `.tar.zst` in `out_dir`, or in
+    /// `save-backups/` in the data directory. Returns the archive's path
+    /// and how many files it holds.
+    pub async fn backup_saves(
+        &self,
+        app_name: &str,
+        out_dir: Option<&Path>,
+    ) -> Result<(PathBuf, usize)> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let files = saves::local_saves(&self.save_dir(&mut game).await?)?;
+        if files.is_empty() {
+            return Err(Error::Other(format!(
+                "{} has no saves to back up",
+                app_name
+            )));
+        }
+
+        let out_dir = match out_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => backup::default_dir()?,
+        };
+        fs::create_dir_all(&out_dir)?;
+        let path = out_dir.join(backup::file_name(app_name));
+        backup::write(&path, &files)?;
+
+        log::info!(
+            "Backed up {} save file(s) of {} to {:?}",
+            files.len(),
+            app_name,
+            path
+        );
+        Ok((path, files.len()))
+    }
+
+    /// Put the files of a backup made by [`Self::backup_saves`] back into
+    /// the game's save folder. Files it replaces are kept in the save
+    /// history. Returns how many files were restored.
+    pub async fn restore_save_backup(&self, app_name: &str, archive: &Path) -> Result<usize> {
+        let mut game = InstalledGame::load(&self.config, app_name)?;
+        let entries = backup::read(archive)?;
+        let saves_dir = self.save_dir(&mut game).await?;
+        let mut history = SaveHistory::load(app_name)?;
+
+        for entry in &entries {
+            let save_path = saves::save_file_path(&saves_dir, &entry.filename)?;
+            if save_path.exists() {
+                history.keep(
+                    &entry.filename,
+                    &fs::read(&save_path)?,
+                    self.config.save_history_versions.max(1),
+                )?;
+                audit::record(
+                    AuditEntry::new(self.initiator, AuditAction::SaveOverwrite, app_name)
+                        .with_path(&save_path),
+                );
+            } else if let Some(parent) = save_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&save_path, &entry.data)?;
+        }
+
+        log::info!(
+            "Restored {} save file(s) of {} from {:?}",
+            entries.len(),
+            app_name,
+            archive
+        );
+        Ok(entries.len())
+    }
+
+    /// Where the game keeps its saves, from the template in Epic's catalog.
+    /// Games without one fall back to `saves/` in their install.
+    async fn save_dir(&self, game: &mut InstalledGame) -> Result<PathBuf> {
+        if game.save_path_template.is_none() {
+            let token = self.auth.get_token()?;
+            game.save_path_template = self
+                .client
+                .get_save_path_template(token, &game.app_name)
+                .await?;
+            if game.save_path_template.is_some() {
+                game.save(&self.config)?;
+            }
+        }
+
+        let Some(template) = &game.save_path_template else {
+            return Ok(game.install_path.join("saves"));
+        };
+        self.save_dirs(game)?.resolve(template)
+    }
+
+    /// The folders the game's save path template can refer to
+    fn save_dirs(&self, game: &InstalledGame) -> Result<saves::SaveDirs> {
+        let account_id = self.auth.account_id();
+        Ok(match self.game_runner(game) {
+            Runner::Native => saves::SaveDirs::native(&game.install_path, account_id)?,
+            _ => saves::SaveDirs::in_prefix(
+                &Self::prefix_path(&game.app_name)?,
+                &game.install_path,
+                account_id,
+            ),
+        })
+    }
+}
+
+/// Cloud and local saves of one game, as shown by `saves list`
+#[derive(Debug, Clone)]
+pub struct SaveListing {
+    pub cloud: Vec<CloudSave>,
+    pub local: Vec<LocalSave>,
+    /// Cloud storage use, when the service reports it
+    pub quota: Option<SaveQuota>,
+    /// Where the local saves are
+    pub dir: PathBuf,
+    /// Whether `cloud-save` will sync this game at all
+    pub sync_allowed: bool,
+}
+
+/// What a cloud save sync would do, as shown by `cloud-save --dry-run`
+#[derive(Debug, Clone)]
+pub struct SyncPlan {
+    pub files: Vec<PlannedSync>,
+    /// Cloud storage use, when the service reports it
+    pub quota: Option<SaveQuota>,
+    /// Where the local saves are
+    pub dir: PathBuf,
+    /// Whether `cloud-save` will sync this game at all
+    pub sync_allowed: bool,
+}
+
+impl SyncPlan {
+    /// Bytes the planned uploads send
+    pub fn upload_size(&self) -> u64 {
+        self.files
+            .iter()
+            .filter(|file| file.action == SyncAction::Upload)
+            .filter_map(|file| file.local.as_ref())
+            .map(|save| save.size)
+            .sum()
+    }
+
+    /// Whether the planned uploads need more than the cloud storage has free
+    pub fn exceeds_quota(&self) -> bool {
+        self.quota
+            .as_ref()
+            .is_some_and(|quota| self.upload_size() > quota.available())
+    }
+}
+
+/// `<install_dir>/<app>.staging`, where an install is assembled
+fn staging_dir(install_path: &Path) -> PathBuf {
+    let mut staging = install_path.as_os_str().to_owned();
+    staging.push(".staging");
+    PathBuf::from(staging)
+}
+
+/// Give an installed file the permissions its manifest entry asks for
+#[cfg(unix)]
+fn apply_mode(path: &Path, file: &FileManifest) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(file.unix_mode()))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn apply_mode(_path: &Path, _file: &FileManifest) -> Result<()> {
+    Ok(())
+}
+
+/// Files that are missing under `install_path` or don't match their hash
+fn pending_files<'a>(
+    install_path: &Path,
+    files: impl IntoIterator<Item = &'a FileManifest>,
+) -> Result<Vec<&'a FileManifest>> {
+    let mut pending = Vec::new();
+
+    for file in files {
+        let path = install_path.join(&file.filename);
+        if !verify::is_present(&path, file) || !file_matches(&path, file)? {
+            pending.push(file);
+        }
+    }
+
+    Ok(pending)
+}
+
+/// Arguments identifying the game, the locale and the player to the game.
+/// Offline launches pass no `account_id`: a game given an account without a
+/// session to go with it tries to sign in, while one given none uses its
+/// offline mode.
+fn launch_args(app_name: &str, locale: &str, account_id: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        format!("-epicapp={}", app_name),
+        "-epicenv=Prod".to_string(),
+        "-EpicPortal".to_string(),
+        format!("-epiclocale={}", locale),
+    ];
+    if let Some(account_id) = account_id {
+        args.push(format!("-epicuserid={}", account_id));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_launches_leave_out_the_account() {
+        let online = launch_args("Fortnite", "it-IT", Some("abc123"));
+        assert_eq!(
+            online,
+            [
+                "-epicapp=Fortnite",
+                "-epicenv=Prod",
+                "-EpicPortal",
+                "-epiclocale=it-IT",
+                "-epicuserid=abc123",
+            ]
+        );
+
+        let offline = launch_args("Fortnite", "it-IT", None);
+        assert_eq!(offline, online[..4]);
     }
 }
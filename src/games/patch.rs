@@ -0,0 +1,123 @@
+//! Chunk data an update can take from the installed files instead of
+//! downloading it. Builds mostly share chunks with the previous version, so
+//! a changed file is largely rebuilt from bytes already on disk. The result
+//! is still checked against the new manifest's hash, so stale or modified
+//! local data only costs a fallback download.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::api::GameManifest;
+
+/// Where a range of a chunk sits inside an installed file
+struct LocalPart {
+    path: PathBuf,
+    file_offset: u64,
+    chunk_offset: u64,
+    size: u64,
+}
+
+pub struct LocalChunks {
+    parts: HashMap<String, Vec<LocalPart>>,
+    reused: AtomicU64,
+}
+
+impl LocalChunks {
+    /// Index the chunk parts of every file `manifest` installed under
+    /// `install_path`
+    pub fn from_manifest(install_path: &Path, manifest: &GameManifest) -> Self {
+        let mut parts: HashMap<String, Vec<LocalPart>> = HashMap::new();
+
+        for file in &manifest.file_list {
+            let path = install_path.join(&file.filename);
+            let mut file_offset = 0;
+
+            for part in &file.file_chunk_parts {
+                parts.entry(part.guid.clone()).or_default().push(LocalPart {
+                    path: path.clone(),
+                    file_offset,
+                    chunk_offset: part.offset,
+                    size: part.size,
+                });
+                file_offset += part.size;
+            }
+        }
+
+        Self {
+            parts,
+            reused: AtomicU64::new(0),
+        }
+    }
+
+    /// Bytes `offset..offset + size` of chunk `guid`, if an installed file
+    /// holds that whole range
+    pub fn read(&self, guid: &str, offset: u64, size: u64) -> Option<Vec<u8>> {
+        let candidates = self.parts.get(guid)?;
+
+        candidates
+            .iter()
+            .filter(|part| {
+                part.chunk_offset <= offset && offset + size <= part.chunk_offset + part.size
+            })
+            .find_map(|part| {
+                let start = part.file_offset + (offset - part.chunk_offset);
+                let data = read_range(&part.path, start, size).ok()?;
+                self.reused.fetch_add(size, Ordering::Relaxed);
+                Some(data)
+            })
+    }
+
+    /// Total bytes served from installed files so far
+    pub fn reused_bytes(&self) -> u64 {
+        self.reused.load(Ordering::Relaxed)
+    }
+}
+
+fn read_range(path: &Path, start: u64, size: u64) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut data = vec![0; size as usize];
+    file.read_exact(&mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ChunkPart, FileManifest};
+    use crate::games::paths::test_manifest;
+    use tempfile::TempDir;
+
+    fn part(guid: &str, offset: u64, size: u64) -> ChunkPart {
+        ChunkPart {
+            guid: guid.to_string(),
+            offset,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_reads_chunk_ranges_from_installed_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Game.exe"), b"headerBODY").unwrap();
+
+        let manifest = test_manifest([FileManifest {
+            filename: "Game.exe".to_string(),
+            file_chunk_parts: vec![part("A", 10, 6), part("B", 0, 4)],
+            ..Default::default()
+        }]);
+
+        let local = LocalChunks::from_manifest(dir.path(), &manifest);
+
+        assert_eq!(local.read("A", 12, 3).unwrap(), b"ade");
+        assert_eq!(local.read("B", 0, 4).unwrap(), b"BODY");
+        // Only part of this range is installed
+        assert!(local.read("A", 8, 4).is_none());
+        assert!(local.read("C", 0, 1).is_none());
+        assert_eq!(local.reused_bytes(), 7);
+    }
+}
@@ -0,0 +1,138 @@
+//! Windows treats paths case-insensitively, so manifests may spell the same
+//! directory as both `Content/` and `content/`. On a case-sensitive
+//! filesystem that would create two directories and the game would only see
+//! one of them. Every path is therefore folded onto the first spelling seen
+//! for each component, the way Windows would resolve it.
+
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(test)]
+use crate::api::FileManifest;
+use crate::api::GameManifest;
+
+/// Rewrite the manifest's file paths and launch executable so paths that only
+/// differ by case share one spelling. Returns the original → rewritten path
+/// of every entry that changed.
+pub fn fold_case(manifest: &mut GameManifest) -> BTreeMap<String, String> {
+    let mut folder = CaseFolder::default();
+    let mut remapped = BTreeMap::new();
+
+    for file in &mut manifest.file_list {
+        let folded = folder.fold(&file.filename);
+        if folded != file.filename {
+            log::debug!("Case collision: {} stored as {}", file.filename, folded);
+            remapped.insert(
+                std::mem::replace(&mut file.filename, folded.clone()),
+                folded,
+            );
+        }
+    }
+
+    if !manifest.launch_exe.is_empty() {
+        manifest.launch_exe = folder.fold(&manifest.launch_exe);
+    }
+
+    remapped
+}
+
+#[derive(Default)]
+struct CaseFolder {
+    /// Lower-cased path prefix → spelling chosen for it
+    seen: HashMap<String, String>,
+}
+
+impl CaseFolder {
+    fn fold(&mut self, path: &str) -> String {
+        let mut folded = String::new();
+        let mut key = String::new();
+
+        for component in path.split(['/', '\\']).filter(|c| !c.is_empty()) {
+            if !key.is_empty() {
+                key.push('/');
+            }
+            key.push_str(&component.to_lowercase());
+
+            let spelling = self
+                .seen
+                .entry(key.clone())
+                .or_insert_with(|| component.to_string());
+
+            if !folded.is_empty() {
+                folded.push('/');
+            }
+            folded.push_str(spelling);
+        }
+
+        folded
+    }
+}
+
+/// Version 1.0 of a game called `Game` made of `files`, for the tests of
+/// everything that reads manifests
+#[cfg(test)]
+pub(super) fn test_manifest(files: impl IntoIterator<Item = FileManifest>) -> GameManifest {
+    GameManifest {
+        manifest_file_version: "1".to_string(),
+        app_name: "Game".to_string(),
+        app_version: "1.0".to_string(),
+        launch_exe: "Game.exe".to_string(),
+        file_list: files.into_iter().collect(),
+        ..Default::default()
+    }
+}
+
+/// A file at `path` whose hash is `hash` repeated, so files with the same
+/// byte compare as identical
+#[cfg(test)]
+pub(super) fn test_file(path: &str, hash: u8) -> FileManifest {
+    FileManifest {
+        filename: path.to_string(),
+        file_hash: vec![hash; 20],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(files: &[&str], launch_exe: &str) -> GameManifest {
+        GameManifest {
+            launch_exe: launch_exe.to_string(),
+            ..test_manifest(files.iter().map(|path| test_file(path, 0)))
+        }
+    }
+
+    #[test]
+    fn test_directories_fold_onto_first_spelling() {
+        let mut manifest = manifest(
+            &[
+                "Game/Content/Paks/pak0.pak",
+                "game/content/Movies/intro.bk2",
+                "Game/Binaries/Win64/Game.exe",
+            ],
+            "GAME\\Binaries\\Win64\\Game.exe",
+        );
+
+        let remapped = fold_case(&mut manifest);
+
+        assert_eq!(
+            manifest.file_list[1].filename,
+            "Game/Content/Movies/intro.bk2"
+        );
+        assert_eq!(manifest.launch_exe, "Game/Binaries/Win64/Game.exe");
+        assert_eq!(remapped.len(), 1);
+        assert_eq!(
+            remapped["game/content/Movies/intro.bk2"],
+            "Game/Content/Movies/intro.bk2"
+        );
+    }
+
+    #[test]
+    fn test_clean_manifest_is_untouched() {
+        let mut manifest = manifest(&["Game/a.pak", "Game/b.pak"], "Game/run.sh");
+
+        assert!(fold_case(&mut manifest).is_empty());
+        assert_eq!(manifest.launch_exe, "Game/run.sh");
+    }
+}
@@ -0,0 +1,250 @@
+//! Checks run before a game is started, so a launch that can't work fails
+//! with what to fix instead of a bare spawn error or a crash inside Wine.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::runner::{find_in_path, find_proton};
+use super::Runner;
+use crate::{Error, Result};
+
+/// Where Vulkan drivers register themselves besides the XDG data and config
+/// directories. NixOS links its drivers under /run.
+const VULKAN_ICD_DIRS: &[&str] = &[
+    "/etc/vulkan/icd.d",
+    "/run/opengl-driver/share/vulkan/icd.d",
+    "/run/opengl-driver-32/share/vulkan/icd.d",
+];
+
+/// Dynamic loaders of 32-bit x86 programs across distributions
+const LOADERS_32BIT: &[&str] = &[
+    "/lib/ld-linux.so.2",
+    "/usr/lib32/ld-linux.so.2",
+    "/lib32/ld-linux.so.2",
+];
+
+/// Everything that would keep `executable` from starting with `runner`,
+/// reported together. Missing parts that may be found in ways this can't
+/// see are only logged.
+pub fn check(executable: &Path, runner: &Runner) -> Result<()> {
+    let mut problems = Vec::new();
+
+    if !executable.is_file() {
+        problems.push(format!(
+            "The executable {:?} is missing. Run 'rauncher repair' to restore it.",
+            executable
+        ));
+    } else if *runner == Runner::Native {
+        if !is_executable(executable) {
+            problems.push(format!(
+                "{:?} isn't executable. Run 'rauncher repair' to fix its permissions.",
+                executable
+            ));
+        }
+        if elf_class(executable) == Some(ElfClass::Bits32) && !any_exists(LOADERS_32BIT) {
+            problems.push(
+                "This is a 32-bit game, but 32-bit libraries aren't installed. \
+                 Install your distribution's multilib or i386 packages."
+                    .to_string(),
+            );
+        }
+    }
+
+    match runner {
+        Runner::Native => {}
+        Runner::Wine(wine) => {
+            let found = if wine.components().count() > 1 {
+                wine.is_file()
+            } else {
+                find_in_path(&wine.to_string_lossy()).is_some()
+            };
+            if !found {
+                problems.push(format!(
+                    "Wine ({}) isn't installed. Install it, or pick another runner from \
+                     'rauncher runner list'.",
+                    wine.display()
+                ));
+            }
+        }
+        Runner::Proton(version) => {
+            if find_proton(version).is_err() {
+                problems.push(format!(
+                    "{} isn't installed. Install it with 'rauncher runner install', \
+                     or pick another runner from 'rauncher runner list'.",
+                    runner
+                ));
+            }
+            // DXVK and VKD3D translate Direct3D to Vulkan. Drivers can live
+            // in places the loader is told about another way, so a miss
+            // doesn't stop the launch.
+            if !has_vulkan_driver(|var| std::env::var_os(var)) {
+                log::warn!(
+                    "Proton needs Vulkan, but no Vulkan driver was found. If the game \
+                     fails to start, install the Vulkan driver for your GPU \
+                     (e.g. mesa-vulkan-drivers)."
+                );
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Other(format!(
+            "Can't launch the game:\n  - {}",
+            problems.join("\n  - ")
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElfClass {
+    Bits32,
+    Bits64,
+}
+
+/// Word size of an ELF binary; `None` for scripts and other files
+fn elf_class(path: &Path) -> Option<ElfClass> {
+    let mut header = [0u8; 5];
+    File::open(path).ok()?.read_exact(&mut header).ok()?;
+
+    match header {
+        [0x7f, b'E', b'L', b'F', 1] => Some(ElfClass::Bits32),
+        [0x7f, b'E', b'L', b'F', 2] => Some(ElfClass::Bits64),
+        _ => None,
+    }
+}
+
+/// Whether the Vulkan loader would find a driver, searching where it does:
+/// the files `VK_DRIVER_FILES` or `VK_ICD_FILENAMES` list if set, otherwise
+/// `vulkan/icd.d` in the XDG config and data directories. `var` reads the
+/// environment.
+fn has_vulkan_driver(var: impl Fn(&str) -> Option<OsString>) -> bool {
+    if let Some(files) = var("VK_DRIVER_FILES").or_else(|| var("VK_ICD_FILENAMES")) {
+        return std::env::split_paths(&files).any(|file| file.is_file());
+    }
+
+    let list = |name: &str, default: &str| -> Vec<PathBuf> {
+        let paths = var(name).filter(|paths| !paths.is_empty());
+        std::env::split_paths(&paths.unwrap_or_else(|| default.into())).collect()
+    };
+    let home = |name: &str, default: &str| -> Option<PathBuf> {
+        var(name)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(default)))
+    };
+
+    home("XDG_CONFIG_HOME", ".config")
+        .into_iter()
+        .chain(list("XDG_CONFIG_DIRS", "/etc/xdg"))
+        .chain(home("XDG_DATA_HOME", ".local/share"))
+        .chain(list("XDG_DATA_DIRS", "/usr/local/share:/usr/share"))
+        .map(|dir| dir.join("vulkan/icd.d"))
+        .chain(VULKAN_ICD_DIRS.iter().map(PathBuf::from))
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+}
+
+fn any_exists(paths: &[&str]) -> bool {
+    paths.iter().any(|path| Path::new(path).exists())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_elf_class() {
+        let dir = TempDir::new().unwrap();
+        let game32 = dir.path().join("game.x86");
+        fs::write(&game32, [0x7f, b'E', b'L', b'F', 1, 1, 1, 0]).unwrap();
+        let script = dir.path().join("start.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+
+        assert_eq!(elf_class(&game32), Some(ElfClass::Bits32));
+        assert_eq!(elf_class(&script), None);
+    }
+
+    #[test]
+    fn test_check_reports_every_problem() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("Game.exe");
+
+        let error = check(
+            &missing,
+            &Runner::Wine(dir.path().join("no-such-wine/bin/wine")),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("rauncher repair"), "{}", error);
+        assert!(error.contains("rauncher runner list"), "{}", error);
+    }
+
+    #[test]
+    fn test_vulkan_drivers_are_found_where_the_loader_looks() {
+        let dir = TempDir::new().unwrap();
+        let icd = dir.path().join("nix/store/mesa/share/vulkan/icd.d");
+        fs::create_dir_all(&icd).unwrap();
+        fs::write(icd.join("radeon_icd.x86_64.json"), "{}").unwrap();
+        let data_dirs = OsString::from(format!(
+            "{}:{}",
+            dir.path().join("empty").display(),
+            dir.path().join("nix/store/mesa/share").display()
+        ));
+        let driver = icd.join("radeon_icd.x86_64.json").into_os_string();
+
+        let env = |vars: Vec<(&'static str, OsString)>| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.clone())
+            }
+        };
+        // Keeps the host's XDG directories out of the search
+        let isolated = |vars: Vec<(&'static str, OsString)>| {
+            let empty = dir.path().join("empty").into_os_string();
+            let mut all = vec![
+                ("HOME", empty.clone()),
+                ("XDG_CONFIG_DIRS", empty.clone()),
+                ("XDG_DATA_DIRS", empty),
+            ];
+            all.retain(|(name, _)| !vars.iter().any(|(var, _)| var == name));
+            all.extend(vars);
+            env(all)
+        };
+
+        assert!(has_vulkan_driver(isolated(vec![(
+            "XDG_DATA_DIRS",
+            data_dirs
+        )])));
+        assert!(has_vulkan_driver(isolated(vec![(
+            "VK_ICD_FILENAMES",
+            driver
+        )])));
+        // The listed files replace the search
+        assert!(!has_vulkan_driver(isolated(vec![(
+            "VK_DRIVER_FILES",
+            dir.path().join("missing.json").into_os_string()
+        )])));
+    }
+}
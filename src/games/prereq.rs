@@ -0,0 +1,137 @@
+//! Redistributables a manifest asks to have installed alongside the game,
+//! such as Visual C++ runtimes or DirectX. They run once per game, inside its
+//! Wine or Proton prefix, before the first launch.
+
+use std::path::Path;
+
+use super::Runner;
+use crate::api::GameManifest;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prerequisite {
+    pub name: String,
+    /// Recorded on the installed game once the installer has run
+    pub ids: Vec<String>,
+    /// Installer, relative to the game directory
+    pub path: String,
+    pub args: String,
+}
+
+impl Prerequisite {
+    /// The prerequisite `manifest` declares, if any
+    pub fn from_manifest(manifest: &GameManifest) -> Option<Self> {
+        if manifest.prereq_path.is_empty() {
+            return None;
+        }
+
+        let path = manifest.prereq_path.replace('\\', "/");
+        let ids = if manifest.prereq_ids.is_empty() {
+            vec![path.clone()]
+        } else {
+            manifest.prereq_ids.clone()
+        };
+        let name = if manifest.prereq_name.is_empty() {
+            path.clone()
+        } else {
+            manifest.prereq_name.clone()
+        };
+
+        Some(Self {
+            name,
+            ids,
+            path,
+            args: manifest.prereq_args.clone(),
+        })
+    }
+
+    /// Whether every id is among those already installed
+    pub fn is_installed(&self, installed: &[String]) -> bool {
+        self.ids.iter().all(|id| installed.contains(id))
+    }
+
+    /// Run the installer through `runner` with `prefix` and wait for it
+    pub fn install(&self, runner: &Runner, install_path: &Path, prefix: &Path) -> Result<()> {
+        let installer = install_path.join(&self.path);
+        if !installer.is_file() {
+            return Err(Error::Other(format!(
+                "Installer for {} not found at {:?}. Run 'rauncher repair' to restore it.",
+                self.name, installer
+            )));
+        }
+
+        let status = runner
+            .command(&installer, prefix)?
+            .args(self.args.split_whitespace())
+            .current_dir(install_path)
+            .status()
+            .map_err(|e| Error::Other(format!("Failed to start {} installer: {}", self.name, e)))?;
+
+        if !status.success() {
+            return Err(Error::Other(format!(
+                "{} installer exited with {}",
+                self.name, status
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    use crate::games::paths::test_manifest;
+
+    fn manifest(prereq_path: &str) -> GameManifest {
+        GameManifest {
+            prereq_ids: vec!["vcredist2019".to_string()],
+            prereq_name: "Visual C++ 2019".to_string(),
+            prereq_path: prereq_path.to_string(),
+            prereq_args: "/quiet /norestart".to_string(),
+            ..test_manifest([])
+        }
+    }
+
+    #[test]
+    fn test_prerequisite_from_manifest() {
+        assert_eq!(Prerequisite::from_manifest(&manifest("")), None);
+
+        let prereq =
+            Prerequisite::from_manifest(&manifest(r"Installers\vc_redist.x64.exe")).unwrap();
+        assert_eq!(prereq.path, "Installers/vc_redist.x64.exe");
+        assert!(!prereq.is_installed(&[]));
+        assert!(prereq.is_installed(&["vcredist2019".to_string()]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_runs_installer_in_prefix() {
+        let dir = TempDir::new().unwrap();
+        let install = dir.path().join("game");
+        fs::create_dir_all(install.join("Installers")).unwrap();
+        let prefix = dir.path().join("prefix");
+        fs::create_dir_all(&prefix).unwrap();
+
+        // `sh` stands in for wine, so the installer is a script that records
+        // how it was called
+        fs::write(
+            install.join("Installers/vc_redist.x64.exe"),
+            "echo \"$*\" > \"$WINEPREFIX/called\"\n",
+        )
+        .unwrap();
+
+        let prereq =
+            Prerequisite::from_manifest(&manifest("Installers/vc_redist.x64.exe")).unwrap();
+        prereq
+            .install(&Runner::Wine(PathBuf::from("sh")), &install, &prefix)
+            .unwrap();
+
+        let called = fs::read_to_string(prefix.join("called")).unwrap();
+        assert_eq!(called, "/quiet /norestart\n");
+    }
+}
@@ -0,0 +1,242 @@
+//! Discord Rich Presence for the game being played. Discord's desktop client
+//! listens on a local socket (a named pipe on Windows) for JSON messages in
+//! length-prefixed frames; the presence goes away with the connection, so
+//! dropping a [`Presence`] also clears it.
+
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::{Error, Result};
+
+/// Sockets Discord tries, numbered from 0 when several clients run
+const SOCKET_COUNT: usize = 10;
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Largest reply accepted; Discord's are a few kilobytes
+const MAX_FRAME_LEN: u32 = 64 * 1024;
+
+// Frame opcodes
+const HANDSHAKE: u32 = 0;
+const FRAME: u32 = 1;
+const CLOSE: u32 = 2;
+
+#[cfg(unix)]
+type Stream = std::os::unix::net::UnixStream;
+#[cfg(not(unix))]
+type Stream = std::fs::File;
+
+pub struct Presence {
+    stream: Stream,
+    nonce: u64,
+}
+
+impl Presence {
+    /// Connect to the running Discord client as the Discord application
+    /// `client_id`
+    pub fn connect(client_id: &str) -> Result<Self> {
+        let stream = open_socket().ok_or_else(|| {
+            Error::Other("Discord isn't running, or doesn't accept connections".to_string())
+        })?;
+        Self::handshake(stream, client_id)
+    }
+
+    fn handshake(stream: Stream, client_id: &str) -> Result<Self> {
+        let mut presence = Self { stream, nonce: 0 };
+        presence.send(HANDSHAKE, &json!({ "v": 1, "client_id": client_id }))?;
+        // Discord answers with a READY event, or closes the connection
+        presence.receive()?;
+        Ok(presence)
+    }
+
+    /// Show `title` as being played since `started`
+    pub fn set_game(&mut self, title: &str, started: SystemTime) -> Result<()> {
+        let start = started
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or_default();
+        self.set_activity(json!({
+            "details": title,
+            "timestamps": { "start": start },
+        }))
+    }
+
+    /// Remove the game from the user's profile
+    pub fn clear(&mut self) -> Result<()> {
+        self.set_activity(Value::Null)
+    }
+
+    fn set_activity(&mut self, activity: Value) -> Result<()> {
+        self.nonce += 1;
+        self.send(
+            FRAME,
+            &json!({
+                "cmd": "SET_ACTIVITY",
+                "args": { "pid": std::process::id(), "activity": activity },
+                "nonce": self.nonce.to_string(),
+            }),
+        )?;
+
+        let reply = self.receive()?;
+        if reply["evt"] == "ERROR" {
+            return Err(Error::Other(format!(
+                "Discord rejected the presence: {}",
+                reply["data"]["message"].as_str().unwrap_or("unknown error")
+            )));
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, opcode: u32, payload: &Value) -> Result<()> {
+        let payload = payload.to_string();
+        let mut frame = Vec::with_capacity(8 + payload.len());
+        frame.extend(opcode.to_le_bytes());
+        frame.extend((payload.len() as u32).to_le_bytes());
+        frame.extend(payload.as_bytes());
+        self.stream.write_all(&frame)?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Value> {
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+        let opcode = u32::from_le_bytes(header[..4].try_into().unwrap());
+        let len = u32::from_le_bytes(header[4..].try_into().unwrap());
+        if len > MAX_FRAME_LEN {
+            return Err(Error::Other(format!(
+                "Discord sent a {} byte message, more than a presence reply can be",
+                len
+            )));
+        }
+
+        let mut payload = vec![0u8; len as usize];
+        self.stream.read_exact(&mut payload)?;
+        let payload: Value = serde_json::from_slice(&payload)?;
+
+        if opcode == CLOSE {
+            return Err(Error::Other(format!(
+                "Discord closed the connection: {}",
+                payload["message"].as_str().unwrap_or("no reason given")
+            )));
+        }
+        Ok(payload)
+    }
+}
+
+#[cfg(unix)]
+fn open_socket() -> Option<Stream> {
+    // Flatpak and Snap builds of Discord put their socket in a subdirectory
+    let dirs: Vec<std::path::PathBuf> = ["XDG_RUNTIME_DIR", "TMPDIR"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(Into::into)
+        .chain([std::path::PathBuf::from("/tmp")])
+        .flat_map(|dir: std::path::PathBuf| {
+            ["", "app/com.discordapp.Discord", "snap.discord"].map(|sub| dir.join(sub))
+        })
+        .collect();
+
+    dirs.iter()
+        .flat_map(|dir| (0..SOCKET_COUNT).map(move |n| dir.join(format!("discord-ipc-{}", n))))
+        .find_map(|path| Stream::connect(path).ok())
+        .inspect(|stream| {
+            let _ = stream.set_read_timeout(Some(REPLY_TIMEOUT));
+        })
+}
+
+#[cfg(not(unix))]
+fn open_socket() -> Option<Stream> {
+    (0..SOCKET_COUNT).find_map(|n| {
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\?\pipe\discord-ipc-{}", n))
+            .ok()
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn read_frame(stream: &mut Stream) -> (u32, Value) {
+        let mut header = [0u8; 8];
+        stream.read_exact(&mut header).unwrap();
+        let len = u32::from_le_bytes(header[4..].try_into().unwrap());
+        let mut payload = vec![0u8; len as usize];
+        stream.read_exact(&mut payload).unwrap();
+        (
+            u32::from_le_bytes(header[..4].try_into().unwrap()),
+            serde_json::from_slice(&payload).unwrap(),
+        )
+    }
+
+    fn write_frame(stream: &mut Stream, opcode: u32, payload: Value) {
+        let payload = payload.to_string();
+        stream.write_all(&opcode.to_le_bytes()).unwrap();
+        stream
+            .write_all(&(payload.len() as u32).to_le_bytes())
+            .unwrap();
+        stream.write_all(payload.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_presence_protocol() {
+        let (client, mut discord) = Stream::pair().unwrap();
+
+        let server = std::thread::spawn(move || {
+            let (opcode, handshake) = read_frame(&mut discord);
+            assert_eq!(opcode, HANDSHAKE);
+            assert_eq!(handshake["client_id"], "1234");
+            write_frame(
+                &mut discord,
+                FRAME,
+                json!({ "cmd": "DISPATCH", "evt": "READY" }),
+            );
+
+            let (_, activity) = read_frame(&mut discord);
+            write_frame(
+                &mut discord,
+                FRAME,
+                json!({ "cmd": "SET_ACTIVITY", "evt": null }),
+            );
+            let (_, cleared) = read_frame(&mut discord);
+            write_frame(
+                &mut discord,
+                FRAME,
+                json!({ "evt": "ERROR", "data": { "message": "bad activity" } }),
+            );
+            (activity, cleared)
+        });
+
+        let mut presence = Presence::handshake(client, "1234").unwrap();
+        presence
+            .set_game("Hades", UNIX_EPOCH + Duration::from_secs(100))
+            .unwrap();
+        let error = presence.clear().unwrap_err().to_string();
+
+        let (activity, cleared) = server.join().unwrap();
+        assert_eq!(activity["args"]["activity"]["details"], "Hades");
+        assert_eq!(activity["args"]["activity"]["timestamps"]["start"], 100);
+        assert!(cleared["args"]["activity"].is_null());
+        assert!(error.contains("bad activity"), "{}", error);
+    }
+
+    #[test]
+    fn test_oversized_frames_are_refused() {
+        let (client, mut discord) = Stream::pair().unwrap();
+
+        let server = std::thread::spawn(move || {
+            read_frame(&mut discord);
+            discord.write_all(&FRAME.to_le_bytes()).unwrap();
+            discord.write_all(&u32::MAX.to_le_bytes()).unwrap();
+        });
+
+        let error = Presence::handshake(client, "1234")
+            .err()
+            .unwrap()
+            .to_string();
+        server.join().unwrap();
+        assert!(error.contains("byte message"), "{}", error);
+    }
+}
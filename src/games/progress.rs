@@ -0,0 +1,260 @@
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::collections::VecDeque;
+use std::io::IsTerminal;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How far back the download speed is averaged
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// Progress of a multi-file download: a bar for overall bytes (with speed and
+/// ETA) and one counting finished files, labelled with the file started last.
+/// Files may download in parallel. When stdout isn't a terminal, each file is
+/// printed on its own line instead.
+pub struct InstallProgress {
+    total_bytes: u64,
+    total_files: usize,
+    bars: Option<Bars>,
+    status: Option<DownloadStatus>,
+}
+
+struct Bars {
+    _multi: MultiProgress,
+    overall: ProgressBar,
+    files: ProgressBar,
+}
+
+impl InstallProgress {
+    pub fn new(total_bytes: u64, total_files: usize) -> Self {
+        let bars = std::io::stdout()
+            .is_terminal()
+            .then(|| Bars::new(total_bytes, total_files));
+
+        Self {
+            total_bytes,
+            total_files,
+            bars,
+            status: None,
+        }
+    }
+
+    /// Also report to `status`, for a frontend that draws its own progress
+    pub fn with_status(mut self, status: DownloadStatus) -> Self {
+        status.begin(self.total_bytes, self.total_files);
+        self.status = Some(status);
+        self
+    }
+
+    pub fn start_file(&self, index: usize, filename: &str, size: u64) {
+        match &self.bars {
+            Some(bars) => bars.files.set_message(filename.to_string()),
+            None => println!("  [{}/{}] {}", index + 1, self.total_files, filename),
+        }
+        if let Some(status) = &self.status {
+            status.update(|tracker| tracker.snapshot.current_file = Some(filename.to_string()));
+        }
+        log::debug!("Downloading {} ({})", filename, crate::format::size(size));
+    }
+
+    pub fn add_bytes(&self, bytes: u64) {
+        if let Some(bars) = &self.bars {
+            bars.overall.inc(bytes);
+        }
+        if let Some(status) = &self.status {
+            status.update(|tracker| tracker.add_bytes(bytes, Instant::now()));
+        }
+    }
+
+    pub fn finish_file(&self) {
+        if let Some(bars) = &self.bars {
+            bars.files.inc(1);
+        }
+        if let Some(status) = &self.status {
+            status.update(|tracker| tracker.snapshot.files += 1);
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(bars) = &self.bars {
+            bars.files.finish_and_clear();
+            bars.overall.finish();
+        }
+    }
+}
+
+/// Progress of a download as a frontend can read it from another thread
+/// while the download runs. Clones report on the same download.
+#[derive(Clone, Default)]
+pub struct DownloadStatus {
+    tracker: Arc<Mutex<Tracker>>,
+}
+
+/// Where a download stands
+#[derive(Debug, Clone, Default)]
+pub struct ProgressSnapshot {
+    pub bytes: u64,
+    pub total_bytes: u64,
+    pub files: usize,
+    pub total_files: usize,
+    /// File started last
+    pub current_file: Option<String>,
+    /// Bytes per second, averaged over the last few seconds
+    pub speed: f64,
+}
+
+#[derive(Default)]
+struct Tracker {
+    snapshot: ProgressSnapshot,
+    started: Option<Instant>,
+    /// Bytes added recently, for the speed
+    recent: VecDeque<(Instant, u64)>,
+}
+
+impl DownloadStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        let mut tracker = self.tracker.lock().unwrap();
+        tracker.snapshot.speed = tracker.speed(Instant::now());
+        tracker.snapshot.clone()
+    }
+
+    /// Start over for a download of `total_bytes` in `total_files` files.
+    /// An update followed by a repair, say, reports each in turn.
+    fn begin(&self, total_bytes: u64, total_files: usize) {
+        self.update(|tracker| {
+            *tracker = Tracker {
+                snapshot: ProgressSnapshot {
+                    total_bytes,
+                    total_files,
+                    ..Default::default()
+                },
+                started: Some(Instant::now()),
+                recent: VecDeque::new(),
+            }
+        });
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Tracker)) {
+        change(&mut self.tracker.lock().unwrap());
+    }
+}
+
+impl ProgressSnapshot {
+    /// Share of the bytes downloaded, from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        (self.bytes as f64 / self.total_bytes as f64).min(1.0) as f32
+    }
+
+    /// Time left at the current speed; `None` while nothing is arriving
+    pub fn eta(&self) -> Option<Duration> {
+        (self.speed > 0.0).then(|| {
+            let left = self.total_bytes.saturating_sub(self.bytes);
+            Duration::from_secs_f64(left as f64 / self.speed)
+        })
+    }
+}
+
+impl Tracker {
+    fn add_bytes(&mut self, bytes: u64, now: Instant) {
+        self.snapshot.bytes += bytes;
+        self.recent.push_back((now, bytes));
+        self.forget_before(now);
+    }
+
+    /// Bytes per second over the window, or since the start if that was
+    /// more recent. Drops to 0 while paused, as nothing arrives.
+    fn speed(&mut self, now: Instant) -> f64 {
+        self.forget_before(now);
+        let Some(started) = self.started else {
+            return 0.0;
+        };
+        let span = now.duration_since(started).min(SPEED_WINDOW).as_secs_f64();
+        if span == 0.0 {
+            return 0.0;
+        }
+        self.recent.iter().map(|&(_, bytes)| bytes).sum::<u64>() as f64 / span
+    }
+
+    fn forget_before(&mut self, now: Instant) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|&(at, _)| now.duration_since(at) > SPEED_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+    }
+}
+
+impl Bars {
+    fn new(total_bytes: u64, total_files: usize) -> Self {
+        let multi = MultiProgress::with_draw_target(ProgressDrawTarget::stdout());
+
+        let overall = multi.add(ProgressBar::new(total_bytes));
+        overall.set_style(
+            ProgressStyle::with_template(
+                "{bar:40.cyan/blue} {bytes}/{total_bytes} {binary_bytes_per_sec} ETA {eta}",
+            )
+            .expect("valid progress template")
+            .progress_chars("=> "),
+        );
+
+        let files = multi.add(ProgressBar::new(total_files as u64));
+        files.set_style(
+            ProgressStyle::with_template("  [{pos}/{len}] {msg}").expect("valid progress template"),
+        );
+
+        Self {
+            _multi: multi,
+            overall,
+            files,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_averages_recent_bytes() {
+        let start = Instant::now();
+        let mut tracker = Tracker {
+            started: Some(start),
+            ..Default::default()
+        };
+        tracker.snapshot.total_bytes = 10_000;
+
+        tracker.add_bytes(1000, start + Duration::from_secs(1));
+        tracker.add_bytes(1000, start + Duration::from_secs(2));
+        assert_eq!(tracker.speed(start + Duration::from_secs(2)), 1000.0);
+
+        // Once the window is full it slides, so a stall shows as 0
+        assert_eq!(tracker.speed(start + Duration::from_secs(10)), 0.0);
+        assert_eq!(tracker.snapshot.bytes, 2000);
+    }
+
+    #[test]
+    fn test_eta_from_speed() {
+        let snapshot = ProgressSnapshot {
+            bytes: 2000,
+            total_bytes: 10_000,
+            speed: 1000.0,
+            ..Default::default()
+        };
+        assert_eq!(snapshot.fraction(), 0.2);
+        assert_eq!(snapshot.eta(), Some(Duration::from_secs(8)));
+
+        let stalled = ProgressSnapshot {
+            speed: 0.0,
+            ..snapshot
+        };
+        assert_eq!(stalled.eta(), None);
+    }
+}
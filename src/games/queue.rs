@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use crate::metrics::metrics;
+use crate::Result;
+
+/// Outcome of one queued install
+#[derive(Debug)]
+pub struct QueueResult {
+    pub app_name: String,
+    pub error: Option<String>,
+}
+
+impl QueueResult {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Games waiting to be installed, processed one at a time in order
+#[derive(Debug, Default)]
+pub struct InstallQueue {
+    pending: VecDeque<String>,
+}
+
+impl InstallQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a game unless it is already queued
+    pub fn push(&mut self, app_name: &str) {
+        if !self.pending.iter().any(|queued| queued == app_name) {
+            self.pending.push_back(app_name.to_string());
+            metrics().set_queue_depth(self.pending.len());
+        }
+    }
+
+    /// Queue every app name listed in a file, one per line. Blank lines
+    /// and lines starting with `#` are ignored.
+    pub fn push_from_file(&mut self, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(path)?;
+
+        for line in contents.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                self.push(line);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<String> {
+        let next = self.pending.pop_front();
+        metrics().set_queue_depth(self.pending.len());
+        next
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_skips_duplicates() {
+        let mut queue = InstallQueue::new();
+        queue.push("Fortnite");
+        queue.push("Fortnite");
+        queue.push("Hades");
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop().as_deref(), Some("Fortnite"));
+        assert_eq!(queue.pop().as_deref(), Some("Hades"));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_push_from_file_ignores_comments() {
+        let dir = TempDir::new().unwrap();
+        let list = dir.path().join("games.txt");
+        fs::write(&list, "# weekend\nFortnite\n\n  Hades  \n").unwrap();
+
+        let mut queue = InstallQueue::new();
+        queue.push_from_file(&list).unwrap();
+
+        assert_eq!(queue.pop().as_deref(), Some("Fortnite"));
+        assert_eq!(queue.pop().as_deref(), Some("Hades"));
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::InstalledGame;
+use crate::api::GameManifest;
+
+/// An installed-game record that exists on disk but can't be parsed
+#[derive(Debug, Clone)]
+pub struct CorruptRecord {
+    /// App name taken from the record's file name
+    pub app_name: String,
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Where a rebuilt record got its data from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoverySource {
+    /// The manifest stored when the game was installed
+    StoredManifest,
+    /// A freshly downloaded manifest checked against the files on disk
+    DownloadedManifest,
+}
+
+impl std::fmt::Display for RecoverySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecoverySource::StoredManifest => write!(f, "stored manifest"),
+            RecoverySource::DownloadedManifest => write!(f, "downloaded manifest"),
+        }
+    }
+}
+
+/// Try to parse every record in `dir`, returning the ones that fail
+pub fn find_corrupt_records(dir: &Path) -> Vec<CorruptRecord> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut corrupt: Vec<CorruptRecord> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let error = match fs::read_to_string(&path) {
+                Ok(contents) => serde_json::from_str::<InstalledGame>(&contents)
+                    .err()?
+                    .to_string(),
+                Err(e) => e.to_string(),
+            };
+
+            Some(CorruptRecord {
+                app_name: path.file_stem()?.to_string_lossy().into_owned(),
+                path,
+                error,
+            })
+        })
+        .collect();
+
+    corrupt.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+    corrupt
+}
+
+/// Rebuild a record from a manifest for a game found at `install_path`
+pub fn record_from_manifest(
+    app_name: &str,
+    install_path: &Path,
+    manifest: &GameManifest,
+) -> InstalledGame {
+    InstalledGame {
+        app_name: app_name.to_string(),
+        app_title: app_name.to_string(),
+        app_version: manifest.app_version.clone(),
+        install_path: install_path.to_path_buf(),
+        executable: manifest.launch_exe.clone(),
+        ..Default::default()
+    }
+}
+
+/// Keep the unreadable file next to the rebuilt one for inspection
+pub fn set_aside(record: &CorruptRecord) -> std::io::Result<PathBuf> {
+    let mut target = record.path.clone().into_os_string();
+    target.push(".corrupt");
+    let target = PathBuf::from(target);
+
+    fs::rename(&record.path, &target)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_corrupt_records() {
+        let dir = TempDir::new().unwrap();
+        let good = InstalledGame {
+            app_name: "Hades".to_string(),
+            ..Default::default()
+        };
+        fs::write(
+            dir.path().join("Hades.json"),
+            serde_json::to_string(&good).unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.path().join("Fortnite.json"), "{\"app_name\": \"Fortn").unwrap();
+
+        let corrupt = find_corrupt_records(dir.path());
+        assert_eq!(corrupt.len(), 1);
+        assert_eq!(corrupt[0].app_name, "Fortnite");
+
+        let moved = set_aside(&corrupt[0]).unwrap();
+        assert!(moved.ends_with("Fortnite.json.corrupt"));
+        assert!(find_corrupt_records(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Earlier builds of a game, kept so `rollback` can undo an update that
+//! broke it. Before an update touches the install, the files it will replace
+//! or remove are copied into a snapshot together with the manifest they
+//! belong to. Restoring puts those copies back and deletes the files the
+//! newer build added.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::copy::copy_file;
+use super::diff;
+use super::symlink;
+use crate::api::GameManifest;
+use crate::config::Config;
+use crate::Result;
+
+/// One kept build, numbered in the order it was replaced
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    index: u32,
+}
+
+impl Snapshot {
+    /// The manifest of the build this snapshot restores
+    pub fn manifest(&self) -> Result<GameManifest> {
+        let contents = fs::read_to_string(self.path.join("manifest.json"))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn files_dir(&self) -> PathBuf {
+        self.path.join("files")
+    }
+}
+
+/// The snapshots of one game
+pub struct Snapshots {
+    dir: PathBuf,
+}
+
+impl Snapshots {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Snapshots of `app_name` in the launcher's data directory
+    pub fn open(app_name: &str) -> Result<Self> {
+        Ok(Self::new(
+            Config::data_dir()?.join("rollback").join(app_name),
+        ))
+    }
+
+    /// Complete snapshots, oldest first
+    pub fn list(&self) -> Result<Vec<Snapshot>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            // Unfinished snapshots carry a `.partial` suffix and don't parse
+            let index = path.file_name().and_then(|n| n.to_str()?.parse().ok());
+            if let Some(index) = index {
+                snapshots.push(Snapshot { path, index });
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.index);
+
+        Ok(snapshots)
+    }
+
+    /// Keep the files of `old` that updating to `new` replaces or removes,
+    /// then drop the oldest snapshots beyond `keep`. Does nothing if the
+    /// newest snapshot already holds `old`, as when an interrupted update
+    /// runs again after some files were replaced.
+    pub fn take(
+        &self,
+        install_path: &Path,
+        old: &GameManifest,
+        new: &GameManifest,
+        keep: usize,
+    ) -> Result<()> {
+        if keep == 0 {
+            return Ok(());
+        }
+
+        let snapshots = self.list()?;
+        if let Some(latest) = snapshots.last() {
+            if latest.manifest()?.app_version == old.app_version {
+                return Ok(());
+            }
+        }
+
+        let diff = diff::diff_manifests(old, new);
+        let touched: HashSet<&str> = diff
+            .changed
+            .iter()
+            .map(|file| file.filename.as_str())
+            .chain(diff.removed.iter().map(String::as_str))
+            .collect();
+
+        let index = snapshots.last().map_or(1, |latest| latest.index + 1);
+        let partial = self.dir.join(format!("{}.partial", index));
+        if partial.exists() {
+            fs::remove_dir_all(&partial)?;
+        }
+        fs::create_dir_all(&partial)?;
+
+        for file in old
+            .file_list
+            .iter()
+            .filter(|f| touched.contains(f.filename.as_str()))
+        {
+            // Links are recreated from the manifest on restore
+            let source = install_path.join(&file.filename);
+            if file.symlink_target().is_some() || !source.is_file() {
+                continue;
+            }
+
+            let target = partial.join("files").join(&file.filename);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            copy_file(&source, &target)?;
+        }
+
+        fs::write(partial.join("manifest.json"), serde_json::to_string(old)?)?;
+        fs::rename(&partial, self.dir.join(index.to_string()))?;
+
+        let snapshots = self.list()?;
+        for snapshot in &snapshots[..snapshots.len().saturating_sub(keep)] {
+            fs::remove_dir_all(&snapshot.path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Turn the install at `install_path`, currently built from `current`,
+    /// back into the build `snapshot` holds, and return that build's
+    /// manifest. The snapshot is kept until `discard`, so an interrupted
+    /// restore can run again.
+    pub fn restore(
+        &self,
+        snapshot: &Snapshot,
+        install_path: &Path,
+        current: &GameManifest,
+    ) -> Result<GameManifest> {
+        let old = snapshot.manifest()?;
+
+        let kept: HashSet<&str> = old.file_list.iter().map(|f| f.filename.as_str()).collect();
+        for file in current
+            .file_list
+            .iter()
+            .filter(|f| !kept.contains(f.filename.as_str()))
+        {
+            let path = install_path.join(&file.filename);
+            if path.is_file() {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        let files_dir = snapshot.files_dir();
+        for file in &old.file_list {
+            if let Some(target) = file.symlink_target() {
+                if !symlink::matches(&install_path.join(&file.filename), target) {
+                    symlink::create(install_path, file, target)?;
+                }
+                continue;
+            }
+
+            let source = files_dir.join(&file.filename);
+            if !source.is_file() {
+                continue;
+            }
+
+            // Removed first, as the newer copy may be read-only
+            let target = install_path.join(&file.filename);
+            if target.symlink_metadata().is_ok() {
+                fs::remove_file(&target)?;
+            } else if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            copy_file(&source, &target)?;
+        }
+
+        Ok(old)
+    }
+
+    /// Drop a snapshot once the build it holds has been restored. It stops
+    /// being listed before any of it is deleted.
+    pub fn discard(&self, snapshot: &Snapshot) -> Result<()> {
+        let partial = self.dir.join(format!("{}.partial", snapshot.index));
+        if partial.exists() {
+            fs::remove_dir_all(&partial)?;
+        }
+        fs::rename(&snapshot.path, &partial)?;
+        fs::remove_dir_all(&partial)?;
+        Ok(())
+    }
+
+    /// Forget every kept build, e.g. when the game is uninstalled
+    pub fn remove_all(&self) -> Result<()> {
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::paths::{test_file, test_manifest};
+    use tempfile::TempDir;
+
+    fn manifest(version: &str, files: &[(&str, u8)]) -> GameManifest {
+        GameManifest {
+            app_version: version.to_string(),
+            ..test_manifest(files.iter().map(|&(path, hash)| test_file(path, hash)))
+        }
+    }
+
+    #[test]
+    fn test_restore_undoes_an_update() {
+        let install = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        let snapshots = Snapshots::new(data.path().to_path_buf());
+
+        let v1 = manifest("1.0", &[("game", 1), ("old.pak", 1), ("same.pak", 1)]);
+        let v2 = manifest("2.0", &[("game", 2), ("new.pak", 2), ("same.pak", 1)]);
+
+        fs::write(install.path().join("game"), "v1").unwrap();
+        fs::write(install.path().join("old.pak"), "old").unwrap();
+        fs::write(install.path().join("same.pak"), "same").unwrap();
+        snapshots.take(install.path(), &v1, &v2, 2).unwrap();
+
+        // Apply the update by hand
+        fs::write(install.path().join("game"), "v2").unwrap();
+        fs::remove_file(install.path().join("old.pak")).unwrap();
+        fs::write(install.path().join("new.pak"), "new").unwrap();
+
+        let snapshot = snapshots.list().unwrap().pop().unwrap();
+        assert!(!snapshot.files_dir().join("same.pak").exists());
+
+        let restored = snapshots.restore(&snapshot, install.path(), &v2).unwrap();
+        assert_eq!(restored.app_version, "1.0");
+        assert_eq!(
+            fs::read_to_string(install.path().join("game")).unwrap(),
+            "v1"
+        );
+        assert_eq!(
+            fs::read_to_string(install.path().join("old.pak")).unwrap(),
+            "old"
+        );
+        assert!(!install.path().join("new.pak").exists());
+
+        // Restoring again after an interruption gives the same result
+        snapshots.restore(&snapshot, install.path(), &v2).unwrap();
+        assert_eq!(
+            fs::read_to_string(install.path().join("old.pak")).unwrap(),
+            "old"
+        );
+
+        snapshots.discard(&snapshot).unwrap();
+        assert!(snapshots.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_take_keeps_the_newest_snapshots() {
+        let install = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        let snapshots = Snapshots::new(data.path().to_path_buf());
+        fs::write(install.path().join("game"), "data").unwrap();
+
+        for version in 1..=3u8 {
+            let old = manifest(&version.to_string(), &[("game", version)]);
+            let new = manifest(&(version + 1).to_string(), &[("game", version + 1)]);
+            snapshots.take(install.path(), &old, &new, 2).unwrap();
+            // Running the same update again doesn't add another snapshot
+            snapshots.take(install.path(), &old, &new, 2).unwrap();
+        }
+
+        let versions: Vec<_> = snapshots
+            .list()
+            .unwrap()
+            .iter()
+            .map(|s| s.manifest().unwrap().app_version)
+            .collect();
+        assert_eq!(versions, ["2", "3"]);
+    }
+}
@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::builds;
+use crate::{Error, Result};
+
+/// How a game's executable is started
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runner {
+    /// Run the executable directly
+    Native,
+    /// Run through a Wine binary
+    Wine(PathBuf),
+    /// Run through a Steam Proton build, identified by its directory name
+    Proton(String),
+}
+
+impl Runner {
+    /// Windows executables need Wine, or Proton when system Wine isn't
+    /// installed; everything else runs natively
+    pub fn detect(executable: &Path) -> Self {
+        Self::detect_from(executable, &installed_runners())
+    }
+
+    /// Pick the first of `installed` for Windows executables, falling back
+    /// to `wine` from `PATH`
+    fn detect_from(executable: &Path, installed: &[Runner]) -> Self {
+        let is_windows = executable
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"));
+
+        if is_windows {
+            installed
+                .first()
+                .cloned()
+                .unwrap_or_else(|| Runner::Wine(PathBuf::from("wine")))
+        } else {
+            Runner::Native
+        }
+    }
+
+    /// Build the command that starts `executable`, using `prefix` as the
+    /// Wine/Proton prefix when one is needed
+    pub fn command(&self, executable: &Path, prefix: &Path) -> Result<Command> {
+        let command = match self {
+            Runner::Native => Command::new(executable),
+            Runner::Wine(wine) => {
+                let mut command = Command::new(wine);
+                command.env("WINEPREFIX", prefix).arg(executable);
+                command
+            }
+            Runner::Proton(version) => {
+                let (proton, steam_root) = find_proton(version)?;
+                let mut command = Command::new(proton);
+                command
+                    .env("STEAM_COMPAT_DATA_PATH", prefix)
+                    .env("STEAM_COMPAT_CLIENT_INSTALL_PATH", steam_root)
+                    .arg("run")
+                    .arg(executable);
+                command
+            }
+        };
+
+        Ok(command)
+    }
+
+    pub fn needs_prefix(&self) -> bool {
+        !matches!(self, Runner::Native)
+    }
+
+    /// Create the Wine prefix at `prefix` if it hasn't been set up yet, so a
+    /// broken Wine shows up here rather than as a failing game. Proton sets up
+    /// its prefix itself.
+    pub fn init_prefix(&self, prefix: &Path) -> Result<()> {
+        let Runner::Wine(wine) = self else {
+            return Ok(());
+        };
+        if prefix.join("system.reg").exists() {
+            return Ok(());
+        }
+
+        println!("Setting up the Wine prefix...");
+        let status = Command::new(wine)
+            .env("WINEPREFIX", prefix)
+            .env("WINEDLLOVERRIDES", "mscoree,mshtml=")
+            .args(["wineboot", "--init"])
+            .status()
+            .map_err(|e| Error::Other(format!("Failed to run {}: {}", wine.display(), e)))?;
+
+        if !status.success() {
+            return Err(Error::Other(format!(
+                "Setting up the Wine prefix at {:?} failed ({}). Delete it to start over.",
+                prefix, status
+            )));
+        }
+        Ok(())
+    }
+
+    /// Directories this runner's files live in, outside the system paths
+    pub fn directories(&self) -> Vec<PathBuf> {
+        match self {
+            Runner::Native => Vec::new(),
+            // `<build>/bin/wine`; a bare `wine` comes from the system
+            Runner::Wine(wine) => wine
+                .parent()
+                .and_then(Path::parent)
+                .filter(|_| wine.is_absolute())
+                .map(Path::to_path_buf)
+                .into_iter()
+                .collect(),
+            Runner::Proton(version) => find_proton(version)
+                .map(|(proton, steam_root)| {
+                    proton
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .into_iter()
+                        .chain([steam_root])
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Build a `winetricks` command that works on `prefix` with this
+    /// runner's Wine. Mono and Gecko install prompts are suppressed, as
+    /// winetricks verbs install what they need themselves.
+    pub fn winetricks(&self, prefix: &Path) -> Result<Command> {
+        let winetricks = find_in_path("winetricks").ok_or_else(|| {
+            Error::Other("winetricks not found. Install it with your package manager.".to_string())
+        })?;
+
+        let mut command = Command::new(winetricks);
+        command
+            .envs(self.wine_env(prefix)?)
+            .env("WINEDLLOVERRIDES", "mscoree,mshtml=")
+            .env("WINEDEBUG", "-all");
+        Ok(command)
+    }
+
+    /// `WINEPREFIX`, `WINE` and `WINESERVER` for tools that drive Wine
+    /// directly. Proton keeps the Wine prefix in `pfx` inside its prefix and
+    /// ships Wine in `files` (`dist` in older builds).
+    pub(super) fn wine_env(&self, prefix: &Path) -> Result<Vec<(&'static str, PathBuf)>> {
+        let (wine_prefix, wine) = match self {
+            Runner::Native => {
+                return Err(Error::Other("Native games have no Wine prefix".to_string()))
+            }
+            Runner::Wine(wine) => (prefix.to_path_buf(), wine.clone()),
+            Runner::Proton(version) => {
+                let (proton, _) = find_proton(version)?;
+                let proton_dir = proton.parent().unwrap_or(Path::new("."));
+                let wine = ["files", "dist"]
+                    .iter()
+                    .map(|dir| proton_dir.join(dir).join("bin/wine"))
+                    .find(|wine| wine.is_file())
+                    .ok_or_else(|| {
+                        Error::Other(format!("Proton '{}' holds no Wine binary", version))
+                    })?;
+                (prefix.join("pfx"), wine)
+            }
+        };
+
+        let mut env = vec![("WINEPREFIX", wine_prefix)];
+        if let Some(wineserver) = wine
+            .parent()
+            .map(|bin| bin.join("wineserver"))
+            .filter(|wineserver| wineserver.is_file())
+        {
+            env.push(("WINESERVER", wineserver));
+        }
+        env.push(("WINE", wine));
+
+        Ok(env)
+    }
+}
+
+impl std::fmt::Display for Runner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Runner::Native => write!(f, "native"),
+            Runner::Wine(path) => write!(f, "wine ({})", path.display()),
+            Runner::Proton(version) => write!(f, "proton ({})", version),
+        }
+    }
+}
+
+/// Runners found on this machine: system Wine first, then every Proton
+/// build Steam knows about, by name, then the builds `rauncher runner
+/// install` added
+pub fn installed_runners() -> Vec<Runner> {
+    let mut runners: Vec<Runner> = find_in_path("wine").map(Runner::Wine).into_iter().collect();
+
+    let mut protons: Vec<String> = steam_roots()
+        .iter()
+        .flat_map(|steam_root| protons_in_steam_root(steam_root))
+        .collect();
+    protons.sort();
+    protons.dedup();
+    runners.extend(protons.into_iter().map(Runner::Proton));
+
+    let builds = builds::installed().unwrap_or_else(|e| {
+        log::warn!("Failed to list installed runner builds: {}", e);
+        Vec::new()
+    });
+    for build in builds {
+        if !runners.contains(&build.runner) {
+            runners.push(build.runner);
+        }
+    }
+
+    runners
+}
+
+pub(super) fn find_in_path(name: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+pub(super) fn steam_roots() -> Vec<PathBuf> {
+    directories::BaseDirs::new()
+        .map(|dirs| {
+            let home = dirs.home_dir();
+            vec![home.join(".steam/steam"), home.join(".local/share/Steam")]
+        })
+        .unwrap_or_default()
+}
+
+/// Names of the Proton builds installed under a Steam root
+fn protons_in_steam_root(steam_root: &Path) -> Vec<String> {
+    ["compatibilitytools.d", "steamapps/common"]
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(steam_root.join(dir)).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("proton").is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+/// Locate the `proton` script of an installed Proton build, returning it
+/// together with the Steam root it belongs to. Builds in the launcher's
+/// runners directory use that directory as their Steam root.
+pub(super) fn find_proton(version: &str) -> Result<(PathBuf, PathBuf)> {
+    for steam_root in steam_roots() {
+        if let Some(proton) = proton_in_steam_root(&steam_root, version) {
+            return Ok((proton, steam_root));
+        }
+    }
+
+    let runners_dir = builds::runners_dir()?;
+    let proton = runners_dir.join(version).join("proton");
+    if proton.is_file() {
+        return Ok((proton, runners_dir));
+    }
+
+    Err(Error::Other(format!(
+        "Proton '{}' not found in Steam's compatibilitytools.d or steamapps/common, \
+         or among the builds from 'rauncher runner install'",
+        version
+    )))
+}
+
+fn proton_in_steam_root(steam_root: &Path, version: &str) -> Option<PathBuf> {
+    ["compatibilitytools.d", "steamapps/common"]
+        .iter()
+        .map(|dir| steam_root.join(dir).join(version).join("proton"))
+        .find(|proton| proton.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_runner() {
+        assert_eq!(
+            Runner::detect_from(Path::new("Binaries/Game.EXE"), &[]),
+            Runner::Wine(PathBuf::from("wine"))
+        );
+        assert_eq!(
+            Runner::detect_from(
+                Path::new("Binaries/Game.exe"),
+                &[Runner::Proton("GE-Proton9-20".to_string())]
+            ),
+            Runner::Proton("GE-Proton9-20".to_string())
+        );
+        assert_eq!(Runner::detect(Path::new("game.x86_64")), Runner::Native);
+    }
+
+    #[test]
+    fn test_wine_env_uses_the_runners_wineserver() {
+        let dir = TempDir::new().unwrap();
+        let bin = dir.path().join("wine-GE-Proton8-26/bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join("wine"), "").unwrap();
+        fs::write(bin.join("wineserver"), "").unwrap();
+        let prefix = dir.path().join("prefix");
+
+        let env = Runner::Wine(bin.join("wine")).wine_env(&prefix).unwrap();
+        assert_eq!(
+            env,
+            vec![
+                ("WINEPREFIX", prefix.clone()),
+                ("WINESERVER", bin.join("wineserver")),
+                ("WINE", bin.join("wine")),
+            ]
+        );
+        assert!(Runner::Native.wine_env(&prefix).is_err());
+    }
+
+    #[test]
+    fn test_proton_in_steam_root() {
+        let dir = TempDir::new().unwrap();
+        let proton_dir = dir.path().join("compatibilitytools.d/GE-Proton9-20");
+        fs::create_dir_all(&proton_dir).unwrap();
+        fs::write(proton_dir.join("proton"), "").unwrap();
+
+        assert_eq!(
+            proton_in_steam_root(dir.path(), "GE-Proton9-20"),
+            Some(proton_dir.join("proton"))
+        );
+        assert_eq!(proton_in_steam_root(dir.path(), "Proton 8.0"), None);
+        assert_eq!(protons_in_steam_root(dir.path()), vec!["GE-Proton9-20"]);
+    }
+
+    #[test]
+    fn test_runner_toml_roundtrip() {
+        #[derive(Serialize, Deserialize)]
+        struct Wrapper {
+            runner: Runner,
+        }
+
+        let toml = toml::to_string(&Wrapper {
+            runner: Runner::Proton("GE-Proton9-20".to_string()),
+        })
+        .unwrap();
+        let parsed: Wrapper = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.runner, Runner::Proton("GE-Proton9-20".to_string()));
+    }
+}
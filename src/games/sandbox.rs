@@ -0,0 +1,172 @@
+//! Optional bubblewrap sandbox around launched games. The game sees the
+//! system read-only, a private `/tmp`, and an empty home directory except for
+//! its own install, the runner it needs and where it keeps saves: its prefix
+//! for Windows games, the XDG config and data directories and its save
+//! folder for native ones.
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::runner::find_in_path;
+use super::saves::SaveDirs;
+use super::LaunchTemplate;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    /// Directories the game may write to
+    pub writable: Vec<PathBuf>,
+    /// Directories under the home directory the game may only read
+    pub readable: Vec<PathBuf>,
+}
+
+impl Sandbox {
+    /// Run `command` inside `bwrap`
+    pub fn apply(&self, command: Command) -> Result<Command> {
+        let bwrap = find_in_path("bwrap").ok_or_else(|| {
+            Error::Other(
+                "bwrap not found. Install bubblewrap or turn the sandbox off for this game."
+                    .to_string(),
+            )
+        })?;
+
+        let home = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+        let mut wrapper = vec![bwrap.to_string_lossy().into_owned()];
+        wrapper.extend(
+            self.args(home.as_deref())
+                .into_iter()
+                .map(|arg| arg.to_string_lossy().into_owned()),
+        );
+
+        Ok(LaunchTemplate {
+            env: Vec::new(),
+            wrapper,
+            args: Vec::new(),
+        }
+        .apply(command))
+    }
+
+    /// Let a native game write where it keeps saves and settings, all under
+    /// the home directory the sandbox hides: the XDG config and data
+    /// directories and its save folder from `template`. While the template
+    /// isn't known yet, Documents stands in for the save folder. Missing
+    /// folders are created, as bwrap only binds what exists.
+    pub fn allow_native_saves(&mut self, dirs: &SaveDirs, template: Option<&str>) -> Result<()> {
+        let mut writable: Vec<PathBuf> = directories::BaseDirs::new()
+            .map(|base| {
+                vec![
+                    base.config_dir().to_path_buf(),
+                    base.data_dir().to_path_buf(),
+                ]
+            })
+            .unwrap_or_default();
+        writable.push(save_folder(dirs, template)?);
+
+        for dir in writable {
+            fs::create_dir_all(&dir)?;
+            self.writable.push(dir);
+        }
+        Ok(())
+    }
+
+    /// `bwrap` arguments, ending in `--` before the wrapped command. Later
+    /// mounts go on top of earlier ones, so the home directory is hidden
+    /// before the allowed parts of it are put back.
+    fn args(&self, home: Option<&Path>) -> Vec<OsString> {
+        let mut args: Vec<OsString> = ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+
+        if let Some(home) = home {
+            args.extend(["--tmpfs".into(), home.into()]);
+        }
+        args.extend(["--tmpfs".into(), "/tmp".into()]);
+        // Display sockets
+        let x11 = Path::new("/tmp/.X11-unix");
+        if x11.exists() {
+            args.extend(["--ro-bind".into(), x11.into(), x11.into()]);
+        }
+
+        for path in self.readable.iter().filter(|path| path.exists()) {
+            args.extend(["--ro-bind".into(), path.into(), path.into()]);
+        }
+        for path in &self.writable {
+            args.extend(["--bind".into(), path.into(), path.into()]);
+        }
+
+        args.extend(["--die-with-parent".into(), "--".into()]);
+        args
+    }
+}
+
+/// Where a native game's saves go, from its save path template
+fn save_folder(dirs: &SaveDirs, template: Option<&str>) -> Result<PathBuf> {
+    match template {
+        Some(template) => dirs.resolve(template),
+        None => Ok(dirs.documents.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_home_is_hidden_before_allowed_paths() {
+        let sandbox = Sandbox {
+            writable: vec![PathBuf::from("/home/player/Games/Game")],
+            readable: Vec::new(),
+        };
+
+        let args: Vec<String> = sandbox
+            .args(Some(Path::new("/home/player")))
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        let hidden = args.iter().position(|arg| arg == "/home/player").unwrap();
+        let bound = args
+            .iter()
+            .position(|arg| arg == "/home/player/Games/Game")
+            .unwrap();
+        assert_eq!(args[hidden - 1], "--tmpfs");
+        assert_eq!(args[bound - 1], "--bind");
+        assert!(hidden < bound);
+        assert_eq!(args.last().unwrap(), "--");
+    }
+
+    #[test]
+    fn test_native_save_folder_is_writable() {
+        let dirs = SaveDirs {
+            install_dir: PathBuf::from("/games/Game"),
+            app_data: PathBuf::from("/home/player/.local/share"),
+            home: PathBuf::from("/home/player"),
+            documents: PathBuf::from("/home/player/Documents"),
+            saved_games: PathBuf::from("/home/player/Saved Games"),
+            account_id: None,
+        };
+
+        assert_eq!(
+            save_folder(&dirs, Some("{AppData}/Game/Saved")).unwrap(),
+            PathBuf::from("/home/player/.local/share/Game/Saved")
+        );
+        assert_eq!(
+            save_folder(&dirs, None).unwrap(),
+            PathBuf::from("/home/player/Documents")
+        );
+
+        let sandbox = Sandbox {
+            writable: vec![save_folder(&dirs, Some("{AppData}/Game/Saved")).unwrap()],
+            readable: Vec::new(),
+        };
+        let args = sandbox.args(Some(Path::new("/home/player")));
+        let bound = args
+            .iter()
+            .position(|arg| arg == "/home/player/.local/share/Game/Saved")
+            .unwrap();
+        assert_eq!(args[bound - 1], "--bind");
+    }
+}
@@ -0,0 +1,894 @@
+//! Where games keep their saves. Epic's catalog gives each game with cloud
+//! saves a folder template such as `{AppData}/Game/Saved/SaveGames`, written
+//! for Windows. Windows games find those folders inside their Wine prefix;
+//! native games get the matching XDG directories.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::api::CloudSave;
+use crate::config::{storage, Config};
+use crate::{Error, Result};
+
+/// What to do when a cloud save differs from the local file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SaveConflictPolicy {
+    /// Keep whichever was written last
+    PreferNewest,
+    PreferCloud,
+    PreferLocal,
+    /// Ask on a terminal; keep the local file where nobody can answer
+    #[default]
+    Ask,
+}
+
+impl SaveConflictPolicy {
+    /// Whether the cloud version should replace the local file, which was
+    /// last modified at `local_modified`. `ask` puts the question to the
+    /// user and returns their answer.
+    pub fn cloud_wins(
+        self,
+        local_modified: SystemTime,
+        cloud_uploaded: &str,
+        ask: impl FnOnce() -> bool,
+    ) -> bool {
+        match self {
+            SaveConflictPolicy::PreferCloud => true,
+            SaveConflictPolicy::PreferLocal => false,
+            // A cloud save of unknown age never replaces local progress
+            SaveConflictPolicy::PreferNewest => cloud_uploaded
+                .parse::<DateTime<Utc>>()
+                .is_ok_and(|uploaded| uploaded > DateTime::<Utc>::from(local_modified)),
+            SaveConflictPolicy::Ask => ask(),
+        }
+    }
+}
+
+impl std::fmt::Display for SaveConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveConflictPolicy::PreferNewest => write!(f, "prefer-newest"),
+            SaveConflictPolicy::PreferCloud => write!(f, "prefer-cloud"),
+            SaveConflictPolicy::PreferLocal => write!(f, "prefer-local"),
+            SaveConflictPolicy::Ask => write!(f, "ask"),
+        }
+    }
+}
+
+/// Folders a save path template can refer to
+#[derive(Debug, Clone)]
+pub struct SaveDirs {
+    pub install_dir: PathBuf,
+    /// `%LOCALAPPDATA%`
+    pub app_data: PathBuf,
+    /// The user's profile directory
+    pub home: PathBuf,
+    /// `Documents`
+    pub documents: PathBuf,
+    pub saved_games: PathBuf,
+    pub account_id: Option<String>,
+}
+
+impl SaveDirs {
+    /// Windows folders inside the Wine or Proton prefix at `prefix`
+    pub fn in_prefix(prefix: &Path, install_dir: &Path, account_id: Option<&str>) -> Self {
+        // Proton keeps the Wine prefix in `pfx` and always names the user
+        // steamuser; plain Wine uses the Unix user name
+        let wine_prefix = match prefix.join("pfx") {
+            pfx if pfx.is_dir() => pfx,
+            _ => prefix.to_path_buf(),
+        };
+        let users = wine_prefix.join("drive_c/users");
+        let user = if users.join("steamuser").is_dir() {
+            "steamuser".to_string()
+        } else {
+            std::env::var("USER").unwrap_or_else(|_| "steamuser".to_string())
+        };
+        let home = users.join(user);
+
+        Self {
+            install_dir: install_dir.to_path_buf(),
+            app_data: home.join("AppData/Local"),
+            documents: home.join("Documents"),
+            saved_games: home.join("Saved Games"),
+            home,
+            account_id: account_id.map(str::to_string),
+        }
+    }
+
+    /// The XDG counterparts of the Windows folders, for native games
+    pub fn native(install_dir: &Path, account_id: Option<&str>) -> Result<Self> {
+        let dirs = directories::UserDirs::new()
+            .ok_or_else(|| Error::Other("Could not determine the home directory".to_string()))?;
+        let home = dirs.home_dir().to_path_buf();
+        let app_data = directories::BaseDirs::new()
+            .map(|base| base.data_dir().to_path_buf())
+            .unwrap_or_else(|| home.join(".local/share"));
+
+        Ok(Self {
+            install_dir: install_dir.to_path_buf(),
+            documents: dirs
+                .document_dir()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| home.join("Documents")),
+            saved_games: home.join("Saved Games"),
+            app_data,
+            home,
+            account_id: account_id.map(str::to_string),
+        })
+    }
+
+    /// Fill in the variables of `template`. They are matched without regard
+    /// to case, as Windows does.
+    pub fn resolve(&self, template: &str) -> Result<PathBuf> {
+        let mut resolved = String::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            resolved.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| invalid(template, "unclosed {"))?;
+            let name = rest[start + 1..end].to_ascii_lowercase();
+            let value = match name.as_str() {
+                "installdir" => self.install_dir.to_string_lossy().into_owned(),
+                "appdata" => self.app_data.to_string_lossy().into_owned(),
+                "userdir" => self.documents.to_string_lossy().into_owned(),
+                "userprofile" => self.home.to_string_lossy().into_owned(),
+                "usersavedgames" => self.saved_games.to_string_lossy().into_owned(),
+                "epicid" => self
+                    .account_id
+                    .clone()
+                    .ok_or_else(|| invalid(template, "it needs the account id; sign in first"))?,
+                _ => return Err(invalid(template, &format!("unknown variable {{{}}}", name))),
+            };
+            resolved.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        resolved.push_str(rest);
+
+        let path = PathBuf::from(resolved.replace('\\', "/"));
+        if !path.is_absolute() {
+            return Err(invalid(template, "it doesn't start at a known folder"));
+        }
+        Ok(path)
+    }
+
+    /// `dir` with its links resolved, if it is a single game's save folder
+    /// that can be deleted as a whole: strictly below one of the known
+    /// folders and holding none of them. Directly in the profile folder
+    /// sit folders every program shares, like `.config`, so a game's own
+    /// folder is at least one level further down.
+    pub fn game_folder(&self, dir: &Path) -> Result<PathBuf> {
+        let refuse = |reason: &str| Error::Other(format!("Not deleting {:?}: {}", dir, reason));
+        if !dir.is_absolute()
+            || dir
+                .components()
+                .any(|c| matches!(c, Component::ParentDir | Component::CurDir))
+        {
+            return Err(refuse("it isn't a plain absolute path"));
+        }
+
+        let resolve = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let resolved = resolve(dir);
+        let home = resolve(&self.home);
+        let folders: Vec<PathBuf> = [
+            &self.install_dir,
+            &self.app_data,
+            &self.documents,
+            &self.saved_games,
+        ]
+        .into_iter()
+        .map(|folder| resolve(folder))
+        .collect();
+
+        if folders
+            .iter()
+            .chain([&home])
+            .any(|folder| folder.starts_with(&resolved))
+        {
+            return Err(refuse("it holds a folder other programs use too"));
+        }
+        let in_folder = folders.iter().any(|folder| resolved.starts_with(folder));
+        let in_profile = resolved
+            .strip_prefix(&home)
+            .is_ok_and(|rest| rest.components().count() >= 2);
+        if !in_folder && !in_profile {
+            return Err(refuse("it isn't inside a game's own folder"));
+        }
+        Ok(resolved)
+    }
+}
+
+fn invalid(template: &str, reason: &str) -> Error {
+    Error::Other(format!(
+        "Can't resolve save path '{}': {}",
+        template, reason
+    ))
+}
+
+/// Which save files cloud sync touches, as glob patterns over their paths
+/// relative to the save folder. A pattern without `/`, like `*.log`, matches
+/// file names in any folder; one ending in `/`, like `Crashes/`, matches
+/// everything in that folder. `*` and `?` stay within one folder and `**`
+/// crosses them. Like Windows paths, matching ignores ASCII case.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFilter {
+    /// Only files matching one of these are synced; all when empty
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Files matching one of these are never synced
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl SaveFilter {
+    /// Whether the save at `filename`, relative with `/` separators, syncs
+    pub fn allows(&self, filename: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| path_matches(p, filename)))
+            && !self.exclude.iter().any(|p| path_matches(p, filename))
+    }
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let components: Vec<&str> = path.split('/').collect();
+
+    if let Some(dir) = pattern.strip_suffix('/') {
+        // Any folder the file is in
+        return (1..components.len()).any(|depth| {
+            if dir.contains('/') {
+                glob_str(dir, &components[..depth].join("/"))
+            } else {
+                glob_str(dir, components[depth - 1])
+            }
+        });
+    }
+
+    if pattern.contains('/') {
+        glob_str(pattern, path)
+    } else {
+        glob_str(pattern, components[components.len() - 1])
+    }
+}
+
+fn glob_str(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob(&pattern, &text)
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also stands for no folder at all
+            (0..=text.len()).any(|skip| glob(rest, &text[skip..]))
+                || rest.first() == Some(&'/') && glob(&rest[1..], text)
+        }
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|&skip| skip == 0 || text[skip - 1] != '/')
+            .any(|skip| glob(rest, &text[skip..])),
+        ['?', rest @ ..] => text.first().is_some_and(|&c| c != '/') && glob(rest, &text[1..]),
+        [c, rest @ ..] => {
+            text.first().is_some_and(|t| t.eq_ignore_ascii_case(c)) && glob(rest, &text[1..])
+        }
+    }
+}
+
+/// How a local save and its cloud version relate, judged by their contents
+/// and what they were at the last sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveComparison {
+    Same,
+    /// Only the cloud version changed since the last sync
+    CloudChanged,
+    /// Only the local file changed since the last sync
+    LocalChanged,
+    /// Both changed, or they were never synced: neither can be dropped safely
+    Conflict,
+}
+
+/// Compare the SHA-1 of a local save and its cloud version with the one
+/// recorded when the file was last synced
+pub fn compare(local: &str, cloud: &str, last_synced: Option<&str>) -> SaveComparison {
+    match last_synced {
+        _ if local == cloud => SaveComparison::Same,
+        Some(synced) if synced == local => SaveComparison::CloudChanged,
+        Some(synced) if synced == cloud => SaveComparison::LocalChanged,
+        _ => SaveComparison::Conflict,
+    }
+}
+
+pub fn sha1_hex(data: &[u8]) -> String {
+    Sha1::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// What a sync would do with one save file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    Download,
+    Upload,
+    /// Both sides match, or the file is unchanged since the last sync
+    Skip,
+    /// Both sides changed; `save_conflict_policy` decides
+    Conflict,
+}
+
+impl std::fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SyncAction::Download => "download",
+            SyncAction::Upload => "upload",
+            SyncAction::Skip => "skip",
+            SyncAction::Conflict => "conflict",
+        })
+    }
+}
+
+/// One file of a dry-run sync, with both sides as they are now
+#[derive(Debug, Clone)]
+pub struct PlannedSync {
+    pub filename: String,
+    pub action: SyncAction,
+    pub local: Option<LocalSave>,
+    pub cloud: Option<CloudSave>,
+}
+
+/// What a download, followed by an upload when `upload` is set, would do.
+/// `cloud` holds the cloud saves with their SHA-1, empty unless
+/// downloading, and `local` the local saves with theirs.
+pub fn plan_sync(
+    cloud: &[(CloudSave, String)],
+    local: &[(LocalSave, String)],
+    sync: &SyncState,
+    upload: bool,
+) -> Vec<PlannedSync> {
+    let mut plan = Vec::new();
+
+    for (save, cloud_hash) in cloud {
+        let local_save = local
+            .iter()
+            .find(|(local, _)| local.filename == save.filename);
+        let action = match local_save {
+            None => SyncAction::Download,
+            Some((_, local_hash)) => {
+                match compare(local_hash, cloud_hash, sync.synced_hash(&save.filename)) {
+                    SaveComparison::Same => SyncAction::Skip,
+                    SaveComparison::CloudChanged => SyncAction::Download,
+                    // The download keeps it, and the upload sends it
+                    SaveComparison::LocalChanged if upload => SyncAction::Upload,
+                    SaveComparison::LocalChanged => SyncAction::Skip,
+                    SaveComparison::Conflict => SyncAction::Conflict,
+                }
+            }
+        };
+        plan.push(PlannedSync {
+            filename: save.filename.clone(),
+            action,
+            local: local_save.map(|(local, _)| local.clone()),
+            cloud: Some(save.clone()),
+        });
+    }
+
+    if upload {
+        for (save, hash) in local {
+            if plan.iter().any(|planned| planned.filename == save.filename) {
+                continue;
+            }
+            let action = if sync.synced_hash(&save.filename) == Some(hash.as_str()) {
+                SyncAction::Skip
+            } else {
+                SyncAction::Upload
+            };
+            plan.push(PlannedSync {
+                filename: save.filename.clone(),
+                action,
+                local: Some(save.clone()),
+                cloud: None,
+            });
+        }
+    }
+
+    plan.sort_by(|a, b| a.filename.cmp(&b.filename));
+    plan
+}
+
+/// Each save file as it was when last uploaded or downloaded, stored in
+/// `save-sync/<app_name>.json` in the data directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    #[serde(default)]
+    files: BTreeMap<String, SyncedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncedFile {
+    sha1: String,
+    synced_at: DateTime<Utc>,
+}
+
+impl SyncState {
+    pub fn load(app_name: &str) -> Result<Self> {
+        let path = Self::path(app_name)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, app_name: &str) -> Result<()> {
+        storage::write_private(&Self::path(app_name)?, serde_json::to_string_pretty(self)?)
+    }
+
+    /// SHA-1 of `filename` at its last sync
+    pub fn synced_hash(&self, filename: &str) -> Option<&str> {
+        self.files.get(filename).map(|file| file.sha1.as_str())
+    }
+
+    /// Note that `filename` was just synced with contents hashing to `sha1`
+    pub fn record(&mut self, filename: &str, sha1: String) {
+        self.files.insert(
+            filename.to_string(),
+            SyncedFile {
+                sha1,
+                synced_at: Utc::now(),
+            },
+        );
+    }
+
+    fn path(app_name: &str) -> Result<PathBuf> {
+        Ok(Config::data_dir()?
+            .join("save-sync")
+            .join(format!("{}.json", app_name)))
+    }
+}
+
+/// Earlier contents of save files that a sync or restore replaced. Each
+/// content is stored once, named by its hash, in `objects/` under
+/// `save-history/<app_name>/` in the data directory; `index.json` next to
+/// it lists which file held it and when.
+#[derive(Debug, Clone)]
+pub struct SaveHistory {
+    dir: PathBuf,
+    versions: Vec<SaveVersion>,
+}
+
+/// One kept version of a save file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveVersion {
+    /// Path relative to the save directory, with `/` separators
+    pub filename: String,
+    pub sha1: String,
+    pub size: u64,
+    /// When the version was replaced
+    pub saved_at: DateTime<Utc>,
+}
+
+impl SaveVersion {
+    /// Short id `saves restore` takes
+    pub fn id(&self) -> &str {
+        &self.sha1[..VERSION_ID_LEN]
+    }
+}
+
+const VERSION_ID_LEN: usize = 10;
+
+impl SaveHistory {
+    pub fn load(app_name: &str) -> Result<Self> {
+        Self::open(Config::data_dir()?.join("save-history").join(app_name))
+    }
+
+    fn open(dir: PathBuf) -> Result<Self> {
+        let index = dir.join("index.json");
+        let versions = if index.exists() {
+            serde_json::from_str(&fs::read_to_string(index)?)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { dir, versions })
+    }
+
+    /// Kept versions, oldest first
+    pub fn versions(&self) -> &[SaveVersion] {
+        &self.versions
+    }
+
+    /// Keep `data` as a version of `filename`, dropping the oldest versions
+    /// of it beyond `limit`. A `limit` of 0 keeps nothing.
+    pub fn keep(&mut self, filename: &str, data: &[u8], limit: usize) -> Result<()> {
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let sha1 = sha1_hex(data);
+        let latest = self
+            .versions
+            .iter()
+            .rev()
+            .find(|version| version.filename == filename);
+        if latest.is_none_or(|version| version.sha1 != sha1) {
+            let object = self.object_path(&sha1);
+            if !object.exists() {
+                storage::write_private(&object, data)?;
+            }
+            self.versions.push(SaveVersion {
+                filename: filename.to_string(),
+                sha1,
+                size: data.len() as u64,
+                saved_at: Utc::now(),
+            });
+        }
+
+        let count = self
+            .versions
+            .iter()
+            .filter(|version| version.filename == filename)
+            .count();
+        let mut excess = count.saturating_sub(limit);
+        self.versions.retain(|version| {
+            let drop = excess > 0 && version.filename == filename;
+            if drop {
+                excess -= 1;
+            }
+            !drop
+        });
+
+        self.remove_unused_objects()?;
+        storage::write_private(
+            &self.dir.join("index.json"),
+            serde_json::to_string_pretty(&self.versions)?,
+        )
+    }
+
+    /// The version whose id starts with `id`
+    pub fn find(&self, id: &str) -> Result<&SaveVersion> {
+        let id = id.to_lowercase();
+        let matches: Vec<&SaveVersion> = self
+            .versions
+            .iter()
+            .filter(|version| !id.is_empty() && version.sha1.starts_with(&id))
+            .collect();
+
+        match matches.last() {
+            None => Err(Error::Other(format!("No save version {}", id))),
+            // The same content kept twice for one file is still one version
+            Some(last)
+                if matches.iter().all(|version| {
+                    version.filename == last.filename && version.sha1 == last.sha1
+                }) =>
+            {
+                Ok(last)
+            }
+            Some(_) => Err(Error::Other(format!(
+                "Save version {} is ambiguous; give more of its id",
+                id
+            ))),
+        }
+    }
+
+    /// Contents of `version`
+    pub fn read(&self, version: &SaveVersion) -> Result<Vec<u8>> {
+        Ok(fs::read(self.object_path(&version.sha1))?)
+    }
+
+    fn object_path(&self, sha1: &str) -> PathBuf {
+        self.dir.join("objects").join(sha1)
+    }
+
+    fn remove_unused_objects(&self) -> Result<()> {
+        let Ok(entries) = fs::read_dir(self.dir.join("objects")) else {
+            return Ok(());
+        };
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !self.versions.iter().any(|version| version.sha1 == name) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Save file under a game's save directory
+#[derive(Debug, Clone)]
+pub struct LocalSave {
+    /// Path relative to the save directory, with `/` separators
+    pub filename: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Every file under `dir`, sorted by name
+pub fn local_saves(dir: &Path) -> Result<Vec<LocalSave>> {
+    let mut saves = Vec::new();
+    if dir.is_dir() {
+        collect(dir, dir, &mut saves)?;
+    }
+    saves.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(saves)
+}
+
+fn collect(root: &Path, dir: &Path, saves: &mut Vec<LocalSave>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let path = entry.path();
+
+        if metadata.is_dir() {
+            collect(root, &path, saves)?;
+        } else if metadata.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            saves.push(LocalSave {
+                filename: relative.to_string_lossy().replace('\\', "/"),
+                path,
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Where a cloud save named `filename` goes under `dir`. Names that would
+/// leave the directory are refused.
+pub fn save_file_path(dir: &Path, filename: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(filename.replace('\\', "/"));
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(Error::Other(format!(
+            "Refusing save file with unsafe name '{}'",
+            filename
+        )));
+    }
+    Ok(dir.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_in_proton_prefix() {
+        let prefix = TempDir::new().unwrap();
+        fs::create_dir_all(prefix.path().join("pfx/drive_c/users/steamuser")).unwrap();
+
+        let dirs = SaveDirs::in_prefix(prefix.path(), Path::new("/games/Game"), Some("abc123"));
+        assert_eq!(
+            dirs.resolve("{AppData}/Game/Saved/{EpicID}").unwrap(),
+            prefix
+                .path()
+                .join("pfx/drive_c/users/steamuser/AppData/Local/Game/Saved/abc123")
+        );
+        assert_eq!(
+            dirs.resolve("{installdir}\\Saves").unwrap(),
+            Path::new("/games/Game/Saves")
+        );
+        assert!(dirs.resolve("{Registry}/Game").is_err());
+        assert!(dirs.resolve("Game/Saved").is_err());
+    }
+
+    #[test]
+    fn test_only_a_games_own_folder_is_deletable() {
+        let home = TempDir::new().unwrap();
+        let home = home.path();
+        let dirs = SaveDirs {
+            install_dir: home.join("Games/Game"),
+            app_data: home.join(".local/share"),
+            home: home.to_path_buf(),
+            documents: home.join("Documents"),
+            saved_games: home.join("Saved Games"),
+            account_id: None,
+        };
+        fs::create_dir_all(home.join("Documents/Game")).unwrap();
+
+        assert!(dirs.game_folder(&home.join("Documents/Game")).is_ok());
+        assert!(dirs
+            .game_folder(&home.join(".local/share/Game/Saved"))
+            .is_ok());
+        assert!(dirs.game_folder(&home.join(".config/Game")).is_ok());
+
+        for dir in [
+            home.to_path_buf(),
+            home.join("Documents"),
+            home.join(".local"),
+            home.join(".config"),
+            home.join("Documents/Game/../.."),
+            PathBuf::from("/tmp/Game"),
+        ] {
+            assert!(dirs.game_folder(&dir).is_err(), "{:?}", dir);
+        }
+
+        // A link that leads back out is judged by where it leads
+        std::os::unix::fs::symlink(home, home.join("Documents/Game/Link")).unwrap();
+        assert!(dirs.game_folder(&home.join("Documents/Game/Link")).is_err());
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let local = "2024-05-01T12:00:00Z"
+            .parse::<DateTime<Utc>>()
+            .unwrap()
+            .into();
+        let never_asked = || panic!("only `ask` asks");
+
+        let newest = SaveConflictPolicy::PreferNewest;
+        assert!(newest.cloud_wins(local, "2024-05-02T08:00:00.000Z", never_asked));
+        assert!(!newest.cloud_wins(local, "2024-04-30T08:00:00Z", never_asked));
+        assert!(!newest.cloud_wins(local, "yesterday", never_asked));
+        assert!(SaveConflictPolicy::PreferCloud.cloud_wins(local, "", never_asked));
+        assert!(!SaveConflictPolicy::PreferLocal.cloud_wins(local, "", never_asked));
+        assert!(SaveConflictPolicy::Ask.cloud_wins(local, "", || true));
+    }
+
+    #[test]
+    fn test_compare_flags_only_genuine_conflicts() {
+        assert_eq!(compare("a", "a", None), SaveComparison::Same);
+        assert_eq!(compare("a", "b", Some("a")), SaveComparison::CloudChanged);
+        assert_eq!(compare("b", "a", Some("a")), SaveComparison::LocalChanged);
+        assert_eq!(compare("b", "c", Some("a")), SaveComparison::Conflict);
+        assert_eq!(compare("a", "b", None), SaveComparison::Conflict);
+    }
+
+    #[test]
+    fn test_save_filter() {
+        let filter = SaveFilter {
+            include: Vec::new(),
+            exclude: vec![
+                "*.log".to_string(),
+                "crashes/".to_string(),
+                "Config/**/*.bak".to_string(),
+            ],
+        };
+        assert!(filter.allows("Slot1.sav"));
+        assert!(!filter.allows("Logs/Game.LOG"));
+        assert!(!filter.allows("Crashes/2026-01-01/dump.dmp"));
+        assert!(filter.allows("NotCrashes/dump.dmp"));
+        assert!(!filter.allows("Config/Settings.bak"));
+        assert!(!filter.allows("Config/Windows/Settings.bak"));
+        assert!(filter.allows("Other/Settings.bak"));
+
+        let filter = SaveFilter {
+            include: vec!["SaveGames/Slot?.sav".to_string()],
+            exclude: Vec::new(),
+        };
+        assert!(filter.allows("SaveGames/Slot1.sav"));
+        assert!(!filter.allows("SaveGames/Slot10.sav"));
+        assert!(!filter.allows("Slot1.sav"));
+    }
+
+    #[test]
+    fn test_plan_sync() {
+        let local = |filename: &str, data: &[u8]| {
+            let save = LocalSave {
+                filename: filename.to_string(),
+                path: PathBuf::from(filename),
+                size: data.len() as u64,
+                modified: SystemTime::UNIX_EPOCH,
+            };
+            (save, sha1_hex(data))
+        };
+        let cloud = |filename: &str, data: &[u8]| {
+            let save = CloudSave {
+                id: filename.to_string(),
+                app_name: "Game".to_string(),
+                filename: filename.to_string(),
+                size: data.len() as u64,
+                uploaded_at: "2026-01-01T00:00:00Z".to_string(),
+            };
+            (save, sha1_hex(data))
+        };
+
+        let mut sync = SyncState::default();
+        sync.record("edited.sav", sha1_hex(b"old"));
+        sync.record("synced.sav", sha1_hex(b"same"));
+        sync.record("updated.sav", sha1_hex(b"old"));
+        let cloud = [
+            cloud("edited.sav", b"old"),
+            cloud("new-in-cloud.sav", b"x"),
+            cloud("updated.sav", b"new"),
+            cloud("both.sav", b"cloud"),
+        ];
+        let local = [
+            local("edited.sav", b"new"),
+            local("synced.sav", b"same"),
+            local("updated.sav", b"old"),
+            local("both.sav", b"local"),
+            local("new-here.sav", b"y"),
+        ];
+
+        let actions = |plan: Vec<PlannedSync>| {
+            plan.into_iter()
+                .map(|planned| (planned.filename, planned.action))
+                .collect::<Vec<_>>()
+        };
+        let owned = |entries: &[(&str, SyncAction)]| {
+            entries
+                .iter()
+                .map(|(name, action)| (name.to_string(), *action))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            actions(plan_sync(&cloud, &local, &sync, false)),
+            owned(&[
+                ("both.sav", SyncAction::Conflict),
+                ("edited.sav", SyncAction::Skip),
+                ("new-in-cloud.sav", SyncAction::Download),
+                ("updated.sav", SyncAction::Download),
+            ])
+        );
+        assert_eq!(
+            actions(plan_sync(&[], &local, &sync, true)),
+            owned(&[
+                ("both.sav", SyncAction::Upload),
+                ("edited.sav", SyncAction::Upload),
+                ("new-here.sav", SyncAction::Upload),
+                ("synced.sav", SyncAction::Skip),
+                ("updated.sav", SyncAction::Skip),
+            ])
+        );
+        let both = actions(plan_sync(&cloud, &local, &sync, true));
+        assert!(both.contains(&("edited.sav".to_string(), SyncAction::Upload)));
+        assert!(both.contains(&("synced.sav".to_string(), SyncAction::Skip)));
+    }
+
+    #[test]
+    fn test_history_keeps_the_latest_versions() {
+        let dir = TempDir::new().unwrap();
+        let mut history = SaveHistory::open(dir.path().to_path_buf()).unwrap();
+        history.keep("slot1.sav", b"one", 2).unwrap();
+        history.keep("slot1.sav", b"one", 2).unwrap();
+        history.keep("slot1.sav", b"two", 2).unwrap();
+        history.keep("slot1.sav", b"three", 2).unwrap();
+        history.keep("slot2.sav", b"two", 2).unwrap();
+
+        let history = SaveHistory::open(dir.path().to_path_buf()).unwrap();
+        let kept: Vec<(&str, u64)> = history
+            .versions()
+            .iter()
+            .map(|version| (version.filename.as_str(), version.size))
+            .collect();
+        assert_eq!(kept, [("slot1.sav", 3), ("slot1.sav", 5), ("slot2.sav", 3)]);
+        // "one" was dropped, "two" is shared by both files
+        assert_eq!(fs::read_dir(dir.path().join("objects")).unwrap().count(), 2);
+
+        let three = history.find(&sha1_hex(b"three")[..6]).unwrap();
+        assert_eq!(history.read(three).unwrap(), b"three");
+        assert!(history.find(&sha1_hex(b"two")).is_err());
+        assert!(history.find(&sha1_hex(b"one")).is_err());
+    }
+
+    #[test]
+    fn test_local_saves_are_found_in_subdirectories() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Profiles/1")).unwrap();
+        fs::write(dir.path().join("Profiles/1/slot1.sav"), "save").unwrap();
+        fs::write(dir.path().join("settings.ini"), "ini").unwrap();
+
+        let names: Vec<String> = local_saves(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|save| save.filename)
+            .collect();
+        assert_eq!(names, ["Profiles/1/slot1.sav", "settings.ini"]);
+
+        assert!(save_file_path(dir.path(), "Profiles/1/slot1.sav").is_ok());
+        assert!(save_file_path(dir.path(), "../../.bashrc").is_err());
+        assert!(save_file_path(dir.path(), "/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Times of day when downloads may run, e.g. overnight when bandwidth is
+//! free. Queued installs and updates wait for the next window to open.
+
+use chrono::{NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::Error;
+
+const DAY: u32 = 24 * 60 * 60;
+
+/// Daily time range written as `"01:00-07:00"`. A window whose end is
+/// before its start runs past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DownloadWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl DownloadWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl TryFrom<String> for DownloadWindow {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Error> {
+        let invalid = || {
+            Error::Config(format!(
+                "Invalid download window: '{}'. Expected HH:MM-HH:MM, e.g. 01:00-07:00",
+                value
+            ))
+        };
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M");
+
+        Ok(Self {
+            start: parse(start).map_err(|_| invalid())?,
+            end: parse(end).map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl From<DownloadWindow> for String {
+    fn from(window: DownloadWindow) -> Self {
+        window.to_string()
+    }
+}
+
+impl std::fmt::Display for DownloadWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// How long until downloads may start at `now`; `None` when they may start
+/// right away, including when no windows are configured
+pub fn time_until_open(windows: &[DownloadWindow], now: NaiveTime) -> Option<Duration> {
+    if windows.is_empty() || windows.iter().any(|window| window.contains(now)) {
+        return None;
+    }
+
+    let now = now.num_seconds_from_midnight();
+    windows
+        .iter()
+        .map(|window| (window.start.num_seconds_from_midnight() + DAY - now) % DAY)
+        .min()
+        .map(|seconds| Duration::from_secs(u64::from(seconds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(text: &str) -> DownloadWindow {
+        DownloadWindow::try_from(text.to_string()).unwrap()
+    }
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_windows_across_midnight() {
+        let night = window("23:00-07:00");
+
+        assert!(night.contains(at(23, 30)));
+        assert!(night.contains(at(3, 0)));
+        assert!(!night.contains(at(7, 0)));
+        assert_eq!(night.to_string(), "23:00-07:00");
+        assert!(DownloadWindow::try_from("tonight".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_time_until_next_window() {
+        let windows = [window("01:00-07:00"), window("12:00-13:00")];
+
+        assert_eq!(time_until_open(&windows, at(3, 0)), None);
+        assert_eq!(
+            time_until_open(&windows, at(11, 30)),
+            Some(Duration::from_secs(30 * 60))
+        );
+        assert_eq!(
+            time_until_open(&windows, at(22, 0)),
+            Some(Duration::from_secs(3 * 3600))
+        );
+        assert_eq!(time_until_open(&[], at(22, 0)), None);
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::Game;
+use crate::config::{storage, Config};
+use crate::Result;
+
+/// Last library fetched from Epic, so searching works without a round trip
+pub fn load_cached_library() -> Result<Option<Vec<Game>>> {
+    let path = library_cache_path()?;
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&fs::read_to_string(&path)?)?))
+}
+
+pub fn save_cached_library(games: &[Game]) -> Result<()> {
+    storage::write_private(&library_cache_path()?, serde_json::to_string(games)?)
+}
+
+fn library_cache_path() -> Result<PathBuf> {
+    Ok(Config::cache_dir()?.join("library.json"))
+}
+
+/// Games matching `query` in their title or app name, best match first
+pub fn search_games<'a>(games: &'a [Game], query: &str) -> Vec<&'a Game> {
+    let mut matches: Vec<(u32, &Game)> = games
+        .iter()
+        .filter_map(|game| {
+            let score =
+                fuzzy_score(&game.app_title, query).max(fuzzy_score(&game.app_name, query))?;
+            Some((score, game))
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| a.1.app_title.cmp(&b.1.app_title))
+    });
+    matches.into_iter().map(|(_, game)| game).collect()
+}
+
+/// Score how well `query` matches `text`, ignoring case. Substrings score
+/// highest (more so at the start); otherwise every query character must
+/// appear in order, and tighter matches score higher.
+fn fuzzy_score(text: &str, query: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    let query = query.to_lowercase();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    if let Some(pos) = text.find(&query) {
+        return Some(if pos == 0 { 1000 } else { 500 });
+    }
+
+    let mut gaps = 0u32;
+    let mut chars = text.chars();
+
+    for wanted in query.chars() {
+        loop {
+            let c = chars.next()?;
+            if c == wanted {
+                break;
+            }
+            gaps += 1;
+        }
+    }
+
+    Some(100u32.saturating_sub(gaps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(app_name: &str, app_title: &str) -> Game {
+        Game {
+            app_name: app_name.to_string(),
+            app_title: app_title.to_string(),
+            app_version: "1.0".to_string(),
+            install_path: None,
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("Rocket League", "rocket") > fuzzy_score("Rocket League", "league"));
+        assert!(fuzzy_score("Rocket League", "rktlg").is_some());
+        assert_eq!(fuzzy_score("Rocket League", "xyz"), None);
+    }
+
+    #[test]
+    fn test_search_orders_by_score() {
+        let games = vec![
+            game("Sugar", "Rocket League"),
+            game("Fortnite", "Fortnite"),
+            game("Hades", "Hades"),
+        ];
+
+        let results: Vec<_> = search_games(&games, "sugar")
+            .into_iter()
+            .map(|g| g.app_name.as_str())
+            .collect();
+        assert_eq!(results, vec!["Sugar"]);
+
+        let results = search_games(&games, "fn");
+        assert_eq!(results[0].app_name, "Fortnite");
+    }
+}
@@ -0,0 +1,320 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+use super::journal::process_is_running;
+use crate::config::{storage, Config};
+use crate::format;
+use crate::Result;
+
+/// How long before the daily limit the player is warned
+const LIMIT_WARNING: Duration = Duration::from_secs(5 * 60);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Seconds played per game for each local calendar day
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PlaytimeLog {
+    days: BTreeMap<NaiveDate, BTreeMap<String, u64>>,
+}
+
+impl PlaytimeLog {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        storage::write_private(&Self::path()?, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Total time played across all games today
+    pub fn played_today(&self) -> Duration {
+        let seconds = self
+            .days
+            .get(&Local::now().date_naive())
+            .map(|games| games.values().sum())
+            .unwrap_or(0);
+
+        Duration::from_secs(seconds)
+    }
+
+    pub fn record(&mut self, app_name: &str, played: Duration) {
+        *self
+            .days
+            .entry(Local::now().date_naive())
+            .or_default()
+            .entry(app_name.to_string())
+            .or_default() += played.as_secs();
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("playtime.json"))
+    }
+}
+
+/// Limits applied to one play session
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionLimits {
+    /// Show a reminder once the session has lasted this long
+    pub reminder_after: Option<Duration>,
+    /// Close the game once the session has lasted this long
+    pub time_left: Option<Duration>,
+}
+
+impl SessionLimits {
+    pub fn is_empty(&self) -> bool {
+        self.reminder_after.is_none() && self.time_left.is_none()
+    }
+}
+
+/// Watch a running game until it exits, showing reminders and closing it
+/// when the daily limit runs out. The session is added to the playtime log.
+pub fn supervise(
+    mut child: Child,
+    app_name: &str,
+    title: &str,
+    limits: SessionLimits,
+) -> Result<Duration> {
+    let started = Instant::now();
+    let mut reminded = false;
+    let mut warned = false;
+
+    while still_running(&mut child)? {
+        let elapsed = started.elapsed();
+
+        if let Some(after) = limits.reminder_after {
+            if !reminded && elapsed >= after {
+                notify(
+                    title,
+                    &format!("You have been playing for {}", format::duration(after)),
+                );
+                reminded = true;
+            }
+        }
+
+        if let Some(left) = limits.time_left {
+            if elapsed >= left {
+                notify(title, "Daily play time is up. Closing the game.");
+                log::info!("Daily limit reached, terminating {}", app_name);
+                kill_group(&child);
+                if child.try_wait()?.is_none() {
+                    child.kill()?;
+                    child.wait()?;
+                }
+                break;
+            }
+
+            if !warned && elapsed + LIMIT_WARNING >= left {
+                let minutes = (left - elapsed).as_secs().div_ceil(60);
+                notify(
+                    title,
+                    &format!("{} minute(s) of play time left today", minutes),
+                );
+                warned = true;
+            }
+        }
+
+        if let Err(e) = super::logs::cap_session(app_name) {
+            log::debug!("Failed to trim the log of {}: {}", app_name, e);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    let played = started.elapsed();
+
+    let mut log = PlaytimeLog::load()?;
+    log.record(app_name, played);
+    log.save()?;
+
+    Ok(played)
+}
+
+/// Games being played, recorded on disk so that other launcher processes,
+/// such as the daemon's save sync, leave their files alone meanwhile
+pub struct ActiveSessions {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ActiveSession {
+    /// Process watching the session
+    supervisor: u32,
+    /// Process group the game was started in
+    game_group: u32,
+}
+
+/// Removes its session record when the session ends
+#[must_use]
+pub struct SessionGuard {
+    path: PathBuf,
+}
+
+impl ActiveSessions {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The records in the launcher's data directory
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(Config::data_dir()?.join("sessions")))
+    }
+
+    /// Record that `app_name` runs in the process group of `game_pid`
+    pub fn begin(&self, app_name: &str, game_pid: u32) -> Result<SessionGuard> {
+        let session = ActiveSession {
+            supervisor: std::process::id(),
+            game_group: game_pid,
+        };
+        let path = self.dir.join(format!("{}.json", app_name));
+        storage::write_private(&path, serde_json::to_vec(&session)?)?;
+        Ok(SessionGuard { path })
+    }
+
+    /// Whether `app_name` is being played. A record left behind by a
+    /// launcher that crashed still counts while the game runs on.
+    pub fn is_active(&self, app_name: &str) -> bool {
+        let path = self.dir.join(format!("{}.json", app_name));
+        let Some(session) = fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice::<ActiveSession>(&data).ok())
+        else {
+            return false;
+        };
+        process_is_running(session.supervisor) || group_alive(session.game_group)
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if let Err(e) = remove_record(&self.path) {
+            log::warn!("Failed to remove session record {:?}: {}", self.path, e);
+        }
+    }
+}
+
+fn remove_record(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the game, or anything else in its process group, still runs.
+/// Launchers and Wine often exit while the game they started keeps going.
+fn still_running(child: &mut Child) -> Result<bool> {
+    Ok(child.try_wait()?.is_none() || group_alive(child.id()))
+}
+
+#[cfg(unix)]
+fn group_alive(pid: u32) -> bool {
+    unsafe { libc::kill(-(pid as libc::pid_t), 0) == 0 }
+}
+
+#[cfg(not(unix))]
+fn group_alive(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn kill_group(child: &Child) {
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(_child: &Child) {}
+
+/// Show a desktop notification, and print it for terminal users
+pub fn notify(title: &str, message: &str) {
+    println!("[{}] {}", title, message);
+
+    if let Err(e) = Command::new("notify-send").arg(title).arg(message).status() {
+        log::debug!("Desktop notification failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playtime_log_sums_today() {
+        let mut log = PlaytimeLog::default();
+        log.record("Fortnite", Duration::from_secs(600));
+        log.record("Hades", Duration::from_secs(300));
+        log.record("Fortnite", Duration::from_secs(60));
+
+        assert_eq!(log.played_today(), Duration::from_secs(960));
+    }
+
+    #[test]
+    fn test_empty_limits() {
+        assert!(SessionLimits::default().is_empty());
+        assert!(!SessionLimits {
+            reminder_after: Some(Duration::from_secs(60)),
+            time_left: None,
+        }
+        .is_empty());
+    }
+
+    #[test]
+    fn test_active_sessions_end_with_their_guard() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sessions = ActiveSessions::new(dir.path().to_path_buf());
+        assert!(!sessions.is_active("Hades"));
+
+        let guard = sessions.begin("Hades", i32::MAX as u32).unwrap();
+        // Watched by this process
+        assert!(sessions.is_active("Hades"));
+        assert!(!sessions.is_active("Fortnite"));
+
+        drop(guard);
+        assert!(!sessions.is_active("Hades"));
+    }
+
+    #[test]
+    fn test_sessions_of_exited_processes_are_over() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sessions = ActiveSessions::new(dir.path().to_path_buf());
+        let stale = ActiveSession {
+            supervisor: i32::MAX as u32,
+            game_group: i32::MAX as u32 - 1,
+        };
+        fs::write(
+            dir.path().join("Hades.json"),
+            serde_json::to_vec(&stale).unwrap(),
+        )
+        .unwrap();
+
+        assert!(!sessions.is_active("Hades"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_session_lasts_while_the_process_group_runs() {
+        // A launcher that starts the game and exits right away
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 1 & exit 0"]);
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command.spawn().unwrap();
+        child.wait().unwrap();
+
+        assert!(still_running(&mut child).unwrap());
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while still_running(&mut child).unwrap() {
+            assert!(Instant::now() < deadline, "process group never exited");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
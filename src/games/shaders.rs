@@ -0,0 +1,119 @@
+//! Shader caches of DXVK and VKD3D-Proton, which translate Direct3D for
+//! Wine and Proton. Left alone they write next to the game's executable or
+//! into the prefix; each game instead gets a directory under
+//! `shader-cache/` in the data directory, where sizes can be reported and
+//! stale caches dropped (for example after a driver update).
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::Config;
+use crate::{Error, Result};
+
+/// Shader cache directory of `app_name`
+pub fn cache_dir(app_name: &str) -> Result<PathBuf> {
+    Ok(caches_root()?.join(check_name(app_name)?))
+}
+
+/// Variables that point DXVK and VKD3D at `dir`
+pub fn cache_env(dir: &Path) -> [(&'static str, PathBuf); 2] {
+    [
+        ("DXVK_STATE_CACHE_PATH", dir.to_path_buf()),
+        ("VKD3D_SHADER_CACHE_PATH", dir.to_path_buf()),
+    ]
+}
+
+/// Bytes the shader caches of `app_name` take up
+pub fn cache_size(app_name: &str) -> Result<u64> {
+    tree_size(&cache_dir(app_name)?)
+}
+
+/// Games that have shader caches
+pub fn cached_games() -> Result<Vec<String>> {
+    let root = caches_root()?;
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut games: Vec<String> = fs::read_dir(root)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    games.sort();
+    Ok(games)
+}
+
+/// Delete the shader caches of `app_name`, returning the bytes freed. The
+/// game rebuilds them as it runs, with some stutter at first.
+pub fn clear_cache(app_name: &str) -> Result<u64> {
+    let dir = cache_dir(app_name)?;
+    let freed = tree_size(&dir)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    Ok(freed)
+}
+
+/// App names become directories, and clearing a cache deletes one, so a
+/// name must be a single plain component
+fn check_name(app_name: &str) -> Result<&str> {
+    let mut components = Path::new(app_name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(app_name),
+        _ => Err(Error::Other(format!("Invalid app name: {}", app_name))),
+    }
+}
+
+fn caches_root() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("shader-cache"))
+}
+
+fn tree_size(path: &Path) -> Result<u64> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(0);
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        size += tree_size(&entry?.path())?;
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tree_size_counts_nested_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Game.dxvk-cache"), [0u8; 100]).unwrap();
+        fs::create_dir(dir.path().join("vkd3d")).unwrap();
+        fs::write(dir.path().join("vkd3d/vkd3d-proton.cache"), [0u8; 50]).unwrap();
+
+        assert_eq!(tree_size(dir.path()).unwrap(), 150);
+        assert_eq!(tree_size(&dir.path().join("missing")).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_app_names_stay_inside_the_cache() {
+        assert!(check_name("Fortnite").is_ok());
+        for name in [
+            "",
+            ".",
+            "..",
+            "../..",
+            "/",
+            "/home/user",
+            "Game/../..",
+            "a/b",
+        ] {
+            assert!(check_name(name).is_err(), "{:?}", name);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Desktop menu entries for installed games. Each one is a `.desktop` file in
+//! `~/.local/share/applications` that starts the game through
+//! `rauncher launch`, so playtime, logs and save sync work as from the CLI.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::KeyImage;
+use crate::config::Config;
+use crate::{Error, Result};
+
+/// Store artwork tried for the icon, most suitable first
+const ICON_IMAGE_TYPES: &[&str] = &["Thumbnail", "DieselGameBoxTall", "DieselGameBox"];
+
+/// Desktop entry of `app_name`
+pub fn shortcut_path(app_name: &str) -> Result<PathBuf> {
+    let dirs = directories::BaseDirs::new()
+        .ok_or_else(|| Error::Other("Could not determine the home directory".to_string()))?;
+    Ok(dirs
+        .data_dir()
+        .join("applications")
+        .join(format!("rauncher-{}.desktop", app_name)))
+}
+
+/// The artwork to use as the icon of a shortcut
+pub fn icon_image(images: &[KeyImage]) -> Option<&KeyImage> {
+    ICON_IMAGE_TYPES
+        .iter()
+        .find_map(|kind| images.iter().find(|image| image.image_type == *kind))
+}
+
+/// Write the desktop entry of `app_name`, with `icon` as the image data and
+/// its file extension
+pub fn create(app_name: &str, title: &str, icon: Option<(&[u8], &str)>) -> Result<PathBuf> {
+    let icon_path = match icon {
+        Some((data, extension)) => {
+            let dir = icons_dir()?;
+            fs::create_dir_all(&dir)?;
+            remove_icons(&dir, app_name)?;
+            let path = dir.join(format!("{}.{}", app_name, extension));
+            fs::write(&path, data)?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    let path = shortcut_path(app_name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let launcher = std::env::current_exe()?;
+    fs::write(
+        &path,
+        desktop_entry(app_name, title, &launcher, icon_path.as_deref()),
+    )?;
+
+    Ok(path)
+}
+
+/// Remove the desktop entry of `app_name` and its icon. Returns whether
+/// there was an entry.
+pub fn remove(app_name: &str) -> Result<bool> {
+    let icons = icons_dir()?;
+    if icons.is_dir() {
+        remove_icons(&icons, app_name)?;
+    }
+
+    let path = shortcut_path(app_name)?;
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path)?;
+    Ok(true)
+}
+
+fn icons_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("icons"))
+}
+
+/// Icons of `app_name` whatever their format, so a new one replaces the old
+fn remove_icons(dir: &Path, app_name: &str) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.file_stem().is_some_and(|stem| stem == app_name) {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn desktop_entry(app_name: &str, title: &str, launcher: &Path, icon: Option<&Path>) -> String {
+    let exec = [&*launcher.to_string_lossy(), "launch", app_name]
+        .iter()
+        .map(|arg| exec_arg(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={}\n\
+         Comment=Play {} with rauncher\n\
+         Exec={}\n\
+         Terminal=false\n\
+         Categories=Game;\n",
+        escape(title),
+        escape(title),
+        exec
+    );
+    if let Some(icon) = icon {
+        entry.push_str(&format!("Icon={}\n", escape(&icon.to_string_lossy())));
+    }
+    entry
+}
+
+/// Escape a string value of a desktop entry
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+/// One argument of an `Exec` key. Arguments with reserved characters are
+/// quoted; `%` would start a field code, so it is doubled.
+fn exec_arg(arg: &str) -> String {
+    const RESERVED: &[char] = &[
+        ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(',
+        ')', '`',
+    ];
+
+    let arg = arg.replace('%', "%%");
+    if !arg.contains(RESERVED) {
+        return arg;
+    }
+
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    escape(&quoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desktop_entry_launches_through_the_cli() {
+        let entry = desktop_entry(
+            "Fortnite",
+            "Fortnite",
+            Path::new("/home/player/My Apps/rauncher"),
+            Some(Path::new(
+                "/home/player/.local/share/rauncher/icons/Fortnite.png",
+            )),
+        );
+
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("\nName=Fortnite\n"));
+        assert!(entry.contains("\nExec=\"/home/player/My Apps/rauncher\" launch Fortnite\n"));
+        assert!(entry.contains("\nIcon=/home/player/.local/share/rauncher/icons/Fortnite.png\n"));
+    }
+
+    #[test]
+    fn test_exec_arg_quoting() {
+        assert_eq!(exec_arg("launch"), "launch");
+        assert_eq!(exec_arg("100%"), "100%%");
+        assert_eq!(exec_arg("a$b"), "\"a\\\\$b\"");
+        assert_eq!(exec_arg("C:\\Games"), "\"C:\\\\\\\\Games\"");
+    }
+
+    #[test]
+    fn test_icon_image_preference() {
+        let image = |kind: &str| KeyImage {
+            image_type: kind.to_string(),
+            url: format!("https://cdn.example/{}.jpg", kind),
+        };
+        let images = vec![image("DieselGameBox"), image("Thumbnail")];
+
+        assert_eq!(icon_image(&images).unwrap().image_type, "Thumbnail");
+        assert!(icon_image(&[image("DieselGameBoxLogo")]).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::Result;
+
+/// How long a game must keep running to pass the check
+pub const SMOKE_TEST_DURATION: Duration = Duration::from_secs(10);
+
+/// Lines of the captured output included in a failure report
+const LOG_TAIL_LINES: usize = 20;
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Outcome of starting a freshly installed game for a few seconds
+#[derive(Debug)]
+pub struct SmokeReport {
+    /// The game was still running when the check ended
+    pub survived: bool,
+    /// Exit status if the game quit on its own
+    pub exit_status: Option<ExitStatus>,
+    /// How long the game ran before it quit or was closed
+    pub runtime: Duration,
+    /// File holding everything the game printed
+    pub log_path: PathBuf,
+    /// Last lines of that output
+    pub log_tail: Vec<String>,
+}
+
+/// Start `command`, capturing its output in `log_path`, and check that it is
+/// still alive after `duration`. A surviving game is closed afterwards.
+pub fn run_smoke_test(
+    mut command: Command,
+    log_path: &Path,
+    duration: Duration,
+) -> Result<SmokeReport> {
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let log = File::create(log_path)?;
+    command
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+
+    // Own process group, so helpers the game starts are closed with it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let started = Instant::now();
+    let mut child = command.spawn()?;
+    let mut exit_status = None;
+
+    while started.elapsed() < duration {
+        if let Some(status) = child.try_wait()? {
+            exit_status = Some(status);
+            break;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    let runtime = started.elapsed();
+
+    if exit_status.is_none() {
+        stop(&mut child)?;
+    }
+
+    Ok(SmokeReport {
+        survived: exit_status.is_none(),
+        exit_status,
+        runtime,
+        log_path: log_path.to_path_buf(),
+        log_tail: tail(log_path, LOG_TAIL_LINES),
+    })
+}
+
+fn stop(child: &mut Child) -> Result<()> {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM);
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    child.kill()?;
+    child.wait()?;
+    Ok(())
+}
+
+fn tail(path: &Path, lines: usize) -> Vec<String> {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let all: Vec<&str> = contents.lines().collect();
+
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_instant_crash_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo missing vcrun2019 >&2; exit 3"]);
+
+        let report = run_smoke_test(
+            command,
+            &dir.path().join("smoke.log"),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        assert!(!report.survived);
+        assert_eq!(report.exit_status.and_then(|s| s.code()), Some(3));
+        assert_eq!(report.log_tail, vec!["missing vcrun2019".to_string()]);
+    }
+
+    #[test]
+    fn test_running_game_is_closed() {
+        let dir = TempDir::new().unwrap();
+        let mut command = Command::new("sleep");
+        command.arg("30");
+
+        let report = run_smoke_test(
+            command,
+            &dir.path().join("smoke.log"),
+            Duration::from_millis(500),
+        )
+        .unwrap();
+
+        assert!(report.survived);
+        assert!(report.runtime < Duration::from_secs(10));
+    }
+}
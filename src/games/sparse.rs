@@ -0,0 +1,100 @@
+//! Writes reconstructed files as sparse files. Padded pak files can hold
+//! long runs of zeros; skipping over them instead of writing them leaves
+//! holes the filesystem doesn't allocate, so the install takes less disk.
+//! Filesystems without sparse file support fill the holes with zeros.
+
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Runs of zeros are only skipped in whole blocks of this size, aligned to
+/// the file offset, since that is the smallest unit a filesystem can leave
+/// unallocated
+const BLOCK_SIZE: u64 = 4096;
+
+pub struct SparseWriter {
+    file: File,
+    /// Start of the current block, up to which everything has been written
+    /// or skipped
+    position: u64,
+    /// The current block, until it is complete
+    block: Vec<u8>,
+    skipped: u64,
+}
+
+impl SparseWriter {
+    pub fn new(file: File) -> Self {
+        Self {
+            file,
+            position: 0,
+            block: Vec::with_capacity(BLOCK_SIZE as usize),
+            skipped: 0,
+        }
+    }
+
+    pub fn write_all(&mut self, mut data: &[u8]) -> io::Result<()> {
+        while !data.is_empty() {
+            let wanted = BLOCK_SIZE as usize - self.block.len();
+            let (head, rest) = data.split_at(data.len().min(wanted));
+            self.block.extend_from_slice(head);
+            data = rest;
+
+            if self.block.len() == BLOCK_SIZE as usize {
+                self.flush_block()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write out any partial block, set the length so trailing holes count
+    /// toward it, and flush to disk. Returns the number of bytes left as holes.
+    pub fn finish(mut self) -> io::Result<u64> {
+        self.flush_block()?;
+        self.file.set_len(self.position)?;
+        self.file.sync_all()?;
+        Ok(self.skipped)
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        let len = self.block.len() as u64;
+        if self.block.iter().all(|&byte| byte == 0) {
+            self.file.seek(SeekFrom::Current(len as i64))?;
+            self.skipped += len;
+        } else {
+            self.file.write_all(&self.block)?;
+        }
+
+        self.position += len;
+        self.block.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_zero_runs_become_holes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("padded.pak");
+
+        let mut expected = b"header".to_vec();
+        expected.resize(1024 * 1024, 0);
+        expected.extend_from_slice(b"footer");
+        expected.resize(expected.len() + 64 * 1024, 0);
+
+        let mut writer = SparseWriter::new(File::create(&path).unwrap());
+        // Uneven pieces, like chunk parts, still line up with block boundaries
+        for piece in expected.chunks(3000) {
+            writer.write_all(piece).unwrap();
+        }
+        let skipped = writer.finish().unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), expected);
+        // Everything but the blocks holding the header and footer
+        assert_eq!(skipped, expected.len() as u64 - 2 * BLOCK_SIZE);
+    }
+}
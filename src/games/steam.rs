@@ -0,0 +1,372 @@
+//! Non-Steam game shortcuts, so installed games show up in Steam's library
+//! and get Steam Input and Big Picture. Each Steam account keeps them in
+//! `userdata/<id>/config/shortcuts.vdf`, a binary key-value file, and their
+//! artwork in the `grid` directory next to it. The shortcuts start games
+//! through `rauncher launch`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::runner::steam_roots;
+use crate::api::KeyImage;
+use crate::{Error, Result};
+
+const SHORTCUTS_FILE: &str = "shortcuts.vdf";
+
+/// Grid artwork by file name suffix, with the store image used for it
+pub const GRID_IMAGES: &[(&str, &str)] = &[
+    // Library capsule
+    ("p", "DieselGameBoxTall"),
+    // Wide capsule shown in recent games
+    ("", "DieselGameBox"),
+    // Banner at the top of the game's page
+    ("_hero", "DieselGameBox"),
+    ("_logo", "DieselGameBoxLogo"),
+];
+
+// Binary VDF value types
+const MAP: u8 = 0x00;
+const STRING: u8 = 0x01;
+const INT: u8 = 0x02;
+const FLOAT: u8 = 0x03;
+const LONG: u8 = 0x07;
+const MAP_END: u8 = 0x08;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Map(Vec<(String, Value)>),
+    String(String),
+    Int(u32),
+    Float(f32),
+    Long(u64),
+}
+
+/// A game to add to Steam
+#[derive(Debug, Clone)]
+pub struct SteamShortcut {
+    pub app_name: String,
+    pub title: String,
+    /// The launcher executable the shortcut runs
+    pub launcher: PathBuf,
+}
+
+impl SteamShortcut {
+    /// Steam's id of the shortcut, derived from its target and name like
+    /// Steam itself does. Grid artwork is named after it.
+    pub fn app_id(&self) -> u32 {
+        let key = format!("{}{}", self.exe(), self.title);
+        crc32(key.as_bytes()) | 0x8000_0000
+    }
+
+    fn exe(&self) -> String {
+        format!("\"{}\"", self.launcher.display())
+    }
+
+    fn launch_options(&self) -> String {
+        format!("launch {}", self.app_name)
+    }
+
+    fn entry(&self) -> Value {
+        let start_dir = self
+            .launcher
+            .parent()
+            .map(|dir| format!("\"{}\"", dir.display()))
+            .unwrap_or_default();
+        let string = |key: &str, value: String| (key.to_string(), Value::String(value));
+        let int = |key: &str, value: u32| (key.to_string(), Value::Int(value));
+
+        Value::Map(vec![
+            int("appid", self.app_id()),
+            string("AppName", self.title.clone()),
+            string("Exe", self.exe()),
+            string("StartDir", start_dir),
+            string("icon", String::new()),
+            string("ShortcutPath", String::new()),
+            string("LaunchOptions", self.launch_options()),
+            int("IsHidden", 0),
+            int("AllowDesktopConfig", 1),
+            int("AllowOverlay", 1),
+            int("OpenVR", 0),
+            int("Devkit", 0),
+            string("DevkitGameID", String::new()),
+            int("DevkitOverrideAppID", 0),
+            int("LastPlayTime", 0),
+            string("FlatpakAppID", String::new()),
+            ("tags".to_string(), Value::Map(Vec::new())),
+        ])
+    }
+
+    /// Whether a shortcut entry is this game's, as written by any earlier
+    /// export, even under a different title
+    fn matches(&self, entry: &Value) -> bool {
+        let Value::Map(fields) = entry else {
+            return false;
+        };
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        };
+
+        field("appid") == Some(&Value::Int(self.app_id()))
+            || (field("Exe") == Some(&Value::String(self.exe()))
+                && field("LaunchOptions") == Some(&Value::String(self.launch_options())))
+    }
+}
+
+/// `config` directories of the Steam accounts signed in on this machine
+pub fn user_config_dirs() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = steam_roots()
+        .into_iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect();
+    roots.sort();
+    roots.dedup();
+
+    roots
+        .iter()
+        .filter_map(|root| fs::read_dir(root.join("userdata")).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            // 0 holds settings of no particular account
+            name != "0" && name.chars().all(|c| c.is_ascii_digit())
+        })
+        .map(|entry| entry.path().join("config"))
+        .collect()
+}
+
+/// The store image for each grid artwork suffix
+pub fn grid_images(images: &[KeyImage]) -> Vec<(&'static str, &KeyImage)> {
+    GRID_IMAGES
+        .iter()
+        .filter_map(|(suffix, kind)| {
+            let image = images.iter().find(|image| image.image_type == *kind)?;
+            Some((*suffix, image))
+        })
+        .collect()
+}
+
+/// Add `shortcut` to the account whose config directory is `config_dir`,
+/// replacing an earlier export of the same game. `artwork` holds the grid
+/// suffix, image data and file extension of each image.
+pub fn add_shortcut(
+    config_dir: &Path,
+    shortcut: &SteamShortcut,
+    artwork: &[(&str, &[u8], &str)],
+) -> Result<()> {
+    let path = config_dir.join(SHORTCUTS_FILE);
+    let mut root = if path.exists() {
+        parse(&fs::read(&path)?)
+            .map_err(|e| Error::Other(format!("Can't read {:?}: {}", path, e)))?
+    } else {
+        Vec::new()
+    };
+
+    let shortcuts = match root
+        .iter_mut()
+        .find(|(key, _)| key.eq_ignore_ascii_case("shortcuts"))
+    {
+        Some((_, Value::Map(shortcuts))) => shortcuts,
+        Some(_) => return Err(Error::Other(format!("Unexpected contents in {:?}", path))),
+        None => {
+            root.push(("shortcuts".to_string(), Value::Map(Vec::new())));
+            match &mut root.last_mut().unwrap().1 {
+                Value::Map(shortcuts) => shortcuts,
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    shortcuts.retain(|(_, entry)| !shortcut.matches(entry));
+    shortcuts.push((String::new(), shortcut.entry()));
+    // Entries are keyed by their position
+    for (index, (key, _)) in shortcuts.iter_mut().enumerate() {
+        *key = index.to_string();
+    }
+
+    fs::create_dir_all(config_dir)?;
+    let partial = path.with_extension("vdf.partial");
+    fs::write(&partial, serialize(&root))?;
+    fs::rename(&partial, &path)?;
+
+    let grid = config_dir.join("grid");
+    fs::create_dir_all(&grid)?;
+    for (suffix, data, extension) in artwork {
+        let name = format!("{}{}.{}", shortcut.app_id(), suffix, extension);
+        fs::write(grid.join(name), data)?;
+    }
+
+    Ok(())
+}
+
+fn parse(data: &[u8]) -> std::result::Result<Vec<(String, Value)>, String> {
+    let mut reader = Reader { data, pos: 0 };
+    reader.map()
+}
+
+fn serialize(entries: &[(String, Value)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_map(&mut out, entries);
+    out
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> std::result::Result<&[u8], String> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| "unexpected end of file".to_string())?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> std::result::Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn string(&mut self) -> std::result::Result<String, String> {
+        let len = self.data[self.pos..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| "unterminated string".to_string())?;
+        let value = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.pos += 1;
+        Ok(value)
+    }
+
+    /// Entries up to the end of the current map
+    fn map(&mut self) -> std::result::Result<Vec<(String, Value)>, String> {
+        let mut entries = Vec::new();
+        loop {
+            let kind = self.array::<1>()?[0];
+            if kind == MAP_END {
+                return Ok(entries);
+            }
+            let key = self.string()?;
+            let value = match kind {
+                MAP => Value::Map(self.map()?),
+                STRING => Value::String(self.string()?),
+                INT => Value::Int(u32::from_le_bytes(self.array()?)),
+                FLOAT => Value::Float(f32::from_le_bytes(self.array()?)),
+                LONG => Value::Long(u64::from_le_bytes(self.array()?)),
+                other => return Err(format!("unknown value type {:#04x}", other)),
+            };
+            entries.push((key, value));
+        }
+    }
+}
+
+fn write_map(out: &mut Vec<u8>, entries: &[(String, Value)]) {
+    for (key, value) in entries {
+        let kind = match value {
+            Value::Map(_) => MAP,
+            Value::String(_) => STRING,
+            Value::Int(_) => INT,
+            Value::Float(_) => FLOAT,
+            Value::Long(_) => LONG,
+        };
+        out.push(kind);
+        write_string(out, key);
+
+        match value {
+            Value::Map(entries) => write_map(out, entries),
+            Value::String(value) => write_string(out, value),
+            Value::Int(value) => out.extend(value.to_le_bytes()),
+            Value::Float(value) => out.extend(value.to_le_bytes()),
+            Value::Long(value) => out.extend(value.to_le_bytes()),
+        }
+    }
+    out.push(MAP_END);
+}
+
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend(value.as_bytes());
+    out.push(0);
+}
+
+/// CRC-32 as used by zlib
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn shortcut(title: &str) -> SteamShortcut {
+        SteamShortcut {
+            app_name: "Fortnite".to_string(),
+            title: title.to_string(),
+            launcher: PathBuf::from("/usr/bin/rauncher"),
+        }
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert!(shortcut("Fortnite").app_id() & 0x8000_0000 != 0);
+    }
+
+    #[test]
+    fn test_add_shortcut_keeps_other_entries() {
+        let dir = TempDir::new().unwrap();
+        let other = vec![(
+            "shortcuts".to_string(),
+            Value::Map(vec![(
+                "0".to_string(),
+                Value::Map(vec![
+                    ("appid".to_string(), Value::Int(0x8123_4567)),
+                    ("AppName".to_string(), Value::String("Other".to_string())),
+                    ("LastPlayTime".to_string(), Value::Long(7)),
+                ]),
+            )]),
+        )];
+        fs::write(dir.path().join(SHORTCUTS_FILE), serialize(&other)).unwrap();
+
+        // Exporting again after a rename replaces the entry
+        add_shortcut(dir.path(), &shortcut("Old Title"), &[]).unwrap();
+        let game = shortcut("Fortnite");
+        add_shortcut(dir.path(), &game, &[("p", b"jpeg", "jpg")]).unwrap();
+
+        let root = parse(&fs::read(dir.path().join(SHORTCUTS_FILE)).unwrap()).unwrap();
+        let Value::Map(shortcuts) = &root[0].1 else {
+            panic!("no shortcuts map");
+        };
+        let keys: Vec<&str> = shortcuts.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["0", "1"]);
+        assert_eq!(shortcuts[0], other_entry(&other));
+        assert!(game.matches(&shortcuts[1].1));
+        assert!(dir
+            .path()
+            .join(format!("grid/{}p.jpg", game.app_id()))
+            .is_file());
+    }
+
+    fn other_entry(root: &[(String, Value)]) -> (String, Value) {
+        match &root[0].1 {
+            Value::Map(shortcuts) => shortcuts[0].clone(),
+            _ => unreachable!(),
+        }
+    }
+}
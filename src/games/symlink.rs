@@ -0,0 +1,133 @@
+//! Symlink entries in file manifests. Some Linux and macOS builds ship links
+//! (e.g. `libfoo.so -> libfoo.so.1`) that have no chunk data; they are
+//! recreated as links rather than downloaded.
+
+use std::fs;
+use std::path::{Component, Path};
+
+use crate::api::FileManifest;
+use crate::{Error, Result};
+
+/// Replace whatever is at `file`'s place under `install_path` with a link to
+/// `target`. Targets that would lead outside the install are refused, so a
+/// manifest can't get later files written elsewhere through a link.
+pub fn create(install_path: &Path, file: &FileManifest, target: &str) -> Result<()> {
+    let path = install_path.join(&file.filename);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Where the link really is, following links among its parents
+    let depth = match path.parent().map(fs::canonicalize) {
+        Some(Ok(parent)) => parent
+            .strip_prefix(install_path.canonicalize()?)
+            .ok()
+            .map(|inside| inside.components().count()),
+        _ => None,
+    };
+    if !depth.is_some_and(|depth| stays_inside(depth, target)) {
+        return Err(Error::Other(format!(
+            "{} links to {}, outside the game directory",
+            file.filename, target
+        )));
+    }
+
+    if path.symlink_metadata().is_ok() {
+        fs::remove_file(&path)?;
+    }
+
+    make_link(target, &path)
+}
+
+#[cfg(unix)]
+fn make_link(target: &str, path: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_link(_target: &str, path: &Path) -> Result<()> {
+    Err(Error::Other(format!(
+        "{:?} is a symlink, which is only supported on Unix",
+        path
+    )))
+}
+
+/// Whether `path` is a link to exactly `target`
+pub fn matches(path: &Path, target: &str) -> bool {
+    fs::read_link(path).is_ok_and(|link| link == Path::new(target))
+}
+
+/// Whether a link `depth` directories below the install root to `target`
+/// resolves to somewhere inside the install. `..` is only allowed before
+/// the first name: after one, it would climb out of wherever that name
+/// leads, which another link may have moved up. With every link checked
+/// like this, chains of links stay inside too.
+fn stays_inside(mut depth: usize, target: &str) -> bool {
+    let mut named = false;
+
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(_) => named = true,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 && !named => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn link(filename: &str) -> FileManifest {
+        FileManifest {
+            filename: filename.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_links_must_stay_inside_the_install() {
+        assert!(stays_inside(1, "libfoo.so.1"));
+        assert!(stays_inside(1, "../lib/game.bin"));
+        assert!(!stays_inside(1, "../../etc/passwd"));
+        assert!(!stays_inside(0, "/usr/bin/game"));
+        assert!(!stays_inside(2, "lib/../../../etc"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_chained_links_stay_inside() {
+        let dir = TempDir::new().unwrap();
+        let install = dir.path().join("game");
+        fs::create_dir_all(install.join("a/b")).unwrap();
+
+        // Chains of links to links are fine
+        create(&install, &link("lib/libfoo.so.1"), "libfoo.so.1.2").unwrap();
+        create(&install, &link("lib/libfoo.so"), "libfoo.so.1").unwrap();
+
+        // "up" points one level up from a/b, so climbing from it counts
+        // from a, not from a/b
+        create(&install, &link("a/b/up"), "..").unwrap();
+        assert!(create(&install, &link("a/b/up/out"), "../../escape").is_err());
+        assert!(create(&install, &link("deep"), "a/b/up/../../..").is_err());
+        create(&install, &link("a/b/up/lib"), "../lib").unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_replaces_existing_entry() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("libfoo.so");
+        fs::write(&path, b"stale copy").unwrap();
+
+        create(dir.path(), &link("libfoo.so"), "libfoo.so.1").unwrap();
+
+        assert!(matches(&path, "libfoo.so.1"));
+        assert!(create(dir.path(), &link("libfoo.so"), "../outside").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Per-game launch templates in the style of Steam's launch options, e.g.
+//! `PROTON_LOG=1 gamescope -f -- %command% -windowed`. Leading `NAME=value`
+//! words set environment variables, the words before `%command%` wrap the
+//! game's command and the words after it are passed to the game.
+
+use std::process::Command;
+
+use crate::{Error, Result};
+
+/// Stands for the command that would start the game without a template
+const COMMAND_PLACEHOLDER: &str = "%command%";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchTemplate {
+    pub env: Vec<(String, String)>,
+    pub wrapper: Vec<String>,
+    pub args: Vec<String>,
+}
+
+impl LaunchTemplate {
+    /// Parse `template`, which must contain `%command%` exactly once as a
+    /// word of its own. Words are split on whitespace; single and double
+    /// quotes and backslashes work as in a shell.
+    pub fn parse(template: &str) -> Result<Self> {
+        let words = split_words(template)?;
+
+        let placeholder = |word: &(String, bool)| word.0 == COMMAND_PLACEHOLDER && !word.1;
+        let position = match words.iter().filter(|word| placeholder(word)).count() {
+            1 => words.iter().position(placeholder).unwrap(),
+            0 => {
+                return Err(invalid(
+                    template,
+                    "it must contain %command% as its own word",
+                ))
+            }
+            _ => return Err(invalid(template, "%command% may only appear once")),
+        };
+        if words
+            .iter()
+            .any(|(word, quoted)| !quoted && word != COMMAND_PLACEHOLDER && word.contains('%'))
+        {
+            return Err(invalid(
+                template,
+                "%command% can't be joined to other text; quote literal % signs",
+            ));
+        }
+
+        let mut words: Vec<String> = words.into_iter().map(|(word, _)| word).collect();
+        let args = words.split_off(position + 1);
+        words.pop();
+
+        let env_count = words
+            .iter()
+            .take_while(|word| env_assignment(word).is_some())
+            .count();
+        let wrapper = words.split_off(env_count);
+        let env = words
+            .iter()
+            .filter_map(|word| env_assignment(word))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        Ok(Self { env, wrapper, args })
+    }
+
+    /// Run `command` through the template: with its environment, inside
+    /// its wrapper and followed by its arguments
+    pub fn apply(&self, command: Command) -> Command {
+        let mut templated = match self.wrapper.split_first() {
+            Some((program, wrapper_args)) => {
+                let mut templated = Command::new(program);
+                templated
+                    .args(wrapper_args)
+                    .arg(command.get_program())
+                    .args(command.get_args());
+                templated
+            }
+            None => {
+                let mut templated = Command::new(command.get_program());
+                templated.args(command.get_args());
+                templated
+            }
+        };
+
+        for (name, value) in command.get_envs() {
+            match value {
+                Some(value) => templated.env(name, value),
+                None => templated.env_remove(name),
+            };
+        }
+        if let Some(dir) = command.get_current_dir() {
+            templated.current_dir(dir);
+        }
+
+        templated
+            .envs(self.env.iter().map(|(name, value)| (name, value)))
+            .args(&self.args);
+        templated
+    }
+}
+
+fn invalid(template: &str, reason: &str) -> Error {
+    Error::Config(format!(
+        "Invalid launch template '{}': {}",
+        template, reason
+    ))
+}
+
+/// `NAME=value` with a valid variable name
+fn env_assignment(word: &str) -> Option<(&str, &str)> {
+    let (name, value) = word.split_once('=')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((name, value))
+}
+
+/// Split `template` into words, noting which had any quoted part
+fn split_words(template: &str) -> Result<Vec<(String, bool)>> {
+    let mut words = Vec::new();
+    let mut word: Option<(String, bool)> = None;
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let current = word.get_or_insert_with(Default::default);
+                current.1 = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.0.push(c),
+                        None => return Err(invalid(template, "unterminated single quote")),
+                    }
+                }
+            }
+            '"' => {
+                let current = word.get_or_insert_with(Default::default);
+                current.1 = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.0.push(c),
+                            Some(c) => {
+                                current.0.push('\\');
+                                current.0.push(c);
+                            }
+                            None => return Err(invalid(template, "unterminated double quote")),
+                        },
+                        Some(c) => current.0.push(c),
+                        None => return Err(invalid(template, "unterminated double quote")),
+                    }
+                }
+            }
+            '\\' => {
+                let current = word.get_or_insert_with(Default::default);
+                current.1 = true;
+                match chars.next() {
+                    Some(c) => current.0.push(c),
+                    None => return Err(invalid(template, "trailing backslash")),
+                }
+            }
+            c => word.get_or_insert_with(Default::default).0.push(c),
+        }
+    }
+    words.extend(word);
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_template() {
+        let template =
+            LaunchTemplate::parse("PROTON_LOG=1 gamescope -f -- %command% -windowed").unwrap();
+        assert_eq!(template.env, [("PROTON_LOG".to_string(), "1".to_string())]);
+        assert_eq!(template.wrapper, ["gamescope", "-f", "--"]);
+        assert_eq!(template.args, ["-windowed"]);
+
+        let quoted = LaunchTemplate::parse(r#"%command% -name "Player One" '100%'"#).unwrap();
+        assert!(quoted.wrapper.is_empty());
+        assert_eq!(quoted.args, ["-name", "Player One", "100%"]);
+
+        for bad in [
+            "gamescope --",
+            "%command% %command%",
+            "gamescope -- %command%-windowed",
+            "%command% -name \"Player",
+            "%command% 100%",
+        ] {
+            assert!(LaunchTemplate::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_apply_wraps_the_command() {
+        let mut command = Command::new("wine");
+        command
+            .env("WINEPREFIX", "/prefix")
+            .arg("Game.exe")
+            .current_dir("/games/Game");
+
+        let template = LaunchTemplate::parse("DXVK_HUD=fps gamemoderun %command% -dx11").unwrap();
+        let templated = template.apply(command);
+
+        assert_eq!(templated.get_program(), "gamemoderun");
+        let args: Vec<_> = templated.get_args().collect();
+        assert_eq!(args, ["wine", "Game.exe", "-dx11"]);
+        let envs: Vec<_> = templated.get_envs().collect();
+        assert!(envs.contains(&("WINEPREFIX".as_ref(), Some("/prefix".as_ref()))));
+        assert!(envs.contains(&("DXVK_HUD".as_ref(), Some("fps".as_ref()))));
+        assert_eq!(
+            templated.get_current_dir(),
+            Some(std::path::Path::new("/games/Game"))
+        );
+    }
+}
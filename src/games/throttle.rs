@@ -0,0 +1,107 @@
+//! Process-wide download speed limit. Every chunk download draws from one
+//! token bucket, so the limit holds however many games and workers are
+//! downloading at once.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket refilled at the configured rate, holding at most one
+/// second's worth of bytes
+pub struct Throttle {
+    /// Bytes per second; 0 means unlimited
+    limit: AtomicU64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    /// Bytes that may be sent right away; negative while a download is
+    /// paying off a chunk larger than the bucket
+    available: f64,
+    updated: Option<Instant>,
+}
+
+static THROTTLE: Throttle = Throttle::new();
+
+pub fn throttle() -> &'static Throttle {
+    &THROTTLE
+}
+
+impl Throttle {
+    const fn new() -> Self {
+        Self {
+            limit: AtomicU64::new(0),
+            bucket: Mutex::new(Bucket {
+                available: 0.0,
+                updated: None,
+            }),
+        }
+    }
+
+    /// Change the limit, in bytes per second, taking effect for the next
+    /// chunk. `None` removes it.
+    pub fn set_limit(&self, bytes_per_second: Option<u64>) {
+        self.limit
+            .store(bytes_per_second.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    pub fn limit(&self) -> Option<u64> {
+        Some(self.limit.load(Ordering::Relaxed)).filter(|&limit| limit > 0)
+    }
+
+    /// Account for `bytes` just downloaded, waiting until the limit allows
+    /// them
+    pub async fn consume(&self, bytes: u64) {
+        let wait = self.reserve(bytes);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn reserve(&self, bytes: u64) -> Duration {
+        let Some(limit) = self.limit() else {
+            return Duration::ZERO;
+        };
+        let rate = limit as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+
+        let now = Instant::now();
+        let refill = bucket.updated.map_or(rate, |updated| {
+            now.duration_since(updated).as_secs_f64() * rate
+        });
+        bucket.available = (bucket.available + refill).min(rate) - bytes as f64;
+        bucket.updated = Some(now);
+
+        if bucket.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.available / rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_never_waits() {
+        let throttle = Throttle::new();
+        assert!(throttle.reserve(u64::MAX).is_zero());
+    }
+
+    #[test]
+    fn test_waits_once_the_bucket_is_empty() {
+        let throttle = Throttle::new();
+        throttle.set_limit(Some(1000));
+
+        // A full second's worth is allowed immediately
+        assert!(throttle.reserve(1000).is_zero());
+
+        let wait = throttle.reserve(500);
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+
+        throttle.set_limit(None);
+        assert!(throttle.reserve(500).is_zero());
+    }
+}
@@ -0,0 +1,241 @@
+use chrono::{Duration, Local, NaiveDateTime, TimeZone, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+const DELETION_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Move a directory into the XDG trash, or into `fallback_dir` when the trash
+/// lives on another filesystem. Only renames are attempted, so this never
+/// copies the game's data; the returned path is where it ended up. Entries
+/// in `fallback_dir` get a `<name>.trashinfo` file beside them recording
+/// when they were trashed, which `purge_expired` goes by.
+pub fn move_to_trash(path: &Path, fallback_dir: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| Error::Other(format!("Invalid path: {:?}", path)))?
+        .to_string_lossy()
+        .into_owned();
+
+    if let Some(trash_dir) = xdg_trash_dir() {
+        match move_to_xdg_trash(path, &trash_dir, &name) {
+            Ok(trashed) => return Ok(trashed),
+            Err(e) => log::debug!("XDG trash unavailable for {:?}: {}", path, e),
+        }
+    }
+
+    move_to_fallback(path, fallback_dir, &name)
+}
+
+fn move_to_fallback(path: &Path, fallback_dir: &Path, name: &str) -> Result<PathBuf> {
+    fs::create_dir_all(fallback_dir)?;
+    let target = unique_path(
+        fallback_dir,
+        &format!("{}-{}", name, Utc::now().format("%Y%m%d%H%M%S")),
+    );
+
+    let info_path = info_path(&target);
+    fs::write(&info_path, trash_info(path))?;
+
+    if let Err(e) = fs::rename(path, &target) {
+        let _ = fs::remove_file(&info_path);
+        return Err(Error::Other(format!(
+            "Could not move {:?} to the trash ({}). Uninstall without trash to delete it.",
+            path, e
+        )));
+    }
+
+    Ok(target)
+}
+
+/// Delete launcher-trash entries trashed more than `retention_days` ago.
+/// Renaming keeps a game's modification time, which says nothing about when
+/// it was trashed, so entries without a readable `.trashinfo` are kept.
+pub fn purge_expired(trash_dir: &Path, retention_days: u32) -> Result<Vec<PathBuf>> {
+    let mut purged = Vec::new();
+
+    if !trash_dir.exists() {
+        return Ok(purged);
+    }
+
+    let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+
+    for entry in fs::read_dir(trash_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "trashinfo") {
+            continue;
+        }
+
+        let info_path = info_path(&path);
+        let Some(deleted) = fs::read_to_string(&info_path)
+            .ok()
+            .and_then(|info| deletion_date(&info))
+        else {
+            log::debug!("No trash time for {:?}, keeping it", path);
+            continue;
+        };
+
+        if deleted < cutoff {
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+            fs::remove_file(&info_path)?;
+            purged.push(path);
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Where the trash time of the launcher-trash entry at `path` is kept
+fn info_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".trashinfo");
+    PathBuf::from(name)
+}
+
+/// A `.trashinfo` record, as the freedesktop.org trash spec writes them
+fn trash_info(original: &Path) -> String {
+    format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        original.display(),
+        Local::now().format(DELETION_DATE_FORMAT)
+    )
+}
+
+/// The `DeletionDate` of a `.trashinfo` record, in local time as the spec
+/// has it
+fn deletion_date(info: &str) -> Option<chrono::DateTime<Utc>> {
+    let date = info
+        .lines()
+        .find_map(|line| line.strip_prefix("DeletionDate="))?;
+    let date = NaiveDateTime::parse_from_str(date.trim(), DELETION_DATE_FORMAT).ok()?;
+    Local
+        .from_local_datetime(&date)
+        .earliest()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn xdg_trash_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| dirs.data_dir().join("Trash"))
+}
+
+/// Follow the freedesktop.org trash spec: data under `files/`, and a
+/// `.trashinfo` record under `info/` so file managers can restore it.
+fn move_to_xdg_trash(path: &Path, trash_dir: &Path, name: &str) -> Result<PathBuf> {
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    let target = unique_path(&files_dir, name);
+    let trash_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| name.to_string());
+
+    let info_path = info_dir.join(format!("{}.trashinfo", trash_name));
+    fs::write(&info_path, trash_info(path))?;
+
+    if let Err(e) = fs::rename(path, &target) {
+        let _ = fs::remove_file(&info_path);
+        return Err(e.into());
+    }
+
+    Ok(target)
+}
+
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let mut candidate = dir.join(name);
+    let mut counter = 1;
+
+    while candidate.exists() {
+        candidate = dir.join(format!("{}.{}", name, counter));
+        counter += 1;
+    }
+
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_xdg_trash_writes_trashinfo() {
+        let dir = TempDir::new().unwrap();
+        let game = dir.path().join("Game");
+        fs::create_dir_all(&game).unwrap();
+        fs::write(game.join("data.pak"), b"data").unwrap();
+
+        let trash = dir.path().join("Trash");
+        let trashed = move_to_xdg_trash(&game, &trash, "Game").unwrap();
+
+        assert!(!game.exists());
+        assert_eq!(fs::read(trashed.join("data.pak")).unwrap(), b"data");
+        let info = fs::read_to_string(trash.join("info/Game.trashinfo")).unwrap();
+        assert!(info.contains(&format!("Path={}", game.display())));
+    }
+
+    #[test]
+    fn test_unique_path_avoids_collisions() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Game")).unwrap();
+
+        assert_eq!(unique_path(dir.path(), "Game"), dir.path().join("Game.1"));
+    }
+
+    #[test]
+    fn test_purge_keeps_recent_entries() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Game-20240101")).unwrap();
+
+        let purged = purge_expired(dir.path(), 7).unwrap();
+        assert!(purged.is_empty());
+        assert!(dir.path().join("Game-20240101").exists());
+    }
+
+    #[test]
+    fn test_purge_goes_by_trash_time_not_mtime() {
+        let dir = TempDir::new().unwrap();
+        let game = dir.path().join("Game");
+        fs::create_dir_all(&game).unwrap();
+        // Installed long ago; renaming keeps this
+        let installed = std::time::SystemTime::now() - std::time::Duration::from_secs(90 * 86400);
+        fs::File::open(&game)
+            .unwrap()
+            .set_modified(installed)
+            .unwrap();
+
+        let trash = dir.path().join("trash");
+        let trashed = move_to_fallback(&game, &trash, "Game").unwrap();
+
+        assert!(purge_expired(&trash, 7).unwrap().is_empty());
+        assert!(trashed.exists());
+    }
+
+    #[test]
+    fn test_purge_removes_entries_trashed_long_ago() {
+        let dir = TempDir::new().unwrap();
+        let entry = dir.path().join("Game-20240101");
+        fs::create_dir_all(&entry).unwrap();
+        fs::write(
+            info_path(&entry),
+            "[Trash Info]\nPath=/games/Game\nDeletionDate=2024-01-01T12:00:00\n",
+        )
+        .unwrap();
+        // No record of when it was trashed
+        fs::create_dir_all(dir.path().join("Unknown")).unwrap();
+
+        let purged = purge_expired(dir.path(), 7).unwrap();
+
+        assert_eq!(purged, vec![entry.clone()]);
+        assert!(!entry.exists());
+        assert!(!info_path(&entry).exists());
+        assert!(dir.path().join("Unknown").exists());
+    }
+}
@@ -0,0 +1,320 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::progress::InstallProgress;
+use super::symlink;
+use crate::api::{FileManifest, GameManifest};
+use crate::config::storage;
+use crate::Result;
+
+/// Outcome of checking an installation against its manifest
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub checked_files: usize,
+    /// Files not re-hashed because they are unchanged since the last verify
+    pub skipped_files: usize,
+    pub missing_files: Vec<String>,
+    pub corrupt_files: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing_files.is_empty() && self.corrupt_files.is_empty()
+    }
+
+    /// Names of every file that failed verification
+    pub fn failed_files(&self) -> impl Iterator<Item = &String> {
+        self.missing_files.iter().chain(self.corrupt_files.iter())
+    }
+}
+
+/// Size and modification time of a file when it last verified, so an
+/// incremental verify can skip hashing it again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct VerifiedFile {
+    size: u64,
+    modified: SystemTime,
+    hash: Vec<u8>,
+}
+
+/// Files of one game that passed verification, stored between runs
+pub struct VerifyCache {
+    path: PathBuf,
+    files: HashMap<String, VerifiedFile>,
+}
+
+impl VerifyCache {
+    /// Start from nothing, so every file is hashed; saving replaces what
+    /// `path` held
+    pub fn empty(path: PathBuf) -> Self {
+        Self {
+            path,
+            files: HashMap::new(),
+        }
+    }
+
+    /// The cache saved at `path`, or an empty one if it is missing or unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let files = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self { path, files }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            storage::create_private_dir(parent)?;
+        }
+        storage::write_private(&self.path, serde_json::to_string(&self.files)?)?;
+        Ok(())
+    }
+
+    fn is_unchanged(&self, file: &FileManifest, current: &VerifiedFile) -> bool {
+        self.files.get(&file.filename) == Some(current)
+    }
+}
+
+/// What checking a single file found
+enum FileStatus {
+    Missing,
+    Corrupt,
+    /// Hash matched, or with `skipped`, the file is unchanged since it last did
+    Intact {
+        verified: Option<VerifiedFile>,
+        skipped: bool,
+    },
+}
+
+/// Re-hash every file listed in the manifest and compare against its stored SHA-1
+pub fn verify_file_integrity(install_path: &Path, manifest: &GameManifest) -> Result<VerifyReport> {
+    verify_files(install_path, manifest, None)
+}
+
+/// Hash the files of `manifest` in parallel with a progress bar. Files
+/// `cache` recorded with the same size and modification time are trusted
+/// without hashing; the cache is updated with what this run found.
+pub fn verify_files(
+    install_path: &Path,
+    manifest: &GameManifest,
+    cache: Option<&mut VerifyCache>,
+) -> Result<VerifyReport> {
+    let progress = InstallProgress::new(
+        manifest.file_list.iter().map(|file| file.size()).sum(),
+        manifest.file_list.len(),
+    );
+
+    let cached = cache.as_deref();
+    let statuses = manifest
+        .file_list
+        .par_iter()
+        .map(|file| {
+            let status = check_file(install_path, file, cached);
+            progress.add_bytes(file.size());
+            progress.finish_file();
+            status
+        })
+        .collect::<Result<Vec<_>>>()?;
+    progress.finish();
+
+    let mut report = VerifyReport::default();
+    let mut verified_files = HashMap::new();
+    for (file, status) in manifest.file_list.iter().zip(statuses) {
+        report.checked_files += 1;
+
+        match status {
+            FileStatus::Missing => {
+                log::warn!("Missing file: {}", file.filename);
+                report.missing_files.push(file.filename.clone());
+            }
+            FileStatus::Corrupt => {
+                log::warn!("Corrupt file: {}", file.filename);
+                report.corrupt_files.push(file.filename.clone());
+            }
+            FileStatus::Intact { verified, skipped } => {
+                if skipped {
+                    report.skipped_files += 1;
+                }
+                if let Some(verified) = verified {
+                    verified_files.insert(file.filename.clone(), verified);
+                }
+            }
+        }
+    }
+
+    if let Some(cache) = cache {
+        cache.files = verified_files;
+    }
+
+    Ok(report)
+}
+
+fn check_file(
+    install_path: &Path,
+    file: &FileManifest,
+    cache: Option<&VerifyCache>,
+) -> Result<FileStatus> {
+    let path = install_path.join(&file.filename);
+    if file.symlink_target().is_some() {
+        return Ok(if !is_present(&path, file) {
+            FileStatus::Missing
+        } else if file_matches(&path, file)? {
+            FileStatus::Intact {
+                verified: None,
+                skipped: false,
+            }
+        } else {
+            FileStatus::Corrupt
+        });
+    }
+
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => return Ok(FileStatus::Missing),
+    };
+
+    let current = metadata.modified().ok().map(|modified| VerifiedFile {
+        size: metadata.len(),
+        modified,
+        hash: file.file_hash.clone(),
+    });
+
+    if let (Some(cache), Some(current)) = (cache, &current) {
+        if cache.is_unchanged(file, current) {
+            return Ok(FileStatus::Intact {
+                verified: Some(current.clone()),
+                skipped: true,
+            });
+        }
+    }
+
+    if file_matches(&path, file)? {
+        Ok(FileStatus::Intact {
+            verified: current,
+            skipped: false,
+        })
+    } else {
+        Ok(FileStatus::Corrupt)
+    }
+}
+
+/// Whether something that could be `file` exists at `path`: a regular file,
+/// or any link for symlink entries
+pub fn is_present(path: &Path, file: &FileManifest) -> bool {
+    match file.symlink_target() {
+        Some(_) => path
+            .symlink_metadata()
+            .is_ok_and(|m| m.file_type().is_symlink()),
+        None => path.is_file(),
+    }
+}
+
+/// Check a single file on disk against its manifest entry. Symlink entries
+/// must point at their target; entries without a recorded hash are only
+/// checked for existence.
+pub fn file_matches(path: &Path, file: &FileManifest) -> Result<bool> {
+    if let Some(target) = file.symlink_target() {
+        return Ok(symlink::matches(path, target));
+    }
+
+    if file.file_hash.is_empty() {
+        return Ok(true);
+    }
+
+    Ok(hash_file(path)?.as_slice() == file.file_hash.as_slice())
+}
+
+/// Compute the SHA-1 digest of a file without loading it fully into memory
+pub fn hash_file(path: &Path) -> Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha1::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::paths::test_manifest;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn entry(name: &str, contents: &[u8]) -> FileManifest {
+        FileManifest {
+            filename: name.to_string(),
+            file_hash: Sha1::digest(contents).to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_reports_missing_and_corrupt() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("good.bin"), b"hello").unwrap();
+        fs::write(dir.path().join("bad.bin"), b"tampered").unwrap();
+
+        let manifest = test_manifest([
+            entry("good.bin", b"hello"),
+            entry("bad.bin", b"original"),
+            entry("gone.bin", b"data"),
+        ]);
+
+        let report = verify_file_integrity(dir.path(), &manifest).unwrap();
+        assert_eq!(report.checked_files, 3);
+        assert_eq!(report.missing_files, vec!["gone.bin".to_string()]);
+        assert_eq!(report.corrupt_files, vec!["bad.bin".to_string()]);
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn test_verify_clean_install() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("data")).unwrap();
+        fs::write(dir.path().join("data/pak0.pak"), b"pak").unwrap();
+
+        let manifest = test_manifest([entry("data/pak0.pak", b"pak")]);
+
+        let report = verify_file_integrity(dir.path(), &manifest).unwrap();
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn test_incremental_verify_rehashes_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("cache/verify.json");
+        fs::write(dir.path().join("a.bin"), b"alpha").unwrap();
+        fs::write(dir.path().join("b.bin"), b"beta").unwrap();
+        let manifest = test_manifest([entry("a.bin", b"alpha"), entry("b.bin", b"beta")]);
+
+        let mut cache = VerifyCache::empty(cache_path.clone());
+        let report = verify_files(dir.path(), &manifest, Some(&mut cache)).unwrap();
+        assert_eq!(report.skipped_files, 0);
+        cache.save().unwrap();
+
+        let mut cache = VerifyCache::load(cache_path.clone());
+        let report = verify_files(dir.path(), &manifest, Some(&mut cache)).unwrap();
+        assert_eq!(report.skipped_files, 2);
+        assert!(report.is_ok());
+
+        fs::write(dir.path().join("b.bin"), b"tampered").unwrap();
+        let report = verify_files(dir.path(), &manifest, Some(&mut cache)).unwrap();
+        assert_eq!(report.skipped_files, 1);
+        assert_eq!(report.corrupt_files, vec!["b.bin".to_string()]);
+    }
+}
@@ -1,62 +1,241 @@
 use eframe::egui;
 use poll_promise::Promise;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::api::Game;
+use crate::api::{self, Game};
+use crate::audit::Initiator;
 use crate::auth::AuthManager;
-use crate::config::Config;
-use crate::games::{GameManager, InstalledGame};
-use crate::Result;
+use crate::cli::prompt;
+use crate::config::{self, Config, ConfigWatcher};
+use crate::format;
+use crate::games::{
+    installed_runners, logs, session, shaders, throttle, GameManager, GameUpdate, InstalledGame,
+    LaunchOptions, Runner, SaveSyncReport, SyncPlan, VerifyReport,
+};
+use crate::i18n::{tr, tr_args};
+use crate::update::{self, Release};
+use crate::{Error, Result};
 
+use super::artwork::CoverArt;
 use super::auth_view::AuthView;
+use super::components::Header;
+use super::downloads::{Download, DownloadKind, Downloads};
+use super::downloads_view::{DownloadsAction, DownloadsView};
+use super::game_detail_view::{DetailAction, GameDetailView};
 use super::library_view::{LibraryAction, LibraryView};
+use super::navigation::{self, Gamepad, Nav};
+use super::runtime;
+use super::saves_view;
 use super::styles;
-use super::components::{Header, StatusBar};
+use super::toasts::{Level, Toasts};
+
+/// Key of the library view state in eframe's storage
+const LIBRARY_VIEW_KEY: &str = "library_view";
+
+/// End of a game's log shown in the log window
+const LOG_VIEW_BYTES: u64 = 1024 * 1024;
 
 enum AppState {
     Login,
     Library,
 }
 
+/// Tabs shown once signed in
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Library,
+    Downloads,
+}
+
 pub struct LauncherApp {
     state: AppState,
+    tab: Tab,
     auth: Arc<Mutex<AuthManager>>,
     config: Arc<Config>,
     auth_view: AuthView,
     library_view: LibraryView,
     library_games: Vec<Game>,
+    covers: CoverArt,
     installed_games: Vec<InstalledGame>,
-    status_message: String,
+    /// Installs and updates, queued or running
+    downloads: Downloads,
+    /// What operations started, finished or failed
+    toasts: Toasts,
     loading_library: bool,
     library_promise: Option<Promise<Result<Vec<Game>>>>,
+    /// Why the library failed to load, until a retry
+    library_error: Option<String>,
+    /// Epic couldn't be reached, so the library holds only the installed
+    /// games, from their local records
+    offline: bool,
+    update_promise: Option<Promise<Result<Option<Release>>>>,
+    available_update: Option<Release>,
+    /// Download speed limit in KiB/s for this session, 0 for none
+    bandwidth_limit: u64,
+    /// Size of text and controls, from `ui_scale` or `--scale`
+    ui_scale: f32,
+    /// `ui_scale` changed and is applied on the next frame
+    rescale: bool,
+    /// Picks up edits to `config.toml` while the launcher is running
+    config_watcher: Option<ConfigWatcher>,
+    /// Background check of installed games: once when the library opens,
+    /// then every interval while `auto_update` is on
+    game_updates_promise: Option<Promise<Result<Vec<GameUpdate>>>>,
+    next_game_update_check: Option<Instant>,
+    /// Background save sync, while `background_save_sync` is on
+    save_sync_promise: Option<Promise<Result<Vec<SaveSyncReport>>>>,
+    next_save_sync: Instant,
+    /// Versions found by the last check and not yet installed, by app name
+    game_updates: HashMap<String, String>,
+    /// Runners offered in each game's runner picker
+    runners: Vec<Runner>,
+    /// Game whose last session log is open, with the end of that log
+    log_window: Option<(String, Vec<String>)>,
+    /// Game waiting for the install to be confirmed, and whether to add it
+    /// to the application menu
+    install_dialog: Option<(String, bool)>,
+    /// Game being added to Steam
+    steam_export: Option<Promise<Result<String>>>,
+    /// Dry run of a cloud save sync being worked out
+    sync_preview_promise: Option<Promise<Result<(String, SyncPlan)>>>,
+    /// Game whose sync preview is open, with the preview
+    sync_preview: Option<(String, SyncPlan)>,
+    /// Page of the game opened from the library
+    game_detail: Option<GameDetailView>,
+    /// Verify running in the background, with its game
+    verify_promise: Option<Promise<(String, Result<VerifyReport>)>>,
+    /// Uninstall waiting to be confirmed
+    uninstall_dialog: Option<UninstallDialog>,
+    /// Uninstall running in the background, with its game
+    uninstall_promise: Option<Promise<(String, Result<()>)>>,
+    /// Logout waiting to be confirmed, as it stops the downloads
+    logout_dialog: bool,
+    /// Game waiting for a destination to move to, with the one typed so far
+    move_dialog: Option<(String, String)>,
+    /// Launch being prepared in the background, with its game and the play
+    /// time left today
+    launch_promise: Option<Promise<(String, Result<Option<Duration>>)>>,
+    /// Game waiting for the parental PIN to play past the daily limit,
+    /// with the PIN typed so far
+    pin_dialog: Option<(String, String)>,
+    /// Move running in the background, with its game
+    move_promise: Option<Promise<(String, Result<PathBuf>)>>,
+    /// Menu entry being created, with its game
+    shortcut_promise: Option<Promise<(String, Result<PathBuf>)>>,
+    /// Presses of a connected gamepad, as keys
+    gamepad: Gamepad,
+}
+
+/// A dialog's buttons from the keyboard or a gamepad: `default` has the
+/// focus whenever nothing else does, so Enter presses it, and Escape
+/// cancels. Returns whether Escape was pressed.
+fn dialog_keys(ui: &egui::Ui, default: &egui::Response) -> bool {
+    if ui.memory(|memory| memory.focused().is_none()) {
+        default.request_focus();
+    }
+    ui.input(|input| input.key_pressed(egui::Key::Escape))
+}
+
+/// What to remove along with a game's files
+struct UninstallDialog {
+    app_name: String,
+    delete_saves: bool,
+    delete_prefix: bool,
+    /// Whether the game has a Wine or Proton prefix to offer deleting
+    has_prefix: bool,
 }
 
 impl LauncherApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    /// `scale` overrides `ui_scale` for this run, as `--scale` does
+    pub fn new(cc: &eframe::CreationContext<'_>, scale: Option<f32>) -> Self {
         styles::setup_custom_style(&cc.egui_ctx);
 
+        // There is no terminal to answer prompts from the GUI
+        prompt::set_non_interactive(true);
+
         let config = Config::load().unwrap_or_default();
         let auth = AuthManager::new().unwrap_or_default();
 
-        // Check if already authenticated
-        let is_authenticated = auth.is_authenticated();
+        // An expired session is renewed as the library loads, or the
+        // library opens offline when Epic can't be reached to renew it
+        let is_authenticated = auth.account_id().is_some();
+
+        // Look for a newer launcher on the configured channel in the background
+        let channel = config.update_channel;
+        let update_promise = runtime::spawn(update::check_for_update(channel));
 
-        Self {
+        throttle().set_limit(config.bandwidth_limit_bytes());
+        let bandwidth_limit = config.bandwidth_limit.unwrap_or(0);
+
+        let ui_scale = scale.unwrap_or(config.ui_scale);
+        cc.egui_ctx.set_zoom_factor(ui_scale);
+
+        let library_view = cc
+            .storage
+            .filter(|_| config.remember_gui_state)
+            .and_then(|storage| eframe::get_value(storage, LIBRARY_VIEW_KEY))
+            .unwrap_or_default();
+
+        let mut app = Self {
             state: if is_authenticated {
                 AppState::Library
             } else {
                 AppState::Login
             },
+            tab: Tab::Library,
             auth: Arc::new(Mutex::new(auth)),
             config: Arc::new(config),
             auth_view: AuthView::default(),
-            library_view: LibraryView::default(),
+            library_view,
             library_games: Vec::new(),
+            covers: CoverArt::default(),
             installed_games: Vec::new(),
-            status_message: String::new(),
+            downloads: Downloads::default(),
+            toasts: Toasts::default(),
             loading_library: false,
             library_promise: None,
+            library_error: None,
+            offline: false,
+            update_promise: Some(update_promise),
+            available_update: None,
+            bandwidth_limit,
+            ui_scale,
+            rescale: false,
+            config_watcher: ConfigWatcher::new()
+                .map_err(|e| log::warn!("Not watching the config file: {}", e))
+                .ok(),
+            game_updates_promise: None,
+            next_game_update_check: Some(Instant::now()),
+            save_sync_promise: None,
+            next_save_sync: Instant::now(),
+            game_updates: HashMap::new(),
+            runners: installed_runners(),
+            log_window: None,
+            install_dialog: None,
+            steam_export: None,
+            sync_preview_promise: None,
+            sync_preview: None,
+            game_detail: None,
+            verify_promise: None,
+            uninstall_dialog: None,
+            uninstall_promise: None,
+            logout_dialog: false,
+            move_dialog: None,
+            pin_dialog: None,
+            launch_promise: None,
+            move_promise: None,
+            shortcut_promise: None,
+            gamepad: Gamepad::spawn(cc.egui_ctx.clone()),
+        };
+
+        if is_authenticated {
+            app.load_library();
+            app.load_installed_games();
         }
+        app
     }
 
     fn handle_login(&mut self) {
@@ -67,57 +246,76 @@ impl LauncherApp {
         self.load_installed_games();
     }
 
+    /// Fetch the account's library in the background
     fn load_library(&mut self) {
         if self.loading_library {
             return;
         }
 
+        let config = (*self.config).clone();
+        let shared_auth = Arc::clone(&self.auth);
+        let auth = (*self.auth.lock().unwrap()).clone();
         self.loading_library = true;
-        let _auth = Arc::clone(&self.auth);
-        let _config = Arc::clone(&self.config);
-
-        // Create a demo library for now since Epic API integration is not complete
-        self.library_games = vec![
-            Game {
-                app_name: "demo_game_1".to_string(),
-                app_title: "Demo Game 1".to_string(),
-                app_version: "1.0.0".to_string(),
-                install_path: None,
-            },
-            Game {
-                app_name: "demo_game_2".to_string(),
-                app_title: "Epic Adventure".to_string(),
-                app_version: "2.1.0".to_string(),
-                install_path: None,
-            },
-            Game {
-                app_name: "demo_game_3".to_string(),
-                app_title: "Racing Challenge".to_string(),
-                app_version: "1.5.2".to_string(),
-                install_path: None,
-            },
-            Game {
-                app_name: "demo_game_4".to_string(),
-                app_title: "Strategy Master".to_string(),
-                app_version: "3.0.1".to_string(),
-                install_path: None,
-            },
-            Game {
-                app_name: "demo_game_5".to_string(),
-                app_title: "Space Shooter".to_string(),
-                app_version: "1.2.0".to_string(),
-                install_path: None,
-            },
-        ];
-        self.loading_library = false;
+        self.library_error = None;
+        self.library_promise = Some(runtime::spawn(async move {
+            let mut manager = GameManager::new(config, auth)?;
+            manager.refresh_session().await?;
+            *shared_auth.lock().unwrap() = manager.auth().clone();
+            manager.list_library().await
+        }));
+    }
 
-        // In real implementation, would use:
-        // let promise = Promise::spawn_async(async move {
-        //     let auth_guard = auth.lock().unwrap();
-        //     let manager = GameManager::new((*config).clone(), (*auth_guard).clone())?;
-        //     manager.list_library().await
-        // });
-        // self.library_promise = Some(promise);
+    /// Above the library while it only holds the installed games
+    fn offline_banner(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(70, 55, 20))
+            .rounding(egui::Rounding::same(6.0))
+            .inner_margin(egui::Margin::symmetric(12.0, 8.0))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(tr("gui-offline")).strong());
+                    ui.label(tr("gui-offline-detail"));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let retry = tr(if self.loading_library {
+                            "gui-retrying"
+                        } else {
+                            "gui-retry"
+                        });
+                        if ui
+                            .add_enabled(!self.loading_library, egui::Button::new(retry))
+                            .clicked()
+                        {
+                            self.load_library();
+                        }
+                    });
+                });
+            });
+        ui.add_space(10.0);
+    }
+
+    /// Shown instead of an empty library while it loads or after it failed
+    fn library_placeholder(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(100.0);
+            if self.loading_library {
+                ui.spinner();
+                ui.add_space(8.0);
+                ui.label(egui::RichText::new(tr("gui-loading-library")).size(16.0));
+            } else if let Some(error) = &self.library_error {
+                ui.label(
+                    egui::RichText::new(tr("gui-library-failed"))
+                        .size(18.0)
+                        .color(styles::ERROR_RED),
+                );
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new(error).color(styles::TEXT_SECONDARY));
+                ui.add_space(12.0);
+                if ui.button(tr("gui-retry")).clicked() {
+                    self.load_library();
+                }
+            }
+        });
     }
 
     fn load_installed_games(&mut self) {
@@ -127,130 +325,835 @@ impl LauncherApp {
             if let Ok(games) = manager.list_installed() {
                 self.installed_games = games;
             }
+
+            if let Ok(interrupted) = manager.interrupted_operations() {
+                if let Some(entry) = interrupted.first() {
+                    self.toasts.add(
+                        Level::Error,
+                        format!(
+                            "The {} of {} was interrupted. Run 'rauncher resume' to finish or undo it.",
+                            entry.operation, entry.app_name
+                        ),
+                    );
+                }
+            }
+
+            if let Ok(corrupt) = manager.corrupt_records() {
+                if !corrupt.is_empty() {
+                    self.toasts.add(
+                        Level::Error,
+                        format!(
+                            "{} installed game record(s) are unreadable. Run 'rauncher doctor --fix' to rebuild them.",
+                            corrupt.len()
+                        ),
+                    );
+                }
+            }
         }
     }
 
-    fn handle_install(&mut self, app_name: String) {
-        self.status_message = format!("Installing {}...", app_name);
+    /// Queue the install; the Downloads tab shows how it is going
+    fn handle_install(&mut self, app_name: String, create_shortcut: bool) {
+        let title = self.game_title(&app_name);
+        if self
+            .downloads
+            .push(&app_name, &title, DownloadKind::Install { create_shortcut })
+        {
+            self.toasts
+                .add(Level::Info, format!("Queued {} for install", title));
+        }
+    }
 
-        // Find the game in our library to get proper title
-        let game_title = self
-            .library_games
+    fn handle_update(&mut self, app_name: String) {
+        let title = self.game_title(&app_name);
+        if self.downloads.push(&app_name, &title, DownloadKind::Update) {
+            self.toasts
+                .add(Level::Info, format!("Queued the update of {}", title));
+        }
+    }
+
+    fn handle_update_all(&mut self) {
+        let mut pending: Vec<(String, String)> = self
+            .game_updates
+            .keys()
+            .filter(|app_name| !self.downloads.contains(app_name))
+            .map(|app_name| (self.game_title(app_name), app_name.clone()))
+            .collect();
+        pending.sort();
+
+        for (title, app_name) in &pending {
+            self.downloads.push(app_name, title, DownloadKind::Update);
+        }
+        self.toasts
+            .add(Level::Info, format!("Queued {} update(s)", pending.len()));
+    }
+
+    fn game_title(&self, app_name: &str) -> String {
+        self.library_games
             .iter()
-            .find(|g| g.app_name == app_name)
-            .map(|g| g.app_title.clone())
-            .unwrap_or_else(|| format!("Game: {}", app_name));
+            .find(|game| game.app_name == app_name)
+            .map_or_else(|| app_name.to_string(), |game| game.app_title.clone())
+    }
+
+    fn finish_download(&mut self, download: Download, result: Result<()>) {
+        let (done, failed) = match download.kind {
+            DownloadKind::Install { .. } => ("Installed", "Failed to install"),
+            DownloadKind::Update => ("Updated", "Failed to update"),
+            DownloadKind::Repair => ("Repaired", "Failed to repair"),
+        };
+
+        match result {
+            Ok(()) => {
+                self.toasts
+                    .add(Level::Success, format!("{} {}", done, download.title));
+                self.notify(&download.title, done);
+                if download.kind == DownloadKind::Update {
+                    self.game_updates.remove(&download.app_name);
+                }
+                if let Some(detail) = self
+                    .game_detail
+                    .as_mut()
+                    .filter(|detail| detail.app_name == download.app_name)
+                {
+                    detail.damaged_files = None;
+                }
+                self.load_installed_games();
+            }
+            Err(Error::Cancelled) => {
+                self.toasts.add(
+                    Level::Info,
+                    format!(
+                        "Cancelled {}. Starting it again picks up where it stopped.",
+                        download.title
+                    ),
+                );
+            }
+            Err(e) => {
+                self.notify(&download.title, &format!("{}: {}", failed, e.summary()));
+                self.toasts
+                    .error(&format!("{} {}", failed, download.title), &e);
+            }
+        }
+    }
+
+    fn handle_show_details(&mut self, app_name: String) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+        self.game_detail = Some(GameDetailView::open(app_name, config, auth));
+    }
+
+    /// Hash the game's files in the background; files unchanged since the
+    /// last verify are trusted
+    fn handle_verify(&mut self, app_name: String) {
+        if self.verify_promise.is_some() {
+            self.toasts
+                .add(Level::Info, "Another game is being verified");
+            return;
+        }
 
-        let game_version = self
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+        self.toasts
+            .add(Level::Info, format!("Verifying {}...", app_name));
+        self.verify_promise = Some(Promise::spawn_thread("verify", move || {
+            let report = GameManager::new(config, auth)
+                .and_then(|manager| manager.verify_game(&app_name, true));
+            (app_name, report)
+        }));
+    }
+
+    fn finish_verify(&mut self, app_name: String, result: Result<VerifyReport>) {
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                return self
+                    .toasts
+                    .error(&format!("Failed to verify {}", app_name), &e)
+            }
+        };
+
+        let damaged = report.failed_files().count();
+        if damaged == 0 {
+            self.toasts.add(
+                Level::Success,
+                format!(
+                    "All {} file(s) of {} are intact",
+                    report.checked_files, app_name
+                ),
+            );
+        } else {
+            self.toasts.add(
+                Level::Error,
+                format!(
+                    "{} file(s) of {} are missing or corrupt. Use Repair to download them again.",
+                    damaged, app_name
+                ),
+            );
+        }
+        if let Some(detail) = self.game_detail.as_mut().filter(|d| d.app_name == app_name) {
+            detail.damaged_files = Some(damaged);
+        }
+    }
+
+    fn handle_repair(&mut self, app_name: String) {
+        let title = self.game_title(&app_name);
+        if self.downloads.push(&app_name, &title, DownloadKind::Repair) {
+            self.toasts
+                .add(Level::Info, format!("Queued the repair of {}", title));
+        }
+    }
+
+    fn handle_library_action(&mut self, action: LibraryAction) {
+        match action {
+            LibraryAction::ShowDetails(app_name) => {
+                self.handle_show_details(app_name);
+            }
+            LibraryAction::Install(app_name) => {
+                self.install_dialog = Some((app_name, true));
+            }
+            LibraryAction::Launch(app_name) => {
+                self.handle_launch(app_name, None);
+            }
+            LibraryAction::Update(app_name) => {
+                self.handle_update(app_name);
+            }
+            LibraryAction::UpdateAll => {
+                self.handle_update_all();
+            }
+            LibraryAction::Uninstall(app_name) => {
+                let has_prefix =
+                    GameManager::prefix_path(&app_name).is_ok_and(|prefix| prefix.exists());
+                self.uninstall_dialog = Some(UninstallDialog {
+                    app_name,
+                    delete_saves: false,
+                    delete_prefix: false,
+                    has_prefix,
+                });
+            }
+            LibraryAction::SetHold(app_name, hold) => {
+                self.handle_set_hold(app_name, hold);
+            }
+            LibraryAction::SetRunner(app_name, runner) => {
+                self.handle_set_runner(app_name, runner);
+            }
+            LibraryAction::Winetricks(app_name) => {
+                self.handle_winetricks(app_name);
+            }
+            LibraryAction::ViewLogs(app_name) => {
+                self.handle_view_logs(app_name);
+            }
+            LibraryAction::AddToSteam(app_name) => {
+                self.handle_add_to_steam(app_name);
+            }
+            LibraryAction::ClearShaders(app_name) => {
+                self.handle_clear_shaders(app_name);
+            }
+            LibraryAction::PreviewSync(app_name) => {
+                self.handle_preview_sync(app_name);
+            }
+            LibraryAction::Verify(app_name) => {
+                self.handle_verify(app_name);
+            }
+            LibraryAction::Repair(app_name) => {
+                self.handle_repair(app_name);
+            }
+            LibraryAction::Move(app_name) => {
+                let destination = self.config.install_dir.display().to_string();
+                self.move_dialog = Some((app_name, destination));
+            }
+            LibraryAction::OpenFolder(app_name) => {
+                self.handle_open_folder(app_name);
+            }
+            LibraryAction::CreateShortcut(app_name) => {
+                self.handle_create_shortcut(app_name);
+            }
+            LibraryAction::Settings(app_name) => {
+                self.handle_show_details(app_name);
+                if let Some(detail) = &mut self.game_detail {
+                    detail.show_settings = true;
+                }
+            }
+        }
+    }
+
+    /// The open game's page, closed if the game left the library
+    /// A window that asks for confirmation or shows a result is open
+    fn dialog_open(&self) -> bool {
+        self.install_dialog.is_some()
+            || self.uninstall_dialog.is_some()
+            || self.logout_dialog
+            || self.move_dialog.is_some()
+            || self.pin_dialog.is_some()
+            || self.log_window.is_some()
+            || self.sync_preview.is_some()
+            || self.toasts.details_open()
+    }
+
+    fn game_detail_page(&mut self, ui: &mut egui::Ui) {
+        let Some(detail) = &mut self.game_detail else {
+            return;
+        };
+        let Some(game) = self
             .library_games
             .iter()
-            .find(|g| g.app_name == app_name)
-            .map(|g| g.app_version.clone())
-            .unwrap_or_else(|| "1.0.0".to_string());
+            .find(|game| game.app_name == detail.app_name)
+        else {
+            self.game_detail = None;
+            return;
+        };
+
+        let action = detail.ui(
+            ui,
+            game,
+            self.installed_games
+                .iter()
+                .find(|installed| installed.app_name == game.app_name),
+            self.downloads.contains(&game.app_name),
+            self.game_updates.get(&game.app_name).map(String::as_str),
+            &self.runners,
+            self.config.game_runners.get(&game.app_name),
+        );
+        match action {
+            Some(DetailAction::Back) => self.game_detail = None,
+            Some(DetailAction::Game(action)) => self.handle_library_action(action),
+            None => {}
+        }
+    }
 
-        // For demo purposes, we'll create a mock installation
-        let config = Arc::clone(&self.config);
-        let app_name_clone = app_name.clone();
+    fn handle_downloads_action(&mut self, action: DownloadsAction) {
+        match action {
+            DownloadsAction::Pause(app_name) => self.downloads.pause(&app_name),
+            DownloadsAction::Resume(app_name) => self.downloads.resume(&app_name),
+            DownloadsAction::Cancel(app_name) => self.downloads.cancel(&app_name),
+            DownloadsAction::MoveUp(app_name) => self.downloads.move_up(&app_name),
+            DownloadsAction::MoveDown(app_name) => self.downloads.move_down(&app_name),
+        }
+    }
 
-        std::thread::spawn(move || {
-            std::thread::sleep(std::time::Duration::from_secs(2));
+    /// Start the game in the background: downloading its cloud saves and
+    /// installing prerequisites can take a while. Past the daily play time
+    /// limit, the parental PIN is asked for first and the launch retried
+    /// with it.
+    fn handle_launch(&mut self, app_name: String, pin: Option<String>) {
+        if self.launch_promise.is_some() {
+            self.toasts.add(Level::Info, "Another game is starting");
+            return;
+        }
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+        let offline = self.offline;
 
-            // Create the installation directory
-            let install_path = config.install_dir.join(&app_name_clone);
-            if let Err(e) = std::fs::create_dir_all(&install_path) {
-                eprintln!("Failed to create install directory: {}", e);
+        let manager = match GameManager::new(config, auth) {
+            Ok(manager) => manager,
+            Err(e) => return self.toasts.error("Error", &e),
+        };
+        if pin.is_none() && matches!(manager.daily_limit_reached(), Ok(true)) {
+            self.pin_dialog = Some((app_name, String::new()));
+            return;
+        }
+
+        self.toasts
+            .add(Level::Info, format!("Starting {}...", app_name));
+        self.launch_promise = Some(Promise::spawn_thread("launch", move || {
+            let launched = launch(manager, &app_name, pin.as_deref(), offline);
+            (app_name, launched)
+        }));
+    }
+
+    fn finish_launch(&mut self, app_name: String, result: Result<Option<Duration>>) {
+        match result {
+            Ok(Some(left)) => self.toasts.add(
+                Level::Success,
+                format!(
+                    "Launched {} ({} of play time left today)",
+                    app_name,
+                    format::duration(left)
+                ),
+            ),
+            Ok(None) => self
+                .toasts
+                .add(Level::Success, format!("Launched {}", app_name)),
+            Err(e) => self
+                .toasts
+                .error(&format!("Failed to launch {}", app_name), &e),
+        }
+    }
+
+    fn check_game_updates(&mut self) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+        let apply = config.auto_update && config.auto_apply_updates;
+
+        self.game_updates_promise = Some(runtime::spawn(async move {
+            GameManager::new(config, auth)?
+                .with_initiator(Initiator::Gui)
+                .check_all_for_updates(apply)
+                .await
+        }));
+    }
+
+    fn sync_saves(&mut self) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        self.save_sync_promise = Some(runtime::spawn(async move {
+            GameManager::new(config, auth)?
+                .with_initiator(Initiator::Gui)
+                .sync_all_saves()
+                .await
+        }));
+    }
+
+    /// Only conflicts are worth interrupting for; clean syncs are logged
+    fn finish_save_sync(&mut self, result: Result<Vec<SaveSyncReport>>) {
+        self.next_save_sync = Instant::now() + self.config.save_sync_interval();
+
+        let reports = match result {
+            Ok(reports) => reports,
+            Err(e) => {
+                log::warn!("Background save sync failed: {}", e);
                 return;
             }
+        };
 
-            // Create a demo installed game entry
-            let game = InstalledGame {
-                app_name: app_name_clone.clone(),
-                app_title: game_title,
-                app_version: game_version,
-                install_path: install_path.clone(),
-                executable: "game.sh".to_string(),
-            };
-
-            // Create a simple demo executable script
-            let executable_path = install_path.join("game.sh");
-            let script_content = format!(
-                "#!/bin/bash\necho 'Launching {}'\necho 'This is a demo game executable'\n",
-                game.app_title
+        let conflicted: Vec<&str> = reports
+            .iter()
+            .filter(|report| !report.conflicts.is_empty())
+            .map(|report| report.app_name.as_str())
+            .collect();
+        for report in reports.iter().filter(|report| report.conflicts.is_empty()) {
+            log::info!(
+                "Synced {} save file(s) of {}",
+                report.transferred,
+                report.app_name
+            );
+        }
+        if !conflicted.is_empty() {
+            let message = format!(
+                "Saves of {} changed locally and in the cloud. Use \"Preview sync\" to review them.",
+                conflicted.join(", ")
             );
-            if let Err(e) = std::fs::write(&executable_path, script_content) {
-                eprintln!("Failed to create demo executable: {}", e);
+            self.notify("Save conflict", &message);
+            self.toasts.add(Level::Error, message);
+        }
+    }
+
+    fn finish_game_update_check(&mut self, result: Result<Vec<GameUpdate>>) {
+        self.next_game_update_check = self
+            .config
+            .auto_update
+            .then(|| Instant::now() + self.config.auto_update_interval());
+
+        let updates = match result {
+            Ok(updates) => updates,
+            Err(e) => {
+                log::warn!("Game update check failed: {}", e);
+                return;
             }
+        };
 
-            // Make it executable on Unix
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Ok(metadata) = std::fs::metadata(&executable_path) {
-                    let mut perms = metadata.permissions();
-                    perms.set_mode(0o755);
-                    let _ = std::fs::set_permissions(&executable_path, perms);
+        let (applied, available): (Vec<_>, Vec<_>) =
+            updates.into_iter().partition(|update| update.applied);
+        // Each new version is announced once, not at every check
+        let found: Vec<String> = available
+            .iter()
+            .filter(|update| self.game_updates.get(&update.app_name) != Some(&update.version))
+            .map(|update| self.game_title(&update.app_name))
+            .collect();
+        self.game_updates = available
+            .into_iter()
+            .map(|update| (update.app_name, update.version))
+            .collect();
+
+        if !applied.is_empty() {
+            let titles: Vec<String> = applied
+                .iter()
+                .map(|update| self.game_title(&update.app_name))
+                .collect();
+            self.notify("Games updated", &titles.join(", "));
+            self.load_installed_games();
+            self.toasts
+                .add(Level::Success, format!("Updated {} game(s)", applied.len()));
+        } else if !self.game_updates.is_empty() {
+            self.toasts.add(
+                Level::Info,
+                format!("Updates available for {} game(s)", self.game_updates.len()),
+            );
+        }
+        if !found.is_empty() {
+            self.notify("Updates available", &found.join(", "));
+        }
+    }
+
+    /// Swap in settings changed on disk. Operations read the config when they
+    /// start, so only new installs and launches use the new values.
+    fn reload_config(&mut self, result: Result<Config>) {
+        match result {
+            Ok(config) => {
+                throttle().set_limit(config.bandwidth_limit_bytes());
+                self.bandwidth_limit = config.bandwidth_limit.unwrap_or(0);
+                if config.ui_scale != self.config.ui_scale {
+                    self.ui_scale = config.ui_scale;
+                    self.rescale = true;
+                }
+                if config.auto_update && self.next_game_update_check.is_none() {
+                    self.next_game_update_check = Some(Instant::now());
+                }
+                self.config = Arc::new(config);
+                if matches!(self.state, AppState::Library) {
+                    self.installed_games.clear();
+                    self.load_installed_games();
                 }
+                self.toasts.add(Level::Info, "Settings reloaded");
             }
+            Err(e) => self.toasts.error("Settings not reloaded", &e),
+        }
+    }
 
-            // Save the installation record
-            if let Err(e) = game.save(&config) {
-                eprintln!("Failed to save game installation: {}", e);
-            }
-        });
+    /// A desktop notification, for news from the background that the
+    /// launcher window may be out of sight for
+    fn notify(&self, title: &str, message: &str) {
+        if !self.config.desktop_notifications {
+            return;
+        }
+        let (title, message) = (title.to_string(), message.to_string());
+        // notify-send may take a moment; the frame shouldn't wait on it
+        std::thread::spawn(move || session::notify(&title, &message));
     }
 
-    fn handle_launch(&mut self, app_name: String) {
+    /// Uninstall in the background, as deleting a large game takes a while.
+    /// Saves are looked up first, since that needs the install record, and
+    /// trashed only once the game is gone, before its prefix.
+    fn handle_uninstall(&mut self, dialog: UninstallDialog) {
         let config = (*self.config).clone();
         let auth = (*self.auth.lock().unwrap()).clone();
+        let UninstallDialog {
+            app_name,
+            delete_saves,
+            delete_prefix,
+            ..
+        } = dialog;
 
-        match GameManager::new(config, auth) {
-            Ok(manager) => match manager.launch_game(&app_name) {
-                Ok(()) => {
-                    self.status_message = format!("Launched {}", app_name);
+        self.toasts
+            .add(Level::Info, format!("Uninstalling {}...", app_name));
+        self.uninstall_promise = Some(runtime::spawn(async move {
+            let uninstalled = async {
+                let manager = GameManager::new(config, auth)?.with_initiator(Initiator::Gui);
+                let saves = if delete_saves {
+                    Some(manager.local_saves_to_delete(&app_name).await?)
+                } else {
+                    None
+                };
+                manager.uninstall_game(&app_name)?;
+                if let Some(saves) = saves {
+                    manager.trash_local_saves(&app_name, &saves)?;
                 }
-                Err(e) => {
-                    self.status_message = format!("Failed to launch {}: {}", app_name, e);
+                if delete_prefix {
+                    manager.delete_prefix(&app_name)?;
                 }
-            },
-            Err(e) => {
-                self.status_message = format!("Error: {}", e);
+                Ok(())
+            };
+            let result = uninstalled.await;
+            (app_name, result)
+        }));
+    }
+
+    fn finish_uninstall(&mut self, app_name: String, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                self.toasts
+                    .add(Level::Success, format!("Uninstalled {}", app_name));
+                self.load_installed_games();
             }
+            Err(e) => self
+                .toasts
+                .error(&format!("Failed to uninstall {}", app_name), &e),
+        }
+    }
+
+    fn logout(&mut self) {
+        self.downloads.cancel_all();
+        if let Ok(mut auth) = self.auth.lock() {
+            let _ = auth.logout();
         }
+        self.state = AppState::Login;
+        self.library_games.clear();
+        self.installed_games.clear();
     }
 
-    fn handle_uninstall(&mut self, app_name: String) {
+    fn handle_set_hold(&mut self, app_name: String, hold: bool) {
         let config = (*self.config).clone();
         let auth = (*self.auth.lock().unwrap()).clone();
 
         match GameManager::new(config, auth) {
-            Ok(manager) => match manager.uninstall_game(&app_name) {
+            Ok(manager) => match manager.set_hold_updates(&app_name, hold) {
                 Ok(()) => {
-                    self.status_message = format!("Uninstalled {}", app_name);
+                    let message = if hold {
+                        format!("Updates for {} are on hold", app_name)
+                    } else {
+                        format!("Updates for {} are no longer on hold", app_name)
+                    };
+                    self.toasts.add(Level::Success, message);
                     self.load_installed_games();
                 }
-                Err(e) => {
-                    self.status_message = format!("Failed to uninstall {}: {}", app_name, e);
-                }
+                Err(e) => self.toasts.error("Failed to update hold setting", &e),
             },
-            Err(e) => {
-                self.status_message = format!("Error: {}", e);
+            Err(e) => self.toasts.error("Error", &e),
+        }
+    }
+
+    /// Move the game's files in the background, as moving to another drive
+    /// copies them
+    fn handle_move(&mut self, app_name: String, destination: PathBuf) {
+        if self.move_promise.is_some() {
+            self.toasts.add(Level::Info, "Another game is being moved");
+            return;
+        }
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        self.toasts
+            .add(Level::Info, format!("Moving {}...", app_name));
+        self.move_promise = Some(Promise::spawn_thread("move", move || {
+            let moved = GameManager::new(config, auth)
+                .and_then(|manager| manager.move_game(&app_name, &destination));
+            (app_name, moved)
+        }));
+    }
+
+    fn finish_move(&mut self, app_name: String, result: Result<PathBuf>) {
+        match result {
+            Ok(path) => {
+                self.toasts.add(
+                    Level::Success,
+                    format!("Moved {} to {}", app_name, path.display()),
+                );
+                self.load_installed_games();
             }
+            Err(e) => self
+                .toasts
+                .error(&format!("Failed to move {}", app_name), &e),
+        }
+    }
+
+    /// Show the game's folder in the desktop's file manager
+    fn handle_open_folder(&mut self, app_name: String) {
+        let Some(game) = self
+            .installed_games
+            .iter()
+            .find(|game| game.app_name == app_name)
+        else {
+            return;
+        };
+        match std::process::Command::new("xdg-open")
+            .arg(&game.install_path)
+            .spawn()
+        {
+            Ok(mut child) => {
+                std::thread::spawn(move || child.wait());
+            }
+            Err(e) => self
+                .toasts
+                .error("Failed to open the install folder", &e.into()),
+        }
+    }
+
+    /// Add the game to the application menu in the background, as its icon
+    /// comes from the store
+    fn handle_create_shortcut(&mut self, app_name: String) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        self.shortcut_promise = Some(runtime::spawn(async move {
+            let created = match GameManager::new(config, auth) {
+                Ok(manager) => manager.create_shortcut(&app_name).await,
+                Err(e) => Err(e),
+            };
+            (app_name, created)
+        }));
+    }
+
+    /// Open the winetricks GUI on the game's prefix. It runs on its own, so
+    /// the launcher stays usable meanwhile.
+    fn handle_winetricks(&mut self, app_name: String) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        match GameManager::new(config, auth).and_then(|manager| {
+            let mut child = manager.winetricks_command(&app_name, &[])?.spawn()?;
+            std::thread::spawn(move || child.wait());
+            Ok(())
+        }) {
+            Ok(()) => self.toasts.add(
+                Level::Success,
+                format!("Opened winetricks for {}", app_name),
+            ),
+            Err(e) => self.toasts.error("Failed to run winetricks", &e),
+        }
+    }
+
+    fn handle_view_logs(&mut self, app_name: String) {
+        match logs::log_path(&app_name).and_then(|path| logs::tail(&path, LOG_VIEW_BYTES)) {
+            Ok(log) => {
+                let lines = log.lines().map(str::to_string).collect();
+                self.log_window = Some((app_name, lines));
+            }
+            Err(_) => self
+                .toasts
+                .add(Level::Info, format!("No logs for {} yet", app_name)),
+        }
+    }
+
+    /// Add the game to Steam in the background, as fetching its artwork
+    /// takes a moment
+    fn handle_add_to_steam(&mut self, app_name: String) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        match GameManager::new(config, auth) {
+            Ok(manager) => {
+                self.toasts
+                    .add(Level::Info, format!("Adding {} to Steam...", app_name));
+                self.steam_export = Some(runtime::spawn(async move {
+                    manager.export_to_steam(&app_name).await.map(|_| app_name)
+                }));
+            }
+            Err(e) => self.toasts.error("Error", &e),
+        }
+    }
+
+    /// Work out in the background what syncing the game's saves both ways
+    /// would do, as the cloud saves have to be fetched to compare them
+    fn handle_preview_sync(&mut self, app_name: String) {
+        let config = (*self.config).clone();
+        let auth = (*self.auth.lock().unwrap()).clone();
+
+        match GameManager::new(config, auth) {
+            Ok(manager) => {
+                self.toasts
+                    .add(Level::Info, format!("Comparing saves of {}...", app_name));
+                self.sync_preview_promise = Some(runtime::spawn(async move {
+                    manager
+                        .plan_cloud_sync(&app_name, true, true)
+                        .await
+                        .map(|plan| (app_name, plan))
+                }));
+            }
+            Err(e) => self.toasts.error("Error", &e),
+        }
+    }
+
+    fn handle_clear_shaders(&mut self, app_name: String) {
+        match shaders::clear_cache(&app_name) {
+            Ok(freed) => self.toasts.add(
+                Level::Success,
+                format!(
+                    "Cleared the shader cache of {} ({})",
+                    app_name,
+                    format::size(freed)
+                ),
+            ),
+            Err(e) => self.toasts.error("Failed to clear the shader cache", &e),
+        }
+    }
+
+    /// Keep the scale picked in the GUI for the next start
+    fn save_ui_scale(&mut self) {
+        let mut config = (*self.config).clone();
+        config.ui_scale = self.ui_scale;
+        match config.save() {
+            Ok(()) => self.config = Arc::new(config),
+            Err(e) => self.toasts.error("Failed to save the UI scale", &e),
+        }
+    }
+
+    fn handle_set_runner(&mut self, app_name: String, runner: Option<Runner>) {
+        let mut config = (*self.config).clone();
+        match &runner {
+            Some(runner) => config.game_runners.insert(app_name.clone(), runner.clone()),
+            None => config.game_runners.remove(&app_name),
+        };
+
+        match config.save() {
+            Ok(()) => {
+                let message = match runner {
+                    Some(runner) => format!("{} will run with {}", app_name, runner),
+                    None => format!("{} will use the default runner", app_name),
+                };
+                self.toasts.add(Level::Success, message);
+                self.config = Arc::new(config);
+            }
+            Err(e) => self.toasts.error("Failed to save runner setting", &e),
         }
     }
 }
 
 impl eframe::App for LauncherApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        if self.config.remember_gui_state {
+            eframe::set_value(storage, LIBRARY_VIEW_KEY, &self.library_view);
+        }
+    }
+
+    fn persist_egui_memory(&self) -> bool {
+        self.config.remember_gui_state
+    }
+
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        self.gamepad.inject(raw_input);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if std::mem::take(&mut self.rescale) {
+            ctx.set_zoom_factor(self.ui_scale);
+        }
+
         // Check for library loading completion
         if let Some(promise) = &self.library_promise {
             if let Some(result) = promise.ready() {
                 match result {
                     Ok(games) => {
                         self.library_games = games.clone();
-                        self.status_message = "Library loaded successfully".to_string();
+                        self.covers.load(
+                            games,
+                            (*self.config).clone(),
+                            (*self.auth.lock().unwrap()).clone(),
+                        );
+                        self.toasts
+                            .add(Level::Success, "Library loaded successfully");
+                        if self.offline {
+                            // Whatever was missed while offline
+                            self.offline = false;
+                            self.next_game_update_check.get_or_insert_with(Instant::now);
+                        }
+                    }
+                    Err(e) if e.is_unreachable() => {
+                        let games = GameManager::new(
+                            (*self.config).clone(),
+                            (*self.auth.lock().unwrap()).clone(),
+                        )
+                        .and_then(|manager| manager.offline_library());
+                        match games {
+                            Ok(games) => {
+                                log::warn!("Library unavailable, showing installed games: {}", e);
+                                self.covers.load(
+                                    &games,
+                                    (*self.config).clone(),
+                                    (*self.auth.lock().unwrap()).clone(),
+                                );
+                                self.library_games = games;
+                                self.offline = true;
+                            }
+                            Err(_) => self.library_error = Some(e.summary()),
+                        }
+                    }
+                    Err(e) if e.requires_login() => {
+                        self.toasts.error("Sign in again to load the library", e);
+                        self.state = AppState::Login;
                     }
                     Err(e) => {
-                        self.status_message = format!("Failed to load library: {}", e);
+                        self.toasts.error("Failed to load library", e);
+                        self.library_error = Some(e.summary());
                     }
                 }
                 self.loading_library = false;
@@ -258,68 +1161,540 @@ impl eframe::App for LauncherApp {
             }
         }
 
+        if let Some(promise) = &self.update_promise {
+            if let Some(result) = promise.ready() {
+                match result {
+                    Ok(release) => self.available_update = release.clone(),
+                    Err(e) => log::debug!("Launcher update check failed: {}", e),
+                }
+                self.update_promise = None;
+            }
+        }
+
+        // Both wait for the library, which renews an expired session first
+        if matches!(self.state, AppState::Library)
+            && !self.offline
+            && !self.loading_library
+            && self.game_updates_promise.is_none()
+            && self
+                .next_game_update_check
+                .is_some_and(|at| Instant::now() >= at)
+        {
+            self.check_game_updates();
+        }
+
+        if self.config.background_save_sync
+            && matches!(self.state, AppState::Library)
+            && !self.offline
+            && !self.loading_library
+            && self.save_sync_promise.is_none()
+            && Instant::now() >= self.next_save_sync
+        {
+            self.sync_saves();
+        }
+
+        if self
+            .save_sync_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok(result)) = self.save_sync_promise.take().map(Promise::try_take) {
+                self.finish_save_sync(result);
+            }
+        }
+
+        if self
+            .game_updates_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok(result)) = self.game_updates_promise.take().map(Promise::try_take) {
+                self.finish_game_update_check(result);
+            }
+        }
+
+        if self
+            .steam_export
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok(result)) = self.steam_export.take().map(Promise::try_take) {
+                match result {
+                    Ok(app_name) => self.toasts.add(
+                        Level::Success,
+                        format!("Added {} to Steam. Restart Steam to see it.", app_name),
+                    ),
+                    Err(e) => self.toasts.error("Failed to add to Steam", &e),
+                }
+            }
+        }
+
+        if self
+            .sync_preview_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok(result)) = self.sync_preview_promise.take().map(Promise::try_take) {
+                match result {
+                    Ok(preview) => self.sync_preview = Some(preview),
+                    Err(e) => self.toasts.error("Failed to compare saves", &e),
+                }
+            }
+        }
+
+        if self
+            .launch_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok((app_name, result))) = self.launch_promise.take().map(Promise::try_take)
+            {
+                self.finish_launch(app_name, result);
+            }
+        }
+
+        if self
+            .verify_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok((app_name, result))) = self.verify_promise.take().map(Promise::try_take)
+            {
+                self.finish_verify(app_name, result);
+            }
+        }
+
+        if self
+            .uninstall_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok((app_name, result))) =
+                self.uninstall_promise.take().map(Promise::try_take)
+            {
+                self.finish_uninstall(app_name, result);
+            }
+        }
+
+        if self
+            .move_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok((app_name, result))) = self.move_promise.take().map(Promise::try_take) {
+                self.finish_move(app_name, result);
+            }
+        }
+
+        if self
+            .shortcut_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok((app_name, result))) =
+                self.shortcut_promise.take().map(Promise::try_take)
+            {
+                match result {
+                    Ok(_) => {
+                        self.toasts.add(
+                            Level::Success,
+                            format!("Added {} to the application menu", app_name),
+                        );
+                    }
+                    Err(e) => self.toasts.error("Failed to create shortcut", &e),
+                }
+            }
+        }
+
+        self.covers.poll(ctx);
+
+        for (download, result) in self.downloads.poll(&self.config, &self.auth) {
+            self.finish_download(download, result);
+        }
+
+        if let Some(result) = self.config_watcher.as_mut().and_then(|w| w.poll()) {
+            self.reload_config(result);
+        }
+
         egui::TopBottomPanel::top("top_panel")
-            .frame(egui::Frame::none()
-                .fill(egui::Color32::from_rgb(22, 24, 28))
-                .inner_margin(egui::Margin::symmetric(20.0, 15.0)))
+            .frame(
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgb(22, 24, 28))
+                    .inner_margin(egui::Margin::symmetric(20.0, 15.0)),
+            )
             .show(ctx, |ui| {
                 let mut logout_requested = false;
                 let is_authenticated = matches!(self.state, AppState::Library);
                 Header::show(ui, is_authenticated, &mut logout_requested);
-                
+
+                if let Some(release) = &self.available_update {
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "rauncher {} is available ({} channel)",
+                                release.version(),
+                                self.config.update_channel
+                            ))
+                            .size(13.0)
+                            .color(styles::EPIC_BLUE),
+                        );
+                        ui.hyperlink_to("Release notes", &release.html_url);
+                    });
+                }
+
+                if is_authenticated {
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.tab, Tab::Library, tr("gui-tab-library"));
+                        let downloads = if self.downloads.is_empty() {
+                            tr("gui-tab-downloads")
+                        } else {
+                            tr_args(
+                                "gui-tab-downloads-count",
+                                &[("count", &self.downloads.len())],
+                            )
+                        };
+                        ui.selectable_value(&mut self.tab, Tab::Downloads, downloads);
+                        ui.separator();
+
+                        ui.label(egui::RichText::new(tr("gui-download-limit")).size(13.0));
+                        let limit = egui::DragValue::new(&mut self.bandwidth_limit)
+                            .speed(64.0)
+                            .custom_formatter(|value, _| {
+                                if value == 0.0 {
+                                    tr("gui-unlimited")
+                                } else {
+                                    format!("{} KiB/s", value)
+                                }
+                            });
+                        if ui.add(limit).changed() {
+                            let bytes = Some(self.bandwidth_limit * 1024).filter(|&b| b > 0);
+                            throttle().set_limit(bytes);
+                        }
+                        ui.separator();
+
+                        ui.label(egui::RichText::new(tr("gui-ui-scale")).size(13.0));
+                        let scale = ui.add(
+                            egui::Slider::new(&mut self.ui_scale, config::UI_SCALE_RANGE)
+                                .step_by(0.25)
+                                .suffix("×"),
+                        );
+                        // Rescaling under the pointer would move the slider
+                        // away from it, so only once it is let go
+                        if scale.drag_stopped() || (scale.changed() && !scale.dragged()) {
+                            self.rescale = true;
+                            self.save_ui_scale();
+                        }
+                    });
+                }
+
                 if logout_requested {
-                    if let Ok(mut auth) = self.auth.lock() {
-                        let _ = auth.logout();
+                    if self.downloads.is_empty() {
+                        self.logout();
+                    } else {
+                        self.logout_dialog = true;
                     }
-                    self.state = AppState::Login;
-                    self.library_games.clear();
-                    self.installed_games.clear();
                 }
             });
 
+        // Dialogs take the navigation keys while they are open
+        let nav = if self.dialog_open() {
+            None
+        } else {
+            navigation::pressed(ctx)
+        };
+
         egui::CentralPanel::default().show(ctx, |ui| {
             match self.state {
                 AppState::Login => {
-                    if self.auth_view.ui(ui, &mut self.auth.lock().unwrap()) {
+                    // The QR code makes the page taller than small windows
+                    let logged_in = egui::ScrollArea::vertical()
+                        .show(ui, |ui| {
+                            self.auth_view.ui(ui, &mut self.auth.lock().unwrap())
+                        })
+                        .inner;
+                    if logged_in {
                         self.handle_login();
                     }
                 }
+                AppState::Library if self.tab == Tab::Downloads => {
+                    if let Some(action) = DownloadsView::ui(ui, &self.downloads) {
+                        self.handle_downloads_action(action);
+                    }
+                }
+                AppState::Library if self.game_detail.is_some() => {
+                    if nav == Some(Nav::Back) {
+                        self.game_detail = None;
+                    } else {
+                        self.game_detail_page(ui);
+                    }
+                }
                 AppState::Library => {
-                    if let Some(action) =
-                        self.library_view
-                            .ui(ui, &self.library_games, &self.installed_games)
+                    if self.offline {
+                        self.offline_banner(ui);
+                    }
+                    if self.library_games.is_empty()
+                        && (self.loading_library || self.library_error.is_some())
                     {
-                        match action {
-                            LibraryAction::Install(app_name) => {
-                                self.handle_install(app_name.clone());
-                                // Mark installation complete after delay
-                                let mut view = self.library_view.clone();
-                                let app_name_clone = app_name.clone();
-                                std::thread::spawn(move || {
-                                    std::thread::sleep(std::time::Duration::from_secs(3));
-                                    view.mark_installation_complete(&app_name_clone);
-                                });
-                            }
-                            LibraryAction::Launch(app_name) => {
-                                self.handle_launch(app_name);
-                            }
-                            LibraryAction::Uninstall(app_name) => {
-                                self.handle_uninstall(app_name);
-                            }
-                        }
+                        self.library_placeholder(ui);
+                    } else if let Some(action) = self.library_view.ui(
+                        ui,
+                        &self.library_games,
+                        &self.covers,
+                        &self.installed_games,
+                        &self.downloads,
+                        &self.game_updates,
+                        &self.runners,
+                        &self.config.game_runners,
+                        nav,
+                    ) {
+                        self.handle_library_action(action);
                     }
                 }
             }
+        });
+
+        if let Some((app_name, create_shortcut)) = &mut self.install_dialog {
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new(tr_args("gui-install-title", &[("game", app_name)]))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.checkbox(create_shortcut, tr("gui-create-desktop-shortcut"));
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let install = ui.button(tr("gui-install"));
+                        let cancel = ui.button(tr("gui-cancel"));
+                        confirmed = install.clicked();
+                        cancelled = cancel.clicked() || dialog_keys(ui, &install);
+                    });
+                });
 
-            // Status bar at bottom using StatusBar component
-            let mut clear_status = false;
-            StatusBar::show(ui, &self.status_message, &mut clear_status);
-            if clear_status {
-                self.status_message.clear();
+            if confirmed {
+                let (app_name, create_shortcut) = self.install_dialog.take().unwrap();
+                self.handle_install(app_name, create_shortcut);
+            } else if cancelled {
+                self.install_dialog = None;
             }
-        });
+        }
+
+        if let Some(dialog) = &mut self.uninstall_dialog {
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new(tr_args(
+                "gui-uninstall-title",
+                &[("game", &dialog.app_name)],
+            ))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr("gui-uninstall-detail"));
+                ui.add_space(8.0);
+                ui.checkbox(&mut dialog.delete_saves, tr("gui-delete-saves"));
+                if dialog.has_prefix {
+                    ui.checkbox(&mut dialog.delete_prefix, tr("gui-delete-prefix"));
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let uninstall = egui::Button::new(tr("gui-uninstall")).fill(styles::ERROR_RED);
+                    confirmed = ui.add(uninstall).clicked();
+                    let cancel = ui.button(tr("gui-cancel"));
+                    cancelled = cancel.clicked() || dialog_keys(ui, &cancel);
+                });
+            });
+
+            if confirmed {
+                let dialog = self.uninstall_dialog.take().unwrap();
+                self.handle_uninstall(dialog);
+            } else if cancelled {
+                self.uninstall_dialog = None;
+            }
+        }
+
+        if self.logout_dialog {
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new(tr("gui-logout-title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr_args(
+                        "gui-logout-detail",
+                        &[("count", &self.downloads.len())],
+                    ));
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        confirmed = ui.button(tr("gui-logout-title")).clicked();
+                        let cancel = ui.button(tr("gui-cancel"));
+                        cancelled = cancel.clicked() || dialog_keys(ui, &cancel);
+                    });
+                });
+
+            if confirmed {
+                self.logout_dialog = false;
+                self.logout();
+            } else if cancelled {
+                self.logout_dialog = false;
+            }
+        }
+
+        if let Some((app_name, destination)) = &mut self.move_dialog {
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new(tr_args("gui-move-title", &[("game", app_name)]))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(tr("gui-move-into"));
+                    ui.add(egui::TextEdit::singleline(destination).desired_width(350.0));
+                    ui.label(
+                        egui::RichText::new(tr("gui-move-detail"))
+                            .size(12.0)
+                            .color(styles::TEXT_SECONDARY),
+                    );
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        confirmed = ui
+                            .add_enabled(
+                                !destination.trim().is_empty(),
+                                egui::Button::new(tr("gui-move-confirm")),
+                            )
+                            .clicked();
+                        let cancel = ui.button(tr("gui-cancel"));
+                        cancelled = cancel.clicked() || dialog_keys(ui, &cancel);
+                    });
+                });
+
+            if confirmed {
+                let (app_name, destination) = self.move_dialog.take().unwrap();
+                self.handle_move(app_name, PathBuf::from(destination.trim()));
+            } else if cancelled {
+                self.move_dialog = None;
+            }
+        }
+
+        if let Some((app_name, pin)) = &mut self.pin_dialog {
+            let mut confirmed = false;
+            let mut cancelled = false;
+            egui::Window::new(tr_args("gui-pin-title", &[("game", app_name)]))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("gui-pin-detail"));
+                    let field = ui.add(
+                        egui::TextEdit::singleline(pin)
+                            .password(true)
+                            .desired_width(120.0),
+                    );
+                    field.request_focus();
+                    confirmed =
+                        field.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        confirmed |= ui.button(tr("gui-pin-confirm")).clicked();
+                        let cancel = ui.button(tr("gui-cancel"));
+                        cancelled = cancel.clicked() || dialog_keys(ui, &cancel);
+                    });
+                });
+
+            if confirmed {
+                let (app_name, pin) = self.pin_dialog.take().unwrap();
+                self.handle_launch(app_name, Some(pin));
+            } else if cancelled {
+                self.pin_dialog = None;
+            }
+        }
+
+        if let Some((app_name, log)) = &self.log_window {
+            let mut open = true;
+            egui::Window::new(format!("{} log", app_name))
+                .open(&mut open)
+                .default_size([700.0, 400.0])
+                .show(ctx, |ui| {
+                    // Only the visible lines are laid out
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    egui::ScrollArea::both().stick_to_bottom(true).show_rows(
+                        ui,
+                        row_height,
+                        log.len(),
+                        |ui, rows| {
+                            for line in &log[rows] {
+                                ui.label(egui::RichText::new(line).monospace());
+                            }
+                        },
+                    );
+                });
+            if !open || ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+                self.log_window = None;
+            }
+        }
+
+        if let Some((app_name, plan)) = &self.sync_preview {
+            let mut open = true;
+            egui::Window::new(format!("Save sync preview: {}", app_name))
+                .open(&mut open)
+                .default_size([700.0, 300.0])
+                .show(ctx, |ui| saves_view::plan_grid(ui, app_name, plan));
+            if !open || ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+                self.sync_preview = None;
+            }
+        }
+
+        if self.toasts.show(ctx) {
+            self.state = AppState::Login;
+        }
 
         // Request repaint for animations/updates
-        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+}
+
+/// Everything before the game runs, then its session watched from another
+/// thread. Returns the play time left today, if limited.
+fn launch(
+    manager: GameManager,
+    app_name: &str,
+    pin: Option<&str>,
+    offline: bool,
+) -> Result<Option<Duration>> {
+    let limits = manager.session_limits(app_name, pin)?;
+    let mut options = LaunchOptions {
+        offline,
+        ..Default::default()
+    };
+    manager.fall_back_to_offline(&mut options);
+    let sync_saves = manager.auto_sync_saves(app_name, &options)?;
+    if sync_saves {
+        // Conflicts follow `save_conflict_policy`, as nobody can answer a
+        // prompt here
+        if let Err(e) = runtime::block_on(manager.download_cloud_saves(app_name)) {
+            log::warn!("Cloud save download for {} failed: {}", app_name, e);
+        }
     }
+    let started = SystemTime::now();
+    let child = manager.launch_game(app_name, &options)?;
+    let upload_since = (sync_saves && api::SAVE_UPLOADS_ENABLED).then_some(started);
+    let time_left = limits.time_left;
+
+    let app_name = app_name.to_string();
+    std::thread::spawn(move || {
+        if let Err(e) = manager.supervise_session(child, &app_name, limits) {
+            log::warn!("Session monitor for {} failed: {}", app_name, e);
+        }
+
+        // Saves the session changed, if the sync policy allows it
+        if let Some(since) = upload_since {
+            let upload =
+                runtime::block_on(manager.upload_cloud_saves_since(&app_name, Some(since)));
+            if let Err(e) = upload {
+                log::warn!("Cloud save upload for {} failed: {}", app_name, e);
+            }
+        }
+    });
+    Ok(time_left)
 }
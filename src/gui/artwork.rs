@@ -0,0 +1,107 @@
+//! Store artwork in the GUI: cover art for the library cards, loaded in the
+//! background and kept as textures for the session, and the decoding shared
+//! with the game pages. Cards keep their painted placeholder until their
+//! art arrives, or for good if the store has none.
+
+use egui::{ColorImage, TextureHandle};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::api::Game;
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::games::GameManager;
+
+use super::runtime;
+
+pub struct CoverArt {
+    textures: HashMap<String, TextureHandle>,
+    /// Games whose art was asked for, so each is looked up once
+    requested: HashSet<String>,
+    sender: Sender<(String, ColorImage)>,
+    receiver: Receiver<(String, ColorImage)>,
+}
+
+impl Default for CoverArt {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            textures: HashMap::new(),
+            requested: HashSet::new(),
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl CoverArt {
+    /// Fetch the art of the games not asked for before
+    pub fn load(&mut self, games: &[Game], config: Config, auth: AuthManager) {
+        let app_names: Vec<String> = games
+            .iter()
+            .filter(|game| self.requested.insert(game.app_name.clone()))
+            .map(|game| game.app_name.clone())
+            .collect();
+        if app_names.is_empty() {
+            return;
+        }
+
+        let sender = self.sender.clone();
+        // Covers arrive through the channel, so nothing waits on the promise
+        let _ = runtime::spawn(async move {
+            let result = match GameManager::new(config, auth) {
+                Ok(manager) => {
+                    manager
+                        .cover_art(&app_names, |app_name, data| {
+                            if let Some(image) = decode(app_name, &data) {
+                                let _ = sender.send((app_name.to_string(), image));
+                            }
+                        })
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                log::warn!("Failed to load cover art: {}", e);
+            }
+        });
+    }
+
+    /// Turn the covers that arrived since the last frame into textures
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        while let Ok((app_name, image)) = self.receiver.try_recv() {
+            let texture =
+                ctx.load_texture(format!("cover-{}", app_name), image, Default::default());
+            self.textures.insert(app_name, texture);
+        }
+    }
+
+    pub fn get(&self, app_name: &str) -> Option<&TextureHandle> {
+        self.textures.get(app_name)
+    }
+}
+
+/// Decode artwork of `app_name`. Images that don't decode are logged and
+/// left out.
+pub fn decode(app_name: &str, data: &[u8]) -> Option<ColorImage> {
+    let image = image::load_from_memory(data)
+        .map_err(|e| log::debug!("Can't show the artwork of {}: {}", app_name, e))
+        .ok()?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Some(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
+
+/// Texture coordinates that crop an image of `size` to fill `rect` without
+/// stretching it, keeping the middle
+pub fn fill_uv(size: [usize; 2], rect: egui::Rect) -> egui::Rect {
+    let image_aspect = size[0] as f32 / size[1].max(1) as f32;
+    let rect_aspect = rect.width() / rect.height().max(1.0);
+
+    let (width, height) = if image_aspect > rect_aspect {
+        (rect_aspect / image_aspect, 1.0)
+    } else {
+        (1.0, image_aspect / rect_aspect)
+    };
+    egui::Rect::from_center_size(egui::pos2(0.5, 0.5), egui::vec2(width, height))
+}
@@ -4,8 +4,13 @@ use std::time::{Duration, Instant};
 
 use crate::api::{DeviceAuthResponse, EpicClient};
 use crate::auth::{AuthManager, AuthToken};
+use crate::i18n::{tr, tr_args};
+use crate::qr::QrCode;
 use crate::Result;
 
+use super::runtime;
+use super::styles;
+
 enum AuthState {
     Idle,
     RequestingDeviceAuth,
@@ -21,6 +26,8 @@ pub struct AuthView {
     state: AuthState,
     verification_url: Option<String>,
     user_code: Option<String>,
+    /// The verification URL, for signing in from a phone
+    qr: Option<QrCode>,
     device_auth_promise: Option<Promise<Result<DeviceAuthResponse>>>,
     poll_promise: Option<Promise<Result<Option<AuthToken>>>>,
 }
@@ -32,6 +39,7 @@ impl Default for AuthView {
             state: AuthState::Idle,
             verification_url: None,
             user_code: None,
+            qr: None,
             device_auth_promise: None,
             poll_promise: None,
         }
@@ -47,6 +55,9 @@ impl AuthView {
                     Ok(device_auth) => {
                         self.verification_url = Some(device_auth.verification_uri_complete.clone());
                         self.user_code = Some(device_auth.user_code.clone());
+                        self.qr = QrCode::encode(device_auth.verification_uri_complete.as_bytes())
+                            .map_err(|e| log::warn!("No QR code for the sign-in URL: {}", e))
+                            .ok();
                         self.state = AuthState::Polling {
                             device_code: device_auth.device_code.clone(),
                             last_poll: Instant::now() - Duration::from_secs(10), // Poll immediately
@@ -54,10 +65,11 @@ impl AuthView {
                         };
                     }
                     Err(e) => {
-                        self.auth_status = format!("Failed to start authentication: {}", e);
+                        self.auth_status = tr_args("gui-auth-start-failed", &[("error", e)]);
                         self.state = AuthState::Idle;
                         self.verification_url = None;
                         self.user_code = None;
+                        self.qr = None;
                     }
                 }
                 self.device_auth_promise = None;
@@ -84,11 +96,11 @@ impl AuthView {
                         Ok(Some(token)) => {
                             // Successfully got token, save it
                             if let Err(e) = auth.set_token(token.clone()) {
-                                self.auth_status = format!("Error saving token: {}", e);
+                                self.auth_status =
+                                    tr_args("gui-auth-save-failed", &[("error", &e)]);
                                 self.state = AuthState::Idle;
                             } else {
-                                self.auth_status =
-                                    "✓ Successfully authenticated with Epic Games!".to_string();
+                                self.auth_status = tr("gui-auth-success");
                                 self.state = AuthState::Idle;
                                 self.poll_promise = None;
                                 return true; // Signal successful login
@@ -99,11 +111,11 @@ impl AuthView {
                             let new_attempts = attempts + 1;
                             if new_attempts >= 120 {
                                 // Timeout after 10 minutes (120 * 5 seconds)
-                                self.auth_status =
-                                    "Authentication timed out. Please try again.".to_string();
+                                self.auth_status = tr("gui-auth-timed-out");
                                 self.state = AuthState::Idle;
                                 self.verification_url = None;
                                 self.user_code = None;
+                                self.qr = None;
                             } else {
                                 self.state = AuthState::Polling {
                                     device_code: device_code.clone(),
@@ -113,10 +125,11 @@ impl AuthView {
                             }
                         }
                         Err(e) => {
-                            self.auth_status = format!("Authentication failed: {}", e);
+                            self.auth_status = tr_args("gui-auth-failed", &[("error", e)]);
                             self.state = AuthState::Idle;
                             self.verification_url = None;
                             self.user_code = None;
+                            self.qr = None;
                         }
                     }
                     self.poll_promise = None;
@@ -126,13 +139,9 @@ impl AuthView {
             // Start new poll if needed
             if self.poll_promise.is_none() && last_poll.elapsed() >= Duration::from_secs(5) {
                 let device_code_clone = device_code.clone();
-                let promise = Promise::spawn_thread("poll_auth", move || {
-                    tokio::runtime::Runtime::new()
-                        .unwrap()
-                        .block_on(async move {
-                            let client = EpicClient::new()?;
-                            client.poll_for_token(&device_code_clone).await
-                        })
+                let promise = runtime::spawn(async move {
+                    let client = EpicClient::new()?;
+                    client.poll_for_token(&device_code_clone).await
                 });
                 self.poll_promise = Some(promise);
             }
@@ -150,7 +159,7 @@ impl AuthView {
             );
             ui.add_space(15.0);
             ui.label(
-                RichText::new("Sign in to your account")
+                RichText::new(tr("gui-auth-subtitle"))
                     .size(18.0)
                     .color(egui::Color32::from_rgb(180, 180, 190)),
             );
@@ -165,14 +174,14 @@ impl AuthView {
                     AuthState::Idle => {
                         // Show enhanced login button with Epic blue
                         let button = egui::Button::new(
-                            RichText::new("Sign In with Epic Games")
+                            RichText::new(tr("gui-auth-sign-in"))
                                 .size(18.0)
                                 .strong()
                                 .color(egui::Color32::WHITE),
                         )
-                        .fill(egui::Color32::from_rgb(0, 121, 214))
+                        .fill(styles::EPIC_BLUE)
                         .min_size(egui::Vec2::new(280.0, 50.0));
-                        
+
                         if ui.add(button).clicked() {
                             self.start_authentication();
                         }
@@ -181,32 +190,32 @@ impl AuthView {
 
                         // Enhanced instructions
                         ui.label(
-                            RichText::new("Click the button above to authenticate with Epic Games")
+                            RichText::new(tr("gui-auth-instructions"))
                                 .size(15.0)
-                                .color(egui::Color32::from_rgb(160, 160, 170)),
+                                .color(styles::TEXT_SECONDARY),
                         );
                         ui.add_space(8.0);
                         ui.label(
-                            RichText::new("You'll receive a code to enter in your browser")
+                            RichText::new(tr("gui-auth-code-note"))
                                 .size(15.0)
-                                .color(egui::Color32::from_rgb(160, 160, 170)),
+                                .color(styles::TEXT_SECONDARY),
                         );
                     }
                     AuthState::RequestingDeviceAuth => {
                         ui.spinner();
                         ui.add_space(10.0);
-                        ui.label("Initializing authentication...");
+                        ui.label(tr("gui-auth-initializing"));
 
                         ui.add_space(20.0);
 
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(tr("gui-cancel")).clicked() {
                             self.cancel_authentication();
                         }
                     }
                     AuthState::Polling { attempts, .. } => {
                         // Show authentication in progress with enhanced styling
                         ui.heading(
-                            RichText::new("⏳ Authentication in Progress")
+                            RichText::new(tr("gui-auth-in-progress"))
                                 .size(24.0)
                                 .strong()
                                 .color(egui::Color32::WHITE),
@@ -215,7 +224,7 @@ impl AuthView {
 
                         if let (Some(url), Some(code)) = (&self.verification_url, &self.user_code) {
                             ui.label(
-                                RichText::new("Please complete authentication in your browser:")
+                                RichText::new(tr("gui-auth-complete-in-browser"))
                                     .size(17.0)
                                     .color(egui::Color32::from_rgb(220, 220, 230)),
                             );
@@ -224,13 +233,16 @@ impl AuthView {
                             // Display verification URL in an enhanced frame
                             egui::Frame::none()
                                 .fill(egui::Color32::from_rgb(32, 34, 40))
-                                .stroke(egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 121, 214)))
+                                .stroke(egui::Stroke::new(
+                                    2.0,
+                                    egui::Color32::from_rgb(0, 121, 214),
+                                ))
                                 .rounding(egui::Rounding::same(6.0))
                                 .inner_margin(20.0)
                                 .show(ui, |ui| {
                                     ui.horizontal(|ui| {
                                         ui.label(
-                                            RichText::new("URL:")
+                                            RichText::new(tr("gui-auth-url"))
                                                 .strong()
                                                 .size(15.0)
                                                 .color(egui::Color32::from_rgb(180, 180, 190)),
@@ -249,7 +261,7 @@ impl AuthView {
 
                                     ui.horizontal(|ui| {
                                         ui.label(
-                                            RichText::new("Code:")
+                                            RichText::new(tr("gui-auth-code"))
                                                 .strong()
                                                 .size(15.0)
                                                 .color(egui::Color32::from_rgb(180, 180, 190)),
@@ -270,23 +282,34 @@ impl AuthView {
 
                             // Enhanced "Open in Browser" button
                             let browser_button = egui::Button::new(
-                                RichText::new("🌐 Open in Browser")
+                                RichText::new(tr("gui-auth-open-browser"))
                                     .size(16.0)
                                     .strong()
                                     .color(egui::Color32::WHITE),
                             )
-                            .fill(egui::Color32::from_rgb(0, 121, 214))
+                            .fill(styles::EPIC_BLUE)
                             .min_size(egui::Vec2::new(200.0, 42.0));
-                            
+
                             if ui.add(browser_button).clicked() {
                                 let _ = webbrowser::open(url);
                             }
 
+                            if let Some(qr) = &self.qr {
+                                ui.add_space(20.0);
+                                ui.label(
+                                    RichText::new(tr("gui-auth-scan"))
+                                        .size(15.0)
+                                        .color(styles::TEXT_SECONDARY),
+                                );
+                                ui.add_space(8.0);
+                                qr_code(ui, qr, 180.0);
+                            }
+
                             ui.add_space(15.0);
                             ui.label(
-                                RichText::new(format!(
-                                    "Waiting for you to complete authentication... (attempt {}/120)",
-                                    attempts + 1
+                                RichText::new(tr_args(
+                                    "gui-auth-waiting",
+                                    &[("attempt", &(attempts + 1)), ("attempts", &120)],
                                 ))
                                 .size(15.0)
                                 .color(egui::Color32::from_rgb(100, 170, 230)),
@@ -295,7 +318,10 @@ impl AuthView {
 
                         ui.add_space(25.0);
 
-                        if ui.button(RichText::new("Cancel").size(14.0)).clicked() {
+                        if ui
+                            .button(RichText::new(tr("gui-cancel")).size(14.0))
+                            .clicked()
+                        {
                             self.cancel_authentication();
                         }
                     }
@@ -325,15 +351,11 @@ impl AuthView {
         self.auth_status = String::new();
         self.verification_url = None;
         self.user_code = None;
+        self.qr = None;
 
-        // Spawn thread to run async device auth request
-        let promise = Promise::spawn_thread("device_auth", || {
-            tokio::runtime::Runtime::new()
-                .unwrap()
-                .block_on(async move {
-                    let client = EpicClient::new()?;
-                    client.request_device_auth().await
-                })
+        let promise = runtime::spawn(async move {
+            let client = EpicClient::new()?;
+            client.request_device_auth().await
         });
 
         self.device_auth_promise = Some(promise);
@@ -345,6 +367,31 @@ impl AuthView {
         self.poll_promise = None;
         self.verification_url = None;
         self.user_code = None;
-        self.auth_status = "Authentication cancelled".to_string();
+        self.qr = None;
+        self.auth_status = tr("gui-auth-cancelled");
+    }
+}
+
+/// Draw `qr` in a `side` points square, on white and with the quiet zone of
+/// four modules scanners look for
+fn qr_code(ui: &mut egui::Ui, qr: &QrCode, side: f32) {
+    const QUIET_ZONE: usize = 4;
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, egui::Color32::WHITE);
+
+    let module = side / (qr.size() + QUIET_ZONE * 2) as f32;
+    for y in 0..qr.size() {
+        for x in 0..qr.size() {
+            if qr.is_dark(x, y) {
+                let min = rect.min
+                    + egui::vec2((x + QUIET_ZONE) as f32, (y + QUIET_ZONE) as f32) * module;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, egui::Vec2::splat(module)),
+                    0.0,
+                    egui::Color32::BLACK,
+                );
+            }
+        }
     }
 }
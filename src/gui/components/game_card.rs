@@ -1,33 +1,86 @@
-use egui::{Color32, RichText, Vec2};
+use egui::{Color32, RichText, TextureHandle, Vec2};
 
 use crate::api::Game;
+use crate::games::{InstalledGame, Runner};
+use crate::gui::{artwork, styles};
+use crate::i18n::{tr, tr_args};
 
 pub struct GameCard;
 
+/// Where the keyboard or gamepad focus is on a card
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CardFocus {
+    None,
+    Card,
+    /// The card's tools are open, with this one selected
+    Menu(usize),
+}
+
+/// One of an installed game's tools
+pub struct Tool {
+    pub label: String,
+    /// Tools that write the game's files wait while it downloads
+    pub enabled: bool,
+    pub action: GameCardAction,
+}
+
 impl GameCard {
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         ui: &mut egui::Ui,
         game: &Game,
-        is_installed: bool,
+        cover: Option<&TextureHandle>,
+        installed: Option<&InstalledGame>,
         is_installing: bool,
+        update_version: Option<&str>,
+        runners: &[Runner],
+        selected_runner: Option<&Runner>,
+        focus: CardFocus,
     ) -> Option<GameCardAction> {
         let mut action = None;
+        let is_installed = installed.is_some();
+        let border = if focus == CardFocus::None {
+            egui::Stroke::new(1.0, Color32::from_rgb(45, 45, 50))
+        } else {
+            egui::Stroke::new(3.0, styles::EPIC_BLUE)
+        };
 
-        egui::Frame::none()
-            .fill(Color32::from_rgb(28, 28, 32))
-            .stroke(egui::Stroke::new(1.0, Color32::from_rgb(45, 45, 50)))
+        let frame = egui::Frame::none()
+            .fill(styles::CARD_BG)
+            .stroke(border)
             .rounding(egui::Rounding::same(6.0))
             .inner_margin(egui::Margin::same(0.0))
             .show(ui, |ui| {
                 ui.set_min_size(Vec2::new(280.0, 340.0));
                 ui.set_max_size(Vec2::new(280.0, 340.0));
 
+                // Right-clicking anywhere on the card opens its menu. Sensed
+                // before the contents, so their own clicks still win.
+                let card = ui.interact(
+                    egui::Rect::from_min_size(ui.max_rect().min, Vec2::new(280.0, 340.0)),
+                    ui.id().with(("card", &game.app_name)),
+                    egui::Sense::click(),
+                );
+                if let Some(installed) = installed {
+                    card.context_menu(|ui| {
+                        if let Some(menu_action) =
+                            Self::context_menu(ui, &installed.app_name, is_installing)
+                        {
+                            action = Some(menu_action);
+                            ui.close_menu();
+                        }
+                    });
+                }
+
                 ui.vertical(|ui| {
-                    // Game image placeholder with gradient effect
+                    // Cover art, which also opens the game's page
                     let (rect, response) =
-                        ui.allocate_exact_size(Vec2::new(280.0, 200.0), egui::Sense::hover());
-                    
-                    // Create a gradient background for the image placeholder
+                        ui.allocate_exact_size(Vec2::new(280.0, 200.0), egui::Sense::click());
+                    if response.clicked() {
+                        action = Some(GameCardAction::ShowDetails(game.app_name.clone()));
+                    }
+                    let response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
+
                     let painter = ui.painter();
                     let image_rounding = egui::Rounding {
                         nw: 6.0,
@@ -35,13 +88,30 @@ impl GameCard {
                         sw: 0.0,
                         se: 0.0,
                     };
-                    
-                    painter.rect_filled(
-                        rect,
-                        image_rounding,
-                        Color32::from_rgb(45, 50, 65),
-                    );
-                    
+
+                    match cover {
+                        // Cropped to fill the space rather than stretched
+                        Some(cover) => egui::Image::new(cover)
+                            .uv(artwork::fill_uv(cover.size(), rect))
+                            .rounding(image_rounding)
+                            .paint_at(ui, rect),
+                        // Placeholder with the title until the art arrives
+                        None => {
+                            painter.rect_filled(
+                                rect,
+                                image_rounding,
+                                Color32::from_rgb(45, 50, 65),
+                            );
+                            painter.text(
+                                rect.center(),
+                                egui::Align2::CENTER_CENTER,
+                                &game.app_title,
+                                egui::FontId::proportional(16.0),
+                                Color32::WHITE,
+                            );
+                        }
+                    }
+
                     // Add a subtle overlay gradient
                     if response.hovered() {
                         painter.rect_filled(
@@ -51,15 +121,6 @@ impl GameCard {
                         );
                     }
 
-                    // Game title on image
-                    painter.text(
-                        rect.center(),
-                        egui::Align2::CENTER_CENTER,
-                        &game.app_title,
-                        egui::FontId::proportional(16.0),
-                        Color32::WHITE,
-                    );
-
                     ui.add_space(15.0);
 
                     // Content area with padding
@@ -77,12 +138,24 @@ impl GameCard {
 
                             ui.add_space(5.0);
 
-                            // Version info
-                            ui.label(
-                                RichText::new(format!("v{}", &game.app_version))
-                                    .size(12.0)
-                                    .color(Color32::from_rgb(160, 160, 160)),
-                            );
+                            // Version info, with a badge when a newer build is out
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(format!("v{}", &game.app_version))
+                                        .size(12.0)
+                                        .color(Color32::from_rgb(160, 160, 160)),
+                                );
+                                if let Some(version) = update_version {
+                                    ui.label(
+                                        RichText::new(tr_args(
+                                            "gui-version-available",
+                                            &[("version", &version)],
+                                        ))
+                                        .size(12.0)
+                                        .color(styles::EPIC_BLUE),
+                                    );
+                                }
+                            });
 
                             ui.add_space(15.0);
 
@@ -91,69 +164,265 @@ impl GameCard {
                                 if is_installed {
                                     // Play button - Epic blue
                                     let play_button = egui::Button::new(
-                                        RichText::new("▶ Play")
+                                        RichText::new(tr("gui-play"))
                                             .size(15.0)
                                             .strong()
                                             .color(Color32::WHITE),
                                     )
-                                    .fill(Color32::from_rgb(0, 121, 214))
+                                    .fill(styles::EPIC_BLUE)
                                     .min_size(Vec2::new(120.0, 36.0));
-                                    
+
                                     if ui.add(play_button).clicked() {
-                                        action = Some(GameCardAction::Launch(game.app_name.clone()));
+                                        action =
+                                            Some(GameCardAction::Launch(game.app_name.clone()));
                                     }
-                                    
+
                                     ui.add_space(5.0);
-                                    
+
+                                    if update_version.is_some()
+                                        && ui
+                                            .add_enabled(
+                                                !is_installing,
+                                                egui::Button::new(
+                                                    RichText::new(tr("gui-update")).size(13.0),
+                                                )
+                                                .min_size(Vec2::new(80.0, 36.0)),
+                                            )
+                                            .clicked()
+                                    {
+                                        action =
+                                            Some(GameCardAction::Update(game.app_name.clone()));
+                                    }
+
                                     // Uninstall button
                                     let uninstall_button = egui::Button::new(
-                                        RichText::new("Uninstall").size(13.0),
+                                        RichText::new(tr("gui-uninstall")).size(13.0),
                                     )
                                     .fill(Color32::from_rgb(60, 60, 65))
                                     .min_size(Vec2::new(100.0, 36.0));
-                                    
+
                                     if ui.add(uninstall_button).clicked() {
-                                        action = Some(GameCardAction::Uninstall(game.app_name.clone()));
+                                        action =
+                                            Some(GameCardAction::Uninstall(game.app_name.clone()));
                                     }
                                 } else if is_installing {
                                     ui.add_enabled_ui(false, |ui| {
                                         let installing_button = egui::Button::new(
-                                            RichText::new("⏳ Installing...")
+                                            RichText::new(tr("gui-installing"))
                                                 .size(15.0)
                                                 .color(Color32::from_rgb(180, 180, 180)),
                                         )
                                         .fill(Color32::from_rgb(50, 50, 55))
                                         .min_size(Vec2::new(200.0, 36.0));
-                                        
+
                                         let _ = ui.add(installing_button);
                                     });
                                 } else {
                                     // Install button - Epic blue
                                     let install_button = egui::Button::new(
-                                        RichText::new("Get")
+                                        RichText::new(tr("gui-get"))
                                             .size(15.0)
                                             .strong()
                                             .color(Color32::WHITE),
                                     )
-                                    .fill(Color32::from_rgb(0, 121, 214))
+                                    .fill(styles::EPIC_BLUE)
                                     .min_size(Vec2::new(200.0, 36.0));
-                                    
+
                                     if ui.add(install_button).clicked() {
-                                        action = Some(GameCardAction::Install(game.app_name.clone()));
+                                        action =
+                                            Some(GameCardAction::Install(game.app_name.clone()));
                                     }
                                 }
                             });
+
+                            if let Some(installed) = installed {
+                                if let Some(settings_action) = Self::settings(
+                                    ui,
+                                    &game.app_name,
+                                    installed,
+                                    runners,
+                                    selected_runner,
+                                ) {
+                                    action = Some(settings_action);
+                                }
+                            }
                         });
                     });
                 });
             });
 
+        // The same tools as on right click, opened from the keyboard or a
+        // gamepad and moved through with its keys
+        if let (CardFocus::Menu(selected), Some(installed)) = (focus, installed) {
+            egui::Area::new(ui.id().with(("card_tools", &game.app_name)))
+                .order(egui::Order::Foreground)
+                .fixed_pos(frame.response.rect.center())
+                .show(ui.ctx(), |ui| {
+                    egui::Frame::menu(ui.style()).show(ui, |ui| {
+                        let tools = Self::tools(&installed.app_name, is_installing);
+                        let last = tools.len() - 1;
+                        for (index, tool) in tools.into_iter().enumerate() {
+                            if index == last {
+                                ui.separator();
+                            }
+                            let item = egui::SelectableLabel::new(index == selected, tool.label);
+                            if ui.add_enabled(tool.enabled, item).clicked() {
+                                action = Some(tool.action);
+                            }
+                        }
+                    });
+                });
+        }
+
+        action
+    }
+
+    /// Tools of an installed game, in menu order; Uninstall comes last
+    pub fn tools(app_name: &str, is_installing: bool) -> Vec<Tool> {
+        let app_name = || app_name.to_string();
+        let tool = |id, enabled, action| Tool {
+            label: tr(id),
+            enabled,
+            action,
+        };
+
+        vec![
+            tool(
+                "gui-verify",
+                !is_installing,
+                GameCardAction::Verify(app_name()),
+            ),
+            tool(
+                "gui-repair",
+                !is_installing,
+                GameCardAction::Repair(app_name()),
+            ),
+            tool("gui-move", !is_installing, GameCardAction::Move(app_name())),
+            tool(
+                "gui-open-folder",
+                true,
+                GameCardAction::OpenFolder(app_name()),
+            ),
+            tool(
+                "gui-create-shortcut",
+                true,
+                GameCardAction::CreateShortcut(app_name()),
+            ),
+            tool(
+                "gui-game-settings",
+                true,
+                GameCardAction::Settings(app_name()),
+            ),
+            tool(
+                "gui-uninstall",
+                !is_installing,
+                GameCardAction::Uninstall(app_name()),
+            ),
+        ]
+    }
+
+    /// Tools of an installed game, on right click
+    fn context_menu(
+        ui: &mut egui::Ui,
+        app_name: &str,
+        is_installing: bool,
+    ) -> Option<GameCardAction> {
+        let mut action = None;
+
+        let tools = Self::tools(app_name, is_installing);
+        let last = tools.len() - 1;
+        for (index, tool) in tools.into_iter().enumerate() {
+            if index == last {
+                ui.separator();
+            }
+            if ui
+                .add_enabled(tool.enabled, egui::Button::new(tool.label))
+                .clicked()
+            {
+                action = Some(tool.action);
+            }
+        }
+
+        action
+    }
+
+    /// Per-game settings and tools of an installed game, shared by the card
+    /// and the game's page
+    pub fn settings(
+        ui: &mut egui::Ui,
+        app_name: &str,
+        installed: &InstalledGame,
+        runners: &[Runner],
+        selected_runner: Option<&Runner>,
+    ) -> Option<GameCardAction> {
+        let mut action = None;
+
+        let mut held = installed.hold_updates;
+        if ui.checkbox(&mut held, tr("gui-hold-updates")).changed() {
+            action = Some(GameCardAction::SetHold(app_name.to_string(), held));
+        }
+
+        // Runner for this game; "Default" follows the global setting
+        let mut runner = selected_runner.cloned();
+        egui::ComboBox::from_id_salt(("runner", app_name))
+            .selected_text(
+                runner
+                    .as_ref()
+                    .map_or_else(|| tr("gui-default-runner"), ToString::to_string),
+            )
+            .width(250.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut runner, None, tr("gui-default-runner"));
+                for option in runners {
+                    ui.selectable_value(&mut runner, Some(option.clone()), option.to_string());
+                }
+            });
+        if runner.as_ref() != selected_runner {
+            action = Some(GameCardAction::SetRunner(app_name.to_string(), runner));
+        }
+
+        ui.horizontal(|ui| {
+            if selected_runner != Some(&Runner::Native) && ui.button(tr("gui-winetricks")).clicked()
+            {
+                action = Some(GameCardAction::Winetricks(app_name.to_string()));
+            }
+            if ui.button(tr("gui-view-logs")).clicked() {
+                action = Some(GameCardAction::ViewLogs(app_name.to_string()));
+            }
+            if ui.button(tr("gui-add-to-steam")).clicked() {
+                action = Some(GameCardAction::AddToSteam(app_name.to_string()));
+            }
+            if ui.button(tr("gui-preview-sync")).clicked() {
+                action = Some(GameCardAction::PreviewSync(app_name.to_string()));
+            }
+            if selected_runner != Some(&Runner::Native)
+                && ui.button(tr("gui-clear-shaders")).clicked()
+            {
+                action = Some(GameCardAction::ClearShaders(app_name.to_string()));
+            }
+        });
+
         action
     }
 }
 
 pub enum GameCardAction {
+    ShowDetails(String),
     Install(String),
     Launch(String),
+    Update(String),
     Uninstall(String),
+    SetHold(String, bool),
+    SetRunner(String, Option<Runner>),
+    Winetricks(String),
+    ViewLogs(String),
+    AddToSteam(String),
+    ClearShaders(String),
+    PreviewSync(String),
+    Verify(String),
+    Repair(String),
+    Move(String),
+    OpenFolder(String),
+    CreateShortcut(String),
+    Settings(String),
 }
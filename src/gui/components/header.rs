@@ -1,5 +1,7 @@
 use egui::RichText;
 
+use crate::i18n::tr;
+
 pub struct Header;
 
 impl Header {
@@ -14,13 +16,12 @@ impl Header {
             );
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if is_authenticated {
-                    if ui
-                        .button(RichText::new("Logout").size(14.0))
+                if is_authenticated
+                    && ui
+                        .button(RichText::new(tr("gui-logout")).size(14.0))
                         .clicked()
-                    {
-                        *on_logout = true;
-                    }
+                {
+                    *on_logout = true;
                 }
             });
         });
@@ -1,10 +1,8 @@
 // GUI Components module
 mod header;
 mod game_card;
-mod status_bar;
 mod search_bar;
 
 pub use header::Header;
-pub use game_card::{GameCard, GameCardAction};
-pub use status_bar::StatusBar;
-pub use search_bar::{SearchBar, GameFilter};
+pub use game_card::{CardFocus, GameCard, GameCardAction};
+pub use search_bar::{SearchBar, GameFilter, SortMode};
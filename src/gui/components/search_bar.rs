@@ -1,11 +1,43 @@
 use egui::RichText;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq)]
+use crate::i18n::{tr, tr_args};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameFilter {
     All,
     Installed,
 }
 
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SortMode {
+    Alphabetical,
+    RecentlyPlayed,
+    RecentlyUpdated,
+    InstallSize,
+    InstalledFirst,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 5] = [
+        SortMode::Alphabetical,
+        SortMode::RecentlyPlayed,
+        SortMode::RecentlyUpdated,
+        SortMode::InstallSize,
+        SortMode::InstalledFirst,
+    ];
+
+    pub fn label(self) -> String {
+        tr(match self {
+            SortMode::Alphabetical => "gui-sort-alphabetical",
+            SortMode::RecentlyPlayed => "gui-sort-recently-played",
+            SortMode::RecentlyUpdated => "gui-sort-recently-updated",
+            SortMode::InstallSize => "gui-sort-install-size",
+            SortMode::InstalledFirst => "gui-sort-installed-first",
+        })
+    }
+}
+
 pub struct SearchBar;
 
 impl SearchBar {
@@ -13,16 +45,17 @@ impl SearchBar {
         ui: &mut egui::Ui,
         search_query: &mut String,
         filter: &mut GameFilter,
+        sort: &mut SortMode,
     ) {
         ui.horizontal(|ui| {
-            ui.heading(RichText::new("Library").size(20.0).strong());
+            ui.heading(RichText::new(tr("gui-library")).size(20.0).strong());
             ui.add_space(20.0);
 
             // Search box with enhanced styling
             ui.label(RichText::new("🔍").size(16.0));
             ui.add_space(5.0);
             let search_edit = egui::TextEdit::singleline(search_query)
-                .hint_text("Search games...")
+                .hint_text(tr("gui-search-hint"))
                 .desired_width(250.0);
             ui.add(search_edit);
 
@@ -31,7 +64,7 @@ impl SearchBar {
             // Filters with Epic-style buttons
             let all_selected = *filter == GameFilter::All;
             if ui
-                .selectable_label(all_selected, RichText::new("All Games").size(14.0))
+                .selectable_label(all_selected, RichText::new(tr("gui-filter-all")).size(14.0))
                 .clicked()
             {
                 *filter = GameFilter::All;
@@ -41,11 +74,24 @@ impl SearchBar {
             
             let installed_selected = *filter == GameFilter::Installed;
             if ui
-                .selectable_label(installed_selected, RichText::new("Installed").size(14.0))
+                .selectable_label(
+                    installed_selected,
+                    RichText::new(tr("gui-filter-installed")).size(14.0),
+                )
                 .clicked()
             {
                 *filter = GameFilter::Installed;
             }
+
+            ui.add_space(20.0);
+
+            egui::ComboBox::from_id_salt("library_sort")
+                .selected_text(tr_args("gui-sort", &[("mode", &sort.label())]))
+                .show_ui(ui, |ui| {
+                    for mode in SortMode::ALL {
+                        ui.selectable_value(sort, mode, mode.label());
+                    }
+                });
         });
     }
 }
@@ -0,0 +1,293 @@
+//! Installs, updates and repairs started from the GUI. They wait in a queue
+//! and run in the background, up to `max_concurrent_games` at a time, each
+//! with its own token to pause or cancel it and a status the Downloads tab
+//! reads.
+
+use poll_promise::Promise;
+use std::sync::Mutex;
+
+use crate::audit::Initiator;
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::games::{CancellationToken, DownloadStatus, GameManager};
+use crate::Result;
+
+use super::runtime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadKind {
+    Install {
+        create_shortcut: bool,
+    },
+    Update,
+    /// Download again the files that fail verification
+    Repair,
+}
+
+pub struct Download {
+    pub app_name: String,
+    pub title: String,
+    pub kind: DownloadKind,
+    pub status: DownloadStatus,
+    cancel: CancellationToken,
+    /// Set once the download has started
+    promise: Option<Promise<Result<()>>>,
+}
+
+impl Download {
+    pub fn is_running(&self) -> bool {
+        self.promise.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.cancel.is_paused()
+    }
+
+    fn start(&mut self, config: &Config, auth: &AuthManager) {
+        let config = config.clone();
+        let auth = auth.clone();
+        let app_name = self.app_name.clone();
+        let kind = self.kind;
+        let cancel = self.cancel.clone();
+        let status = self.status.clone();
+
+        self.promise = Some(runtime::spawn(async move {
+            let manager = GameManager::new(config, auth)?
+                .with_initiator(Initiator::Gui)
+                .with_cancellation(cancel)
+                .with_progress(status);
+
+            match kind {
+                DownloadKind::Install { create_shortcut } => {
+                    manager.install_game(&app_name).await?;
+                    if create_shortcut {
+                        if let Err(e) = manager.create_shortcut(&app_name).await {
+                            log::warn!("Failed to create shortcut for {}: {}", app_name, e);
+                        }
+                    }
+                    Ok(())
+                }
+                DownloadKind::Update => manager.update_game(&app_name).await,
+                DownloadKind::Repair => manager.repair_game(&app_name).await.map(|_| ()),
+            }
+        }));
+    }
+}
+
+/// Running downloads come first, then the queued ones in the order they
+/// will start
+#[derive(Default)]
+pub struct Downloads {
+    queue: Vec<Download>,
+}
+
+impl Downloads {
+    /// Queue a download unless the game already has one
+    pub fn push(&mut self, app_name: &str, title: &str, kind: DownloadKind) -> bool {
+        if self.contains(app_name) {
+            return false;
+        }
+        self.queue.push(Download {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            kind,
+            status: DownloadStatus::new(),
+            cancel: CancellationToken::new(),
+            promise: None,
+        });
+        true
+    }
+
+    pub fn contains(&self, app_name: &str) -> bool {
+        self.queue
+            .iter()
+            .any(|download| download.app_name == app_name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Download> {
+        self.queue.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// A paused download keeps its place among the running ones
+    pub fn pause(&self, app_name: &str) {
+        if let Some(download) = self.find(app_name) {
+            download.cancel.pause();
+        }
+    }
+
+    pub fn resume(&self, app_name: &str) {
+        if let Some(download) = self.find(app_name) {
+            download.cancel.resume();
+        }
+    }
+
+    /// Drop a queued download, or stop a running one at its next chunk.
+    /// Files it finished are kept for a later attempt to resume from.
+    pub fn cancel(&mut self, app_name: &str) {
+        let Some(index) = self.position(app_name) else {
+            return;
+        };
+        if self.queue[index].is_running() {
+            self.queue[index].cancel.cancel();
+        } else {
+            self.queue.remove(index);
+        }
+    }
+
+    /// Drop every queued download and stop the running ones
+    pub fn cancel_all(&mut self) {
+        self.queue.retain(|download| download.is_running());
+        for download in &self.queue {
+            download.cancel.cancel();
+        }
+    }
+
+    /// Start a queued download one place earlier; running ones stay ahead
+    pub fn move_up(&mut self, app_name: &str) {
+        if let Some(index) = self.position(app_name).filter(|&i| i > 0) {
+            if !self.queue[index - 1].is_running() && !self.queue[index].is_running() {
+                self.queue.swap(index - 1, index);
+            }
+        }
+    }
+
+    pub fn move_down(&mut self, app_name: &str) {
+        if let Some(index) = self
+            .position(app_name)
+            .filter(|&i| i + 1 < self.queue.len())
+        {
+            if !self.queue[index].is_running() {
+                self.queue.swap(index, index + 1);
+            }
+        }
+    }
+
+    /// Take out the downloads that ended, with how they ended, and start
+    /// queued ones in the slots they left
+    pub fn poll(
+        &mut self,
+        config: &Config,
+        auth: &Mutex<AuthManager>,
+    ) -> Vec<(Download, Result<()>)> {
+        let mut finished = Vec::new();
+        let mut index = 0;
+        while index < self.queue.len() {
+            if self.queue[index]
+                .promise
+                .as_ref()
+                .is_some_and(|promise| promise.ready().is_some())
+            {
+                let mut download = self.queue.remove(index);
+                if let Some(Ok(result)) = download.promise.take().map(Promise::try_take) {
+                    finished.push((download, result));
+                }
+            } else {
+                index += 1;
+            }
+        }
+
+        let slots = config.max_concurrent_games.max(1);
+        let running = self
+            .queue
+            .iter()
+            .filter(|download| download.is_running())
+            .count();
+        if running < slots && running < self.queue.len() {
+            let auth = auth.lock().unwrap();
+            for download in self.queue.iter_mut().skip(running).take(slots - running) {
+                download.start(config, &auth);
+            }
+        }
+
+        finished
+    }
+
+    fn find(&self, app_name: &str) -> Option<&Download> {
+        self.queue
+            .iter()
+            .find(|download| download.app_name == app_name)
+    }
+
+    fn position(&self, app_name: &str) -> Option<usize> {
+        self.queue
+            .iter()
+            .position(|download| download.app_name == app_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(app_names: &[&str]) -> Downloads {
+        let mut downloads = Downloads::default();
+        for app_name in app_names {
+            assert!(downloads.push(app_name, app_name, DownloadKind::Update));
+        }
+        downloads
+    }
+
+    /// Mark `app_name` as started, without running anything. It runs until
+    /// the returned sender is dropped.
+    fn run(downloads: &mut Downloads, app_name: &str) -> poll_promise::Sender<Result<()>> {
+        let (sender, promise) = Promise::new();
+        let index = downloads.position(app_name).unwrap();
+        downloads.queue[index].promise = Some(promise);
+        sender
+    }
+
+    fn order(downloads: &Downloads) -> Vec<&str> {
+        downloads
+            .iter()
+            .map(|download| download.app_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_queued_downloads_reorder_behind_running_ones() {
+        let mut downloads = queue(&["Running", "A", "B", "C"]);
+        let _running = run(&mut downloads, "Running");
+        assert!(!downloads.push("A", "A", DownloadKind::Repair));
+
+        downloads.move_up("C");
+        assert_eq!(order(&downloads), ["Running", "A", "C", "B"]);
+        downloads.move_down("A");
+        assert_eq!(order(&downloads), ["Running", "C", "A", "B"]);
+        downloads.move_down("B");
+        assert_eq!(order(&downloads), ["Running", "C", "A", "B"]);
+
+        // Nothing overtakes or is moved out of the running downloads
+        downloads.move_up("C");
+        assert_eq!(order(&downloads), ["Running", "C", "A", "B"]);
+        downloads.move_down("Running");
+        assert_eq!(order(&downloads), ["Running", "C", "A", "B"]);
+    }
+
+    #[test]
+    fn test_cancel_drops_queued_and_stops_running_downloads() {
+        let mut downloads = queue(&["Running", "A", "B"]);
+        let _running = run(&mut downloads, "Running");
+
+        downloads.cancel("A");
+        assert_eq!(order(&downloads), ["Running", "B"]);
+
+        // Kept until it stops, so its place in the queue is still taken
+        downloads.cancel("Running");
+        assert_eq!(order(&downloads), ["Running", "B"]);
+        assert!(downloads.find("Running").unwrap().cancel.is_cancelled());
+
+        downloads.push("C", "C", DownloadKind::Update);
+        downloads.cancel_all();
+        assert_eq!(order(&downloads), ["Running"]);
+        downloads.cancel("Missing");
+        assert_eq!(downloads.len(), 1);
+    }
+}
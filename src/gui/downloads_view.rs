@@ -0,0 +1,155 @@
+use egui::{Color32, RichText, ScrollArea};
+
+use super::downloads::{Download, DownloadKind, Downloads};
+use super::styles;
+use crate::format;
+use crate::i18n::{tr, tr_args};
+
+/// The Downloads tab: running installs and updates with their progress,
+/// then the queued ones in order
+pub struct DownloadsView;
+
+impl DownloadsView {
+    pub fn ui(ui: &mut egui::Ui, downloads: &Downloads) -> Option<DownloadsAction> {
+        let mut action = None;
+
+        if downloads.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(100.0);
+                ui.label(
+                    RichText::new(tr("gui-no-downloads"))
+                        .size(18.0)
+                        .color(Color32::GRAY),
+                );
+                ui.label(RichText::new(tr("gui-no-downloads-detail")).color(Color32::GRAY));
+            });
+            return None;
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            let count = downloads.len();
+            for (index, download) in downloads.iter().enumerate() {
+                egui::Frame::none()
+                    .fill(styles::CARD_BG)
+                    .rounding(egui::Rounding::same(6.0))
+                    .inner_margin(egui::Margin::same(12.0))
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        if let Some(row_action) = Self::row(ui, download, index, count) {
+                            action = Some(row_action);
+                        }
+                    });
+                ui.add_space(8.0);
+            }
+        });
+
+        action
+    }
+
+    fn row(
+        ui: &mut egui::Ui,
+        download: &Download,
+        index: usize,
+        count: usize,
+    ) -> Option<DownloadsAction> {
+        let mut action = None;
+        let app_name = || download.app_name.clone();
+        let kind = tr(match download.kind {
+            DownloadKind::Install { .. } => "gui-kind-install",
+            DownloadKind::Update => "gui-kind-update",
+            DownloadKind::Repair => "gui-kind-repair",
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&download.title).size(16.0).strong());
+            ui.label(RichText::new(kind).size(12.0).color(styles::TEXT_SECONDARY));
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr("gui-cancel")).clicked() {
+                    action = Some(DownloadsAction::Cancel(app_name()));
+                }
+                if download.is_running() {
+                    if download.is_paused() {
+                        if ui.button(tr("gui-resume")).clicked() {
+                            action = Some(DownloadsAction::Resume(app_name()));
+                        }
+                    } else if ui.button(tr("gui-pause")).clicked() {
+                        action = Some(DownloadsAction::Pause(app_name()));
+                    }
+                } else {
+                    if ui
+                        .add_enabled(index + 1 < count, egui::Button::new("⏷"))
+                        .on_hover_text(tr("gui-start-later"))
+                        .clicked()
+                    {
+                        action = Some(DownloadsAction::MoveDown(app_name()));
+                    }
+                    if ui
+                        .add_enabled(index > 0, egui::Button::new("⏶"))
+                        .on_hover_text(tr("gui-start-sooner"))
+                        .clicked()
+                    {
+                        action = Some(DownloadsAction::MoveUp(app_name()));
+                    }
+                }
+            });
+        });
+
+        if !download.is_running() {
+            ui.label(RichText::new(tr("gui-queued")).color(styles::TEXT_SECONDARY));
+            return action;
+        }
+
+        let progress = download.status.snapshot();
+        if progress.total_bytes == 0 {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(RichText::new(tr("gui-preparing")).color(styles::TEXT_SECONDARY));
+            });
+            return action;
+        }
+
+        ui.add(
+            egui::ProgressBar::new(progress.fraction())
+                .fill(styles::EPIC_BLUE)
+                .show_percentage(),
+        );
+        ui.horizontal(|ui| {
+            let mut details = tr_args(
+                "gui-bytes-done",
+                &[
+                    ("done", &format::size(progress.bytes)),
+                    ("total", &format::size(progress.total_bytes)),
+                ],
+            );
+            if download.is_paused() {
+                details.push_str(&format!(" · {}", tr("gui-paused")));
+            } else {
+                details.push_str(&format!(" · {}", format::speed(progress.speed)));
+                if let Some(eta) = progress.eta() {
+                    let left = tr_args("gui-time-left", &[("time", &format::duration(eta))]);
+                    details.push_str(&format!(" · {}", left));
+                }
+            }
+            let files = tr_args(
+                "gui-files-done",
+                &[("done", &progress.files), ("total", &progress.total_files)],
+            );
+            details.push_str(&format!(" · {}", files));
+            ui.label(RichText::new(details).size(13.0));
+        });
+        if let Some(file) = &progress.current_file {
+            ui.label(RichText::new(file).size(12.0).color(styles::TEXT_SECONDARY));
+        }
+
+        action
+    }
+}
+
+pub enum DownloadsAction {
+    Pause(String),
+    Resume(String),
+    Cancel(String),
+    MoveUp(String),
+    MoveDown(String),
+}
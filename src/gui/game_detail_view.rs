@@ -0,0 +1,350 @@
+use egui::{Color32, RichText, ScrollArea, TextureHandle, Vec2};
+use poll_promise::Promise;
+use std::time::Duration;
+
+use crate::api::Game;
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::format;
+use crate::games::{GameDetails, GameManager, InstalledGame, Runner};
+use crate::i18n::{tr, tr_args};
+use crate::Result;
+
+use super::components::GameCard;
+use super::library_view::LibraryAction;
+use super::saves_view::SavesView;
+use super::{artwork, runtime, styles};
+
+const BANNER_HEIGHT: f32 = 300.0;
+
+/// A game's page, opened from its card. The store details load in the
+/// background; the rest comes from the library and the install record.
+pub struct GameDetailView {
+    pub app_name: String,
+    config: Config,
+    auth: AuthManager,
+    tab: DetailTab,
+    /// Opened with the Saves tab the first time
+    saves: Option<SavesView>,
+    details_promise: Option<Promise<Result<GameDetails>>>,
+    /// The store details, or why they didn't load
+    details: Option<std::result::Result<GameDetails, String>>,
+    artwork: Option<TextureHandle>,
+    /// Files the last verify found missing or corrupt
+    pub damaged_files: Option<usize>,
+    /// Unfold the settings on the next frame, when opened to change them
+    pub show_settings: bool,
+    /// Give Play, or Get, the keyboard focus once the page shows, so Enter
+    /// or a gamepad's A presses it
+    focus_primary: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DetailTab {
+    Overview,
+    Saves,
+}
+
+pub enum DetailAction {
+    Back,
+    Game(LibraryAction),
+}
+
+impl GameDetailView {
+    pub fn open(app_name: String, config: Config, auth: AuthManager) -> Self {
+        let details_promise = runtime::spawn({
+            let app_name = app_name.clone();
+            let config = config.clone();
+            let auth = auth.clone();
+            async move {
+                GameManager::new(config, auth)?
+                    .game_details(&app_name)
+                    .await
+            }
+        });
+
+        Self {
+            app_name,
+            config,
+            auth,
+            tab: DetailTab::Overview,
+            saves: None,
+            details_promise: Some(details_promise),
+            details: None,
+            artwork: None,
+            damaged_files: None,
+            show_settings: false,
+            focus_primary: true,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        game: &Game,
+        installed: Option<&InstalledGame>,
+        is_downloading: bool,
+        update_version: Option<&str>,
+        runners: &[Runner],
+        selected_runner: Option<&Runner>,
+    ) -> Option<DetailAction> {
+        self.poll_details(ui.ctx());
+        let mut action = None;
+        let app_name = || game.app_name.clone();
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("gui-back-to-library")).clicked() {
+                action = Some(DetailAction::Back);
+            }
+            if installed.is_some() {
+                ui.separator();
+                ui.selectable_value(&mut self.tab, DetailTab::Overview, tr("gui-overview"));
+                ui.selectable_value(&mut self.tab, DetailTab::Saves, tr("gui-saves"));
+            }
+        });
+        ui.add_space(10.0);
+
+        if self.tab == DetailTab::Saves && installed.is_some() {
+            let saves = self.saves.get_or_insert_with(|| {
+                SavesView::open(
+                    game.app_name.clone(),
+                    self.config.clone(),
+                    self.auth.clone(),
+                )
+            });
+            ScrollArea::vertical().show(ui, |ui| saves.ui(ui));
+            return action;
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            self.banner(ui, &game.app_title);
+            ui.add_space(15.0);
+
+            ui.heading(RichText::new(&game.app_title).size(26.0).strong());
+            ui.horizontal(|ui| {
+                let version = installed.map_or(&game.app_version, |game| &game.app_version);
+                ui.label(RichText::new(format!("v{}", version)).color(styles::TEXT_SECONDARY));
+                if let Some(version) = update_version {
+                    ui.label(
+                        RichText::new(tr_args("gui-version-available", &[("version", &version)]))
+                            .color(styles::EPIC_BLUE),
+                    );
+                }
+            });
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                let game_action = |action: fn(String) -> LibraryAction| {
+                    Some(DetailAction::Game(action(app_name())))
+                };
+
+                match installed {
+                    Some(_) => {
+                        let play = egui::Button::new(RichText::new(tr("gui-play")).strong())
+                            .fill(styles::EPIC_BLUE)
+                            .min_size(Vec2::new(120.0, 32.0));
+                        let play = ui.add(play);
+                        if std::mem::take(&mut self.focus_primary) {
+                            play.request_focus();
+                        }
+                        if play.clicked() {
+                            action = game_action(LibraryAction::Launch);
+                        }
+                        if update_version.is_some()
+                            && ui
+                                .add_enabled(!is_downloading, egui::Button::new(tr("gui-update")))
+                                .clicked()
+                        {
+                            action = game_action(LibraryAction::Update);
+                        }
+                        if ui
+                            .add_enabled(!is_downloading, egui::Button::new(tr("gui-verify")))
+                            .clicked()
+                        {
+                            action = game_action(LibraryAction::Verify);
+                        }
+                        if self.damaged_files.is_some_and(|damaged| damaged > 0)
+                            && ui
+                                .add_enabled(!is_downloading, egui::Button::new(tr("gui-repair")))
+                                .clicked()
+                        {
+                            action = game_action(LibraryAction::Repair);
+                        }
+                        if ui
+                            .add_enabled(!is_downloading, egui::Button::new(tr("gui-uninstall")))
+                            .clicked()
+                        {
+                            action = game_action(LibraryAction::Uninstall);
+                        }
+                    }
+                    None if is_downloading => {
+                        ui.add_enabled(false, egui::Button::new(tr("gui-installing")));
+                    }
+                    None => {
+                        let install = egui::Button::new(RichText::new(tr("gui-get")).strong())
+                            .fill(styles::EPIC_BLUE)
+                            .min_size(Vec2::new(120.0, 32.0));
+                        let install = ui.add(install);
+                        if std::mem::take(&mut self.focus_primary) {
+                            install.request_focus();
+                        }
+                        if install.clicked() {
+                            action = game_action(LibraryAction::Install);
+                        }
+                    }
+                }
+            });
+            if let Some(damaged) = self.damaged_files {
+                ui.label(if damaged == 0 {
+                    RichText::new(tr("gui-verified-intact")).color(styles::SUCCESS_GREEN)
+                } else {
+                    RichText::new(tr_args("gui-damaged-files", &[("count", &damaged)]))
+                        .color(styles::ERROR_RED)
+                });
+            }
+            ui.add_space(15.0);
+
+            egui::Grid::new(("game_facts", &game.app_name))
+                .num_columns(2)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    let install_size = match &self.details {
+                        Some(Ok(details)) => details.install_size.map(format::size),
+                        _ => None,
+                    };
+                    ui.label(RichText::new(tr("gui-install-size")).color(styles::TEXT_SECONDARY));
+                    ui.label(install_size.unwrap_or_else(|| "-".to_string()));
+                    ui.end_row();
+
+                    if let Some(installed) = installed {
+                        ui.label(RichText::new(tr("gui-playtime")).color(styles::TEXT_SECONDARY));
+                        ui.label(format::duration(Duration::from_secs(
+                            installed.playtime_secs,
+                        )));
+                        ui.end_row();
+
+                        ui.label(
+                            RichText::new(tr("gui-last-played")).color(styles::TEXT_SECONDARY),
+                        );
+                        ui.label(
+                            installed
+                                .last_played
+                                .map_or_else(|| tr("gui-never"), format::date),
+                        );
+                        ui.end_row();
+
+                        ui.label(RichText::new(tr("gui-location")).color(styles::TEXT_SECONDARY));
+                        ui.label(installed.install_path.display().to_string());
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(15.0);
+
+            match &self.details {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(
+                            RichText::new(tr("gui-loading-store")).color(styles::TEXT_SECONDARY),
+                        );
+                    });
+                }
+                Some(Err(error)) => {
+                    ui.label(
+                        RichText::new(tr_args("gui-store-failed", &[("error", error)]))
+                            .color(styles::ERROR_RED),
+                    );
+                }
+                Some(Ok(details)) => {
+                    if !details.description.is_empty() {
+                        ui.label(RichText::new(&details.description).size(15.0));
+                        ui.add_space(15.0);
+                    }
+
+                    ui.label(RichText::new(tr("gui-add-ons")).size(18.0).strong());
+                    if details.dlcs.is_empty() {
+                        ui.label(RichText::new(tr("gui-none-owned")).color(styles::TEXT_SECONDARY));
+                    }
+                    for dlc in &details.dlcs {
+                        ui.label(format!("• {}", dlc.app_title));
+                    }
+                }
+            }
+
+            if let Some(installed) = installed {
+                ui.add_space(15.0);
+                let settings = egui::CollapsingHeader::new(
+                    RichText::new(tr("gui-settings")).size(18.0).strong(),
+                )
+                .id_salt(("game_settings", &game.app_name))
+                .open(self.show_settings.then_some(true))
+                .show(ui, |ui| {
+                    if let Some(settings_action) =
+                        GameCard::settings(ui, &game.app_name, installed, runners, selected_runner)
+                    {
+                        action = Some(DetailAction::Game(settings_action.into()));
+                    }
+                });
+                if self.show_settings {
+                    settings
+                        .header_response
+                        .scroll_to_me(Some(egui::Align::TOP));
+                    self.show_settings = false;
+                }
+            }
+        });
+
+        action
+    }
+
+    fn poll_details(&mut self, ctx: &egui::Context) {
+        if self
+            .details_promise
+            .as_ref()
+            .is_none_or(|p| p.ready().is_none())
+        {
+            return;
+        }
+        if let Some(Ok(result)) = self.details_promise.take().map(Promise::try_take) {
+            if let Ok(details) = &result {
+                self.artwork = details
+                    .artwork
+                    .as_deref()
+                    .and_then(|data| artwork::decode(&self.app_name, data))
+                    .map(|image| {
+                        let name = format!("artwork-{}", self.app_name);
+                        ctx.load_texture(name, image, Default::default())
+                    });
+            }
+            self.details = Some(result.map_err(|e| e.summary()));
+        }
+    }
+
+    /// The store artwork, or the card's placeholder while there is none
+    fn banner(&self, ui: &mut egui::Ui, title: &str) {
+        let width = ui.available_width();
+        if let Some(texture) = &self.artwork {
+            ui.add(
+                egui::Image::new(texture)
+                    .max_width(width)
+                    .max_height(BANNER_HEIGHT)
+                    .maintain_aspect_ratio(true)
+                    .rounding(6.0),
+            );
+            return;
+        }
+
+        let (rect, _) =
+            ui.allocate_exact_size(Vec2::new(width, BANNER_HEIGHT), egui::Sense::hover());
+        ui.painter()
+            .rect_filled(rect, 6.0, Color32::from_rgb(45, 50, 65));
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            title,
+            egui::FontId::proportional(24.0),
+            Color32::WHITE,
+        );
+    }
+}
@@ -1,38 +1,90 @@
-use egui::{Color32, RichText, ScrollArea};
-use std::sync::{Arc, Mutex};
+use egui::{Color32, RichText, ScrollArea, Vec2};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::api::Game;
-use crate::games::InstalledGame;
-use super::components::{GameCard, GameCardAction, SearchBar, GameFilter};
+use crate::format;
+use crate::games::{InstalledGame, Runner};
+use crate::i18n::{tr, tr_args};
 
-#[derive(Clone)]
+use super::artwork::CoverArt;
+use super::components::{CardFocus, GameCard, GameCardAction, GameFilter, SearchBar, SortMode};
+use super::downloads::Downloads;
+use super::navigation::Nav;
+use super::{artwork, styles};
+
+/// Games in the "Continue playing" strip
+const RECENT_GAMES: usize = 8;
+const RECENT_TILE: Vec2 = Vec2::new(200.0, 112.0);
+
+/// Filter, sort order and search text are remembered between runs
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LibraryView {
     filter: GameFilter,
+    sort: SortMode,
     search_query: String,
-    installing_games: Arc<Mutex<Vec<String>>>,
+    /// Card with the keyboard or gamepad focus, by its place in the grid
+    #[serde(skip)]
+    focus: Option<usize>,
+    /// Selected tool while the focused card's tools are open
+    #[serde(skip)]
+    menu: Option<usize>,
+    /// The focus moved, so its card should be scrolled into view
+    #[serde(skip)]
+    scroll_to_focus: bool,
 }
 
 impl Default for LibraryView {
     fn default() -> Self {
         Self {
             filter: GameFilter::All,
+            sort: SortMode::Alphabetical,
             search_query: String::new(),
-            installing_games: Arc::new(Mutex::new(Vec::new())),
+            focus: None,
+            menu: None,
+            scroll_to_focus: false,
         }
     }
 }
 
 impl LibraryView {
+    #[allow(clippy::too_many_arguments)]
     pub fn ui(
         &mut self,
         ui: &mut egui::Ui,
         library_games: &[Game],
+        covers: &CoverArt,
         installed_games: &[InstalledGame],
+        downloads: &Downloads,
+        game_updates: &HashMap<String, String>,
+        runners: &[Runner],
+        game_runners: &HashMap<String, Runner>,
+        nav: Option<Nav>,
     ) -> Option<LibraryAction> {
         let mut action = None;
 
-        // Top bar with search and filters using the SearchBar component
-        SearchBar::show(ui, &mut self.search_query, &mut self.filter);
+        // Top bar with search and filters using the SearchBar component,
+        // and a way to queue every pending update at once
+        ui.horizontal(|ui| {
+            SearchBar::show(ui, &mut self.search_query, &mut self.filter, &mut self.sort);
+
+            let pending = game_updates
+                .keys()
+                .filter(|app_name| !downloads.contains(app_name))
+                .count();
+            if pending > 0 {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let update_all = egui::Button::new(
+                        RichText::new(tr_args("gui-update-all", &[("count", &pending)])).size(14.0),
+                    )
+                    .fill(styles::EPIC_BLUE);
+                    if ui.add(update_all).clicked() {
+                        action = Some(LibraryAction::UpdateAll);
+                    }
+                });
+            }
+        });
 
         ui.separator();
         ui.add_space(15.0);
@@ -42,9 +94,11 @@ impl LibraryView {
             let available_width = ui.available_width();
             let card_width = 280.0; // Slightly larger cards
             let card_spacing = 15.0; // More spacing between cards
-            let cards_per_row = (available_width / (card_width + card_spacing)).floor().max(1.0) as usize;
+            let cards_per_row = (available_width / (card_width + card_spacing))
+                .floor()
+                .max(1.0) as usize;
 
-            let games_to_show: Vec<_> = match self.filter {
+            let mut games_to_show: Vec<_> = match self.filter {
                 GameFilter::All => library_games
                     .iter()
                     .filter(|g| {
@@ -65,42 +119,80 @@ impl LibraryView {
                     })
                     .collect(),
             };
+            sort_games(&mut games_to_show, self.sort, installed_games);
+
+            if let Some(nav) = nav {
+                if let Some(nav_action) = self.navigate(
+                    nav,
+                    &games_to_show,
+                    cards_per_row,
+                    installed_games,
+                    downloads,
+                ) {
+                    action = Some(nav_action);
+                }
+            }
+
+            if self.search_query.is_empty() {
+                if let Some(recent_action) =
+                    Self::continue_playing(ui, library_games, covers, installed_games)
+                {
+                    action = Some(recent_action);
+                }
+            }
 
             if games_to_show.is_empty() {
                 ui.vertical_centered(|ui| {
                     ui.add_space(100.0);
                     ui.label(
-                        RichText::new("No games found")
+                        RichText::new(tr("gui-no-games"))
                             .size(18.0)
                             .color(Color32::GRAY),
                     );
                     if self.search_query.is_empty() {
-                        ui.label(
-                            RichText::new("Your library is empty or not yet loaded")
-                                .color(Color32::GRAY),
-                        );
+                        ui.label(RichText::new(tr("gui-library-empty")).color(Color32::GRAY));
                     }
                 });
             } else {
                 // Display games in a grid with enhanced spacing
-                for row_games in games_to_show.chunks(cards_per_row) {
+                for (row, row_games) in games_to_show.chunks(cards_per_row).enumerate() {
                     ui.horizontal(|ui| {
-                        for game in row_games {
-                            let is_installed = installed_games
+                        for (column, game) in row_games.iter().enumerate() {
+                            let focused = self.focus == Some(row * cards_per_row + column);
+                            let focus = match self.menu {
+                                _ if !focused => CardFocus::None,
+                                Some(tool) => CardFocus::Menu(tool),
+                                None => CardFocus::Card,
+                            };
+                            let installed = installed_games
                                 .iter()
-                                .any(|ig| ig.app_name == game.app_name);
-                            let is_installing = self
-                                .installing_games
-                                .lock()
-                                .unwrap()
-                                .contains(&game.app_name);
-                            
-                            if let Some(game_action) = GameCard::show(ui, game, is_installed, is_installing) {
-                                action = Some(match game_action {
-                                    GameCardAction::Install(name) => LibraryAction::Install(name),
-                                    GameCardAction::Launch(name) => LibraryAction::Launch(name),
-                                    GameCardAction::Uninstall(name) => LibraryAction::Uninstall(name),
-                                });
+                                .find(|ig| ig.app_name == game.app_name);
+                            let update_version =
+                                game_updates.get(&game.app_name).map(String::as_str);
+                            let selected_runner = game_runners.get(&game.app_name);
+                            let is_installing = downloads.contains(&game.app_name);
+
+                            let card = ui.scope(|ui| {
+                                GameCard::show(
+                                    ui,
+                                    game,
+                                    covers.get(&game.app_name),
+                                    installed,
+                                    is_installing,
+                                    update_version,
+                                    runners,
+                                    selected_runner,
+                                    focus,
+                                )
+                            });
+                            if focused && self.scroll_to_focus {
+                                ui.scroll_to_rect(card.response.rect, None);
+                                self.scroll_to_focus = false;
+                            }
+                            if let Some(game_action) = card.inner {
+                                // Picking a tool with the mouse closes them too
+                                self.menu = None;
+                                action = Some(game_action.into());
                             }
                             ui.add_space(card_spacing);
                         }
@@ -113,16 +205,255 @@ impl LibraryView {
         action
     }
 
-    pub fn mark_installation_complete(&mut self, app_name: &str) {
-        self.installing_games
-            .lock()
-            .unwrap()
-            .retain(|name| name != app_name);
+    /// Move the focus around the grid of `games`, or act on the focused game:
+    /// Enter launches it, or opens its page when it isn't installed
+    fn navigate(
+        &mut self,
+        nav: Nav,
+        games: &[&Game],
+        cards_per_row: usize,
+        installed_games: &[InstalledGame],
+        downloads: &Downloads,
+    ) -> Option<LibraryAction> {
+        let Some(last) = games.len().checked_sub(1) else {
+            self.focus = None;
+            self.menu = None;
+            return None;
+        };
+        // The first press only shows where the focus is
+        let Some(index) = self.focus.map(|index| index.min(last)) else {
+            if nav != Nav::Back {
+                self.focus = Some(0);
+                self.scroll_to_focus = true;
+            }
+            return None;
+        };
+        let game = games[index];
+        let is_installed = installed_games
+            .iter()
+            .any(|installed| installed.app_name == game.app_name);
+
+        if let Some(selected) = self.menu.filter(|_| is_installed) {
+            let tools = GameCard::tools(&game.app_name, downloads.contains(&game.app_name));
+            let enabled = |index: &usize| tools[*index].enabled;
+            match nav {
+                Nav::Up => {
+                    self.menu = (0..selected).rev().find(enabled).or(Some(selected));
+                }
+                Nav::Down => {
+                    self.menu = (selected + 1..tools.len()).find(enabled).or(Some(selected));
+                }
+                Nav::Activate => {
+                    self.menu = None;
+                    return tools
+                        .into_iter()
+                        .nth(selected)
+                        .filter(|tool| tool.enabled)
+                        .map(|tool| tool.action.into());
+                }
+                Nav::Menu | Nav::Back => self.menu = None,
+                Nav::Left | Nav::Right => {}
+            }
+            return None;
+        }
+        self.menu = None;
+
+        let moved = match nav {
+            Nav::Left => index.saturating_sub(1),
+            Nav::Right => (index + 1).min(last),
+            Nav::Up => index.checked_sub(cards_per_row).unwrap_or(index),
+            // From a full row onto a shorter last one, the last card
+            Nav::Down if index / cards_per_row < last / cards_per_row => {
+                (index + cards_per_row).min(last)
+            }
+            Nav::Down => index,
+            Nav::Activate if is_installed => {
+                return Some(LibraryAction::Launch(game.app_name.clone()))
+            }
+            Nav::Activate => return Some(LibraryAction::ShowDetails(game.app_name.clone())),
+            Nav::Menu => {
+                if is_installed {
+                    let tools = GameCard::tools(&game.app_name, downloads.contains(&game.app_name));
+                    self.menu = tools.iter().position(|tool| tool.enabled);
+                }
+                return None;
+            }
+            Nav::Back => {
+                self.focus = None;
+                return None;
+            }
+        };
+        self.focus = Some(moved);
+        self.scroll_to_focus = true;
+        None
+    }
+
+    /// The last games played, newest first, each launched with one click
+    fn continue_playing(
+        ui: &mut egui::Ui,
+        library_games: &[Game],
+        covers: &CoverArt,
+        installed_games: &[InstalledGame],
+    ) -> Option<LibraryAction> {
+        let mut recent: Vec<_> = installed_games
+            .iter()
+            .filter_map(|installed| Some((installed.last_played?, installed)))
+            .filter_map(|(played, installed)| {
+                let game = library_games
+                    .iter()
+                    .find(|game| game.app_name == installed.app_name)?;
+                Some((played, game))
+            })
+            .collect();
+        if recent.is_empty() {
+            return None;
+        }
+        recent.sort_by_key(|(played, _)| std::cmp::Reverse(*played));
+
+        let mut action = None;
+        ui.label(
+            RichText::new(tr("gui-continue-playing"))
+                .size(18.0)
+                .strong(),
+        );
+        ui.add_space(8.0);
+        ScrollArea::horizontal()
+            .id_salt("continue_playing")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (played, game) in recent.into_iter().take(RECENT_GAMES) {
+                        ui.vertical(|ui| {
+                            let (rect, response) =
+                                ui.allocate_exact_size(RECENT_TILE, egui::Sense::click());
+                            match covers.get(&game.app_name) {
+                                Some(cover) => egui::Image::new(cover)
+                                    .uv(artwork::fill_uv(cover.size(), rect))
+                                    .rounding(6.0)
+                                    .paint_at(ui, rect),
+                                None => {
+                                    ui.painter().rect_filled(
+                                        rect,
+                                        6.0,
+                                        Color32::from_rgb(45, 50, 65),
+                                    );
+                                    ui.painter().text(
+                                        rect.center(),
+                                        egui::Align2::CENTER_CENTER,
+                                        &game.app_title,
+                                        egui::FontId::proportional(14.0),
+                                        Color32::WHITE,
+                                    );
+                                }
+                            }
+                            if response.hovered() {
+                                ui.painter()
+                                    .rect_filled(rect, 6.0, Color32::from_black_alpha(120));
+                                ui.painter().text(
+                                    rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    tr("gui-play"),
+                                    egui::FontId::proportional(18.0),
+                                    Color32::WHITE,
+                                );
+                            }
+                            if response
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                action = Some(LibraryAction::Launch(game.app_name.clone()));
+                            }
+
+                            ui.set_max_width(RECENT_TILE.x);
+                            ui.label(RichText::new(&game.app_title).size(13.0).strong());
+                            ui.label(
+                                RichText::new(format::date(played))
+                                    .size(11.0)
+                                    .color(styles::TEXT_SECONDARY),
+                            );
+                        });
+                        ui.add_space(10.0);
+                    }
+                });
+            });
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(15.0);
+
+        action
+    }
+}
+
+/// Games without what a mode sorts by (never played, not installed) go
+/// last; ties keep alphabetical order
+fn sort_games(games: &mut [&Game], sort: SortMode, installed_games: &[InstalledGame]) {
+    let installed: HashMap<&str, &InstalledGame> = installed_games
+        .iter()
+        .map(|game| (game.app_name.as_str(), game))
+        .collect();
+    let installed = |game: &Game| installed.get(game.app_name.as_str()).copied();
+
+    games.sort_by_cached_key(|game| game.app_title.to_lowercase());
+    match sort {
+        SortMode::Alphabetical => {}
+        SortMode::RecentlyPlayed => games.sort_by_key(|game| {
+            std::cmp::Reverse(installed(game).and_then(|game| game.last_played))
+        }),
+        SortMode::RecentlyUpdated => games.sort_by_key(|game| {
+            std::cmp::Reverse(installed(game).and_then(|game| game.updated_at))
+        }),
+        SortMode::InstallSize => games.sort_by_key(|game| {
+            std::cmp::Reverse(installed(game).map_or(0, |game| game.install_size))
+        }),
+        SortMode::InstalledFirst => games.sort_by_key(|game| installed(game).is_none()),
     }
 }
 
 pub enum LibraryAction {
+    ShowDetails(String),
     Install(String),
     Launch(String),
+    Update(String),
+    /// Queue every update found by the last check
+    UpdateAll,
     Uninstall(String),
+    SetHold(String, bool),
+    SetRunner(String, Option<Runner>),
+    Winetricks(String),
+    ViewLogs(String),
+    AddToSteam(String),
+    ClearShaders(String),
+    PreviewSync(String),
+    Verify(String),
+    Repair(String),
+    /// Ask where to move the game to
+    Move(String),
+    OpenFolder(String),
+    CreateShortcut(String),
+    /// The game's page, with its settings open
+    Settings(String),
+}
+
+impl From<GameCardAction> for LibraryAction {
+    fn from(action: GameCardAction) -> Self {
+        match action {
+            GameCardAction::ShowDetails(name) => LibraryAction::ShowDetails(name),
+            GameCardAction::Install(name) => LibraryAction::Install(name),
+            GameCardAction::Launch(name) => LibraryAction::Launch(name),
+            GameCardAction::Update(name) => LibraryAction::Update(name),
+            GameCardAction::Uninstall(name) => LibraryAction::Uninstall(name),
+            GameCardAction::SetHold(name, hold) => LibraryAction::SetHold(name, hold),
+            GameCardAction::SetRunner(name, runner) => LibraryAction::SetRunner(name, runner),
+            GameCardAction::Winetricks(name) => LibraryAction::Winetricks(name),
+            GameCardAction::ViewLogs(name) => LibraryAction::ViewLogs(name),
+            GameCardAction::AddToSteam(name) => LibraryAction::AddToSteam(name),
+            GameCardAction::ClearShaders(name) => LibraryAction::ClearShaders(name),
+            GameCardAction::PreviewSync(name) => LibraryAction::PreviewSync(name),
+            GameCardAction::Verify(name) => LibraryAction::Verify(name),
+            GameCardAction::Repair(name) => LibraryAction::Repair(name),
+            GameCardAction::Move(name) => LibraryAction::Move(name),
+            GameCardAction::OpenFolder(name) => LibraryAction::OpenFolder(name),
+            GameCardAction::CreateShortcut(name) => LibraryAction::CreateShortcut(name),
+            GameCardAction::Settings(name) => LibraryAction::Settings(name),
+        }
+    }
 }
@@ -1,7 +1,37 @@
 mod app;
+mod artwork;
 mod auth_view;
+mod downloads;
+mod downloads_view;
+mod game_detail_view;
 mod library_view;
+mod navigation;
+mod runtime;
+mod saves_view;
 mod styles;
+mod toasts;
 mod components;
 
 pub use app::LauncherApp;
+
+use crate::config::{storage, Config};
+use crate::Result;
+
+/// Window setup shared by every way of starting the GUI. Window and view
+/// state are kept in the configured state file when `remember_gui_state` is on.
+pub fn native_options(config: &Config) -> Result<eframe::NativeOptions> {
+    let state_path = config.gui_state_path()?;
+    if let Some(parent) = state_path.parent() {
+        storage::create_private_dir(parent)?;
+    }
+
+    Ok(eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([1200.0, 800.0])
+            .with_min_inner_size([800.0, 600.0])
+            .with_title("R Games Launcher"),
+        persist_window: config.remember_gui_state,
+        persistence_path: Some(state_path),
+        ..Default::default()
+    })
+}
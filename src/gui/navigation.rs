@@ -0,0 +1,180 @@
+//! Driving the launcher without a mouse, from the couch. The arrow keys move
+//! between games, Enter launches, Shift+F10 opens a game's tools and Escape
+//! goes back. Gamepads are read through the Linux joystick interface and
+//! their buttons turned into those same keys, so everything that works from
+//! the keyboard works from a pad.
+
+use egui::{Event, Key, Modifiers};
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often to look for a gamepad while none is connected
+const GAMEPAD_POLL: Duration = Duration::from_secs(2);
+
+/// How far a stick has to be pushed to count as a d-pad press
+const STICK_THRESHOLD: i16 = 16_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nav {
+    Up,
+    Down,
+    Left,
+    Right,
+    Activate,
+    /// The context-menu key: a game's tools
+    Menu,
+    Back,
+}
+
+/// The navigation key pressed this frame. None while a widget such as the
+/// search field or a dialog button has the keyboard, since egui moves
+/// between and presses those with the same keys.
+pub fn pressed(ctx: &egui::Context) -> Option<Nav> {
+    if ctx.memory(|memory| memory.focused().is_some() || memory.any_popup_open()) {
+        return None;
+    }
+    ctx.input(|input| {
+        // winit doesn't report the Menu key itself, so its usual stand-in.
+        // The event's own modifiers, as a gamepad's press comes with them.
+        let menu = input.events.iter().any(|event| {
+            matches!(event, Event::Key { key: Key::F10, pressed: true, modifiers, .. } if modifiers.shift)
+        });
+        if menu {
+            return Some(Nav::Menu);
+        }
+        [
+            (Key::ArrowUp, Nav::Up),
+            (Key::ArrowDown, Nav::Down),
+            (Key::ArrowLeft, Nav::Left),
+            (Key::ArrowRight, Nav::Right),
+            (Key::Enter, Nav::Activate),
+            (Key::Escape, Nav::Back),
+        ]
+        .into_iter()
+        .find(|(key, _)| input.key_pressed(*key))
+        .map(|(_, nav)| nav)
+    })
+}
+
+/// Key presses from the first gamepad found, read on a thread of their own
+/// and handed to egui with the rest of each frame's input
+pub struct Gamepad {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl Gamepad {
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let queue = Arc::clone(&events);
+        let spawned = std::thread::Builder::new()
+            .name("gamepad".to_string())
+            .spawn(move || loop {
+                match find_gamepad().map(File::open) {
+                    Some(Ok(device)) => read_gamepad(device, &queue, &ctx),
+                    Some(Err(e)) => log::debug!("Can't open gamepad: {}", e),
+                    None => {}
+                }
+                std::thread::sleep(GAMEPAD_POLL);
+            });
+        if let Err(e) = spawned {
+            log::warn!("Gamepads won't work: {}", e);
+        }
+        Self { events }
+    }
+
+    /// Add what the gamepad pressed since the last frame to this one's input
+    pub fn inject(&self, input: &mut egui::RawInput) {
+        input.events.append(&mut self.events.lock().unwrap());
+    }
+}
+
+fn find_gamepad() -> Option<PathBuf> {
+    let mut devices: Vec<_> = std::fs::read_dir("/dev/input")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("js"))
+        })
+        .collect();
+    devices.sort();
+    devices.into_iter().next()
+}
+
+/// Turn the pad's events into key presses until it is unplugged. Each event
+/// of `struct js_event` is a timestamp, a value, a type and a number.
+fn read_gamepad(mut device: File, queue: &Mutex<Vec<Event>>, ctx: &egui::Context) {
+    log::info!("Gamepad connected");
+    // Which way each axis was pushed, so holding it presses only once
+    let mut axes = [0i8; 8];
+    let mut event = [0u8; 8];
+    while device.read_exact(&mut event).is_ok() {
+        let value = i16::from_ne_bytes([event[4], event[5]]);
+        let kind = event[6];
+        let number = event[7];
+        // The initial state sent on connecting isn't a press
+        if kind & 0x80 != 0 {
+            continue;
+        }
+
+        let key = match kind {
+            0x01 if value == 1 => button_key(number),
+            0x02 => {
+                let Some(held) = axes.get_mut(usize::from(number)) else {
+                    continue;
+                };
+                let direction = match value {
+                    v if v <= -STICK_THRESHOLD => -1,
+                    v if v >= STICK_THRESHOLD => 1,
+                    _ => 0,
+                };
+                let pushed = direction != 0 && direction != *held;
+                *held = direction;
+                pushed.then(|| axis_key(number, direction)).flatten()
+            }
+            _ => None,
+        };
+
+        if let Some((key, modifiers)) = key {
+            let mut queue = queue.lock().unwrap();
+            for pressed in [true, false] {
+                queue.push(Event::Key {
+                    key,
+                    physical_key: None,
+                    pressed,
+                    repeat: false,
+                    modifiers,
+                });
+            }
+            ctx.request_repaint();
+        }
+    }
+    log::info!("Gamepad disconnected");
+}
+
+/// A, B and X or Y in the usual Xbox layout, which most pads report
+fn button_key(number: u8) -> Option<(Key, Modifiers)> {
+    match number {
+        0 => Some((Key::Enter, Modifiers::NONE)),
+        1 => Some((Key::Escape, Modifiers::NONE)),
+        2 | 3 => Some((Key::F10, Modifiers::SHIFT)),
+        _ => None,
+    }
+}
+
+/// The left stick (axes 0 and 1) and the d-pad (6 and 7)
+fn axis_key(number: u8, direction: i8) -> Option<(Key, Modifiers)> {
+    let key = match (number, direction) {
+        (0 | 6, -1) => Key::ArrowLeft,
+        (0 | 6, 1) => Key::ArrowRight,
+        (1 | 7, -1) => Key::ArrowUp,
+        (1 | 7, 1) => Key::ArrowDown,
+        _ => return None,
+    };
+    Some((key, Modifiers::NONE))
+}
@@ -0,0 +1,37 @@
+//! The one tokio runtime behind the GUI's background work. Network calls
+//! run on it as tasks and hand their result back through a [`Promise`],
+//! which the UI polls every frame; a runtime per call would start and tear
+//! down a thread pool each time.
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+use poll_promise::Promise;
+use tokio::runtime::Runtime;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// The shared runtime, started on first use
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("rauncher-gui")
+            .build()
+            .expect("Failed to start the GUI's async runtime")
+    })
+}
+
+/// Run `future` in the background, promising its output
+pub fn spawn<T: Send + 'static>(future: impl Future<Output = T> + Send + 'static) -> Promise<T> {
+    let (sender, promise) = Promise::new();
+    runtime().spawn(async move { sender.send(future.await) });
+    promise
+}
+
+/// Wait for `future` on the shared runtime. For threads of the GUI's own
+/// that need an async call in between blocking work; never call it from a
+/// task already on the runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    runtime().block_on(future)
+}
@@ -0,0 +1,320 @@
+use egui::{RichText, ScrollArea};
+use poll_promise::Promise;
+
+use crate::api;
+use crate::audit::Initiator;
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::format;
+use crate::games::{GameManager, SaveVersion, SyncAction, SyncPlan};
+use crate::i18n::{tr, tr_args};
+use crate::{Error, Result};
+
+use super::{runtime, styles};
+
+/// A game's saves tab: its local and cloud save files side by side, with
+/// what a sync would do to each, and the earlier versions syncs replaced
+pub struct SavesView {
+    app_name: String,
+    config: Config,
+    auth: AuthManager,
+    /// Dry run of a sync both ways, which also lists every file
+    plan_promise: Option<Promise<Result<SyncPlan>>>,
+    plan: Option<std::result::Result<SyncPlan, String>>,
+    history: std::result::Result<Vec<SaveVersion>, String>,
+    /// Upload, download or restore in progress, reporting what it did
+    task: Option<Promise<Result<String>>>,
+    /// How the last task ended
+    outcome: Option<std::result::Result<String, String>>,
+}
+
+impl SavesView {
+    pub fn open(app_name: String, config: Config, auth: AuthManager) -> Self {
+        let mut view = Self {
+            app_name,
+            config,
+            auth,
+            plan_promise: None,
+            plan: None,
+            history: Ok(Vec::new()),
+            task: None,
+            outcome: None,
+        };
+        view.refresh();
+        view
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.poll();
+        let busy = self.task.is_some() || self.plan_promise.is_some();
+        let sync_allowed = !matches!(&self.plan, Some(Ok(plan)) if !plan.sync_allowed);
+
+        ui.horizontal(|ui| {
+            let upload = ui
+                .add_enabled(
+                    !busy && sync_allowed && api::SAVE_UPLOADS_ENABLED,
+                    egui::Button::new(tr("gui-saves-upload")),
+                )
+                .on_hover_text(tr("gui-saves-upload-hover"));
+            let upload = if api::SAVE_UPLOADS_ENABLED {
+                upload
+            } else {
+                upload.on_disabled_hover_text(Error::SaveUploadsUnsupported.summary())
+            };
+            if upload.clicked() {
+                self.start("Uploading", |manager, app_name| async move {
+                    manager.upload_cloud_saves(&app_name).await?;
+                    Ok(tr("gui-saves-uploaded"))
+                });
+            }
+            if ui
+                .add_enabled(
+                    !busy && sync_allowed,
+                    egui::Button::new(tr("gui-saves-download")),
+                )
+                .on_hover_text(tr("gui-saves-download-hover"))
+                .clicked()
+            {
+                self.start("Downloading", |manager, app_name| async move {
+                    manager.download_cloud_saves(&app_name).await?;
+                    Ok(tr("gui-saves-downloaded"))
+                });
+            }
+            if ui
+                .add_enabled(!busy, egui::Button::new(tr("gui-preview-sync")))
+                .on_hover_text(tr("gui-saves-preview-hover"))
+                .clicked()
+            {
+                self.refresh();
+            }
+        });
+
+        if self.task.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(RichText::new(tr("gui-saves-working")).color(styles::TEXT_SECONDARY));
+            });
+        } else if let Some(outcome) = &self.outcome {
+            match outcome {
+                Ok(message) => ui.colored_label(styles::SUCCESS_GREEN, message),
+                Err(error) => ui.colored_label(styles::ERROR_RED, error),
+            };
+        }
+        ui.add_space(10.0);
+
+        match &self.plan {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(
+                        RichText::new(tr("gui-saves-comparing")).color(styles::TEXT_SECONDARY),
+                    );
+                });
+            }
+            Some(Err(error)) => {
+                ui.colored_label(
+                    styles::ERROR_RED,
+                    tr_args("gui-saves-compare-failed", &[("error", error)]),
+                );
+            }
+            Some(Ok(plan)) => plan_grid(ui, &self.app_name, plan),
+        }
+
+        ui.add_space(15.0);
+        ui.label(RichText::new(tr("gui-saves-earlier")).size(16.0).strong());
+        let mut restore = None;
+        match &self.history {
+            Err(error) => {
+                ui.colored_label(styles::ERROR_RED, error);
+            }
+            Ok(versions) if versions.is_empty() => {
+                ui.label(RichText::new(tr("gui-saves-none-kept")).color(styles::TEXT_SECONDARY));
+            }
+            Ok(versions) => {
+                egui::Grid::new(("save_history", &self.app_name))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for heading in ["gui-saves-file", "gui-saves-size", "gui-saves-replaced"] {
+                            ui.strong(tr(heading));
+                        }
+                        ui.label("");
+                        ui.end_row();
+
+                        // Newest first
+                        for version in versions.iter().rev() {
+                            ui.label(&version.filename);
+                            ui.label(format::size(version.size));
+                            ui.label(format::date(version.saved_at));
+                            if ui
+                                .add_enabled(!busy, egui::Button::new(tr("gui-saves-restore")))
+                                .on_hover_text(tr("gui-saves-restore-hover"))
+                                .clicked()
+                            {
+                                restore = Some(version.id().to_string());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            }
+        }
+
+        if let Some(version) = restore {
+            self.start("Restoring", move |manager, app_name| async move {
+                let restored = manager.restore_save(&app_name, &version).await?;
+                Ok(tr_args(
+                    "gui-saves-restored",
+                    &[("file", &restored.filename)],
+                ))
+            });
+        }
+    }
+
+    /// Compare both sides again and reread the history
+    fn refresh(&mut self) {
+        let config = self.config.clone();
+        let auth = self.auth.clone();
+        let app_name = self.app_name.clone();
+
+        self.history = GameManager::new(config.clone(), auth.clone())
+            .and_then(|manager| manager.save_history(&app_name))
+            .map_err(|e| e.summary());
+        self.plan = None;
+        self.plan_promise = Some(runtime::spawn(async move {
+            GameManager::new(config, auth)?
+                .plan_cloud_sync(&app_name, true, true)
+                .await
+        }));
+    }
+
+    fn start<F, Fut>(&mut self, what: &str, task: F)
+    where
+        F: FnOnce(GameManager, String) -> Fut,
+        Fut: std::future::Future<Output = Result<String>> + Send + 'static,
+    {
+        log::info!("{} saves of {}", what, self.app_name);
+        let manager = match GameManager::new(self.config.clone(), self.auth.clone()) {
+            Ok(manager) => manager.with_initiator(Initiator::Gui),
+            Err(e) => {
+                self.outcome = Some(Err(e.summary()));
+                return;
+            }
+        };
+        self.outcome = None;
+        self.task = Some(runtime::spawn(task(manager, self.app_name.clone())));
+    }
+
+    fn poll(&mut self) {
+        if self
+            .plan_promise
+            .as_ref()
+            .is_some_and(|p| p.ready().is_some())
+        {
+            if let Some(Ok(result)) = self.plan_promise.take().map(Promise::try_take) {
+                self.plan = Some(result.map_err(|e| e.summary()));
+            }
+        }
+
+        if self.task.as_ref().is_some_and(|p| p.ready().is_some()) {
+            if let Some(Ok(result)) = self.task.take().map(Promise::try_take) {
+                self.outcome = Some(result.map_err(|e| e.summary()));
+                self.refresh();
+            }
+        }
+    }
+}
+
+/// What a sync would do with each file, with the cloud storage left. Shared
+/// by the saves tab and the "Preview sync" window.
+pub fn plan_grid(ui: &mut egui::Ui, app_name: &str, plan: &SyncPlan) {
+    if !plan.sync_allowed {
+        ui.label(tr("gui-saves-sync-disabled"));
+        return;
+    }
+    ui.label(tr_args("gui-saves-plan", &[("dir", &plan.dir.display())]));
+    ui.add_space(8.0);
+    if plan.files.is_empty() {
+        ui.label(tr("gui-saves-plan-empty"));
+        return;
+    }
+
+    ScrollArea::vertical()
+        .id_salt(("sync_plan", app_name))
+        .max_height(300.0)
+        .show(ui, |ui| {
+            egui::Grid::new(("sync_plan", app_name))
+                .striped(true)
+                .show(ui, |ui| {
+                    for heading in [
+                        "gui-saves-action",
+                        "gui-saves-file",
+                        "gui-saves-local",
+                        "gui-saves-modified",
+                        "gui-saves-cloud",
+                        "gui-saves-uploaded-at",
+                    ] {
+                        ui.strong(tr(heading));
+                    }
+                    ui.end_row();
+
+                    for file in &plan.files {
+                        if file.action == SyncAction::Conflict {
+                            ui.colored_label(styles::ERROR_RED, tr("gui-saves-conflict"))
+                                .on_hover_text(tr("gui-saves-conflict-hover"));
+                        } else {
+                            ui.label(file.action.to_string());
+                        }
+                        ui.label(&file.filename);
+                        match &file.local {
+                            Some(save) => {
+                                ui.label(format::size(save.size));
+                                ui.label(format::date(save.modified.into()));
+                            }
+                            None => {
+                                ui.label("-");
+                                ui.label("-");
+                            }
+                        }
+                        match &file.cloud {
+                            Some(save) => {
+                                ui.label(format::size(save.size));
+                                ui.label(
+                                    save.uploaded_at
+                                        .parse()
+                                        .map(format::date)
+                                        .unwrap_or_else(|_| save.uploaded_at.clone()),
+                                );
+                            }
+                            None => {
+                                ui.label("-");
+                                ui.label("-");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(quota) = &plan.quota {
+        ui.add_space(8.0);
+        ui.label(tr_args(
+            "gui-saves-quota",
+            &[
+                ("used", &format::size(quota.used_bytes)),
+                ("quota", &format::size(quota.quota_bytes)),
+                ("game_used", &format::size(quota.used_by(app_name))),
+            ],
+        ));
+        if plan.exceeds_quota() {
+            ui.colored_label(
+                styles::ERROR_RED,
+                tr_args(
+                    "gui-saves-quota-exceeded",
+                    &[
+                        ("needed", &format::size(plan.upload_size())),
+                        ("left", &format::size(quota.available())),
+                    ],
+                ),
+            );
+        }
+    }
+}
@@ -41,8 +41,10 @@ pub fn setup_custom_style(ctx: &egui::Context) {
 
 // Epic Games-inspired color palette
 pub const CARD_BG: Color32 = Color32::from_rgb(28, 28, 32);
+#[allow(dead_code)]
 pub const CARD_HOVER_BG: Color32 = Color32::from_rgb(35, 35, 40);
 pub const EPIC_BLUE: Color32 = Color32::from_rgb(0, 121, 214);
+#[allow(dead_code)]
 pub const EPIC_BLUE_HOVER: Color32 = Color32::from_rgb(0, 145, 255);
 pub const SUCCESS_GREEN: Color32 = Color32::from_rgb(76, 175, 80);
 pub const ERROR_RED: Color32 = Color32::from_rgb(244, 67, 54);
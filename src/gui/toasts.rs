@@ -0,0 +1,199 @@
+//! Messages about what the launcher did, stacked in the bottom right corner
+//! so one operation finishing doesn't hide what another said. They go away
+//! on their own, errors after longer, and stay while the pointer is on them.
+//! An error's code and hint are a click away.
+
+use egui::{Color32, RichText};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::i18n::tr;
+use crate::Error;
+
+use super::styles;
+
+/// Toasts shown at once; the oldest make way for new ones
+const MAX_TOASTS: usize = 5;
+
+const WIDTH: f32 = 320.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Success,
+    Error,
+}
+
+impl Level {
+    fn lifetime(self) -> Duration {
+        match self {
+            Level::Info | Level::Success => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(15),
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Level::Info => styles::EPIC_BLUE,
+            Level::Success => styles::SUCCESS_GREEN,
+            Level::Error => styles::ERROR_RED,
+        }
+    }
+}
+
+struct Toast {
+    id: u64,
+    level: Level,
+    message: String,
+    /// Opened in a window on a click
+    details: Option<String>,
+    /// The error goes away by signing in again
+    sign_in: bool,
+    expires: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+    next_id: u64,
+    /// Message and details of the toast clicked last
+    details: Option<(String, String)>,
+}
+
+impl Toasts {
+    pub fn add(&mut self, level: Level, message: impl Into<String>) {
+        self.push(level, message.into(), None, false);
+    }
+
+    /// `context` as an error toast, with the error's code, message and hint
+    /// as its details. Auth failures also offer a way back to the login
+    /// screen.
+    pub fn error(&mut self, context: &str, error: &Error) {
+        let mut details = format!("{}: {}", error.code(), error);
+        if let Some(hint) = error.hint() {
+            details.push_str("\n\n");
+            details.push_str(&hint);
+        }
+        self.push(
+            Level::Error,
+            context.to_string(),
+            Some(details),
+            error.requires_login(),
+        );
+    }
+
+    fn push(&mut self, level: Level, message: String, details: Option<String>, sign_in: bool) {
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            id: self.next_id,
+            level,
+            message,
+            details,
+            sign_in,
+            expires: Instant::now() + level.lifetime(),
+        });
+        self.next_id += 1;
+    }
+
+    /// Whether a toast's details are open, which has the keyboard
+    pub fn details_open(&self) -> bool {
+        self.details.is_some()
+    }
+
+    /// Draw the toasts, newest at the bottom, and the details window.
+    /// Returns whether signing in again was asked for.
+    pub fn show(&mut self, ctx: &egui::Context) -> bool {
+        let now = Instant::now();
+        self.toasts.retain(|toast| toast.expires > now);
+
+        let mut sign_in = false;
+        let mut dismissed = None;
+        if !self.toasts.is_empty() {
+            egui::Area::new(egui::Id::new("toasts"))
+                .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+                .order(egui::Order::Foreground)
+                .show(ctx, |ui| {
+                    for toast in &mut self.toasts {
+                        let frame = egui::Frame::none()
+                            .fill(styles::CARD_BG)
+                            .stroke(egui::Stroke::new(1.0, toast.level.color()))
+                            .rounding(egui::Rounding::same(6.0))
+                            .inner_margin(10.0)
+                            .show(ui, |ui| {
+                                ui.set_width(WIDTH);
+                                let message = ui
+                                    .add(
+                                        egui::Label::new(
+                                            RichText::new(&toast.message)
+                                                .color(toast.level.color()),
+                                        )
+                                        .wrap()
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand);
+                                if message.clicked() {
+                                    if let Some(details) = &toast.details {
+                                        self.details =
+                                            Some((toast.message.clone(), details.clone()));
+                                    }
+                                    dismissed = Some(toast.id);
+                                }
+
+                                if toast.details.is_some() || toast.sign_in {
+                                    ui.horizontal(|ui| {
+                                        if toast.details.is_some() {
+                                            ui.label(
+                                                RichText::new(tr("gui-toast-details"))
+                                                    .size(12.0)
+                                                    .color(styles::TEXT_SECONDARY),
+                                            );
+                                        }
+                                        if toast.sign_in
+                                            && ui.small_button(tr("gui-sign-in")).clicked()
+                                        {
+                                            sign_in = true;
+                                            dismissed = Some(toast.id);
+                                        }
+                                    });
+                                }
+                            });
+                        // Keep what is being read
+                        if frame.response.contains_pointer() {
+                            toast.expires = toast.expires.max(now + Duration::from_secs(2));
+                        }
+                        ui.add_space(6.0);
+                    }
+                });
+        }
+        if let Some(id) = dismissed {
+            self.toasts.retain(|toast| toast.id != id);
+        }
+        if sign_in {
+            // Nothing before signing in again applies any more
+            self.toasts.retain(|toast| !toast.sign_in);
+        }
+
+        if let Some(expires) = self.toasts.iter().map(|toast| toast.expires).min() {
+            ctx.request_repaint_after(expires.saturating_duration_since(now));
+        }
+
+        if let Some((message, details)) = &self.details {
+            let mut open = true;
+            egui::Window::new(message.as_str())
+                .id(egui::Id::new("toast_details"))
+                .open(&mut open)
+                .collapsible(false)
+                .default_width(480.0)
+                .show(ctx, |ui| {
+                    ui.label(details.as_str());
+                });
+            if !open || ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+                self.details = None;
+            }
+        }
+
+        sign_in
+    }
+}
@@ -0,0 +1,412 @@
+# English, the reference catalog: every message the code uses is here.
+# CLI help is not, as its English text is the doc comments in src/cli.
+
+## Errors
+
+error-label = Error
+error-hint-label = Hint
+error-see-label = See
+error-api = API error: { $detail }
+error-auth = Authentication error: { $detail }
+error-config = Configuration error: { $detail }
+error-io = IO error: { $detail }
+error-http = HTTP error: { $detail }
+error-json = JSON error: { $detail }
+error-toml = TOML error: { $detail }
+error-not-authenticated = Not authenticated
+error-game-not-found = Game not found: { $game }
+error-cancelled = Cancelled
+error-save-uploads-unsupported = Uploading cloud saves isn't supported yet
+error-hint-login = sign in again with 'rauncher auth'
+error-hint-network = check your connection or try again later; Epic's services may be down
+error-hint-config = fix the setting with 'rauncher config set' or edit config.toml
+error-hint-data = launcher metadata may be damaged; run 'rauncher doctor'
+error-hint-game = check the app name with 'rauncher list'; 'rauncher info --remote' also shows games that aren't installed
+error-hint-cancelled = run the same command again to pick up where it stopped
+error-hint-save-uploads = local saves are kept as they are; upload them from Epic's launcher
+
+## Shared GUI labels
+
+gui-cancel = Cancel
+gui-retry = Retry
+gui-install = Install
+gui-uninstall = Uninstall
+gui-update = Update
+gui-play = ▶ Play
+gui-get = Get
+gui-installing = ⏳ Installing...
+gui-version-available = ⬆ v{ $version } available
+
+## Header, tabs and toasts
+
+gui-logout = Logout
+gui-sign-in = Sign in
+gui-toast-details = Click for details
+gui-tab-library = Library
+gui-tab-downloads = Downloads
+gui-tab-downloads-count = Downloads ({ $count })
+gui-download-limit = Download limit
+gui-unlimited = unlimited
+gui-ui-scale = UI scale
+
+## Library
+
+gui-library = Library
+gui-search-hint = Search games...
+gui-filter-all = All Games
+gui-filter-installed = Installed
+gui-sort = Sort: { $mode }
+gui-sort-alphabetical = A to Z
+gui-sort-recently-played = Recently played
+gui-sort-recently-updated = Recently updated
+gui-sort-install-size = Install size
+gui-sort-installed-first = Installed first
+gui-update-all = ⬆ Update all ({ $count })
+gui-no-games = No games found
+gui-library-empty = Your library is empty or not yet loaded
+gui-continue-playing = Continue playing
+gui-loading-library = Loading your library...
+gui-library-failed = Couldn't load your library
+gui-offline = ⚠ Offline
+gui-offline-detail = Epic can't be reached. Installed games can still be played; store and cloud features wait until you're back online.
+gui-retrying = Retrying...
+
+## Game cards and pages
+
+gui-hold-updates = Hold updates
+gui-default-runner = Default runner
+gui-winetricks = Winetricks
+gui-view-logs = View logs
+gui-add-to-steam = Add to Steam
+gui-preview-sync = Preview sync
+gui-clear-shaders = Clear shaders
+gui-verify = Verify
+gui-repair = Repair
+gui-move = Move...
+gui-open-folder = Open install folder
+gui-create-shortcut = Create shortcut
+gui-game-settings = Game settings
+gui-back-to-library = ← Library
+gui-overview = Overview
+gui-saves = Saves
+gui-settings = Settings
+gui-add-ons = Add-ons
+gui-none-owned = None owned
+gui-verified-intact = Every file verified intact
+gui-damaged-files = { $count } file(s) missing or corrupt
+gui-install-size = Install size
+gui-playtime = Playtime
+gui-last-played = Last played
+gui-never = Never
+gui-location = Location
+gui-loading-store = Loading store details...
+gui-store-failed = Couldn't load store details: { $error }
+
+## Downloads
+
+gui-no-downloads = No downloads
+gui-no-downloads-detail = Installs and updates you start show up here
+gui-pause = Pause
+gui-resume = Resume
+gui-start-later = Start later
+gui-start-sooner = Start sooner
+gui-queued = Queued
+gui-preparing = Preparing...
+gui-paused = Paused
+gui-bytes-done = { $done } of { $total }
+gui-time-left = { $time } left
+gui-files-done = { $done }/{ $total } files
+gui-kind-install = Install
+gui-kind-update = Update
+gui-kind-repair = Repair
+
+## Dialogs
+
+gui-install-title = Install { $game }
+gui-create-desktop-shortcut = Create desktop shortcut
+gui-uninstall-title = Uninstall { $game }
+gui-uninstall-detail = The game's files will be removed.
+gui-delete-saves = Also move local saves to the trash
+gui-delete-prefix = Also delete the Wine prefix
+gui-logout-title = Log out
+gui-logout-detail = Logging out stops { $count } download(s). Starting them again later picks up where they stopped.
+gui-move-title = Move { $game }
+gui-move-into = Move the game's folder into:
+gui-move-detail = Moving to another drive copies every file, which can take a while.
+gui-move-confirm = Move
+gui-pin-title = Play { $game }
+gui-pin-detail = Today's play time is used up. The parental PIN allows one more session.
+gui-pin-confirm = Unlock
+
+## Sign in
+
+gui-auth-subtitle = Sign in to your account
+gui-auth-sign-in = Sign In with Epic Games
+gui-auth-instructions = Click the button above to authenticate with Epic Games
+gui-auth-code-note = You'll receive a code to enter in your browser
+gui-auth-initializing = Initializing authentication...
+gui-auth-in-progress = ⏳ Authentication in Progress
+gui-auth-complete-in-browser = Please complete authentication in your browser:
+gui-auth-url = URL:
+gui-auth-code = Code:
+gui-auth-open-browser = 🌐 Open in Browser
+gui-auth-scan = Or scan with your phone:
+gui-auth-waiting = Waiting for you to complete authentication... (attempt { $attempt }/{ $attempts })
+gui-auth-success = ✓ Successfully authenticated with Epic Games!
+gui-auth-start-failed = Failed to start authentication: { $error }
+gui-auth-save-failed = Error saving token: { $error }
+gui-auth-timed-out = Authentication timed out. Please try again.
+gui-auth-failed = Authentication failed: { $error }
+gui-auth-cancelled = Authentication cancelled
+
+## Saves tab
+
+gui-saves-upload = ⬆ Upload
+gui-saves-upload-hover = Send local saves that changed to the cloud
+gui-saves-uploaded = Uploaded the saves
+gui-saves-download = ⬇ Download
+gui-saves-download-hover = Fetch cloud saves that changed
+gui-saves-downloaded = Downloaded the saves
+gui-saves-preview-hover = Compare both sides again without changing anything
+gui-saves-working = Working...
+gui-saves-comparing = Comparing saves...
+gui-saves-compare-failed = Couldn't compare saves: { $error }
+gui-saves-earlier = Earlier versions
+gui-saves-none-kept = None kept. Syncs keep replaced files while save_history_versions is above 0.
+gui-saves-file = File
+gui-saves-size = Size
+gui-saves-replaced = Replaced
+gui-saves-restore = Restore
+gui-saves-restore-hover = The current file is kept here in its place
+gui-saves-restored = Restored { $file }
+gui-saves-sync-disabled = Cloud save sync is disabled for this game.
+gui-saves-plan = Downloading and then uploading the saves in { $dir } would:
+gui-saves-plan-empty = Nothing; there are no saves.
+gui-saves-action = Action
+gui-saves-local = Local
+gui-saves-modified = Modified
+gui-saves-cloud = Cloud
+gui-saves-uploaded-at = Uploaded
+gui-saves-conflict = ⚠ conflict
+gui-saves-conflict-hover = Changed locally and in the cloud since the last sync
+gui-saves-quota = Cloud storage: { $used } of { $quota } used, { $game_used } by this game
+gui-saves-quota-exceeded = The uploads need { $needed }, more than the { $left } left
+
+## Command line output
+
+msg-yes = Yes
+msg-no = No
+msg-none = (none)
+msg-unknown = unknown
+msg-not-set = (not set)
+msg-path = Path: { $path }
+msg-tag-installed = installed
+msg-tag-not-installed = not installed
+msg-tag-archived = archived
+msg-tag-held = held
+msg-tag-prerelease = pre-release
+msg-tag-default = default
+msg-gui-failed = Failed to run GUI: { $error }
+msg-interrupted = Warning: { $operation } of { $game } was interrupted ({ $date }).
+msg-interrupted-hint = Run 'rauncher resume' to finish, or 'rauncher resume --rollback' to undo.
+msg-cancelling = Cancelling after the current chunk; run the command again to resume.
+msg-logged-out = Successfully logged out
+msg-auth-title = Epic Games Store Authentication
+msg-auth-starting = Starting authentication process...
+msg-auth-browser = Please authenticate using your web browser:
+msg-auth-open-url = 1. Open this URL: { $url }
+msg-auth-enter-code = 2. Enter this code: { $code }
+msg-auth-waiting = Waiting for authentication...
+msg-auth-success = ✓ Successfully authenticated with Epic Games Store!
+msg-auth-next = You can now:
+msg-auth-next-list = List your games: rauncher list
+msg-auth-next-install = Install a game: rauncher install <app_name>
+msg-installed-games = Installed Games:
+msg-no-games-installed = No games installed
+msg-library = Library:
+msg-library-empty = No games in library (or authentication required)
+msg-dlc = DLC:
+msg-no-dlc = No DLC in library
+msg-dlc-for = for { $game }
+msg-no-matches = No games match '{ $query }'
+msg-no-games-to-install = No games to install.
+msg-installing = Installing { $count } game(s)
+msg-verifying-launch = Verifying that { $game } launches...
+msg-ran-for = ✓ { $game } ran for { $time }
+msg-quit-after = ✗ { $game } quit after { $time } ({ $status })
+msg-full-log = Full log: { $path }
+msg-could-not-start = ✗ Could not start { $game }: { $error }
+msg-install-summary = Install summary:
+msg-game-installed = Game installed successfully!
+msg-all-installed = All games installed successfully!
+msg-installs-failed = { $failed } of { $total } install(s) failed
+msg-importing = Importing { $game } from { $path }
+msg-imported = ✓ Game imported successfully!
+msg-launch-offline = Not signed in or the session expired; launching offline.
+msg-downloading-saves = Downloading cloud saves...
+msg-save-download-failed = Cloud save download failed, launching anyway: { $error }
+msg-launched = Game launched successfully!
+msg-output-goes-to = Its output goes to { $path }
+msg-watching-time = Watching play time; keep this terminal open.
+msg-uploading-on-exit = Saves are uploaded when the game exits; keep this terminal open.
+msg-tracking-time = Tracking play time until the game exits.
+msg-session-length = Session length: { $time }
+msg-session-monitor-failed = Session monitor failed: { $error }
+msg-save-upload-failed = Cloud save upload failed: { $error }
+msg-pin-unlockable = A daily limit is set without a parental PIN, so nothing can unlock it. Remove time_limits from config.toml to start over.
+msg-pin-prompt = Parental PIN:
+msg-pin-current = Current PIN:
+msg-pin-new = New PIN:
+msg-pin-repeat = Repeat PIN:
+msg-pin-incorrect = Incorrect PIN.
+msg-pin-mismatch = PINs are empty or don't match. Run interactively to set one.
+msg-pin-set = ✓ Parental PIN set
+msg-pin-removed = ✓ Parental PIN and daily limit removed
+msg-uninstalled = Game uninstalled successfully!
+msg-moving = Moving { $game } to { $path }...
+msg-moved = ✓ Game moved to { $path }
+msg-archive-installed-size = Installed size: { $size }
+msg-archive-estimate = Estimated archive size: { $size }
+msg-archive-savings = Estimated savings: { $size }
+msg-archiving = Archiving { $game }...
+msg-archived = ✓ Game archived ({ $size } on disk)
+msg-unarchived = ✓ Game files restored
+msg-verifying = Verifying game files for { $game }...
+msg-file-missing = Missing: { $file }
+msg-file-corrupt = Corrupt: { $file }
+msg-verify-summary = Checked { $checked } file(s): { $missing } missing, { $corrupt } corrupt
+msg-verify-skipped = { $count } file(s) unchanged since the last verify were not re-hashed
+msg-verify-ok = ✓ All files verified successfully
+msg-verify-failed = Verification failed for { $game }
+msg-checking-files = Checking game files for { $game }...
+msg-no-damage = ✓ No damaged files found
+msg-repaired = ✓ Repaired { $count } file(s)
+msg-game-info = Game Information:
+msg-latest-release = Latest Release:
+msg-status-title = R Games Launcher Status
+msg-configuration = Configuration:
+msg-isolation-problems = Metadata isolation problems:
+msg-field-name = Name
+msg-field-title = Title
+msg-field-version = Version
+msg-field-installed = Installed
+msg-field-install-path = Install Path
+msg-field-executable = Executable
+msg-field-case-remaps = Case-merged Paths
+msg-field-playtime = Playtime
+msg-field-last-played = Last Played
+msg-field-shader-cache = Shader Cache
+msg-field-cloud-sync = Cloud Save Sync
+msg-field-download-size = Download Size
+msg-field-install-size = Install Size
+msg-field-files = Files
+msg-field-launch-exe = Launch Executable
+msg-field-authenticated = Authenticated
+msg-field-install-dir = Install Directory
+msg-field-log-level = Log Level
+msg-field-update-channel = Update Channel
+msg-field-shared-install-dir = Shared Install Directory
+msg-field-config-path = Config Path
+msg-field-data-dir = Data Directory
+msg-field-cache-dir = Cache Directory
+msg-sync-enabled = enabled
+msg-sync-off = disabled
+msg-sync-enabled-default = enabled (default)
+msg-held = ✓ Updates for { $game } are on hold
+msg-unheld = ✓ Updates for { $game } are no longer on hold
+msg-skipping-held = Skipping { $game } (updates on hold)
+msg-checking-updates = Checking for updates for { $game }...
+msg-update-available = ✓ Update available: version { $version }
+msg-up-to-date = ✓ Game is up to date
+msg-update-check-failed = Failed to check for updates: { $error }
+msg-updating = Updating { $game }...
+msg-update-complete = ✓ Update complete!
+msg-update-failed = Failed to update { $game }: { $error }
+msg-updates-failed = Not every game could be updated
+msg-rolled-back = ✓ Rolled { $game } back to version { $version }
+msg-rollback-hold-hint = Run 'rauncher update { $game } --hold' to keep it on this version.
+msg-sync-enabled-for = ✓ Cloud save sync enabled for { $game }
+msg-sync-disabled-for = ✓ Cloud save sync disabled for { $game }
+msg-sync-direction = Specify --download or --upload
+msg-sync-disabled = Cloud save sync is disabled for { $game }
+msg-sync-dry-run = Dry run for saves in { $dir }; nothing was changed.
+msg-no-saves = (no saves)
+msg-column-action = ACTION
+msg-column-file = FILE
+msg-column-local = LOCAL
+msg-column-modified = MODIFIED
+msg-column-cloud = CLOUD
+msg-column-uploaded = UPLOADED
+msg-sync-conflicts = { $count } conflict(s) would be settled by save_conflict_policy
+msg-cloud-storage = Cloud storage: { $used } of { $quota } used
+msg-quota-exceeded = Warning: the uploads need { $needed }, more than the { $left } left
+msg-no-logs = No logs for { $game } yet. They are written when it is launched.
+msg-session-logs = Session Logs (latest first):
+msg-shortcut-added = ✓ Added { $game } to the application menu ({ $path })
+msg-shortcut-removed = ✓ Removed { $game } from the application menu
+msg-no-shortcut = { $game } has no menu entry
+msg-steam-added = ✓ Added { $game } to Steam ({ $count } account(s))
+msg-restart-steam = Restart Steam to see the changes.
+msg-steam-failed = Not every game could be added to Steam
+msg-lutris-failed = Not every game could be exported to Lutris
+msg-cloud-saves = Cloud Saves:
+msg-local-saves = Local Saves in { $dir } (sent by --upload):
+msg-cloud-storage-game = Cloud Storage: { $used } of { $quota } used, { $game_used } by { $game }
+msg-no-save-versions = No earlier save versions of { $game } are kept
+msg-save-versions = Earlier save versions of { $game }:
+msg-save-restore-hint = Put one back with: rauncher saves restore { $game } <version>
+msg-saves-backed-up = ✓ Backed up { $count } save file(s) to { $path }
+msg-saves-restored = ✓ Restored { $count } save file(s) from { $path }
+msg-save-restored = ✓ Restored { $file } from { $date }
+msg-winetricks-installed = ✓ Installed { $verbs } for { $game }
+msg-winetricks-failed = winetricks exited with { $status }
+msg-available-builds = Available Builds:
+msg-no-runners = No Wine or Proton installation found.
+msg-runners = Runners:
+msg-runner-for-game = { $game } uses { $runner }
+msg-runner-installed = ✓ Installed { $name }
+msg-runner-use-hint = Use it for a game by adding it to [game_runners]:
+msg-runner-removed = ✓ Removed { $name }
+msg-runner-still-used = Warning: [game_runners] still sets it for { $game }
+msg-egl-imported = Imported from EGL: { $game }
+msg-egl-exported = Exported to EGL: { $game }
+msg-egl-skipped = Not exported, not in your library: { $game }
+msg-egl-done = ✓ Sync complete: { $imported } imported, { $exported } exported
+msg-nothing-to-clean = Nothing to clean up.
+msg-would-reclaim = Would reclaim { $size }. Run without --dry-run to remove.
+msg-reclaimed = ✓ Reclaimed { $size }
+msg-launcher-update = rauncher { $version } is available ({ $channel } channel)
+msg-launcher-up-to-date = ✓ rauncher { $version } is up to date
+msg-freed = ✓ Freed { $size }
+msg-cache-usage = { $entries } entries, { $used } of { $size }
+msg-cache-ttl = Entries expire after { $hours } hour(s)
+msg-shaders-cleared = ✓ Cleared the shader caches of { $count } game(s), freeing { $size }
+msg-shader-caches = Shader Caches:
+msg-records-ok = ✓ All installed-game records are readable
+msg-records-unreadable = Unreadable installed-game records:
+msg-rebuild-prompt = Try to rebuild them?
+msg-rebuild-hint = Run 'rauncher doctor --fix' to rebuild them.
+msg-rebuilt = ✓ Rebuilt { $game } from { $source }
+msg-rebuild-failed = ✗ Could not rebuild { $game }: { $error }
+msg-records-failed = { $count } record(s) could not be rebuilt
+msg-no-interrupted = ✓ No interrupted operations
+msg-resuming = Resuming { $operation } of { $game }
+msg-rolling-back = Rolling back { $operation } of { $game }
+msg-resume-done = ✓ { $operation } { $game }: done
+msg-operations-failed = { $count } operation(s) could not be finished
+msg-history-audit-only = Only the audit log is recorded. Use --audit to view it.
+msg-audit-log = Audit Log:
+msg-audit-empty = Audit log is empty
+msg-daemon-disabled = auto_update and background_save_sync are off. Run 'rauncher config set auto_update true' or 'rauncher config set background_save_sync true' first.
+msg-daemon-update-interval = Checking for game updates every { $interval }
+msg-daemon-sync-interval = Syncing saves every { $interval }
+msg-session-refresh-failed = Session refresh failed, trying again next cycle: { $error }
+msg-daemon-update-failed = Update check failed: { $error }
+msg-game-updated = Game updated
+msg-game-updated-detail = Updated { $game } to version { $version }
+msg-update-available-title = Update available
+msg-update-available-detail = Update available for { $game }: version { $version }
+msg-saves-synced = ✓ Synced { $count } save file(s) of { $game }
+msg-save-conflict = Save conflict
+msg-save-conflict-detail = { $game } changed locally and in the cloud: { $files }. Run 'rauncher cloud-save { $game } --download --upload --dry-run' to review.
+msg-save-sync-failed = Save sync failed: { $error }
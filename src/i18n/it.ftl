@@ -0,0 +1,468 @@
+# Italiano
+
+## Errori
+
+error-label = Errore
+error-hint-label = Suggerimento
+error-see-label = Vedi
+error-api = Errore dell'API: { $detail }
+error-auth = Errore di autenticazione: { $detail }
+error-config = Errore di configurazione: { $detail }
+error-io = Errore di I/O: { $detail }
+error-http = Errore HTTP: { $detail }
+error-json = Errore JSON: { $detail }
+error-toml = Errore TOML: { $detail }
+error-not-authenticated = Accesso non effettuato
+error-game-not-found = Gioco non trovato: { $game }
+error-cancelled = Annullato
+error-save-uploads-unsupported = Il caricamento dei salvataggi nel cloud non è ancora supportato
+error-hint-login = accedi di nuovo con 'rauncher auth'
+error-hint-network = controlla la connessione o riprova più tardi; i servizi di Epic potrebbero non essere disponibili
+error-hint-config = correggi l'impostazione con 'rauncher config set' o modifica config.toml
+error-hint-data = i metadati del launcher potrebbero essere danneggiati; esegui 'rauncher doctor'
+error-hint-game = controlla il nome dell'app con 'rauncher list'; 'rauncher info --remote' mostra anche i giochi non installati
+error-hint-cancelled = esegui di nuovo lo stesso comando per riprendere da dove si è fermato
+error-hint-save-uploads = i salvataggi locali restano invariati; caricali dal launcher di Epic
+
+## Etichette comuni della GUI
+
+gui-cancel = Annulla
+gui-retry = Riprova
+gui-install = Installa
+gui-uninstall = Disinstalla
+gui-update = Aggiorna
+gui-play = ▶ Gioca
+gui-get = Ottieni
+gui-installing = ⏳ Installazione...
+gui-version-available = ⬆ v{ $version } disponibile
+
+## Intestazione, schede e notifiche
+
+gui-logout = Esci
+gui-sign-in = Accedi
+gui-toast-details = Fai clic per i dettagli
+gui-tab-library = Libreria
+gui-tab-downloads = Download
+gui-tab-downloads-count = Download ({ $count })
+gui-download-limit = Limite di download
+gui-unlimited = illimitato
+gui-ui-scale = Scala interfaccia
+
+## Libreria
+
+gui-library = Libreria
+gui-search-hint = Cerca giochi...
+gui-filter-all = Tutti i giochi
+gui-filter-installed = Installati
+gui-sort = Ordina: { $mode }
+gui-sort-alphabetical = Dalla A alla Z
+gui-sort-recently-played = Giocati di recente
+gui-sort-recently-updated = Aggiornati di recente
+gui-sort-install-size = Spazio occupato
+gui-sort-installed-first = Prima gli installati
+gui-update-all = ⬆ Aggiorna tutti ({ $count })
+gui-no-games = Nessun gioco trovato
+gui-library-empty = La libreria è vuota o non ancora caricata
+gui-continue-playing = Continua a giocare
+gui-loading-library = Caricamento della libreria...
+gui-library-failed = Impossibile caricare la libreria
+gui-offline = ⚠ Offline
+gui-offline-detail = Epic non è raggiungibile. I giochi installati si possono ancora avviare; negozio e cloud tornano quando sarai di nuovo online.
+gui-retrying = Nuovo tentativo...
+
+## Schede e pagine dei giochi
+
+gui-hold-updates = Blocca aggiornamenti
+gui-default-runner = Runner predefinito
+gui-winetricks = Winetricks
+gui-view-logs = Mostra log
+gui-add-to-steam = Aggiungi a Steam
+gui-preview-sync = Anteprima sincronizzazione
+gui-clear-shaders = Svuota shader
+gui-verify = Verifica
+gui-repair = Ripara
+gui-move = Sposta...
+gui-open-folder = Apri cartella di installazione
+gui-create-shortcut = Crea collegamento
+gui-game-settings = Impostazioni del gioco
+gui-back-to-library = ← Libreria
+gui-overview = Panoramica
+gui-saves = Salvataggi
+gui-settings = Impostazioni
+gui-add-ons = Contenuti aggiuntivi
+gui-none-owned = Nessuno posseduto
+gui-verified-intact = Tutti i file sono integri
+gui-damaged-files = { $count } file mancanti o danneggiati
+gui-install-size = Spazio occupato
+gui-playtime = Tempo di gioco
+gui-last-played = Ultima partita
+gui-never = Mai
+gui-location = Posizione
+gui-loading-store = Caricamento dei dettagli dal negozio...
+gui-store-failed = Impossibile caricare i dettagli dal negozio: { $error }
+
+## Download
+
+gui-no-downloads = Nessun download
+gui-no-downloads-detail = Le installazioni e gli aggiornamenti avviati compaiono qui
+gui-pause = Pausa
+gui-resume = Riprendi
+gui-start-later = Avvia dopo
+gui-start-sooner = Avvia prima
+gui-queued = In coda
+gui-preparing = Preparazione...
+gui-paused = In pausa
+gui-bytes-done = { $done } di { $total }
+gui-time-left = { $time } rimanenti
+gui-files-done = { $done }/{ $total } file
+gui-kind-install = Installazione
+gui-kind-update = Aggiornamento
+gui-kind-repair = Riparazione
+
+## Finestre di dialogo
+
+gui-install-title = Installa { $game }
+gui-create-desktop-shortcut = Crea collegamento sul desktop
+gui-uninstall-title = Disinstalla { $game }
+gui-uninstall-detail = I file del gioco verranno rimossi.
+gui-delete-saves = Sposta anche i salvataggi locali nel cestino
+gui-delete-prefix = Elimina anche il prefisso Wine
+gui-logout-title = Esci
+gui-logout-detail = Uscire interrompe { $count } download. Riavviandoli in seguito riprenderanno da dove si sono fermati.
+gui-move-title = Sposta { $game }
+gui-move-into = Sposta la cartella del gioco in:
+gui-move-detail = Spostare su un altro disco copia ogni file, il che può richiedere tempo.
+gui-move-confirm = Sposta
+gui-pin-title = Gioca a { $game }
+gui-pin-detail = Il tempo di gioco di oggi è esaurito. Il PIN parentale consente un'altra sessione.
+gui-pin-confirm = Sblocca
+
+## Accesso
+
+gui-auth-subtitle = Accedi al tuo account
+gui-auth-sign-in = Accedi con Epic Games
+gui-auth-instructions = Fai clic sul pulsante qui sopra per accedere con Epic Games
+gui-auth-code-note = Riceverai un codice da inserire nel browser
+gui-auth-initializing = Avvio dell'autenticazione...
+gui-auth-in-progress = ⏳ Autenticazione in corso
+gui-auth-complete-in-browser = Completa l'autenticazione nel browser:
+gui-auth-url = URL:
+gui-auth-code = Codice:
+gui-auth-open-browser = 🌐 Apri nel browser
+gui-auth-scan = Oppure inquadralo con il telefono:
+gui-auth-waiting = In attesa che tu completi l'autenticazione... (tentativo { $attempt }/{ $attempts })
+gui-auth-success = ✓ Accesso a Epic Games effettuato!
+gui-auth-start-failed = Impossibile avviare l'autenticazione: { $error }
+gui-auth-save-failed = Errore nel salvataggio del token: { $error }
+gui-auth-timed-out = Tempo scaduto per l'autenticazione. Riprova.
+gui-auth-failed = Autenticazione non riuscita: { $error }
+gui-auth-cancelled = Autenticazione annullata
+
+## Scheda dei salvataggi
+
+gui-saves-upload = ⬆ Carica
+gui-saves-upload-hover = Invia al cloud i salvataggi locali modificati
+gui-saves-uploaded = Salvataggi caricati
+gui-saves-download = ⬇ Scarica
+gui-saves-download-hover = Scarica i salvataggi modificati nel cloud
+gui-saves-downloaded = Salvataggi scaricati
+gui-saves-preview-hover = Confronta di nuovo le due parti senza modificare nulla
+gui-saves-working = In corso...
+gui-saves-comparing = Confronto dei salvataggi...
+gui-saves-compare-failed = Impossibile confrontare i salvataggi: { $error }
+gui-saves-earlier = Versioni precedenti
+gui-saves-none-kept = Nessuna conservata. Le sincronizzazioni conservano i file sostituiti se save_history_versions è maggiore di 0.
+gui-saves-file = File
+gui-saves-size = Dimensione
+gui-saves-replaced = Sostituito
+gui-saves-restore = Ripristina
+gui-saves-restore-hover = Il file attuale viene conservato qui al suo posto
+gui-saves-restored = Ripristinato { $file }
+gui-saves-sync-disabled = La sincronizzazione dei salvataggi è disattivata per questo gioco.
+gui-saves-plan = Scaricare e poi caricare i salvataggi in { $dir } comporterebbe:
+gui-saves-plan-empty = Niente; non ci sono salvataggi.
+gui-saves-action = Azione
+gui-saves-local = Locale
+gui-saves-modified = Modificato
+gui-saves-cloud = Cloud
+gui-saves-uploaded-at = Caricato
+gui-saves-conflict = ⚠ conflitto
+gui-saves-conflict-hover = Modificato sia in locale sia nel cloud dall'ultima sincronizzazione
+gui-saves-quota = Spazio nel cloud: { $used } di { $quota } usati, { $game_used } da questo gioco
+gui-saves-quota-exceeded = I caricamenti richiedono { $needed }, più dei { $left } rimasti
+
+## Output della riga di comando
+
+msg-yes = Sì
+msg-no = No
+msg-none = (nessuno)
+msg-unknown = sconosciuta
+msg-not-set = (non impostato)
+msg-path = Percorso: { $path }
+msg-tag-installed = installato
+msg-tag-not-installed = non installato
+msg-tag-archived = archiviato
+msg-tag-held = bloccato
+msg-tag-prerelease = pre-release
+msg-tag-default = predefinito
+msg-gui-failed = Impossibile avviare la GUI: { $error }
+msg-interrupted = Attenzione: { $operation } di { $game } è stata interrotta ({ $date }).
+msg-interrupted-hint = Esegui 'rauncher resume' per completarla, o 'rauncher resume --rollback' per annullarla.
+msg-cancelling = Annullamento dopo il blocco corrente; esegui di nuovo il comando per riprendere.
+msg-logged-out = Disconnessione effettuata
+msg-auth-title = Accesso a Epic Games Store
+msg-auth-starting = Avvio dell'autenticazione...
+msg-auth-browser = Accedi dal browser web:
+msg-auth-open-url = 1. Apri questo URL: { $url }
+msg-auth-enter-code = 2. Inserisci questo codice: { $code }
+msg-auth-waiting = In attesa dell'autenticazione...
+msg-auth-success = ✓ Accesso a Epic Games Store effettuato!
+msg-auth-next = Ora puoi:
+msg-auth-next-list = Elencare i tuoi giochi: rauncher list
+msg-auth-next-install = Installare un gioco: rauncher install <app_name>
+msg-installed-games = Giochi installati:
+msg-no-games-installed = Nessun gioco installato
+msg-library = Libreria:
+msg-library-empty = Nessun gioco nella libreria (o accesso necessario)
+msg-dlc = DLC:
+msg-no-dlc = Nessun DLC nella libreria
+msg-dlc-for = per { $game }
+msg-no-matches = Nessun gioco corrisponde a '{ $query }'
+msg-no-games-to-install = Nessun gioco da installare.
+msg-installing = Installazione di { $count } giochi
+msg-verifying-launch = Verifica che { $game } si avvii...
+msg-ran-for = ✓ { $game } è rimasto in esecuzione per { $time }
+msg-quit-after = ✗ { $game } si è chiuso dopo { $time } ({ $status })
+msg-full-log = Log completo: { $path }
+msg-could-not-start = ✗ Impossibile avviare { $game }: { $error }
+msg-install-summary = Riepilogo delle installazioni:
+msg-game-installed = Gioco installato!
+msg-all-installed = Tutti i giochi sono stati installati!
+msg-installs-failed = { $failed } installazioni su { $total } non riuscite
+msg-importing = Importazione di { $game } da { $path }
+msg-imported = ✓ Gioco importato!
+msg-launch-offline = Accesso non effettuato o sessione scaduta; avvio offline.
+msg-downloading-saves = Download dei salvataggi dal cloud...
+msg-save-download-failed = Download dei salvataggi dal cloud non riuscito, avvio comunque: { $error }
+msg-launched = Gioco avviato!
+msg-output-goes-to = Il suo output va in { $path }
+msg-watching-time = Controllo del tempo di gioco; tieni aperto questo terminale.
+msg-uploading-on-exit = I salvataggi vengono caricati alla chiusura del gioco; tieni aperto questo terminale.
+msg-tracking-time = Registrazione del tempo di gioco fino alla chiusura del gioco.
+msg-session-length = Durata della sessione: { $time }
+msg-session-monitor-failed = Controllo della sessione non riuscito: { $error }
+msg-save-upload-failed = Caricamento dei salvataggi nel cloud non riuscito: { $error }
+msg-pin-unlockable = È impostato un limite giornaliero senza PIN parentale, quindi nulla può sbloccarlo. Rimuovi time_limits da config.toml per ricominciare.
+msg-pin-prompt = PIN parentale:
+msg-pin-current = PIN attuale:
+msg-pin-new = Nuovo PIN:
+msg-pin-repeat = Ripeti il PIN:
+msg-pin-incorrect = PIN errato.
+msg-pin-mismatch = I PIN sono vuoti o non corrispondono. Esegui in modo interattivo per impostarne uno.
+msg-pin-set = ✓ PIN parentale impostato
+msg-pin-removed = ✓ PIN parentale e limite giornaliero rimossi
+msg-uninstalled = Gioco disinstallato!
+msg-moving = Spostamento di { $game } in { $path }...
+msg-moved = ✓ Gioco spostato in { $path }
+msg-archive-installed-size = Spazio occupato: { $size }
+msg-archive-estimate = Dimensione stimata dell'archivio: { $size }
+msg-archive-savings = Risparmio stimato: { $size }
+msg-archiving = Archiviazione di { $game }...
+msg-archived = ✓ Gioco archiviato ({ $size } su disco)
+msg-unarchived = ✓ File del gioco ripristinati
+msg-verifying = Verifica dei file di { $game }...
+msg-file-missing = Mancante: { $file }
+msg-file-corrupt = Danneggiato: { $file }
+msg-verify-summary = Controllati { $checked } file: { $missing } mancanti, { $corrupt } danneggiati
+msg-verify-skipped = { $count } file invariati dall'ultima verifica non sono stati ricontrollati
+msg-verify-ok = ✓ Tutti i file sono integri
+msg-verify-failed = Verifica di { $game } non riuscita
+msg-checking-files = Controllo dei file di { $game }...
+msg-no-damage = ✓ Nessun file danneggiato
+msg-repaired = ✓ Riparati { $count } file
+msg-game-info = Informazioni sul gioco:
+msg-latest-release = Ultima versione:
+msg-status-title = Stato di R Games Launcher
+msg-configuration = Configurazione:
+msg-isolation-problems = Problemi di isolamento dei metadati:
+msg-field-name = Nome
+msg-field-title = Titolo
+msg-field-version = Versione
+msg-field-installed = Installato
+msg-field-install-path = Percorso di installazione
+msg-field-executable = Eseguibile
+msg-field-case-remaps = Percorsi unificati per maiuscole
+msg-field-playtime = Tempo di gioco
+msg-field-last-played = Ultima partita
+msg-field-shader-cache = Cache degli shader
+msg-field-cloud-sync = Sincronizzazione dei salvataggi
+msg-field-download-size = Dimensione del download
+msg-field-install-size = Spazio occupato
+msg-field-files = File
+msg-field-launch-exe = Eseguibile di avvio
+msg-field-authenticated = Accesso effettuato
+msg-field-install-dir = Cartella di installazione
+msg-field-log-level = Livello di log
+msg-field-update-channel = Canale di aggiornamento
+msg-field-shared-install-dir = Cartella di installazione condivisa
+msg-field-config-path = Percorso della configurazione
+msg-field-data-dir = Cartella dei dati
+msg-field-cache-dir = Cartella della cache
+msg-sync-enabled = attiva
+msg-sync-off = disattivata
+msg-sync-enabled-default = attiva (predefinito)
+msg-held = ✓ Aggiornamenti di { $game } bloccati
+msg-unheld = ✓ Aggiornamenti di { $game } sbloccati
+msg-skipping-held = { $game } saltato (aggiornamenti bloccati)
+msg-checking-updates = Ricerca di aggiornamenti per { $game }...
+msg-update-available = ✓ Aggiornamento disponibile: versione { $version }
+msg-up-to-date = ✓ Il gioco è aggiornato
+msg-update-check-failed = Ricerca di aggiornamenti non riuscita: { $error }
+msg-updating = Aggiornamento di { $game }...
+msg-update-complete = ✓ Aggiornamento completato!
+msg-update-failed = Aggiornamento di { $game } non riuscito: { $error }
+msg-updates-failed = Non è stato possibile aggiornare tutti i giochi
+msg-rolled-back = ✓ { $game } riportato alla versione { $version }
+msg-rollback-hold-hint = Esegui 'rauncher update { $game } --hold' per mantenerlo su questa versione.
+msg-sync-enabled-for = ✓ Sincronizzazione dei salvataggi attivata per { $game }
+msg-sync-disabled-for = ✓ Sincronizzazione dei salvataggi disattivata per { $game }
+msg-sync-direction = Specifica --download o --upload
+msg-sync-disabled = La sincronizzazione dei salvataggi è disattivata per { $game }
+msg-sync-dry-run = Prova per i salvataggi in { $dir }; non è stato modificato nulla.
+msg-no-saves = (nessun salvataggio)
+msg-column-action = AZIONE
+msg-column-file = FILE
+msg-column-local = LOCALE
+msg-column-modified = MODIFICATO
+msg-column-cloud = CLOUD
+msg-column-uploaded = CARICATO
+msg-sync-conflicts = { $count } conflitti verrebbero risolti da save_conflict_policy
+msg-cloud-storage = Spazio nel cloud: { $used } di { $quota } usati
+msg-quota-exceeded = Attenzione: i caricamenti richiedono { $needed }, più dei { $left } rimasti
+msg-no-logs = Ancora nessun log per { $game }. Vengono scritti quando il gioco viene avviato.
+msg-session-logs = Log delle sessioni (dal più recente):
+msg-shortcut-added = ✓ { $game } aggiunto al menu delle applicazioni ({ $path })
+msg-shortcut-removed = ✓ { $game } rimosso dal menu delle applicazioni
+msg-no-shortcut = { $game } non ha una voce nel menu
+msg-steam-added = ✓ { $game } aggiunto a Steam ({ $count } account)
+msg-restart-steam = Riavvia Steam per vedere le modifiche.
+msg-steam-failed = Non è stato possibile aggiungere tutti i giochi a Steam
+msg-lutris-failed = Non è stato possibile esportare tutti i giochi in Lutris
+msg-cloud-saves = Salvataggi nel cloud:
+msg-local-saves = Salvataggi locali in { $dir } (inviati da --upload):
+msg-cloud-storage-game = Spazio nel cloud: { $used } di { $quota } usati, { $game_used } da { $game }
+msg-no-save-versions = Nessuna versione precedente dei salvataggi di { $game } conservata
+msg-save-versions = Versioni precedenti dei salvataggi di { $game }:
+msg-save-restore-hint = Ripristinane una con: rauncher saves restore { $game } <version>
+msg-saves-backed-up = ✓ Backup di { $count } file di salvataggio in { $path }
+msg-saves-restored = ✓ Ripristinati { $count } file di salvataggio da { $path }
+msg-save-restored = ✓ Ripristinato { $file } del { $date }
+msg-winetricks-installed = ✓ Installati { $verbs } per { $game }
+msg-winetricks-failed = winetricks è terminato con { $status }
+msg-available-builds = Versioni disponibili:
+msg-no-runners = Nessuna installazione di Wine o Proton trovata.
+msg-runners = Runner:
+msg-runner-for-game = { $game } usa { $runner }
+msg-runner-installed = ✓ Installato { $name }
+msg-runner-use-hint = Usalo per un gioco aggiungendolo a [game_runners]:
+msg-runner-removed = ✓ Rimosso { $name }
+msg-runner-still-used = Attenzione: [game_runners] lo imposta ancora per { $game }
+msg-egl-imported = Importato da EGL: { $game }
+msg-egl-exported = Esportato in EGL: { $game }
+msg-egl-skipped = Non esportato, non è nella tua libreria: { $game }
+msg-egl-done = ✓ Sincronizzazione completata: { $imported } importati, { $exported } esportati
+msg-nothing-to-clean = Niente da ripulire.
+msg-would-reclaim = Si libererebbero { $size }. Esegui senza --dry-run per rimuovere.
+msg-reclaimed = ✓ Liberati { $size }
+msg-launcher-update = rauncher { $version } è disponibile (canale { $channel })
+msg-launcher-up-to-date = ✓ rauncher { $version } è aggiornato
+msg-freed = ✓ Liberati { $size }
+msg-cache-usage = { $entries } voci, { $used } di { $size }
+msg-cache-ttl = Le voci scadono dopo { $hours } ore
+msg-shaders-cleared = ✓ Svuotate le cache degli shader di { $count } giochi, liberando { $size }
+msg-shader-caches = Cache degli shader:
+msg-records-ok = ✓ Tutti i record dei giochi installati sono leggibili
+msg-records-unreadable = Record dei giochi installati illeggibili:
+msg-rebuild-prompt = Provare a ricostruirli?
+msg-rebuild-hint = Esegui 'rauncher doctor --fix' per ricostruirli.
+msg-rebuilt = ✓ { $game } ricostruito da { $source }
+msg-rebuild-failed = ✗ Impossibile ricostruire { $game }: { $error }
+msg-records-failed = Impossibile ricostruire { $count } record
+msg-no-interrupted = ✓ Nessuna operazione interrotta
+msg-resuming = Ripresa di { $operation } di { $game }
+msg-rolling-back = Annullamento di { $operation } di { $game }
+msg-resume-done = ✓ { $operation } { $game }: fatto
+msg-operations-failed = Impossibile completare { $count } operazioni
+msg-history-audit-only = Viene registrato solo il log di controllo. Usa --audit per vederlo.
+msg-audit-log = Log di controllo:
+msg-audit-empty = Il log di controllo è vuoto
+msg-daemon-disabled = auto_update e background_save_sync sono disattivati. Esegui prima 'rauncher config set auto_update true' o 'rauncher config set background_save_sync true'.
+msg-daemon-update-interval = Ricerca di aggiornamenti dei giochi ogni { $interval }
+msg-daemon-sync-interval = Sincronizzazione dei salvataggi ogni { $interval }
+msg-session-refresh-failed = Rinnovo della sessione non riuscito, nuovo tentativo al prossimo ciclo: { $error }
+msg-daemon-update-failed = Ricerca di aggiornamenti non riuscita: { $error }
+msg-game-updated = Gioco aggiornato
+msg-game-updated-detail = { $game } aggiornato alla versione { $version }
+msg-update-available-title = Aggiornamento disponibile
+msg-update-available-detail = Aggiornamento disponibile per { $game }: versione { $version }
+msg-saves-synced = ✓ Sincronizzati { $count } file di salvataggio di { $game }
+msg-save-conflict = Conflitto nei salvataggi
+msg-save-conflict-detail = { $game } è cambiato sia in locale sia nel cloud: { $files }. Esegui 'rauncher cloud-save { $game } --download --upload --dry-run' per controllare.
+msg-save-sync-failed = Sincronizzazione dei salvataggi non riuscita: { $error }
+
+## Guida della riga di comando: cli-<comando> è la descrizione di un
+## comando, cli-<comando>--<opzione> quella di un'opzione
+
+cli = Launcher di Epic Games per Linux scritto in Rust, pensato per la GUI
+cli--verbose = Log più dettagliati (-v info, -vv debug, -vvv trace)
+cli--log_filter = Filtro dei log per modulo, es. `api=trace,games=debug`
+cli--non_interactive = Non chiedere mai nulla; usa le impostazioni configurate per ogni decisione
+cli--data_dir = Tieni configurazione, dati e cache in questa cartella (anche RAUNCHER_DATA_DIR)
+cli--json_errors = Riporta un errore come JSON con codice, suggerimento e URL di aiuto
+cli--scale = Ingrandisci testo e controlli della GUI, es. 2 per una TV; sostituisce `ui_scale`
+cli-auth = Accedi a Epic Games Store
+cli-list = Elenca i giochi della libreria
+cli-search = Cerca nella libreria per titolo o nome dell'app
+cli-install = Installa uno o più giochi
+cli-import = Registra un'installazione esistente di un gioco
+cli-launch = Avvia un gioco
+cli-parental-pin = Imposta o rimuovi il PIN parentale che protegge il limite giornaliero di gioco
+cli-uninstall = Disinstalla un gioco
+cli-move = Sposta un gioco installato in un'altra cartella
+cli-archive = Comprimi un gioco usato di rado in un'immagine disco per risparmiare spazio
+cli-verify = Verifica i file installati rispetto al manifest
+cli-repair = Scarica di nuovo i file che non superano la verifica
+cli-info = Mostra le informazioni su un gioco
+cli-status = Mostra stato e configurazione
+cli-check-update = Controlla se è disponibile una nuova versione di rauncher
+cli-config = Leggi o modifica le impostazioni senza toccare config.toml
+cli-config-list = Mostra ogni impostazione con il suo valore
+cli-config-get = Mostra un'impostazione, es. `time_limits.daily_limit_minutes`
+cli-config-set = Modifica un'impostazione; il valore viene controllato prima di salvarlo
+cli-config-unset = Riporta un'impostazione al valore predefinito
+cli-update = Controlla gli aggiornamenti dei giochi
+cli-rollback = Torna alla versione che un gioco aveva prima dell'ultimo aggiornamento
+cli-cloud-save = Gestisci i salvataggi nel cloud
+cli-logs = Mostra cosa ha scritto un gioco durante l'ultima sessione
+cli-shortcut = Aggiungi un gioco al menu delle applicazioni
+cli-steam-export = Aggiungi i giochi installati a Steam come giochi non Steam, con le loro immagini
+cli-lutris-export = Scrivi gli installer Lutris dei giochi installati, da importare con `lutris -i`
+cli-saves = Esamina i salvataggi nel cloud e locali
+cli-saves-list = Mostra i salvataggi nel cloud e i file locali che `cloud-save --upload` invierebbe
+cli-saves-history = Mostra le versioni precedenti dei salvataggi sostituite dalle sincronizzazioni
+cli-saves-backup = Comprimi la cartella dei salvataggi in un .tar.zst datato, per backup offline
+cli-saves-restore = Ripristina una versione precedente di un salvataggio, o un backup
+cli-prefix = Gestisci il prefisso Wine o Proton di un gioco
+cli-prefix-winetricks = Esegui winetricks sul prefisso del gioco, es. per installare dxvk o vcrun2019
+cli-runner = Versioni di Wine e Proton che avviano i giochi Windows
+cli-runner-list = Mostra i runner trovati su questo computer
+cli-runner-install = Scarica una versione di Proton-GE nella cartella dei runner del launcher
+cli-runner-remove = Elimina una versione aggiunta con `runner install`
+cli-egl-sync = Sincronizza i giochi installati con Epic Games Launcher in un prefisso Wine
+cli-clean = Rimuovi cache, manifest orfani, backup degli aggiornamenti e download parziali
+cli-cache = Mostra quanto occupano le cache dei download e degli shader
+cli-doctor = Controlla i metadati del launcher alla ricerca di problemi
+cli-resume = Completa installazioni, aggiornamenti e disinstallazioni interrotti da un crash
+cli-history = Mostra le operazioni passate del launcher
+cli-daemon = Resta in esecuzione e controlla gli aggiornamenti dei giochi ogni `auto_update_interval_hours`
+cli-gui = Avvia la GUI
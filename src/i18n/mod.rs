@@ -0,0 +1,189 @@
+//! Translations of user-facing text. Each shipped language is a catalog in
+//! a subset of Fluent's syntax, compiled into the binary:
+//!
+//! ```text
+//! # Comment
+//! gui-uninstall-title = Uninstall { $game }
+//! ```
+//!
+//! One message per line; `{ $name }` is replaced by the argument of that
+//! name. The language follows the user's locale (`LC_ALL`, `LC_MESSAGES`
+//! or `LANG`), and a message missing from its catalog falls back to English.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Shipped catalogs by language code; English comes first and is complete
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("en.ftl")),
+    ("it", include_str!("it.ftl")),
+];
+
+static ENGLISH: OnceLock<Catalog> = OnceLock::new();
+static CURRENT: OnceLock<(&str, Catalog)> = OnceLock::new();
+
+/// The messages of one language, by id
+#[derive(Debug, Default)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn parse(source: &str) -> Self {
+        let messages = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(id, message)| (id.trim().to_string(), message.trim().to_string()))
+            .collect();
+        Self { messages }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+}
+
+/// Catalog of a shipped language, by its code
+pub fn catalog(language: &str) -> Option<Catalog> {
+    CATALOGS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, source)| Catalog::parse(source))
+}
+
+/// Codes of the shipped languages
+pub fn languages() -> impl Iterator<Item = &'static str> {
+    CATALOGS.iter().map(|(code, _)| *code)
+}
+
+/// Language of the user's locale if it is shipped, English otherwise
+pub fn detect() -> &'static str {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    language_of(&locale)
+}
+
+/// "it_IT.UTF-8" → "it"; "C", "POSIX" and unshipped languages → "en"
+fn language_of(locale: &str) -> &'static str {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    languages().find(|code| *code == language).unwrap_or("en")
+}
+
+fn english() -> &'static Catalog {
+    ENGLISH.get_or_init(|| catalog("en").unwrap_or_default())
+}
+
+/// The user's language and its catalog
+fn current() -> &'static (&'static str, Catalog) {
+    CURRENT.get_or_init(|| {
+        // Tests compare against English text whatever the machine's locale
+        let language = if cfg!(test) { "en" } else { detect() };
+        (language, catalog(language).unwrap_or_default())
+    })
+}
+
+/// The message in the user's language only, without falling back to
+/// English; for text whose English form lives elsewhere, like CLI help
+pub fn translation(id: &str) -> Option<&'static str> {
+    let (language, catalog) = current();
+    if *language == "en" {
+        return None;
+    }
+    catalog.get(id)
+}
+
+/// The message `id` in the user's language
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// The message `id` in the user's language, with its `{ $name }`
+/// placeholders filled from `args`
+pub fn tr_args(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let Some(message) = current().1.get(id).or_else(|| english().get(id)) else {
+        log::debug!("No message {}", id);
+        return id.to_string();
+    };
+    fill(message, args)
+}
+
+fn fill(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find("{ $") {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = rest[start + 3..start + end].trim();
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => text.push_str(&value.to_string()),
+            None => text.push_str(&rest[start..start + end + 1]),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders_are_filled() {
+        let catalog = Catalog::parse("# comment\n\ngreeting = Hello, { $name }! { $missing }\n");
+        let message = catalog.get("greeting").unwrap();
+
+        assert_eq!(
+            fill(message, &[("name", &"Ada")]),
+            "Hello, Ada! { $missing }"
+        );
+        assert_eq!(catalog.ids().count(), 1);
+    }
+
+    #[test]
+    fn test_language_follows_locale() {
+        assert_eq!(language_of("it_IT.UTF-8"), "it");
+        assert_eq!(language_of("it"), "it");
+        assert_eq!(language_of("de_DE@euro"), "en");
+        assert_eq!(language_of("C"), "en");
+        assert_eq!(language_of(""), "en");
+    }
+
+    #[test]
+    fn test_catalogs_cover_english() {
+        let english = catalog("en").unwrap();
+        for language in languages().skip(1) {
+            let translated = catalog(language).unwrap();
+            let missing: Vec<_> = english
+                .ids()
+                .filter(|id| translated.get(id).is_none())
+                .collect();
+            assert!(missing.is_empty(), "{} lacks {:?}", language, missing);
+        }
+    }
+
+    #[test]
+    fn test_messages_fall_back_to_english_then_id() {
+        assert_eq!(tr("no-such-message"), "no-such-message");
+        assert_eq!(
+            tr_args("error-game-not-found", &[("game", &"Hades")]),
+            "Game not found: Hades"
+        );
+    }
+}
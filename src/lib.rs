@@ -1,9 +1,16 @@
 pub mod api;
+pub mod audit;
 pub mod auth;
 pub mod cli;
 pub mod config;
+pub mod egl;
 pub mod error;
+pub mod format;
 pub mod games;
 pub mod gui;
+pub mod i18n;
+pub mod metrics;
+pub mod qr;
+pub mod update;
 
 pub use error::{Error, Result};
@@ -1,132 +1,410 @@
-use clap::Parser;
+use clap::FromArgMatches;
 use rauncher::{
+    api,
+    audit::Initiator,
     auth::AuthManager,
-    cli::{Cli, Commands},
-    config::Config,
-    games::GameManager,
-    Result,
+    cli::{self, prompt, Cli, Commands, ConfigAction, PrefixAction, RunnerAction, SavesAction},
+    config::{self, storage, Config},
+    format,
+    games::{
+        builds::{self, BuildKind},
+        installed_runners, logs, session, shaders, throttle, CancellationToken, GameManager,
+        InstallQueue, Journal, LaunchOptions, Runner, SyncAction, SyncPlan, SMOKE_TEST_DURATION,
+    },
+    i18n::{tr, tr_args},
+    metrics,
+    update::{self, UpdateChannel},
+    Error, Result,
 };
+use std::collections::HashMap;
+
+/// Setting value as shown by `rauncher config`
+fn show_setting(value: Option<toml::Value>) -> String {
+    match value {
+        Some(toml::Value::String(text)) => text,
+        Some(value) => value.to_string(),
+        None => tr("msg-not-set"),
+    }
+}
+
+/// A title underlined to its width
+fn print_heading(title: &str) {
+    println!("{}", title);
+    println!("{}", "=".repeat(title.chars().count()));
+}
+
+/// "Label: value", the label translated
+fn print_field(indent: &str, label: &str, value: impl std::fmt::Display) {
+    println!("{}{}: {}", indent, tr(label), value);
+}
+
+fn yes_no(yes: bool) -> String {
+    tr(if yes { "msg-yes" } else { "msg-no" })
+}
+
+/// The table `cloud-save --dry-run` prints
+fn print_sync_plan(app_name: &str, plan: &SyncPlan) {
+    if !plan.sync_allowed {
+        println!("{}", tr_args("msg-sync-disabled", &[("game", &app_name)]));
+        return;
+    }
+    println!(
+        "{}",
+        tr_args("msg-sync-dry-run", &[("dir", &plan.dir.display())])
+    );
+    if plan.files.is_empty() {
+        println!("  {}", tr("msg-no-saves"));
+        return;
+    }
+
+    println!(
+        "  {:<9} {:<32} {:>10} {:<20} {:>10} {}",
+        tr("msg-column-action"),
+        tr("msg-column-file"),
+        tr("msg-column-local"),
+        tr("msg-column-modified"),
+        tr("msg-column-cloud"),
+        tr("msg-column-uploaded")
+    );
+    for file in &plan.files {
+        let (local_size, modified) = match &file.local {
+            Some(save) => (
+                format::Bytes(save.size).to_string(),
+                format::date(save.modified.into()),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let (cloud_size, uploaded) = match &file.cloud {
+            Some(save) => (
+                format::Bytes(save.size).to_string(),
+                save.uploaded_at
+                    .parse()
+                    .map(format::date)
+                    .unwrap_or_else(|_| save.uploaded_at.clone()),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        println!(
+            "  {:<9} {:<32} {:>10} {:<20} {:>10} {}",
+            file.action.to_string(),
+            file.filename,
+            local_size,
+            modified,
+            cloud_size,
+            uploaded
+        );
+    }
+
+    let conflicts = plan
+        .files
+        .iter()
+        .filter(|file| file.action == SyncAction::Conflict)
+        .count();
+    if conflicts > 0 {
+        println!(
+            "\n{}",
+            tr_args("msg-sync-conflicts", &[("count", &conflicts)])
+        );
+    }
+
+    if let Some(quota) = &plan.quota {
+        println!(
+            "\n{}",
+            tr_args(
+                "msg-cloud-storage",
+                &[
+                    ("used", &format::Bytes(quota.used_bytes)),
+                    ("quota", &format::Bytes(quota.quota_bytes))
+                ]
+            )
+        );
+        if plan.exceeds_quota() {
+            println!(
+                "{}",
+                tr_args(
+                    "msg-quota-exceeded",
+                    &[
+                        ("needed", &format::Bytes(plan.upload_size())),
+                        ("left", &format::Bytes(quota.available()))
+                    ]
+                )
+            );
+        }
+    }
+}
+
+/// Point out operations a crash left unfinished
+fn warn_interrupted_operations() {
+    let interrupted = match Journal::open().and_then(|journal| journal.incomplete()) {
+        Ok(interrupted) => interrupted,
+        Err(e) => {
+            log::warn!("Failed to read the operation journal: {}", e);
+            return;
+        }
+    };
+
+    for entry in &interrupted {
+        eprintln!(
+            "{}",
+            tr_args(
+                "msg-interrupted",
+                &[
+                    ("operation", &entry.operation),
+                    ("game", &entry.app_name),
+                    ("date", &format::date(entry.started_at))
+                ]
+            )
+        );
+    }
+    if !interrupted.is_empty() {
+        eprintln!("{}\n", tr("msg-interrupted-hint"));
+    }
+}
+
+/// Token cancelled by the first Ctrl+C, so downloads stop cleanly and keep
+/// what they finished. A second Ctrl+C exits immediately.
+fn cancel_on_ctrl_c() -> CancellationToken {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("\n{}", tr("msg-cancelling"));
+            cancel.cancel();
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+
+    token
+}
+
+/// Start the Prometheus endpoint in the background if one is configured
+fn start_metrics_endpoint(config: &Config) {
+    let Some(address) = &config.metrics_address else {
+        return;
+    };
+
+    // Address is checked by Config::validate, so a parse failure here is unexpected
+    match address.parse() {
+        Ok(addr) => {
+            tokio::spawn(async move {
+                if let Err(e) = metrics::serve(addr).await {
+                    log::warn!("Metrics endpoint stopped: {}", e);
+                }
+            });
+        }
+        Err(e) => log::warn!("Invalid metrics address {}: {}", address, e),
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() {
+    let matches = cli::localized_command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let json_errors = cli.json_errors;
+
+    if let Err(e) = run(cli).await {
+        if json_errors {
+            eprintln!("{}", serde_json::json!({ "error": e }));
+        } else {
+            eprintln!("{} [{}]: {}", tr("error-label"), e.code(), e);
+            if let Some(hint) = e.hint() {
+                eprintln!("  {}: {}", tr("error-hint-label"), hint);
+            }
+            eprintln!("  {}: {}", tr("error-see-label"), e.help_url());
+        }
+        std::process::exit(e.exit_code());
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    // Initialize logging; explicit flags take precedence over RUST_LOG
+    match cli.log_filter() {
+        Some(filter) => env_logger::Builder::new().parse_filters(&filter).init(),
+        None => env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+            .init(),
+    }
+
+    prompt::set_non_interactive(cli.non_interactive);
 
-    // Initialize logging
-    let log_level = if cli.verbose { "debug" } else { "info" };
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level)).init();
+    if let Some(dir) = &cli.data_dir {
+        config::set_portable_root(dir.clone());
+    }
 
     // Load configuration
     let config = Config::load()?;
     log::debug!("Configuration loaded");
 
+    throttle().set_limit(config.bandwidth_limit_bytes());
+
     // Initialize auth manager
     let mut auth = AuthManager::new()?;
 
+    if !matches!(cli.command, Some(Commands::Resume { .. })) {
+        warn_interrupted_operations();
+    }
+
+    let scale = cli.scale;
+
     // Launch GUI by default if no command is specified
     match cli.command {
         None => {
             // Launch GUI when no command is provided
-            use rauncher::gui::LauncherApp;
+            use rauncher::gui::{self, LauncherApp};
+
+            start_metrics_endpoint(&config);
 
-            let native_options = eframe::NativeOptions {
-                viewport: egui::ViewportBuilder::default()
-                    .with_inner_size([1200.0, 800.0])
-                    .with_min_inner_size([800.0, 600.0])
-                    .with_title("R Games Launcher"),
-                ..Default::default()
-            };
+            let native_options = gui::native_options(&config)?;
 
-            if let Err(e) = eframe::run_native(
+            eframe::run_native(
                 "R Games Launcher",
                 native_options,
-                Box::new(|cc| Ok(Box::new(LauncherApp::new(cc)))),
-            ) {
-                eprintln!("Failed to run GUI: {}", e);
-                std::process::exit(1);
-            }
+                Box::new(move |cc| Ok(Box::new(LauncherApp::new(cc, scale)))),
+            )
+            .map_err(|e| Error::Other(tr_args("msg-gui-failed", &[("error", &e)])))?;
         }
 
         Some(command) => match command {
             Commands::Auth { logout } => {
                 if logout {
                     auth.logout()?;
-                    println!("Successfully logged out");
+                    println!("{}", tr("msg-logged-out"));
                 } else {
                     use rauncher::api::EpicClient;
 
-                    println!("Epic Games Store Authentication");
-                    println!("================================");
+                    print_heading(&tr("msg-auth-title"));
                     println!();
 
                     let client = EpicClient::new()?;
 
-                    println!("Starting authentication process...");
-
-                    match client.authenticate().await {
-                        Ok((user_code, verification_url, token)) => {
-                            println!();
-                            println!("Please authenticate using your web browser:");
-                            println!();
-                            println!("  1. Open this URL: {}", verification_url);
-                            println!("  2. Enter this code: {}", user_code);
-                            println!();
-                            println!("Waiting for authentication...");
-
-                            // Save the token
-                            auth.set_token(token)?;
-
-                            println!();
-                            println!("✓ Successfully authenticated with Epic Games Store!");
-                            println!();
-                            println!("You can now:");
-                            println!("  - List your games: rauncher list");
-                            println!("  - Install a game: rauncher install <app_name>");
-                        }
-                        Err(e) => {
-                            eprintln!();
-                            eprintln!("Authentication failed: {}", e);
-                            eprintln!();
-                            eprintln!("Please try again. If the problem persists, check:");
-                            eprintln!("  - Your internet connection");
-                            eprintln!("  - Epic Games services status");
-                            std::process::exit(1);
-                        }
-                    }
+                    println!("{}", tr("msg-auth-starting"));
+
+                    let (user_code, verification_url, token) = client.authenticate().await?;
+                    println!();
+                    println!("{}", tr("msg-auth-browser"));
+                    println!();
+                    println!(
+                        "  {}",
+                        tr_args("msg-auth-open-url", &[("url", &verification_url)])
+                    );
+                    println!(
+                        "  {}",
+                        tr_args("msg-auth-enter-code", &[("code", &user_code)])
+                    );
+                    println!();
+                    println!("{}", tr("msg-auth-waiting"));
+
+                    // Save the token
+                    auth.set_token(token)?;
+
+                    println!();
+                    println!("{}", tr("msg-auth-success"));
+                    println!();
+                    println!("{}", tr("msg-auth-next"));
+                    println!("  - {}", tr("msg-auth-next-list"));
+                    println!("  - {}", tr("msg-auth-next-install"));
                 }
             }
 
-            Commands::List { installed } => {
+            Commands::List {
+                installed,
+                include_dlc,
+                dlc_only,
+            } => {
                 if installed {
                     let manager = GameManager::new(config, auth)?;
                     let games = manager.list_installed()?;
 
                     if games.is_empty() {
-                        println!("No games installed");
+                        println!("{}", tr("msg-no-games-installed"));
                     } else {
-                        println!("Installed Games:");
-                        println!("================");
+                        print_heading(&tr("msg-installed-games"));
                         for game in games {
                             println!(
-                                "  {} - {} (v{})",
-                                game.app_name, game.app_title, game.app_version
+                                "  {} - {} (v{}){}{}",
+                                game.app_name,
+                                game.app_title,
+                                game.app_version,
+                                if game.archive_path.is_some() {
+                                    format!(" [{}]", tr("msg-tag-archived"))
+                                } else {
+                                    String::new()
+                                },
+                                if game.hold_updates {
+                                    format!(" [{}]", tr("msg-tag-held"))
+                                } else {
+                                    String::new()
+                                }
+                            );
+                            println!(
+                                "    {}",
+                                tr_args("msg-path", &[("path", &game.install_path.display())])
                             );
-                            println!("    Path: {:?}", game.install_path);
                         }
                     }
                 } else {
                     if !auth.is_authenticated() {
-                        eprintln!("Error: Not authenticated. Run 'rauncher auth' first.");
-                        std::process::exit(1);
+                        return Err(Error::NotAuthenticated);
                     }
 
                     let manager = GameManager::new(config, auth)?;
                     let games = manager.list_library().await?;
 
-                    if games.is_empty() {
-                        println!("No games in library (or authentication required)");
+                    if include_dlc || dlc_only {
+                        let dlcs = manager.list_dlcs().await?;
+                        let installed_games = manager.list_installed()?;
+                        let status = |app_name: &str| {
+                            if installed_games.iter().any(|g| g.app_name == app_name) {
+                                tr("msg-tag-installed")
+                            } else {
+                                tr("msg-tag-not-installed")
+                            }
+                        };
+
+                        if dlc_only {
+                            if dlcs.is_empty() {
+                                println!("{}", tr("msg-no-dlc"));
+                            } else {
+                                print_heading(&tr("msg-dlc"));
+                                for dlc in &dlcs {
+                                    println!(
+                                        "  {} - {} ({}) [{}]",
+                                        dlc.app_name,
+                                        dlc.app_title,
+                                        tr_args("msg-dlc-for", &[("game", &dlc.base_game)]),
+                                        status(&dlc.app_name)
+                                    );
+                                }
+                            }
+                        } else {
+                            print_heading(&tr("msg-library"));
+                            for game in games
+                                .iter()
+                                .filter(|g| !dlcs.iter().any(|d| d.app_name == g.app_name))
+                            {
+                                println!(
+                                    "  {} - {} (v{}) [{}]",
+                                    game.app_name,
+                                    game.app_title,
+                                    game.app_version,
+                                    status(&game.app_name)
+                                );
+                                for dlc in dlcs.iter().filter(|d| d.base_game == game.app_name) {
+                                    println!(
+                                        "    └ {} - {} [{}]",
+                                        dlc.app_name,
+                                        dlc.app_title,
+                                        status(&dlc.app_name)
+                                    );
+                                }
+                            }
+                        }
+                    } else if games.is_empty() {
+                        println!("{}", tr("msg-library-empty"));
                     } else {
-                        println!("Library:");
-                        println!("========");
+                        print_heading(&tr("msg-library"));
                         for game in games {
                             println!(
                                 "  {} - {} (v{})",
@@ -137,189 +415,1626 @@ async fn main() -> Result<()> {
                 }
             }
 
-            Commands::Install { app_name } => {
+            Commands::Search {
+                query,
+                installed,
+                not_installed,
+                refresh,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let games = manager.search_library(&query, refresh).await?;
+
+                let installed_games = manager.list_installed()?;
+                let is_installed =
+                    |app_name: &str| installed_games.iter().any(|g| g.app_name == app_name);
+
+                let results: Vec<_> = games
+                    .iter()
+                    .filter(|game| !installed || is_installed(&game.app_name))
+                    .filter(|game| !not_installed || !is_installed(&game.app_name))
+                    .collect();
+
+                if results.is_empty() {
+                    println!("{}", tr_args("msg-no-matches", &[("query", &query)]));
+                } else {
+                    for game in results {
+                        println!(
+                            "  {} - {}{}",
+                            game.app_name,
+                            game.app_title,
+                            if is_installed(&game.app_name) {
+                                format!(" [{}]", tr("msg-tag-installed"))
+                            } else {
+                                String::new()
+                            }
+                        );
+                    }
+                }
+            }
+
+            Commands::Install {
+                app_names,
+                file,
+                verify_launch,
+                now,
+            } => {
                 if !auth.is_authenticated() {
-                    eprintln!("Error: Not authenticated. Run 'rauncher auth' first.");
-                    std::process::exit(1);
+                    return Err(Error::NotAuthenticated);
                 }
 
-                let manager = GameManager::new(config, auth)?;
-                println!("Installing game: {}", app_name);
+                let mut queue = InstallQueue::new();
+                for app_name in &app_names {
+                    queue.push(app_name);
+                }
+                if let Some(file) = file {
+                    queue.push_from_file(&file)?;
+                }
+
+                if queue.is_empty() {
+                    return Err(Error::Other(tr("msg-no-games-to-install")));
+                }
+
+                start_metrics_endpoint(&config);
+
+                let mut config = config;
+                if now {
+                    config.download_windows.clear();
+                }
+
+                let verify_launch = verify_launch || config.verify_launch_after_install;
+                let manager = GameManager::new(config, auth)?.with_cancellation(cancel_on_ctrl_c());
+                println!("{}", tr_args("msg-installing", &[("count", &queue.len())]));
+
+                let results = manager.install_queue(&mut queue).await;
+                let failed: Vec<_> = results.iter().filter(|r| !r.is_ok()).collect();
 
-                match manager.install_game(&app_name).await {
-                    Ok(()) => println!("Game installed successfully!"),
-                    Err(e) => {
-                        eprintln!("Failed to install game: {}", e);
-                        std::process::exit(1);
+                for result in results.iter().filter(|r| verify_launch && r.is_ok()) {
+                    println!(
+                        "\n{}",
+                        tr_args("msg-verifying-launch", &[("game", &result.app_name)])
+                    );
+
+                    match manager.smoke_launch(&result.app_name, SMOKE_TEST_DURATION) {
+                        Ok(report) if report.survived => {
+                            println!(
+                                "{}",
+                                tr_args(
+                                    "msg-ran-for",
+                                    &[
+                                        ("game", &result.app_name),
+                                        ("time", &format::duration(report.runtime))
+                                    ]
+                                )
+                            )
+                        }
+                        Ok(report) => {
+                            let status = report
+                                .exit_status
+                                .map(|s| s.to_string())
+                                .unwrap_or_default();
+                            println!(
+                                "{}",
+                                tr_args(
+                                    "msg-quit-after",
+                                    &[
+                                        ("game", &result.app_name),
+                                        ("time", &format::duration(report.runtime)),
+                                        ("status", &status)
+                                    ]
+                                )
+                            );
+                            for line in &report.log_tail {
+                                println!("    {}", line);
+                            }
+                            println!(
+                                "  {}",
+                                tr_args("msg-full-log", &[("path", &report.log_path.display())])
+                            );
+                        }
+                        Err(e) => println!(
+                            "{}",
+                            tr_args(
+                                "msg-could-not-start",
+                                &[("game", &result.app_name), ("error", &e)]
+                            )
+                        ),
                     }
                 }
+
+                if results.len() > 1 {
+                    println!("\n{}", tr("msg-install-summary"));
+                    for result in &results {
+                        match &result.error {
+                            None => println!("  ✓ {}", result.app_name),
+                            Some(e) => println!("  ✗ {} - {}", result.app_name, e),
+                        }
+                    }
+                }
+
+                if failed.is_empty() && results.len() == 1 {
+                    println!("{}", tr("msg-game-installed"));
+                } else if failed.is_empty() {
+                    println!("{}", tr("msg-all-installed"));
+                } else {
+                    return Err(Error::Other(tr_args(
+                        "msg-installs-failed",
+                        &[("failed", &failed.len()), ("total", &results.len())],
+                    )));
+                }
+            }
+
+            Commands::Import {
+                app_name,
+                path,
+                repair,
+            } => {
+                if !auth.is_authenticated() {
+                    return Err(Error::NotAuthenticated);
+                }
+
+                let manager = GameManager::new(config, auth)?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-importing",
+                        &[("game", &app_name), ("path", &path.display())]
+                    )
+                );
+
+                manager.import_game(&app_name, &path, repair).await?;
+                println!("{}", tr("msg-imported"));
             }
 
-            Commands::Launch { app_name } => {
+            Commands::Launch {
+                app_name,
+                verify_entitlement,
+                offline,
+                wine,
+                proton,
+                no_wine,
+                gpu,
+            } => {
+                let verify_entitlement = verify_entitlement || config.verify_entitlement_on_launch;
                 let manager = GameManager::new(config, auth)?;
 
-                match manager.launch_game(&app_name) {
-                    Ok(()) => println!("Game launched successfully!"),
-                    Err(e) => {
-                        eprintln!("Failed to launch game: {}", e);
-                        std::process::exit(1);
+                let runner = match (wine, proton) {
+                    (Some(wine), _) => Some(Runner::Wine(wine)),
+                    (_, Some(proton)) => Some(Runner::Proton(proton)),
+                    _ if no_wine => Some(Runner::Native),
+                    _ => None,
+                };
+                let mut options = LaunchOptions {
+                    offline,
+                    runner,
+                    gpu,
+                };
+                if manager.fall_back_to_offline(&mut options) {
+                    println!("{}", tr("msg-launch-offline"));
+                }
+
+                if verify_entitlement && !options.offline {
+                    manager.verify_entitlement(&app_name).await?;
+                }
+
+                let sync_saves = manager.auto_sync_saves(&app_name, &options)?;
+
+                let limits = manager.session_limits(&app_name, None)?;
+
+                if sync_saves {
+                    println!("{}", tr("msg-downloading-saves"));
+                    if let Err(e) = manager.download_cloud_saves(&app_name).await {
+                        eprintln!("{}", tr_args("msg-save-download-failed", &[("error", &e)]));
+                    }
+                }
+
+                let upload_saves = sync_saves && api::SAVE_UPLOADS_ENABLED;
+                let started = std::time::SystemTime::now();
+                let child = manager.launch_game(&app_name, &options)?;
+                println!("{}", tr("msg-launched"));
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-output-goes-to",
+                        &[("path", &logs::log_path(&app_name)?.display())]
+                    )
+                );
+
+                if !limits.is_empty() {
+                    println!("{}", tr("msg-watching-time"));
+                } else if upload_saves {
+                    println!("{}", tr("msg-uploading-on-exit"));
+                } else {
+                    println!("{}", tr("msg-tracking-time"));
+                }
+                match manager.supervise_session(child, &app_name, limits) {
+                    Ok(played) => println!(
+                        "{}",
+                        tr_args("msg-session-length", &[("time", &format::duration(played))])
+                    ),
+                    Err(e) => eprintln!(
+                        "{}",
+                        tr_args("msg-session-monitor-failed", &[("error", &e)])
+                    ),
+                }
+
+                if upload_saves {
+                    if let Err(e) = manager
+                        .upload_cloud_saves_since(&app_name, Some(started))
+                        .await
+                    {
+                        eprintln!("{}", tr_args("msg-save-upload-failed", &[("error", &e)]));
                     }
                 }
             }
 
-            Commands::Uninstall { app_name } => {
-                let manager = GameManager::new(config, auth)?;
+            Commands::ParentalPin { clear } => {
+                let mut config = config;
+                let limits = &mut config.time_limits;
 
-                match manager.uninstall_game(&app_name) {
-                    Ok(()) => println!("Game uninstalled successfully!"),
-                    Err(e) => {
-                        eprintln!("Failed to uninstall game: {}", e);
-                        std::process::exit(1);
+                if limits.locked() {
+                    if limits.parental_pin.is_none() {
+                        return Err(Error::Other(tr("msg-pin-unlockable")));
+                    }
+                    let current = prompt::ask(&tr("msg-pin-current")).unwrap_or_default();
+                    if !limits.pin_matches(&current) {
+                        return Err(Error::Other(tr("msg-pin-incorrect")));
                     }
                 }
+
+                if clear {
+                    limits.parental_pin = None;
+                    limits.daily_limit_minutes = None;
+                    config.save()?;
+                    println!("{}", tr("msg-pin-removed"));
+                    return Ok(());
+                }
+
+                let pin = prompt::ask(&tr("msg-pin-new")).unwrap_or_default();
+                if pin.trim().is_empty() || prompt::ask(&tr("msg-pin-repeat")) != Some(pin.clone())
+                {
+                    return Err(Error::Other(tr("msg-pin-mismatch")));
+                }
+
+                limits.set_pin(&pin)?;
+                config.save()?;
+                println!("{}", tr("msg-pin-set"));
+            }
+
+            Commands::Uninstall { app_name, trash } => {
+                let mut config = config;
+                config.uninstall_to_trash |= trash;
+
+                let manager = GameManager::new(config, auth)?;
+
+                manager.uninstall_game(&app_name)?;
+                println!("{}", tr("msg-uninstalled"));
+            }
+
+            Commands::Move {
+                app_name,
+                destination,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-moving",
+                        &[("game", &app_name), ("path", &destination.display())]
+                    )
+                );
+
+                let new_path = manager.move_game(&app_name, &destination)?;
+                println!("{}", tr_args("msg-moved", &[("path", &new_path.display())]));
+            }
+
+            Commands::Archive {
+                app_name,
+                estimate,
+                restore,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let result = if estimate {
+                    manager.estimate_archive(&app_name).map(|estimate| {
+                        println!(
+                            "{}",
+                            tr_args(
+                                "msg-archive-installed-size",
+                                &[("size", &format::size(estimate.installed_bytes))]
+                            )
+                        );
+                        println!(
+                            "{}",
+                            tr_args(
+                                "msg-archive-estimate",
+                                &[("size", &format::size(estimate.estimated_bytes))]
+                            )
+                        );
+                        println!(
+                            "{}",
+                            tr_args(
+                                "msg-archive-savings",
+                                &[("size", &format::size(estimate.savings()))]
+                            )
+                        );
+                    })
+                } else if restore {
+                    manager
+                        .unarchive_game(&app_name)
+                        .map(|()| println!("{}", tr("msg-unarchived")))
+                } else {
+                    println!("{}", tr_args("msg-archiving", &[("game", &app_name)]));
+                    manager.archive_game(&app_name).map(|size| {
+                        println!(
+                            "{}",
+                            tr_args("msg-archived", &[("size", &format::size(size))])
+                        )
+                    })
+                };
+
+                result?;
             }
 
-            Commands::Info { app_name } => {
+            Commands::Verify {
+                app_name,
+                incremental,
+            } => {
                 let manager = GameManager::new(config, auth)?;
+                println!("{}", tr_args("msg-verifying", &[("game", &app_name)]));
+
+                let report = manager.verify_game(&app_name, incremental)?;
+                for file in &report.missing_files {
+                    println!("  {}", tr_args("msg-file-missing", &[("file", &file)]));
+                }
+                for file in &report.corrupt_files {
+                    println!("  {}", tr_args("msg-file-corrupt", &[("file", &file)]));
+                }
+
+                println!();
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-verify-summary",
+                        &[
+                            ("checked", &report.checked_files),
+                            ("missing", &report.missing_files.len()),
+                            ("corrupt", &report.corrupt_files.len())
+                        ]
+                    )
+                );
+                if report.skipped_files > 0 {
+                    println!(
+                        "{}",
+                        tr_args("msg-verify-skipped", &[("count", &report.skipped_files)])
+                    );
+                }
+
+                if report.is_ok() {
+                    println!("{}", tr("msg-verify-ok"));
+                } else {
+                    return Err(Error::Other(tr_args(
+                        "msg-verify-failed",
+                        &[("game", &app_name)],
+                    )));
+                }
+            }
+
+            Commands::Repair { app_name } => {
+                if !auth.is_authenticated() {
+                    return Err(Error::NotAuthenticated);
+                }
+
+                let manager = GameManager::new(config, auth)?.with_cancellation(cancel_on_ctrl_c());
+                println!("{}", tr_args("msg-checking-files", &[("game", &app_name)]));
+
+                match manager.repair_game(&app_name).await? {
+                    repaired if repaired.is_empty() => {
+                        println!("{}", tr("msg-no-damage"));
+                    }
+                    repaired => {
+                        println!("{}", tr_args("msg-repaired", &[("count", &repaired.len())]));
+                    }
+                }
+            }
 
-                match manager
+            Commands::Info { app_name, remote } => {
+                let manager = GameManager::new(config, auth)?;
+                let installed = manager
                     .list_installed()?
-                    .iter()
-                    .find(|g| g.app_name == app_name)
-                {
+                    .into_iter()
+                    .find(|g| g.app_name == app_name);
+
+                print_heading(&tr("msg-game-info"));
+
+                match &installed {
                     Some(game) => {
-                        println!("Game Information:");
-                        println!("================");
-                        println!("Name: {}", game.app_name);
-                        println!("Title: {}", game.app_title);
-                        println!("Version: {}", game.app_version);
-                        println!("Install Path: {:?}", game.install_path);
-                        println!("Executable: {}", game.executable);
+                        print_field("", "msg-field-name", &game.app_name);
+                        print_field("", "msg-field-title", &game.app_title);
+                        print_field("", "msg-field-version", &game.app_version);
+                        print_field("", "msg-field-install-path", game.install_path.display());
+                        print_field("", "msg-field-executable", &game.executable);
+                        if !game.case_remaps.is_empty() {
+                            print_field("", "msg-field-case-remaps", game.case_remaps.len());
+                        }
+                        print_field(
+                            "",
+                            "msg-field-playtime",
+                            format::duration(std::time::Duration::from_secs(game.playtime_secs)),
+                        );
+                        if let Some(last_played) = game.last_played {
+                            print_field("", "msg-field-last-played", format::date(last_played));
+                        }
+                        print_field(
+                            "",
+                            "msg-field-shader-cache",
+                            format::size(shaders::cache_size(&game.app_name)?),
+                        );
+                        print_field(
+                            "",
+                            "msg-field-cloud-sync",
+                            tr(match game.cloud_sync_enabled {
+                                Some(true) => "msg-sync-enabled",
+                                Some(false) => "msg-sync-off",
+                                None => "msg-sync-enabled-default",
+                            }),
+                        );
                     }
-                    None => {
-                        eprintln!("Game not found: {}", app_name);
-                        std::process::exit(1);
+                    None if remote => {
+                        print_field("", "msg-field-name", &app_name);
+                        print_field("", "msg-field-installed", tr("msg-no"));
                     }
+                    None => return Err(Error::GameNotFound(app_name)),
+                }
+
+                if remote {
+                    let manifest = manager.remote_manifest(&app_name).await?;
+                    println!();
+                    println!("{}", tr("msg-latest-release"));
+                    print_field("  ", "msg-field-version", &manifest.app_version);
+                    print_field(
+                        "  ",
+                        "msg-field-download-size",
+                        manifest
+                            .download_size()
+                            .map(format::size)
+                            .unwrap_or_else(|| tr("msg-unknown")),
+                    );
+                    print_field(
+                        "  ",
+                        "msg-field-install-size",
+                        format::size(manifest.install_size()),
+                    );
+                    print_field("  ", "msg-field-files", manifest.file_list.len());
+                    print_field("  ", "msg-field-launch-exe", &manifest.launch_exe);
                 }
             }
 
             Commands::Status => {
-                println!("R Games Launcher Status");
-                println!("=======================");
+                print_heading(&tr("msg-status-title"));
                 println!();
-                println!("Version: {}", env!("CARGO_PKG_VERSION"));
-                println!(
-                    "Authenticated: {}",
-                    if auth.is_authenticated() { "Yes" } else { "No" }
+                print_field("", "msg-field-version", env!("CARGO_PKG_VERSION"));
+                print_field(
+                    "",
+                    "msg-field-authenticated",
+                    yes_no(auth.is_authenticated()),
                 );
                 println!();
-                println!("Configuration:");
-                println!("  Install Directory: {:?}", config.install_dir);
-                println!("  Log Level: {}", config.log_level);
+                println!("{}", tr("msg-configuration"));
+                print_field("  ", "msg-field-install-dir", config.install_dir.display());
+                print_field("  ", "msg-field-log-level", &config.log_level);
+                print_field("  ", "msg-field-update-channel", config.update_channel);
+                print_field(
+                    "  ",
+                    "msg-field-shared-install-dir",
+                    yes_no(config.shared_install_dir),
+                );
                 println!();
 
+                let config_dir = Config::config_path()
+                    .ok()
+                    .and_then(|p| p.parent().map(|p| p.to_path_buf()));
+
                 if let Ok(config_path) = Config::config_path() {
-                    println!("Config Path: {:?}", config_path);
+                    print_field("", "msg-field-config-path", config_path.display());
                 }
 
-                if let Ok(data_dir) = Config::data_dir() {
-                    println!("Data Directory: {:?}", data_dir);
+                let data_dir = Config::data_dir().ok();
+                if let Some(data_dir) = &data_dir {
+                    print_field("", "msg-field-data-dir", data_dir.display());
+                }
+
+                // Metadata must stay private to this user, even on shared machines
+                let mut issues = Vec::new();
+                for dir in config_dir.iter().chain(data_dir.iter()) {
+                    match storage::audit_private_dir(dir) {
+                        Ok(found) => issues.extend(
+                            found
+                                .into_iter()
+                                .filter(|issue| !issue.path.starts_with(&config.install_dir)),
+                        ),
+                        Err(e) => log::warn!("Could not check permissions of {:?}: {}", dir, e),
+                    }
+                }
+
+                if !issues.is_empty() {
+                    println!();
+                    println!("{}", tr("msg-isolation-problems"));
+                    for issue in &issues {
+                        println!("  {:?}: {}", issue.path, issue.problem);
+                    }
                 }
             }
 
             Commands::Update {
                 app_name,
+                all,
+                hold,
+                unhold,
                 check_only,
+                now,
             } => {
+                // Without --all, clap has already required a name
+                let app_name = app_name.unwrap_or_default();
+
+                if hold || unhold {
+                    let manager = GameManager::new(config, auth)?;
+
+                    manager.set_hold_updates(&app_name, hold)?;
+                    if hold {
+                        println!("{}", tr_args("msg-held", &[("game", &app_name)]));
+                    } else {
+                        println!("{}", tr_args("msg-unheld", &[("game", &app_name)]));
+                    }
+                    return Ok(());
+                }
+
                 if !auth.is_authenticated() {
-                    eprintln!("Error: Not authenticated. Run 'rauncher auth' first.");
-                    std::process::exit(1);
+                    return Err(Error::NotAuthenticated);
                 }
 
-                let manager = GameManager::new(config, auth)?;
+                start_metrics_endpoint(&config);
 
-                if check_only {
-                    println!("Checking for updates for {}...", app_name);
-                    match manager.check_for_updates(&app_name).await {
-                        Ok(Some(version)) => {
-                            println!("✓ Update available: version {}", version);
-                        }
-                        Ok(None) => {
-                            println!("✓ Game is up to date");
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to check for updates: {}", e);
-                            std::process::exit(1);
-                        }
+                let mut config = config;
+                if now {
+                    config.download_windows.clear();
+                }
+
+                let cancel = cancel_on_ctrl_c();
+                let manager = GameManager::new(config, auth)?.with_cancellation(cancel.clone());
+
+                let app_names = if all {
+                    let (held, games): (Vec<_>, Vec<_>) = manager
+                        .list_installed()?
+                        .into_iter()
+                        .partition(|game| game.hold_updates);
+                    for game in held {
+                        println!(
+                            "{}",
+                            tr_args("msg-skipping-held", &[("game", &game.app_name)])
+                        );
                     }
+                    games.into_iter().map(|game| game.app_name).collect()
                 } else {
-                    match manager.update_game(&app_name).await {
-                        Ok(()) => println!("✓ Update complete!"),
-                        Err(e) => {
-                            eprintln!("Failed to update game: {}", e);
-                            std::process::exit(1);
+                    vec![app_name]
+                };
+
+                let mut failed = false;
+                for app_name in app_names {
+                    if cancel.is_cancelled() {
+                        break;
+                    }
+
+                    if check_only {
+                        println!(
+                            "{}",
+                            tr_args("msg-checking-updates", &[("game", &app_name)])
+                        );
+                        match manager.check_for_updates(&app_name).await {
+                            Ok(Some(version)) => {
+                                println!(
+                                    "{}",
+                                    tr_args("msg-update-available", &[("version", &version)])
+                                );
+                            }
+                            Ok(None) => {
+                                println!("{}", tr("msg-up-to-date"));
+                            }
+                            Err(e) => {
+                                eprintln!(
+                                    "{}",
+                                    tr_args("msg-update-check-failed", &[("error", &e)])
+                                );
+                                failed = true;
+                            }
+                        }
+                    } else {
+                        if all {
+                            println!("{}", tr_args("msg-updating", &[("game", &app_name)]));
+                        }
+                        match manager.update_game(&app_name).await {
+                            Ok(()) => println!("{}", tr("msg-update-complete")),
+                            Err(e) => {
+                                eprintln!(
+                                    "{}",
+                                    tr_args(
+                                        "msg-update-failed",
+                                        &[("game", &app_name), ("error", &e)]
+                                    )
+                                );
+                                failed = true;
+                            }
                         }
                     }
                 }
+
+                if failed {
+                    return Err(Error::Other(tr("msg-updates-failed")));
+                }
+            }
+
+            Commands::Rollback { app_name } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let version = manager.rollback_game(&app_name)?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-rolled-back",
+                        &[("game", &app_name), ("version", &version)]
+                    )
+                );
+                println!(
+                    "{}",
+                    tr_args("msg-rollback-hold-hint", &[("game", &app_name)])
+                );
             }
 
             Commands::CloudSave {
                 app_name,
                 download,
                 upload,
+                dry_run,
+                enable_sync,
+                disable_sync,
             } => {
+                if enable_sync || disable_sync {
+                    let manager = GameManager::new(config, auth)?;
+
+                    manager.set_cloud_sync(&app_name, enable_sync)?;
+                    if enable_sync {
+                        println!(
+                            "{}",
+                            tr_args("msg-sync-enabled-for", &[("game", &app_name)])
+                        );
+                    } else {
+                        println!(
+                            "{}",
+                            tr_args("msg-sync-disabled-for", &[("game", &app_name)])
+                        );
+                    }
+                    return Ok(());
+                }
+
                 if !auth.is_authenticated() {
-                    eprintln!("Error: Not authenticated. Run 'rauncher auth' first.");
-                    std::process::exit(1);
+                    return Err(Error::NotAuthenticated);
                 }
 
                 let manager = GameManager::new(config, auth)?;
 
                 if !download && !upload {
-                    eprintln!("Error: Specify --download or --upload");
-                    std::process::exit(1);
+                    return Err(Error::Other(tr("msg-sync-direction")));
+                }
+
+                if dry_run {
+                    let plan = manager.plan_cloud_sync(&app_name, download, upload).await?;
+                    print_sync_plan(&app_name, &plan);
+                    return Ok(());
                 }
 
                 if download {
-                    match manager.download_cloud_saves(&app_name).await {
-                        Ok(()) => {}
+                    manager.download_cloud_saves(&app_name).await?;
+                }
+
+                if upload {
+                    manager.upload_cloud_saves(&app_name).await?;
+                }
+            }
+
+            Commands::Logs { app_name, list } => {
+                let session_logs = logs::session_logs(&app_name)?;
+                if session_logs.is_empty() {
+                    println!("{}", tr_args("msg-no-logs", &[("game", &app_name)]));
+                    return Ok(());
+                }
+
+                if list {
+                    println!("{}", tr("msg-session-logs"));
+                    for path in &session_logs {
+                        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                        println!("  {} ({})", path.display(), format::Bytes(size));
+                    }
+                } else {
+                    print!(
+                        "{}",
+                        String::from_utf8_lossy(&std::fs::read(&session_logs[0])?)
+                    );
+                }
+            }
+
+            Commands::Shortcut {
+                app_name,
+                remove: true,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                match manager.remove_shortcut(&app_name)? {
+                    true => println!(
+                        "{}",
+                        tr_args("msg-shortcut-removed", &[("game", &app_name)])
+                    ),
+                    false => println!("{}", tr_args("msg-no-shortcut", &[("game", &app_name)])),
+                }
+            }
+
+            Commands::Shortcut { app_name, .. } => {
+                let manager = GameManager::new(config, auth)?;
+                let path = manager.create_shortcut(&app_name).await?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-shortcut-added",
+                        &[("game", &app_name), ("path", &path.display())]
+                    )
+                );
+            }
+
+            Commands::SteamExport { app_names } => {
+                let manager = GameManager::new(config, auth)?;
+                let app_names = if app_names.is_empty() {
+                    manager
+                        .list_installed()?
+                        .into_iter()
+                        .map(|game| game.app_name)
+                        .collect()
+                } else {
+                    app_names
+                };
+                if app_names.is_empty() {
+                    println!("{}", tr("msg-no-games-installed"));
+                    return Ok(());
+                }
+
+                let mut failed = false;
+                for app_name in &app_names {
+                    match manager.export_to_steam(app_name).await {
+                        Ok(accounts) => {
+                            println!(
+                                "{}",
+                                tr_args(
+                                    "msg-steam-added",
+                                    &[("game", &app_name), ("count", &accounts)]
+                                )
+                            )
+                        }
                         Err(e) => {
-                            eprintln!("Failed to download cloud saves: {}", e);
-                            std::process::exit(1);
+                            eprintln!("✗ {} - {}", app_name, e);
+                            failed = true;
                         }
                     }
                 }
 
-                if upload {
-                    match manager.upload_cloud_saves(&app_name).await {
-                        Ok(()) => {}
+                println!("{}", tr("msg-restart-steam"));
+                if failed {
+                    return Err(Error::Other(tr("msg-steam-failed")));
+                }
+            }
+
+            Commands::LutrisExport { app_names, output } => {
+                let manager = GameManager::new(config, auth)?;
+                let app_names = if app_names.is_empty() {
+                    manager
+                        .list_installed()?
+                        .into_iter()
+                        .map(|game| game.app_name)
+                        .collect()
+                } else {
+                    app_names
+                };
+                if app_names.is_empty() {
+                    println!("{}", tr("msg-no-games-installed"));
+                    return Ok(());
+                }
+
+                let output = match output {
+                    Some(output) => output,
+                    None => Config::data_dir()?.join("lutris"),
+                };
+                let mut failed = false;
+                for app_name in &app_names {
+                    match manager.export_to_lutris(app_name, &output) {
+                        Ok(path) => println!("✓ {} - lutris -i {:?}", app_name, path),
                         Err(e) => {
-                            eprintln!("Failed to upload cloud saves: {}", e);
-                            std::process::exit(1);
+                            eprintln!("✗ {} - {}", app_name, e);
+                            failed = true;
                         }
                     }
                 }
+
+                if failed {
+                    return Err(Error::Other(tr("msg-lutris-failed")));
+                }
             }
 
-            Commands::Gui => {
-                use rauncher::gui::LauncherApp;
-
-                let native_options = eframe::NativeOptions {
-                    viewport: egui::ViewportBuilder::default()
-                        .with_inner_size([1200.0, 800.0])
-                        .with_min_inner_size([800.0, 600.0])
-                        .with_title("R Games Launcher"),
-                    ..Default::default()
+            Commands::Saves {
+                action: SavesAction::List { app_name },
+            } => {
+                if !auth.is_authenticated() {
+                    return Err(Error::NotAuthenticated);
+                }
+
+                let manager = GameManager::new(config, auth)?;
+                let listing = manager.list_cloud_saves(&app_name).await?;
+
+                if !listing.sync_allowed {
+                    println!("{}\n", tr_args("msg-sync-disabled", &[("game", &app_name)]));
+                }
+
+                println!("{}", tr("msg-cloud-saves"));
+                if listing.cloud.is_empty() {
+                    println!("  {}", tr("msg-none"));
+                }
+                for save in &listing.cloud {
+                    let uploaded = save
+                        .uploaded_at
+                        .parse()
+                        .map(format::date)
+                        .unwrap_or_else(|_| save.uploaded_at.clone());
+                    println!(
+                        "  {:<32} {:>10}  {}",
+                        save.filename,
+                        format::Bytes(save.size),
+                        uploaded
+                    );
+                }
+
+                println!(
+                    "\n{}",
+                    tr_args("msg-local-saves", &[("dir", &listing.dir.display())])
+                );
+                if listing.local.is_empty() {
+                    println!("  {}", tr("msg-none"));
+                }
+                for save in &listing.local {
+                    println!("  {:<32} {:>10}", save.filename, format::Bytes(save.size));
+                }
+
+                if let Some(quota) = &listing.quota {
+                    println!(
+                        "\n{}",
+                        tr_args(
+                            "msg-cloud-storage-game",
+                            &[
+                                ("used", &format::Bytes(quota.used_bytes)),
+                                ("quota", &format::Bytes(quota.quota_bytes)),
+                                ("game_used", &format::Bytes(quota.used_by(&app_name))),
+                                ("game", &app_name)
+                            ]
+                        )
+                    );
+                }
+            }
+
+            Commands::Saves {
+                action: SavesAction::History { app_name },
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let versions = manager.save_history(&app_name)?;
+
+                if versions.is_empty() {
+                    println!(
+                        "{}",
+                        tr_args("msg-no-save-versions", &[("game", &app_name)])
+                    );
+                    return Ok(());
+                }
+                println!("{}", tr_args("msg-save-versions", &[("game", &app_name)]));
+                for version in versions.iter().rev() {
+                    println!(
+                        "  {}  {:<32} {:>10}  {}",
+                        version.id(),
+                        version.filename,
+                        format::Bytes(version.size),
+                        format::date(version.saved_at)
+                    );
+                }
+                println!(
+                    "\n{}",
+                    tr_args("msg-save-restore-hint", &[("game", &app_name)])
+                );
+            }
+
+            Commands::Saves {
+                action: SavesAction::Backup { app_name, output },
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let (path, files) = manager.backup_saves(&app_name, output.as_deref()).await?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-saves-backed-up",
+                        &[("count", &files), ("path", &path.display())]
+                    )
+                );
+            }
+
+            Commands::Saves {
+                action:
+                    SavesAction::Restore {
+                        app_name,
+                        from: Some(archive),
+                        ..
+                    },
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let files = manager.restore_save_backup(&app_name, &archive).await?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-saves-restored",
+                        &[("count", &files), ("path", &archive.display())]
+                    )
+                );
+            }
+
+            Commands::Saves {
+                action:
+                    SavesAction::Restore {
+                        app_name, version, ..
+                    },
+            } => {
+                // clap requires a version when no archive is given
+                let version = version.unwrap_or_default();
+                let manager = GameManager::new(config, auth)?;
+                let version = manager.restore_save(&app_name, &version).await?;
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-save-restored",
+                        &[
+                            ("file", &version.filename),
+                            ("date", &format::date(version.saved_at))
+                        ]
+                    )
+                );
+            }
+
+            Commands::Prefix {
+                action: PrefixAction::Winetricks { app_name, verbs },
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let status = manager
+                    .winetricks_command(&app_name, &verbs)
+                    .and_then(|mut command| Ok(command.status()?));
+
+                match status? {
+                    status if status.success() => {
+                        if !verbs.is_empty() {
+                            println!(
+                                "{}",
+                                tr_args(
+                                    "msg-winetricks-installed",
+                                    &[("verbs", &verbs.join(", ")), ("game", &app_name)]
+                                )
+                            );
+                        }
+                    }
+                    status => {
+                        return Err(Error::Other(tr_args(
+                            "msg-winetricks-failed",
+                            &[("status", &status)],
+                        )));
+                    }
+                }
+            }
+
+            Commands::Runner {
+                action:
+                    RunnerAction::List {
+                        available: true,
+                        wine,
+                    },
+            } => {
+                let kind = if wine {
+                    BuildKind::Wine
+                } else {
+                    BuildKind::Proton
                 };
+                let releases = builds::available(kind).await?;
+                let installed: Vec<String> = builds::installed()?
+                    .into_iter()
+                    .map(|build| build.name)
+                    .collect();
 
-                if let Err(e) = eframe::run_native(
+                println!("{}", tr("msg-available-builds"));
+                for release in &releases {
+                    let mut notes = Vec::new();
+                    if release.prerelease {
+                        notes.push(tr("msg-tag-prerelease"));
+                    }
+                    if installed.contains(&kind.build_name(&release.tag_name)) {
+                        notes.push(tr("msg-tag-installed"));
+                    }
+
+                    if notes.is_empty() {
+                        println!("  {}", release.tag_name);
+                    } else {
+                        println!("  {} ({})", release.tag_name, notes.join(", "));
+                    }
+                }
+            }
+
+            Commands::Runner {
+                action: RunnerAction::List { .. },
+            } => {
+                let runners = installed_runners();
+                if runners.is_empty() {
+                    println!("{}", tr("msg-no-runners"));
+                    return Ok(());
+                }
+
+                let default = config.runner.clone().unwrap_or_else(|| runners[0].clone());
+                println!("{}", tr("msg-runners"));
+                for runner in &runners {
+                    let marker = if *runner == default {
+                        format!(" ({})", tr("msg-tag-default"))
+                    } else {
+                        String::new()
+                    };
+                    println!("  {}{}", runner, marker);
+                }
+
+                for (app_name, runner) in &config.game_runners {
+                    println!(
+                        "\n{}",
+                        tr_args(
+                            "msg-runner-for-game",
+                            &[("game", &app_name), ("runner", &runner)]
+                        )
+                    );
+                }
+            }
+
+            Commands::Runner {
+                action: RunnerAction::Install { tag, wine },
+            } => {
+                let kind = if wine {
+                    BuildKind::Wine
+                } else {
+                    BuildKind::Proton
+                };
+                let build = builds::install(kind, tag.as_deref()).await?;
+                println!(
+                    "{}",
+                    tr_args("msg-runner-installed", &[("name", &build.name)])
+                );
+                println!("  {}", tr("msg-runner-use-hint"));
+                println!(
+                    "  <app_name> = {}",
+                    toml::Value::try_from(&build.runner)
+                        .map(|value| value.to_string())
+                        .unwrap_or_default()
+                );
+            }
+
+            Commands::Runner {
+                action: RunnerAction::Remove { name },
+            } => {
+                let in_use: Vec<&String> = builds::installed()?
+                    .into_iter()
+                    .find(|build| build.name == name)
+                    .map(|build| {
+                        config
+                            .game_runners
+                            .iter()
+                            .filter(|(_, runner)| **runner == build.runner)
+                            .map(|(app_name, _)| app_name)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                builds::remove(&name)?;
+                println!("{}", tr_args("msg-runner-removed", &[("name", &name)]));
+                for app_name in in_use {
+                    println!(
+                        "  {}",
+                        tr_args("msg-runner-still-used", &[("game", &app_name)])
+                    );
+                }
+            }
+
+            Commands::EglSync {
+                prefix,
+                import_only,
+                export_only,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let report = manager
+                    .egl_sync(&prefix, !export_only, !import_only)
+                    .await?;
+                for app_name in &report.imported {
+                    println!("  {}", tr_args("msg-egl-imported", &[("game", &app_name)]));
+                }
+                for app_name in &report.exported {
+                    println!("  {}", tr_args("msg-egl-exported", &[("game", &app_name)]));
+                }
+                for app_name in &report.skipped {
+                    println!("  {}", tr_args("msg-egl-skipped", &[("game", &app_name)]));
+                }
+                println!(
+                    "{}",
+                    tr_args(
+                        "msg-egl-done",
+                        &[
+                            ("imported", &report.imported.len()),
+                            ("exported", &report.exported.len())
+                        ]
+                    )
+                );
+            }
+
+            Commands::Clean { dry_run } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let leftovers = manager.clean(dry_run)?;
+
+                if leftovers.is_empty() {
+                    println!("{}", tr("msg-nothing-to-clean"));
+                    return Ok(());
+                }
+
+                for item in &leftovers {
+                    println!(
+                        "  {:>10}  {:<18} {:?}",
+                        format::Bytes(item.bytes),
+                        item.kind.to_string(),
+                        item.path
+                    );
+                }
+
+                let total: u64 = leftovers.iter().map(|item| item.bytes).sum();
+                if dry_run {
+                    println!(
+                        "\n{}",
+                        tr_args("msg-would-reclaim", &[("size", &format::size(total))])
+                    );
+                } else {
+                    println!(
+                        "\n{}",
+                        tr_args("msg-reclaimed", &[("size", &format::size(total))])
+                    );
+                }
+            }
+
+            Commands::CheckUpdate { beta } => {
+                let channel = if beta {
+                    UpdateChannel::Beta
+                } else {
+                    config.update_channel
+                };
+
+                match update::check_for_update(channel).await? {
+                    Some(release) => {
+                        println!(
+                            "{}",
+                            tr_args(
+                                "msg-launcher-update",
+                                &[("version", &release.version()), ("channel", &channel)]
+                            )
+                        );
+                        println!("  {}", release.html_url);
+                    }
+                    None => println!(
+                        "{}",
+                        tr_args(
+                            "msg-launcher-up-to-date",
+                            &[("version", &env!("CARGO_PKG_VERSION"))]
+                        )
+                    ),
+                }
+            }
+
+            Commands::Config { action } => {
+                let mut config = config;
+
+                let key = match &action {
+                    ConfigAction::List => {
+                        for key in rauncher::config::SETTINGS {
+                            println!("{} = {}", key, show_setting(config.get(key)?));
+                        }
+                        return Ok(());
+                    }
+                    ConfigAction::Get { key } => {
+                        println!("{}", show_setting(config.get(key)?));
+                        return Ok(());
+                    }
+                    ConfigAction::Set { key, .. } | ConfigAction::Unset { key } => key,
+                };
+
+                // Time limits stay behind the parental PIN
+                if key.starts_with("time_limits.") && config.time_limits.locked() {
+                    let pin = prompt::ask(&tr("msg-pin-prompt")).unwrap_or_default();
+                    if !config.time_limits.pin_matches(&pin) {
+                        return Err(Error::Other(tr("msg-pin-incorrect")));
+                    }
+                }
+
+                let result = match &action {
+                    ConfigAction::Set { key, value } => config.set(key, value),
+                    _ => config.unset(key),
+                };
+
+                result?;
+
+                config.save()?;
+                println!("✓ {} = {}", key, show_setting(config.get(key)?));
+            }
+
+            Commands::Cache {
+                prune,
+                clear_shaders,
+            } => {
+                let ttl_hours = config.cache_ttl_hours;
+                let size_mb = config.cache_size_mb;
+                let manager = GameManager::new(config, auth)?;
+                let cache = manager.cache()?;
+
+                if prune {
+                    let freed = cache.prune()?;
+                    println!(
+                        "{}",
+                        tr_args("msg-freed", &[("size", &format::size(freed))])
+                    );
+                }
+
+                let usage = cache.usage()?;
+                print_field("", "msg-field-cache-dir", Config::cache_dir()?.display());
+                println!(
+                    "  {}",
+                    tr_args(
+                        "msg-cache-usage",
+                        &[
+                            ("entries", &usage.entries),
+                            ("used", &format::size(usage.bytes)),
+                            ("size", &format::size(size_mb * 1024 * 1024))
+                        ]
+                    )
+                );
+                println!("  {}", tr_args("msg-cache-ttl", &[("hours", &ttl_hours)]));
+
+                if let Some(app_names) = clear_shaders {
+                    let app_names = if app_names.is_empty() {
+                        shaders::cached_games()?
+                    } else {
+                        app_names
+                    };
+                    let mut freed = 0;
+                    for app_name in &app_names {
+                        freed += shaders::clear_cache(app_name)?;
+                    }
+                    println!(
+                        "{}",
+                        tr_args(
+                            "msg-shaders-cleared",
+                            &[("count", &app_names.len()), ("size", &format::size(freed))]
+                        )
+                    );
+                }
+
+                let games = shaders::cached_games()?;
+                if !games.is_empty() {
+                    println!("{}", tr("msg-shader-caches"));
+                    for app_name in &games {
+                        println!(
+                            "  {} - {}",
+                            app_name,
+                            format::size(shaders::cache_size(app_name)?)
+                        );
+                    }
+                }
+            }
+
+            Commands::Doctor { fix } => {
+                let allow_download = auth.is_authenticated();
+                let manager = GameManager::new(config, auth)?;
+                let corrupt = manager.corrupt_records()?;
+
+                if corrupt.is_empty() {
+                    println!("{}", tr("msg-records-ok"));
+                    return Ok(());
+                }
+
+                println!("{}", tr("msg-records-unreadable"));
+                for record in &corrupt {
+                    println!(
+                        "  {} ({:?}): {}",
+                        record.app_name, record.path, record.error
+                    );
+                }
+
+                let fix = fix || prompt::confirm(&tr("msg-rebuild-prompt"), false);
+                if !fix {
+                    return Err(Error::Other(tr("msg-rebuild-hint")));
+                }
+
+                let mut failed = 0;
+                for record in &corrupt {
+                    match manager.recover_record(record, allow_download).await {
+                        Ok(source) => println!(
+                            "{}",
+                            tr_args(
+                                "msg-rebuilt",
+                                &[("game", &record.app_name), ("source", &source)]
+                            )
+                        ),
+                        Err(e) => {
+                            failed += 1;
+                            eprintln!(
+                                "{}",
+                                tr_args(
+                                    "msg-rebuild-failed",
+                                    &[("game", &record.app_name), ("error", &e)]
+                                )
+                            );
+                        }
+                    }
+                }
+
+                if failed > 0 {
+                    return Err(Error::Other(tr_args(
+                        "msg-records-failed",
+                        &[("count", &failed)],
+                    )));
+                }
+            }
+
+            Commands::Resume { rollback } => {
+                let manager = GameManager::new(config, auth)?;
+                let interrupted = manager.interrupted_operations()?;
+
+                if interrupted.is_empty() {
+                    println!("{}", tr("msg-no-interrupted"));
+                    return Ok(());
+                }
+
+                let mut failed = 0;
+                for entry in &interrupted {
+                    let args: &[(&str, &dyn std::fmt::Display)] =
+                        &[("operation", &entry.operation), ("game", &entry.app_name)];
+                    if rollback {
+                        println!("\n==> {}", tr_args("msg-rolling-back", args));
+                    } else {
+                        println!("\n==> {}", tr_args("msg-resuming", args));
+                    }
+
+                    match manager.resume_operation(entry, rollback).await {
+                        Ok(()) => println!(
+                            "{}",
+                            tr_args(
+                                "msg-resume-done",
+                                &[("operation", &entry.operation), ("game", &entry.app_name)]
+                            )
+                        ),
+                        Err(e) => {
+                            failed += 1;
+                            eprintln!("✗ {} {}: {}", entry.operation, entry.app_name, e);
+                        }
+                    }
+                }
+
+                if failed > 0 {
+                    return Err(Error::Other(tr_args(
+                        "msg-operations-failed",
+                        &[("count", &failed)],
+                    )));
+                }
+            }
+
+            Commands::History { audit, limit } => {
+                if !audit {
+                    return Err(Error::Other(tr("msg-history-audit-only")));
+                }
+
+                let entries = rauncher::audit::read_entries()?;
+
+                if entries.is_empty() {
+                    println!("{}", tr("msg-audit-empty"));
+                } else {
+                    print_heading(&tr("msg-audit-log"));
+                    let skip = entries.len().saturating_sub(limit);
+                    for entry in entries.iter().skip(skip) {
+                        println!(
+                            "  {} [{}] {} {}",
+                            format::date(entry.timestamp),
+                            entry.initiator,
+                            entry.action,
+                            entry.app_name.as_deref().unwrap_or("-")
+                        );
+                        for path in &entry.paths {
+                            println!("    {:?}", path);
+                        }
+                    }
+                }
+            }
+
+            Commands::Daemon => {
+                if !config.auto_update && !config.background_save_sync {
+                    return Err(Error::Other(tr("msg-daemon-disabled")));
+                }
+
+                // An expired session is renewed below
+                if auth.get_refresh_token().is_none() {
+                    return Err(Error::NotAuthenticated);
+                }
+
+                start_metrics_endpoint(&config);
+                // Nobody answers prompts in the background
+                prompt::set_non_interactive(true);
+
+                let update_interval = config.auto_update.then(|| config.auto_update_interval());
+                let sync_interval = config
+                    .background_save_sync
+                    .then(|| config.save_sync_interval());
+                let apply = config.auto_apply_updates;
+                let notifications = config.desktop_notifications;
+                let alert = |title: &str, message: &str| {
+                    if notifications {
+                        session::notify(title, message);
+                    } else {
+                        println!("[{}] {}", title, message);
+                    }
+                };
+                let mut manager = GameManager::new(config, auth)?.with_initiator(Initiator::Daemon);
+
+                if let Some(interval) = update_interval {
+                    println!(
+                        "{}",
+                        tr_args(
+                            "msg-daemon-update-interval",
+                            &[("interval", &format::duration(interval))]
+                        )
+                    );
+                }
+                if let Some(interval) = sync_interval {
+                    println!(
+                        "{}",
+                        tr_args(
+                            "msg-daemon-sync-interval",
+                            &[("interval", &format::duration(interval))]
+                        )
+                    );
+                }
+
+                let mut next_update = tokio::time::Instant::now();
+                let mut next_sync = tokio::time::Instant::now();
+                // Versions already announced, so a pending update doesn't
+                // notify again at every check
+                let mut announced: HashMap<String, String> = HashMap::new();
+                loop {
+                    let now = tokio::time::Instant::now();
+
+                    // Sessions last hours; the daemon runs for days
+                    if let Err(e) = manager.refresh_session().await {
+                        if e.requires_login() {
+                            return Err(e);
+                        }
+                        eprintln!(
+                            "{}",
+                            tr_args("msg-session-refresh-failed", &[("error", &e)])
+                        );
+                    }
+
+                    if let Some(interval) = update_interval.filter(|_| now >= next_update) {
+                        match manager.check_all_for_updates(apply).await {
+                            Ok(updates) => {
+                                for update in updates {
+                                    if update.applied {
+                                        alert(
+                                            &tr("msg-game-updated"),
+                                            &tr_args(
+                                                "msg-game-updated-detail",
+                                                &[
+                                                    ("game", &update.app_name),
+                                                    ("version", &update.version),
+                                                ],
+                                            ),
+                                        );
+                                    } else if announced.get(&update.app_name)
+                                        != Some(&update.version)
+                                    {
+                                        alert(
+                                            &tr("msg-update-available-title"),
+                                            &tr_args(
+                                                "msg-update-available-detail",
+                                                &[
+                                                    ("game", &update.app_name),
+                                                    ("version", &update.version),
+                                                ],
+                                            ),
+                                        );
+                                        announced.insert(update.app_name, update.version);
+                                    }
+                                }
+                            }
+                            Err(e) => eprintln!(
+                                "{}",
+                                tr_args("msg-daemon-update-failed", &[("error", &e)])
+                            ),
+                        }
+                        next_update = now + interval;
+                    }
+
+                    if let Some(interval) = sync_interval.filter(|_| now >= next_sync) {
+                        match manager.sync_all_saves().await {
+                            Ok(reports) => {
+                                for report in reports {
+                                    if report.conflicts.is_empty() {
+                                        println!(
+                                            "{}",
+                                            tr_args(
+                                                "msg-saves-synced",
+                                                &[
+                                                    ("count", &report.transferred),
+                                                    ("game", &report.app_name)
+                                                ]
+                                            )
+                                        );
+                                    } else {
+                                        alert(
+                                            &tr("msg-save-conflict"),
+                                            &tr_args(
+                                                "msg-save-conflict-detail",
+                                                &[
+                                                    ("game", &report.app_name),
+                                                    ("files", &report.conflicts.join(", ")),
+                                                ],
+                                            ),
+                                        );
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                eprintln!("{}", tr_args("msg-save-sync-failed", &[("error", &e)]))
+                            }
+                        }
+                        next_sync = now + interval;
+                    }
+
+                    let wake = [
+                        update_interval.map(|_| next_update),
+                        sync_interval.map(|_| next_sync),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                    .unwrap();
+                    tokio::time::sleep_until(wake).await;
+                }
+            }
+
+            Commands::Gui => {
+                use rauncher::gui::{self, LauncherApp};
+
+                start_metrics_endpoint(&config);
+
+                let native_options = gui::native_options(&config)?;
+
+                eframe::run_native(
                     "R Games Launcher",
                     native_options,
-                    Box::new(|cc| Ok(Box::new(LauncherApp::new(cc)))),
-                ) {
-                    eprintln!("Failed to run GUI: {}", e);
-                    std::process::exit(1);
-                }
+                    Box::new(move |cc| Ok(Box::new(LauncherApp::new(cc, scale)))),
+                )
+                .map_err(|e| Error::Other(tr_args("msg-gui-failed", &[("error", &e)])))?;
             }
         },
     }
@@ -0,0 +1,192 @@
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::Result;
+
+/// Process-wide launcher counters, exported in the Prometheus text format
+pub struct Metrics {
+    downloads_in_progress: AtomicI64,
+    downloaded_bytes: AtomicU64,
+    queue_depth: AtomicI64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    api_errors: AtomicU64,
+}
+
+static METRICS: Metrics = Metrics {
+    downloads_in_progress: AtomicI64::new(0),
+    downloaded_bytes: AtomicU64::new(0),
+    queue_depth: AtomicI64::new(0),
+    cache_hits: AtomicU64::new(0),
+    cache_misses: AtomicU64::new(0),
+    api_errors: AtomicU64::new(0),
+};
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    /// Mark a download as active until the returned guard is dropped
+    pub fn start_download(&self) -> DownloadGuard<'_> {
+        self.downloads_in_progress.fetch_add(1, Ordering::Relaxed);
+        DownloadGuard { metrics: self }
+    }
+
+    pub fn add_downloaded_bytes(&self, bytes: u64) {
+        self.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn set_queue_depth(&self, depth: usize) {
+        self.queue_depth.store(depth as i64, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_api_error(&self) {
+        self.api_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        let gauges = [
+            (
+                "rauncher_downloads_in_progress",
+                "Number of game downloads currently running",
+                self.downloads_in_progress.load(Ordering::Relaxed),
+            ),
+            (
+                "rauncher_download_queue_depth",
+                "Number of downloads waiting in the queue",
+                self.queue_depth.load(Ordering::Relaxed),
+            ),
+        ];
+
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} gauge", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        // Rates (bytes/sec, hit ratio) are derived from these with rate() in PromQL
+        let counters = [
+            (
+                "rauncher_downloaded_bytes_total",
+                "Total bytes downloaded from the CDN",
+                self.downloaded_bytes.load(Ordering::Relaxed),
+            ),
+            (
+                "rauncher_cache_hits_total",
+                "Chunk and manifest cache hits",
+                self.cache_hits.load(Ordering::Relaxed),
+            ),
+            (
+                "rauncher_cache_misses_total",
+                "Chunk and manifest cache misses",
+                self.cache_misses.load(Ordering::Relaxed),
+            ),
+            (
+                "rauncher_api_errors_total",
+                "Failed requests to Epic Games services",
+                self.api_errors.load(Ordering::Relaxed),
+            ),
+        ];
+
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        out
+    }
+}
+
+pub struct DownloadGuard<'a> {
+    metrics: &'a Metrics,
+}
+
+impl Drop for DownloadGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics
+            .downloads_in_progress
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Serve `GET /metrics` on the given address until the process exits
+pub async fn serve(addr: SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("Serving metrics on http://{}/metrics", addr);
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        tokio::spawn(async move {
+            let mut buffer = [0u8; 1024];
+            let read = match stream.read(&mut buffer).await {
+                Ok(read) => read,
+                Err(_) => return,
+            };
+
+            let request = String::from_utf8_lossy(&buffer[..read]);
+            let response = if request.starts_with("GET /metrics ") {
+                let body = metrics().render();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_contains_all_metrics() {
+        let rendered = metrics().render();
+        assert!(rendered.contains("# TYPE rauncher_downloads_in_progress gauge"));
+        assert!(rendered.contains("# TYPE rauncher_downloaded_bytes_total counter"));
+        assert!(rendered.contains("rauncher_api_errors_total"));
+        assert!(rendered.contains("rauncher_cache_hits_total"));
+    }
+
+    #[test]
+    fn test_download_guard_tracks_in_progress() {
+        let local = Metrics {
+            downloads_in_progress: AtomicI64::new(0),
+            downloaded_bytes: AtomicU64::new(0),
+            queue_depth: AtomicI64::new(0),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            api_errors: AtomicU64::new(0),
+        };
+
+        {
+            let _guard = local.start_download();
+            assert!(local.render().contains("rauncher_downloads_in_progress 1"));
+        }
+
+        assert!(local.render().contains("rauncher_downloads_in_progress 0"));
+    }
+}
@@ -0,0 +1,509 @@
+//! QR codes, for handing a URL to a phone. Only what that needs: byte mode
+//! at error correction level M, versions 1 to 10, which holds up to 213
+//! bytes.
+
+use crate::{Error, Result};
+
+const MAX_VERSION: usize = 10;
+
+/// Error correction codewords per block at level M, by version
+const ECC_PER_BLOCK: [usize; MAX_VERSION + 1] = [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26];
+
+/// Error correction blocks at level M, by version
+const BLOCKS: [usize; MAX_VERSION + 1] = [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5];
+
+/// Centres of the alignment patterns along each axis, by version
+const ALIGNMENT: [&[usize]; MAX_VERSION + 1] = [
+    &[],
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+    &[6, 28, 50],
+];
+
+/// A square of dark and light modules, without the quiet zone around it
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrCode {
+    /// The smallest code that holds `data`
+    pub fn encode(data: &[u8]) -> Result<Self> {
+        let version = (1..=MAX_VERSION)
+            .find(|&version| data_bits(data.len(), version) <= data_codewords(version) * 8)
+            .ok_or_else(|| Error::Other(format!("Too long for a QR code: {} bytes", data.len())))?;
+        let codewords = add_error_correction(&data_codewords_for(data, version), version);
+
+        let mut code = Grid::new(version);
+        code.draw_function_patterns();
+        code.draw_codewords(&codewords);
+
+        // The mask that leaves the fewest patterns a scanner could trip on
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                let mut masked = code.clone();
+                masked.apply_mask(mask);
+                masked.draw_format(mask);
+                masked.penalty()
+            })
+            .unwrap_or(0);
+        code.apply_mask(mask);
+        code.draw_format(mask);
+
+        Ok(Self {
+            size: code.size,
+            modules: code.modules,
+        })
+    }
+
+    /// Modules along each side
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module in column `x` of row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+}
+
+/// Bits the mode, length and bytes of `len` bytes of data take
+fn data_bits(len: usize, version: usize) -> usize {
+    let length_bits = if version < 10 { 8 } else { 16 };
+    4 + length_bits + len * 8
+}
+
+/// Modules left for data and error correction once the fixed patterns and
+/// format and version information are placed
+fn raw_data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules
+}
+
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8 - ECC_PER_BLOCK[version] * BLOCKS[version]
+}
+
+/// Mode indicator, length and data, terminated and padded to fill the
+/// version's data codewords
+fn data_codewords_for(data: &[u8], version: usize) -> Vec<u8> {
+    let capacity = data_codewords(version) * 8;
+    let mut bits = Vec::with_capacity(capacity);
+    let mut push = |value: usize, count: usize| {
+        bits.extend((0..count).rev().map(|i| (value >> i) & 1 == 1));
+    };
+    push(0b0100, 4);
+    push(data.len(), data_bits(0, version) - 4);
+    for &byte in data {
+        push(byte.into(), 8);
+    }
+    let terminator = (capacity - bits.len()).min(4);
+    bits.extend(std::iter::repeat_n(false, terminator));
+    bits.resize(bits.len().next_multiple_of(8), false);
+
+    let mut codewords: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | u8::from(bit)))
+        .collect();
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() == capacity / 8 {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+/// Split the data into the version's blocks, add each one's error
+/// correction and interleave them
+fn add_error_correction(data: &[u8], version: usize) -> Vec<u8> {
+    let blocks = BLOCKS[version];
+    let ecc_len = ECC_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    // Blocks differ by at most one data codeword; the short ones come first
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_len = raw_codewords / blocks - ecc_len;
+    let divisor = reed_solomon_divisor(ecc_len);
+
+    let mut rest = data;
+    let mut data_blocks = Vec::with_capacity(blocks);
+    let mut ecc_blocks = Vec::with_capacity(blocks);
+    for block in 0..blocks {
+        let len = short_len + usize::from(block >= short_blocks);
+        let (block, tail) = rest.split_at(len);
+        rest = tail;
+        ecc_blocks.push(reed_solomon_remainder(block, &divisor));
+        data_blocks.push(block);
+    }
+
+    let mut codewords = Vec::with_capacity(raw_codewords);
+    for i in 0..=short_len {
+        codewords.extend(data_blocks.iter().filter_map(|block| block.get(i)));
+    }
+    for i in 0..ecc_len {
+        codewords.extend(ecc_blocks.iter().map(|block| block[i]));
+    }
+    codewords
+}
+
+/// Multiply in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut product: u16 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= u16::from((y >> i) & 1) * u16::from(x);
+    }
+    product as u8
+}
+
+/// Coefficients of the generator polynomial of degree `degree`, highest
+/// first and without the leading 1
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut divisor = vec![0; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = gf_multiply(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    divisor
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        for (r, &d) in remainder.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    remainder
+}
+
+/// A code being built: its modules, and which of them are fixed patterns
+/// that data and masks leave alone
+#[derive(Clone)]
+struct Grid {
+    version: usize,
+    size: usize,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl Grid {
+    fn new(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder(3, 3);
+        self.draw_finder(size - 4, 3);
+        self.draw_finder(3, size - 4);
+
+        let centres = ALIGNMENT[self.version];
+        let last = centres.len().saturating_sub(1);
+        for (i, &x) in centres.iter().enumerate() {
+            for (j, &y) in centres.iter().enumerate() {
+                // Those corners belong to the finders
+                let on_finder = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !on_finder {
+                    self.draw_alignment(x, y);
+                }
+            }
+        }
+
+        // Reserved now, drawn once the mask is known
+        self.draw_format(0);
+        self.draw_version();
+    }
+
+    /// The 7×7 finder centred on (x, y) with its light separator
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let (Some(mx), Some(my)) = (
+                    x.checked_add_signed(dx as isize),
+                    y.checked_add_signed(dy as isize),
+                ) else {
+                    continue;
+                };
+                if mx < self.size && my < self.size {
+                    let ring = dx.abs().max(dy.abs());
+                    self.set_function(mx, my, ring != 2 && ring != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, x: usize, y: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let ring = dx.abs().max(dy.abs());
+                self.set_function(
+                    x.wrapping_add_signed(dx as isize),
+                    y.wrapping_add_signed(dy as isize),
+                    ring != 1,
+                );
+            }
+        }
+    }
+
+    /// The error correction level (M) and mask, twice, and the dark module
+    fn draw_format(&mut self, mask: usize) {
+        // Level M is 00, so the data is only the mask
+        let data = mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        let size = self.size;
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Versions 7 and up spell out their number next to two finders
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let bits = version_bits(self.version);
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Fill the free modules two columns at a time, snaking up and down
+    /// from the bottom right; column 6 holds the timing pattern
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut bit = 0;
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..size {
+                let y = if upward {
+                    size - 1 - vertical
+                } else {
+                    vertical
+                };
+                for x in [right, right - 1] {
+                    if !self.function[y * size + x] && bit < codewords.len() * 8 {
+                        self.modules[y * size + x] = (codewords[bit / 8] >> (7 - bit % 8)) & 1 == 1;
+                        bit += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: usize) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let flip = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if flip && !self.function[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// How hard the code is to scan: long runs, 2×2 blocks, shapes like a
+    /// finder and an uneven share of dark modules all count against it
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+
+        let lines = (0..size).flat_map(|i| {
+            [
+                (0..size).map(|j| self.get(j, i)).collect::<Vec<_>>(),
+                (0..size).map(|j| self.get(i, j)).collect::<Vec<_>>(),
+            ]
+        });
+        for line in lines {
+            for run in line.chunk_by(|a, b| a == b) {
+                if run.len() >= 5 {
+                    penalty += run.len() - 2;
+                }
+            }
+            const FINDER: [bool; 7] = [true, false, true, true, true, false, true];
+            for (start, window) in line.windows(7).enumerate() {
+                if window != FINDER {
+                    continue;
+                }
+                let light_before = start >= 4 && line[start - 4..start].iter().all(|&dark| !dark);
+                let light_after = line
+                    .get(start + 7..start + 11)
+                    .is_some_and(|after| after.iter().all(|&dark| !dark));
+                if light_before || light_after {
+                    penalty += 40;
+                }
+            }
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.get(x, y);
+                if dark == self.get(x + 1, y)
+                    && dark == self.get(x, y + 1)
+                    && dark == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().filter(|&&dark| dark).count();
+        let total = self.modules.len();
+        // Each 5% away from half dark
+        penalty += (dark * 20).abs_diff(total * 10) / total * 10;
+        penalty
+    }
+}
+
+/// The version number followed by its 12 BCH error correction bits
+fn version_bits(version: usize) -> usize {
+    let mut remainder = version;
+    for _ in 0..12 {
+        remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+    }
+    version << 12 | remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_correction_matches_the_standard_example() {
+        // "HELLO WORLD" at 1-M, as worked through in the specification's
+        // tutorials
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+
+        assert_eq!(ecc, [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn test_capacity_by_version() {
+        assert_eq!(data_codewords(1), 16);
+        assert_eq!(data_codewords(4), 64);
+        assert_eq!(data_codewords(10), 216);
+        assert_eq!(version_bits(7), 0b000111_110010_010100);
+
+        assert_eq!(QrCode::encode(&[b'a'; 14]).unwrap().size(), 21);
+        assert_eq!(QrCode::encode(&[b'a'; 15]).unwrap().size(), 25);
+        assert_eq!(QrCode::encode(&[b'a'; 213]).unwrap().size(), 57);
+        assert!(QrCode::encode(&[b'a'; 214]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_patterns_are_in_place() {
+        let url = "https://www.epicgames.com/activate?userCode=ABCD1234";
+        let code = QrCode::encode(url.as_bytes()).unwrap();
+        let size = code.size();
+        assert_eq!(size, 33);
+
+        // Finder corners, their separators and the timing patterns
+        for (x, y) in [(0, 0), (size - 1, 0), (0, size - 1), (6, 6), (3, 3)] {
+            assert!(code.is_dark(x, y), "({}, {})", x, y);
+        }
+        for (x, y) in [(7, 7), (size - 8, 7), (7, size - 8), (1, 1)] {
+            assert!(!code.is_dark(x, y), "({}, {})", x, y);
+        }
+        for i in 8..size - 8 {
+            assert_eq!(code.is_dark(i, 6), i % 2 == 0);
+            assert_eq!(code.is_dark(6, i), i % 2 == 0);
+        }
+        assert!(code.is_dark(8, size - 8));
+        // Alignment pattern of version 4
+        assert!(code.is_dark(26, 26));
+        assert!(!code.is_dark(25, 26));
+
+        // Both copies of the format information agree
+        let first: Vec<_> = (0..6).map(|i| code.is_dark(8, i)).collect();
+        let second: Vec<_> = (0..6).map(|i| code.is_dark(size - 1 - i, 8)).collect();
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,149 @@
+//! Checks GitHub for newer releases of rauncher itself. Users on the beta
+//! channel are also offered release candidates.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::{Error, Result};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/kairosci/rauncher/releases";
+
+/// Which launcher releases to offer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Stable releases plus pre-releases such as `0.3.0-rc.1`
+    Beta,
+}
+
+impl std::fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateChannel::Stable => write!(f, "stable"),
+            UpdateChannel::Beta => write!(f, "beta"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+impl Release {
+    /// Tag without its leading `v`
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+}
+
+/// Newest published release on `channel` that is newer than this build
+pub async fn check_for_update(channel: UpdateChannel) -> Result<Option<Release>> {
+    let releases: Vec<Release> = reqwest::Client::builder()
+        .user_agent(concat!("rauncher/", env!("CARGO_PKG_VERSION")))
+        .build()?
+        .get(RELEASES_URL)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Api(format!("Failed to check for launcher updates: {}", e)))?
+        .json()
+        .await?;
+
+    Ok(newest_release(releases, channel)
+        .filter(|release| is_newer(release.version(), env!("CARGO_PKG_VERSION"))))
+}
+
+fn newest_release(releases: Vec<Release>, channel: UpdateChannel) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter(|release| channel == UpdateChannel::Beta || !release.prerelease)
+        .max_by(|a, b| compare_versions(a.version(), b.version()))
+}
+
+/// `candidate` is a later version than `current`
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    compare_versions(candidate, current) == Ordering::Greater
+}
+
+/// Semver-style ordering: numeric core first, then a pre-release sorts
+/// before the release it leads up to (`1.0.0-rc.2` < `1.0.0`).
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (core_a, pre_a) = a.split_once('-').unwrap_or((a, ""));
+    let (core_b, pre_b) = b.split_once('-').unwrap_or((b, ""));
+
+    let numbers = |core: &str| -> Vec<u64> {
+        core.split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+
+    numbers(core_a)
+        .cmp(&numbers(core_b))
+        .then_with(|| match (pre_a.is_empty(), pre_b.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => compare_pre_release(pre_a, pre_b),
+        })
+}
+
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    for (x, y) in a.split('.').zip(b.split('.')) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a.split('.').count().cmp(&b.split('.').count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            html_url: String::new(),
+            prerelease,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(is_newer("0.2.0", "0.1.9"));
+        assert!(is_newer("0.10.0", "0.9.0"));
+        assert!(is_newer("1.0.0", "1.0.0-rc.2"));
+        assert!(is_newer("1.0.0-rc.10", "1.0.0-rc.2"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+    }
+
+    #[test]
+    fn test_channel_filters_prereleases() {
+        let releases = vec![
+            release("v0.2.0", false),
+            release("v0.3.0-rc.1", true),
+            release("v0.1.0", false),
+        ];
+
+        let stable = newest_release(releases.clone(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.version(), "0.2.0");
+
+        let beta = newest_release(releases, UpdateChannel::Beta).unwrap();
+        assert_eq!(beta.version(), "0.3.0-rc.1");
+    }
+}
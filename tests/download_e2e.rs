@@ -0,0 +1,568 @@
+//! End-to-end download tests: a synthetic game is served from a local HTTP
+//! server acting as the CDN mirror, and every flow must leave files on disk
+//! that are byte-identical to the originals.
+
+use rauncher::api::{ChunkPart, FileManifest, GameManifest};
+use rauncher::auth::{AuthManager, AuthToken};
+use rauncher::config::Config;
+use rauncher::games::{hash_file, CancellationToken, GameManager};
+use rauncher::Error;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Small enough that files span several chunks and chunks span several files
+const CHUNK_SIZE: usize = 16;
+
+/// Point the launcher's data, config and cache directories at a scratch
+/// directory shared by every test in this file, and sign in with a dummy token
+fn test_root() -> &'static Path {
+    static ROOT: OnceLock<TempDir> = OnceLock::new();
+
+    ROOT.get_or_init(|| {
+        let root = TempDir::new().unwrap();
+        std::env::set_var("XDG_DATA_HOME", root.path().join("data"));
+        std::env::set_var("XDG_CONFIG_HOME", root.path().join("config"));
+        std::env::set_var("XDG_CACHE_HOME", root.path().join("cache"));
+
+        AuthToken {
+            access_token: "test".to_string(),
+            refresh_token: "test".to_string(),
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+            account_id: "test".to_string(),
+        }
+        .save()
+        .unwrap();
+
+        root
+    })
+    .path()
+}
+
+/// Files of one version of a synthetic game, split into chunks
+struct SyntheticGame {
+    files: Vec<(String, Vec<u8>)>,
+    manifest: GameManifest,
+    chunks: HashMap<String, Vec<u8>>,
+}
+
+impl SyntheticGame {
+    fn new(app_name: &str, version: &str, files: &[(&str, &[u8])]) -> Self {
+        let files: Vec<(String, Vec<u8>)> = files
+            .iter()
+            .map(|(name, data)| (name.to_string(), data.to_vec()))
+            .collect();
+
+        // Chunk ids are derived from content, so identical data shares chunks
+        let stream: Vec<u8> = files.iter().flat_map(|(_, data)| data.clone()).collect();
+        let chunk_ids: Vec<String> = stream
+            .chunks(CHUNK_SIZE)
+            .map(|chunk| hex(&Sha1::digest(chunk)))
+            .collect();
+
+        let mut chunks = HashMap::new();
+        let mut chunk_sha_list = HashMap::new();
+        for (guid, chunk) in chunk_ids.iter().zip(stream.chunks(CHUNK_SIZE)) {
+            chunks.insert(guid.clone(), chunk.to_vec());
+            chunk_sha_list.insert(guid.clone(), Sha1::digest(chunk).to_vec());
+        }
+
+        let mut offset = 0;
+        let mut file_list = Vec::new();
+        for (name, data) in &files {
+            let mut parts = Vec::new();
+            let mut position = offset;
+
+            while position < offset + data.len() {
+                let in_chunk = position % CHUNK_SIZE;
+                let size = (CHUNK_SIZE - in_chunk).min(offset + data.len() - position);
+                parts.push(ChunkPart {
+                    guid: chunk_ids[position / CHUNK_SIZE].clone(),
+                    offset: in_chunk as u64,
+                    size: size as u64,
+                });
+                position += size;
+            }
+
+            file_list.push(FileManifest {
+                filename: name.clone(),
+                file_hash: Sha1::digest(data).to_vec(),
+                file_chunk_parts: parts,
+                ..Default::default()
+            });
+            offset += data.len();
+        }
+
+        let manifest = GameManifest {
+            manifest_file_version: "21".to_string(),
+            is_file_data: false,
+            app_name: app_name.to_string(),
+            app_version: version.to_string(),
+            launch_exe: files[0].0.clone(),
+            launch_command: String::new(),
+            build_size: stream.len() as u64,
+            file_list,
+            chunk_hash_list: HashMap::new(),
+            chunk_sha_list,
+            data_group_list: HashMap::new(),
+            chunk_filesize_list: HashMap::new(),
+            prereq_ids: Vec::new(),
+            prereq_name: String::new(),
+            prereq_path: String::new(),
+            prereq_args: String::new(),
+        };
+
+        Self {
+            files,
+            manifest,
+            chunks,
+        }
+    }
+
+    /// Guid of the chunk behind the `part`th chunk part of `filename`
+    fn chunk_of(&self, filename: &str, part: usize) -> String {
+        self.manifest
+            .file_list
+            .iter()
+            .find(|file| file.filename == filename)
+            .map(|file| file.file_chunk_parts[part].guid.clone())
+            .unwrap()
+    }
+
+    fn assert_installed_at(&self, install_path: &Path) {
+        for (name, data) in &self.files {
+            let installed = fs::read(install_path.join(name))
+                .unwrap_or_else(|e| panic!("{} was not installed: {}", name, e));
+            assert_eq!(&installed, data, "{} differs from the original", name);
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Minimal HTTP/1.1 server for the mirror layout: `<app>/manifest.json`
+/// and `chunks/<guid>`. Tracks how often each path was requested, and
+/// answers `If-None-Match` with the SHA-1 of the body as its ETag.
+#[derive(Clone, Default)]
+struct ChunkServer {
+    routes: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    hits: Arc<Mutex<HashMap<String, usize>>>,
+    not_modified: Arc<Mutex<usize>>,
+}
+
+impl ChunkServer {
+    async fn start() -> (Self, String) {
+        let server = Self::default();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = server.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let server = handle.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buffer[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request);
+                    let path = request
+                        .split_whitespace()
+                        .nth(1)
+                        .unwrap_or("/")
+                        .trim_start_matches('/')
+                        .to_string();
+
+                    *server.hits.lock().unwrap().entry(path.clone()).or_default() += 1;
+                    let body = server.routes.lock().unwrap().get(&path).cloned();
+
+                    let etag = body
+                        .as_ref()
+                        .map(|body| format!("\"{}\"", hex(&Sha1::digest(body))));
+                    let if_none_match = request.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("if-none-match")
+                            .then(|| value.trim().to_string())
+                    });
+
+                    let (status, body) = match body {
+                        Some(_) if if_none_match.is_some() && if_none_match == etag => {
+                            *server.not_modified.lock().unwrap() += 1;
+                            ("304 Not Modified", Vec::new())
+                        }
+                        Some(body) => ("200 OK", body),
+                        None => ("404 Not Found", Vec::new()),
+                    };
+                    let header = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+                        status,
+                        body.len(),
+                        etag.map(|etag| format!("ETag: {}\r\n", etag))
+                            .unwrap_or_default()
+                    );
+                    let _ = stream.write_all(header.as_bytes()).await;
+                    let _ = stream.write_all(&body).await;
+                });
+            }
+        });
+
+        (server, url)
+    }
+
+    fn publish(&self, game: &SyntheticGame) {
+        let mut routes = self.routes.lock().unwrap();
+        routes.insert(
+            format!("{}/manifest.json", game.manifest.app_name),
+            serde_json::to_vec(&game.manifest).unwrap(),
+        );
+        for (guid, data) in &game.chunks {
+            routes.insert(format!("chunks/{}", guid), data.clone());
+        }
+    }
+
+    fn set_chunk(&self, guid: &str, data: Option<Vec<u8>>) {
+        let path = format!("chunks/{}", guid);
+        let mut routes = self.routes.lock().unwrap();
+        match data {
+            Some(data) => routes.insert(path, data),
+            None => routes.remove(&path),
+        };
+    }
+
+    fn chunk_hits(&self, guid: &str) -> usize {
+        let hits = self.hits.lock().unwrap();
+        hits.get(&format!("chunks/{}", guid)).copied().unwrap_or(0)
+    }
+}
+
+fn manager(cdn_url: &str, install_dir: PathBuf) -> GameManager {
+    GameManager::new(config(cdn_url, install_dir), AuthManager::new().unwrap()).unwrap()
+}
+
+fn config(cdn_url: &str, install_dir: PathBuf) -> Config {
+    Config {
+        install_dir,
+        cdn_base_url: Some(cdn_url.to_string()),
+        // Every test must see the server's current manifest
+        cache_size_mb: 0,
+        // Failure tests rely on files being fetched in manifest order
+        workers_per_download: 1,
+        ..Config::default()
+    }
+}
+
+fn game_v1(app_name: &str) -> SyntheticGame {
+    SyntheticGame::new(
+        app_name,
+        "1.0",
+        &[
+            ("Game.exe", b"MZ synthetic game binary, version one"),
+            ("Content/Paks/pak0.pak", &[7u8; 100]),
+            ("Content/readme.txt", b"hello"),
+        ],
+    )
+}
+
+#[tokio::test]
+async fn test_install_is_byte_identical() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2EInstall");
+    server.publish(&game);
+
+    let manager = manager(&url, root.join("install"));
+    manager.install_game("E2EInstall").await.unwrap();
+
+    let install_path = root.join("install/E2EInstall");
+    game.assert_installed_at(&install_path);
+    assert!(manager.verify_game("E2EInstall", false).unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_parallel_install_is_byte_identical() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2EParallel");
+    server.publish(&game);
+
+    let config = Config {
+        workers_per_download: 3,
+        ..config(&url, root.join("parallel"))
+    };
+    let manager = GameManager::new(config, AuthManager::new().unwrap()).unwrap();
+    manager.install_game("E2EParallel").await.unwrap();
+
+    game.assert_installed_at(&root.join("parallel/E2EParallel"));
+}
+
+#[tokio::test]
+async fn test_shared_chunks_are_downloaded_once() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = SyntheticGame::new(
+        "E2EDedup",
+        "1.0",
+        &[
+            ("Game.exe", &[0x5a; 2 * CHUNK_SIZE]),
+            ("Content/copy.bin", &[0x5a; 2 * CHUNK_SIZE]),
+        ],
+    );
+    server.publish(&game);
+
+    let config = Config {
+        cache_size_mb: 1,
+        cache_chunks: true,
+        ..config(&url, root.join("dedup"))
+    };
+    let manager = GameManager::new(config, AuthManager::new().unwrap()).unwrap();
+    manager.install_game("E2EDedup").await.unwrap();
+
+    let guid = game.chunk_of("Game.exe", 0);
+    assert_eq!(game.chunks.len(), 1);
+    assert_eq!(server.chunk_hits(&guid), 1);
+
+    // Repairing straight after the install is served from the cache too
+    let install_path = root.join("dedup/E2EDedup");
+    fs::remove_file(install_path.join("Content/copy.bin")).unwrap();
+    manager.repair_game("E2EDedup").await.unwrap();
+
+    game.assert_installed_at(&install_path);
+    assert_eq!(server.chunk_hits(&guid), 1);
+}
+
+#[tokio::test]
+async fn test_cached_manifest_is_revalidated() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    server.publish(&game_v1("E2EManifestCache"));
+
+    let config = Config {
+        cache_size_mb: 1,
+        ..config(&url, root.join("manifest-cache"))
+    };
+    let manager = GameManager::new(config, AuthManager::new().unwrap()).unwrap();
+    manager.install_game("E2EManifestCache").await.unwrap();
+
+    // Unchanged on the server, so the cached copy is used
+    let manifest = manager.remote_manifest("E2EManifestCache").await.unwrap();
+    assert_eq!(manifest.app_version, "1.0");
+    assert_eq!(*server.not_modified.lock().unwrap(), 1);
+
+    // A new build is seen straight away, not once the cache expires
+    let v2 = SyntheticGame::new(
+        "E2EManifestCache",
+        "2.0",
+        &[("Game.exe", b"MZ synthetic game binary, version two")],
+    );
+    server.publish(&v2);
+    let manifest = manager.remote_manifest("E2EManifestCache").await.unwrap();
+    assert_eq!(manifest.app_version, "2.0");
+    assert_eq!(*server.not_modified.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_cancelled_install_resumes() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2ECancel");
+    server.publish(&game);
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let cancelled = manager(&url, root.join("cancel")).with_cancellation(token);
+    assert!(matches!(
+        cancelled.install_game("E2ECancel").await,
+        Err(Error::Cancelled)
+    ));
+    assert!(!root.join("cancel/E2ECancel").exists());
+
+    let manager = manager(&url, root.join("cancel"));
+    manager.install_game("E2ECancel").await.unwrap();
+    game.assert_installed_at(&root.join("cancel/E2ECancel"));
+}
+
+#[tokio::test]
+async fn test_corrupt_chunk_is_rejected() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2ECorrupt");
+    server.publish(&game);
+
+    let guid = game.chunk_of("Content/readme.txt", 0);
+    let mut corrupt = game.chunks[&guid].clone();
+    corrupt[0] ^= 0xff;
+    server.set_chunk(&guid, Some(corrupt));
+
+    let manager = manager(&url, root.join("corrupt"));
+    assert!(manager.install_game("E2ECorrupt").await.is_err());
+
+    // The damaged chunk is fetched again before giving up
+    assert_eq!(server.chunk_hits(&guid), 2);
+    assert!(!root.join("corrupt/E2ECorrupt").exists());
+    assert!(!root
+        .join("corrupt/E2ECorrupt.staging/Content/Paks/pak0.pak")
+        .exists());
+}
+
+#[tokio::test]
+async fn test_missing_chunk_then_resume() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2EResume");
+    server.publish(&game);
+
+    let first = game.chunk_of("Game.exe", 0);
+    let missing = game.chunk_of("Content/readme.txt", 0);
+    server.set_chunk(&missing, None);
+
+    let manager = manager(&url, root.join("resume"));
+    assert!(manager.install_game("E2EResume").await.is_err());
+    // Nothing appears in the install directory until every file is in
+    assert!(root.join("resume/E2EResume.staging/Game.exe").exists());
+    assert!(!root.join("resume/E2EResume").exists());
+    let hits_before = server.chunk_hits(&first);
+
+    server.set_chunk(&missing, Some(game.chunks[&missing].clone()));
+    manager.install_game("E2EResume").await.unwrap();
+
+    game.assert_installed_at(&root.join("resume/E2EResume"));
+    assert!(!root.join("resume/E2EResume.staging").exists());
+    // Files completed by the first attempt are not downloaded again
+    assert_eq!(server.chunk_hits(&first), hits_before);
+}
+
+#[tokio::test]
+async fn test_repair_restores_damaged_files() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let game = game_v1("E2ERepair");
+    server.publish(&game);
+
+    let manager = manager(&url, root.join("repair"));
+    manager.install_game("E2ERepair").await.unwrap();
+
+    let install_path = root.join("repair/E2ERepair");
+    fs::write(install_path.join("Game.exe"), b"truncated").unwrap();
+    fs::remove_file(install_path.join("Content/readme.txt")).unwrap();
+
+    let mut repaired = manager.repair_game("E2ERepair").await.unwrap();
+    repaired.sort();
+
+    assert_eq!(repaired, vec!["Content/readme.txt", "Game.exe"]);
+    game.assert_installed_at(&install_path);
+}
+
+#[tokio::test]
+async fn test_update_downloads_only_changes() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let v1 = game_v1("E2EUpdate");
+    server.publish(&v1);
+
+    let manager = manager(&url, root.join("update"));
+    manager.install_game("E2EUpdate").await.unwrap();
+
+    let v2 = SyntheticGame::new(
+        "E2EUpdate",
+        "2.0",
+        &[
+            ("Game.exe", b"MZ synthetic game binary, version two"),
+            ("Content/Paks/pak0.pak", &[7u8; 100]),
+            ("Content/Paks/pak1.pak", b"new content"),
+        ],
+    );
+    server.publish(&v2);
+
+    // Its first part shares a chunk with Game.exe; the next one is pak0's alone
+    let unchanged = v2.chunk_of("Content/Paks/pak0.pak", 1);
+    let hits_before = server.chunk_hits(&unchanged);
+
+    manager.update_game("E2EUpdate").await.unwrap();
+
+    let install_path = root.join("update/E2EUpdate");
+    v2.assert_installed_at(&install_path);
+    assert!(!install_path.join("Content/readme.txt").exists());
+    assert_eq!(server.chunk_hits(&unchanged), hits_before);
+    assert_eq!(
+        hash_file(&install_path.join("Game.exe")).unwrap(),
+        v2.manifest.file_list[0].file_hash
+    );
+}
+
+#[tokio::test]
+async fn test_rollback_restores_previous_build() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    let v1 = game_v1("E2ERollback");
+    server.publish(&v1);
+
+    let manager = manager(&url, root.join("rollback"));
+    manager.install_game("E2ERollback").await.unwrap();
+
+    let v2 = SyntheticGame::new(
+        "E2ERollback",
+        "2.0",
+        &[
+            ("Game.exe", b"MZ synthetic game binary, version two"),
+            ("Content/Paks/pak1.pak", b"new content"),
+        ],
+    );
+    server.publish(&v2);
+    manager.update_game("E2ERollback").await.unwrap();
+
+    let install_path = root.join("rollback/E2ERollback");
+    v2.assert_installed_at(&install_path);
+
+    assert_eq!(
+        manager.rollback_game("E2ERollback").unwrap(),
+        v1.manifest.app_version
+    );
+    v1.assert_installed_at(&install_path);
+    assert!(!install_path.join("Content/Paks/pak1.pak").exists());
+
+    // The only kept build has been used
+    assert!(manager.rollback_game("E2ERollback").is_err());
+}
+
+#[tokio::test]
+async fn test_update_check_skips_held_games() {
+    let root = test_root();
+    let (server, url) = ChunkServer::start().await;
+    server.publish(&game_v1("E2EAutoUpdate"));
+
+    let manager = manager(&url, root.join("auto-update"));
+    manager.install_game("E2EAutoUpdate").await.unwrap();
+
+    let v2 = SyntheticGame::new(
+        "E2EAutoUpdate",
+        "2.0",
+        &[("Game.exe", b"MZ synthetic game binary, version two")],
+    );
+    server.publish(&v2);
+
+    manager.set_hold_updates("E2EAutoUpdate", true).unwrap();
+    assert!(manager
+        .check_all_for_updates(true)
+        .await
+        .unwrap()
+        .is_empty());
+
+    manager.set_hold_updates("E2EAutoUpdate", false).unwrap();
+    let updates = manager.check_all_for_updates(true).await.unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].version, "2.0");
+    assert!(updates[0].applied);
+    v2.assert_installed_at(&root.join("auto-update/E2EAutoUpdate"));
+}
@@ -0,0 +1,20 @@
+use rauncher::config::{self, Config};
+use tempfile::TempDir;
+
+/// Everything the launcher stores follows the data directory override
+#[test]
+fn test_portable_root_relocates_state() {
+    let root = TempDir::new().unwrap();
+    config::set_portable_root(root.path().to_path_buf());
+
+    assert_eq!(
+        Config::config_path().unwrap(),
+        root.path().join("config.toml")
+    );
+    assert_eq!(Config::data_dir().unwrap(), root.path().join("data"));
+    assert_eq!(Config::cache_dir().unwrap(), root.path().join("cache"));
+
+    let config = Config::load().unwrap();
+    assert_eq!(config.install_dir, root.path().join("data/games"));
+    assert!(root.path().join("config.toml").is_file());
+}